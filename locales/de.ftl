@@ -0,0 +1,634 @@
+# German messages for lictool.
+
+## Errors
+
+error-prefix = Fehler:
+error-caused-by = Ursache:
+error-not-found = Keine Lizenz mit der angegebenen ID gefunden.
+error-file-not-found = { $path } existiert nicht.
+error-license-not-found = Keine Lizenz für { $id } gefunden. Meinten Sie: { $suggestions }?
+error-already-exists = Die Datei { $file } existiert bereits.
+error-missing-directory = Das Verzeichnis { $dir } existiert nicht; mit --parents wird es angelegt.
+error-unencodable = Das Zeichen { $ch } kann nicht als { $encoding } kodiert werden.
+error-hook-failed = Der Post-Write-Hook `{ $command }` ist mit { $status } fehlgeschlagen.
+error-unknown-profile = Das Profil { $name } ist in keiner Konfigurationsdatei definiert.
+error-missing-license-id = Es wurde keine Lizenz-ID angegeben und keine default-license konfiguriert.
+error-license-drift = { $file } weicht vom kanonischen { $license }-Text ab.
+error-compound-license = { $expression } kombiniert mehrere Lizenzen; vergleichen Sie stattdessen die Datei jeder einzelnen.
+error-compound-replace = { $expression } ist keine einzelne Lizenz; ersetzen Sie eine Lizenz nach der anderen.
+error-compound-before-with = { $expression } kombiniert mehrere Lizenzen und kann nicht vor WITH stehen.
+error-invalid-expression = Ungültiger Lizenzausdruck `{ $expression }`: { $reason }
+error-policy-violation = { $expression } wird von der Lizenzrichtlinie nicht akzeptiert.
+error-unknown-licenses = Unbekannte Lizenz-IDs: { $ids }
+error-unfilled-placeholders = Die Lizenz enthält noch Platzhalter: { $placeholders }
+error-missing-field-value = Ohne Eingabeaufforderung wurde kein Wert für { $field } gefunden; übergeben Sie { $flag } oder legen Sie ihn in der Konfiguration fest.
+error-placeholder-violations = { $count ->
+    [one] Ein nicht ausgefüllter Platzhalter wurde gefunden.
+   *[other] { $count } nicht ausgefüllte Platzhalter wurden gefunden.
+}
+error-header-conflicts = { $count ->
+    [one] Eine Datei hat
+   *[other] { $count } Dateien haben
+} einen SPDX-Header, der der Projektlizenz widerspricht.
+error-missing-asset-dirs = Keines von { $dirs } existiert; geben Sie die zu lizenzierenden Dateien oder Verzeichnisse an.
+error-misnamed-license-files = { $count ->
+    [one] Eine Lizenzdatei ist
+   *[other] { $count } Lizenzdateien sind
+} nicht nach der Konvention benannt; mit --fix werden sie umbenannt.
+error-missing-license-ref-text = { $id } ist eine eigene Lizenz; ihr Text wird als [overrides.{ $id }] mit text = "<Datei>" hinterlegt.
+error-invalid-license-ref = { $id } ist keine gültige ID einer eigenen Lizenz; sie muss mit LicenseRef- beginnen, gefolgt von Buchstaben, Ziffern, Punkten oder Bindestrichen.
+error-unsupported-file-type = Für { $file } ist kein Kommentarstil bekannt; unter [header.languages] lässt sich einer konfigurieren.
+error-empty-family = Keine Lizenz-ID beginnt mit { $prefix }.
+error-missing-release-asset = Release { $version } enthält kein { $name } für diese Plattform; stattdessen mit `cargo install lictool` installieren.
+error-checksum-mismatch = Die heruntergeladene Datei stimmt nicht mit ihrer Prüfsumme überein (erwartet { $expected }, erhalten { $actual }); es wurde nichts ersetzt.
+error-diagnostics-failed = { $count ->
+    [one] Eine Prüfung ist
+   *[other] { $count } Prüfungen sind
+} fehlgeschlagen.
+error-unsupported-archive = { $path } ist kein unterstütztes Archiv; erwartet wird eine .tar-, .tar.gz-, .tgz-, .crate-, .zip-, .jar- oder .whl-Datei.
+error-not-ready-to-publish = { $count ->
+    [one] Ein Problem muss
+   *[other] { $count } Probleme müssen
+} vor der Veröffentlichung behoben werden.
+error-cargo-package = Die Dateien des Pakets konnten nicht mit `cargo package --list` aufgelistet werden
+error-cargo-metadata = Die Abhängigkeiten des Projekts konnten nicht mit `cargo metadata` aufgelistet werden
+error-invalid-pattern = Ungültiges Muster { $pattern }: { $reason }
+error-missing-headers = { $count ->
+    [one] Einer Datei fehlt
+   *[other] { $count } Dateien fehlt
+} ein SPDX-Header.
+error-malformed-snippets = { $count ->
+    [one] Ein SPDX-Snippet ist
+   *[other] { $count } SPDX-Snippets sind
+} nicht korrekt markiert.
+error-invalid-dataset = { $path } ist kein mit `lictool db export` exportierter Lizenzdatensatz.
+error-corrupted-dataset = { $file } aus { $path } stimmt nicht mit der Prüfsumme überein; der Datensatz wurde nicht importiert.
+error-corrupted-mirror = { $file } des importierten Spiegels stimmt nicht mit der Prüfsumme überein; bitte `lictool db import` erneut ausführen.
+error-unknown-command = Es gibt keinen Befehl { $name }, und im PATH wurde kein Plugin lictool-{ $name } gefunden.
+error-unknown-command-suggestion = Es gibt keinen Befehl { $name }, und im PATH wurde kein Plugin lictool-{ $name } gefunden. Meinten Sie { $suggestion }?
+error-run-plugin = Das Plugin { $plugin } konnte nicht ausgeführt werden
+error-plugin-failed = Das Plugin lictool-{ $name } ist mit dem Exit-Code { $code } fehlgeschlagen.
+error-invalid-script = Das Platzhalter-Skript { $path } ist fehlgeschlagen: { $message }
+error-lictool-not-runnable = lictool konnte nicht ausgeführt werden. Installieren Sie es mit `cargo install lictool`.
+error-journal = Die Änderungen konnten nicht im Rückgängig-Journal festgehalten werden.
+error-unsupported-format = --format { $format } wird von diesem Befehl oder seinen Optionen nicht unterstützt.
+error-invalid-sbom = { $path } ist kein SPDX-Dokument im JSON-Format.
+error-sbom-discrepancies = { $count ->
+    [one] Eine Abweichung
+   *[other] { $count } Abweichungen
+} zwischen dem SPDX-Dokument und dem Projekt gefunden.
+error-invalid-pack = { $path } ist kein mit `lictool pack export` exportiertes Paket.
+error-aborted = Abgebrochen.
+error-no-manifest = Keine Cargo.toml, package.json oder pyproject.toml gefunden.
+error-undetected-license = Die Lizenz von { $file } wurde nicht erkannt; gib ihren SPDX-Ausdruck an.
+error-manifests-out-of-sync = { $count ->
+    [one] Ein Manifest gibt
+   *[other] { $count } Manifeste geben
+} nicht die Lizenz der Lizenzdatei an; führe `lictool sync-manifest` aus, um sie zu aktualisieren.
+error-denied-dependencies = { $count ->
+    [one] Eine Abhängigkeit hat
+   *[other] { $count } Abhängigkeiten haben
+} eine Lizenz, die die Richtlinie ablehnt.
+error-unknown = Ein unbekannter Fehler ist aufgetreten: { $error }
+error-read-git-history = Die Autoren konnten nicht aus der Git-Historie gelesen werden
+error-current-dir = Das aktuelle Verzeichnis konnte nicht gelesen werden
+error-read-file = { $path } konnte nicht gelesen werden
+error-parse-file = { $path } konnte nicht geparst werden
+error-confirmation-required = Ohne Bestätigung werden keine Dateien entfernt; mit --yes fortfahren.
+error-remove-file = { $path } konnte nicht entfernt werden
+error-nothing-to-undo = Es gibt keinen Vorgang, der rückgängig gemacht werden kann.
+error-no-license-file = Es wurde keine Lizenzdatei gefunden; zuerst mit `lictool add` eine hinzufügen.
+error-changed-since-operation = Seit dem letzten Vorgang geändert, mit --force trotzdem zurücksetzen: { $files }
+error-config-not-found = Die Konfigurationsdatei { $path } existiert nicht.
+error-config-key-not-set = { $key } ist nicht gesetzt.
+error-unknown-config-keys = { $file } enthält unbekannte Schlüssel:
+    { $keys }
+error-write-file = { $path } konnte nicht geschrieben werden
+error-invalid-baseline = { $path } ist keine gültige Baseline-Datei
+error-request = Anfrage an { $url } fehlgeschlagen
+error-proxy = Ungültige Proxy-URL { $url }
+error-ca-bundle = Die CA-Zertifikate aus { $path } konnten nicht geladen werden
+error-http-status = { $url } antwortete mit { $status } ({ $origin })
+error-decode-response = Die Antwort von { $url } konnte nicht dekodiert werden ({ $status }, { $origin })
+http-origin-cache = aus dem Cache
+http-origin-network = aus dem Netzwerk
+http-origin-mirror = aus dem importierten Spiegel
+
+## Validation
+
+invalid-year = `{ $year }` ist kein Jahr zwischen { $min } und { $max }
+invalid-year-range = Der Zeitraum `{ $range }` endet, bevor er beginnt
+year-no-git-history = from-git setzt ein Git-Repository mit mindestens einem Commit voraus
+invalid-email = `{ $email }` ist keine gültige E-Mail-Adresse
+invalid-mode = `{ $mode }` ist kein gültiger oktaler Dateimodus
+invalid-variable = `{ $variable }` hat nicht die Form SCHLÜSSEL=WERT
+invalid-source = `{ $source }` ist weder spdx noch github noch die URL eines Spiegels
+
+## Prompts
+
+prompt-select-license = Lizenz auswählen
+prompt-field = Bitte { $field } eingeben
+prompt-create-directory = Das Verzeichnis { $dir } existiert nicht. Anlegen?
+prompt-new-file-name = Bitte einen neuen Dateinamen angeben, um nichts zu überschreiben.
+prompt-confirm-remove = Die Lizenz entfernen?
+field-owner = den Namen des Autors
+field-year = das Erstellungsjahr
+field-repo = den Namen des Programms
+field-email = die E-Mail-Adresse
+review-prompt = Sieht die Vorschau richtig aus?
+review-write = Lizenz schreiben
+review-edit = { $field } bearbeiten
+review-change-license = Andere Lizenz wählen
+review-abort = Abbrechen
+init-public-domain-note = { $license } knüpft die Weiterverwendung an keine Bedingungen: Jeder darf das Werk für beliebige Zwecke kopieren, ändern und verbreiten, ohne die Urheber zu nennen. Es müssen keine Angaben ausgefüllt werden.
+init-prompt-another = Eine weitere Lizenz hinzufügen, etwa für eine Doppellizenzierung?
+
+## Progress
+
+fetching-licenses = SPDX-Lizenzliste wird abgerufen…
+fetching-exceptions = SPDX-Ausnahmeliste wird abgerufen…
+fetching-license-texts = Lizenztexte werden abgerufen…
+fetching-license = Lizenz { $license } wird abgerufen…
+fetching-license-source = Lizenztext wird heruntergeladen…
+fetching-pack = Paket wird heruntergeladen…
+fetching-latest-release = Suche nach einem neueren Release…
+downloading-release = lictool { $version } wird heruntergeladen…
+fetching-licenses-details = { $count ->
+    [one] Eine Lizenz wird
+   *[other] { $count } Lizenzen werden
+} abgerufen…
+
+## Listing licenses
+
+list-column-id = ID
+list-column-osi = OSI
+list-column-fsf = FSF
+list-column-supported = Unterstützt
+list-column-name = Name
+
+## License picker
+
+picker-group-custom = Eigene Vorlagen
+picker-group-default = Standard
+picker-group-favorites = Favoriten
+picker-group-recent = Zuletzt verwendet
+picker-group-all = Alle Lizenzen
+picker-filter-osi = nur OSI
+picker-filter-deprecated = veraltete ausblenden
+picker-filter-popular = nur verbreitete
+browse-search = Suche:
+browse-keys = ↑↓ bewegen  Bild↑/Bild↓ blättern  ^O/^D/^P filtern  Enter initialisieren  Esc beenden
+browse-no-details = Die Details dieser Lizenz konnten nicht abgerufen werden.
+
+## Deprecated licenses
+
+deprecated-prompt = { $license } ist veraltet; welche Lizenz soll stattdessen verwendet werden?
+deprecated-keep = { $license } beibehalten
+deprecated-warning = { $license } ist veraltet; erwäge stattdessen { $successors }
+
+## Summaries
+
+file-created = Die Datei { $file } wurde erstellt.
+file-backed-up = { $file } wurde nach { $backup } gesichert.
+license-written-stdout = Die Lizenz wurde auf die Standardausgabe geschrieben.
+favorite-added = { $license } wurde zu den Favoriten hinzugefügt.
+favorite-removed = { $license } wurde aus den Favoriten entfernt.
+license-ref-registered = { $id } wurde mit seinem Text in { $file } registriert.
+license-committed = Die Lizenz { $license } wurde als { $commit } committet.
+
+## Configuration
+
+config-unknown-key = unbekannter Schlüssel { $key }
+config-unknown-key-suggestion = unbekannter Schlüssel { $key }, meinten Sie { $suggestion }?
+config-valid = { $file } ist gültig.
+config-no-files = Es wurde keine Konfigurationsdatei gefunden.
+config-set = { $key } wurde in { $file } gesetzt.
+
+## Choosing a license
+
+choose-prompt-commercial = Darf das Projekt kommerziell genutzt werden?
+choose-prompt-permissive = Darf das Projekt in proprietärer Software verwendet werden, ohne Änderungen zu teilen?
+choose-prompt-attribution = Müssen Weiterverwender Ihren Urheberrechtshinweis erhalten?
+choose-prompt-library = Ist das Projekt eine Bibliothek, die Software unter anderen Lizenzen nutzen können soll?
+choose-prompt-network = Muss der Quellcode geteilt werden, wenn die Software über ein Netzwerk angeboten wird?
+choose-prompt-patents = Sollen Beitragende ihre Patente ausdrücklich lizenzieren?
+choose-prompt-license = Empfohlene Lizenzen
+
+## Creative Commons
+
+cc-prompt-attribution = Müssen Weiterverwender Sie als Urheber nennen?
+cc-prompt-commercial = Darf das Werk kommerziell genutzt werden?
+cc-prompt-adaptations = Dürfen Bearbeitungen des Werks weitergegeben werden?
+cc-prompt-share-alike = Müssen Bearbeitungen unter derselben Lizenz weitergegeben werden?
+cc-chosen = { $license } passt zu diesen Bedingungen.
+cc-mapped = { $pattern } wurde { $license } zugeordnet.
+cc-remapped = { $pattern } wurde { $license } statt { $previous } zugeordnet.
+
+## Dry runs
+
+dry-run-create = würde erstellt ({ $lines ->
+    [one] eine Zeile
+   *[other] { $lines } Zeilen
+})
+dry-run-modify = würde geändert (+{ $added } -{ $removed })
+dry-run-unchanged = bliebe unverändert
+
+## Removing licenses
+
+rm-plan = Folgende Änderungen werden vorgenommen:
+rm-manifest-license = Lizenzfeld entfernen
+rm-config-default-license = default-license entfernen
+rm-nothing = Es wurden keine Lizenzdateien oder Lizenzfelder gefunden.
+rm-cancelled = Es wurde nichts entfernt.
+rm-done = Die Lizenz wurde entfernt.
+
+## Switching licenses
+
+switch-manifest-license = Lizenzfeld auf { $license } setzen
+switch-config-default-license = default-license auf { $license } setzen
+switch-kept = { $field }: { $value }
+switch-done = Die Lizenz wurde auf { $license } umgestellt.
+
+## Status
+
+status-license = Lizenz
+status-manifests = Manifeste
+status-headers = Header
+status-policy = Richtlinie
+status-no-license-file = keine Lizenzdatei
+status-undetected = unbekannter Lizenztext
+status-no-manifests = keine Lizenz angegeben
+status-no-source-files = keine Dateien, die einen Header haben können
+status-header-coverage = { $percent } % ({ $covered }/{ $total } Dateien)
+status-no-violations = keine Verstöße
+
+## License list updates
+
+list-update-available = SPDX-Lizenzliste { $upstream } ist erschienen, zwischengespeichert ist { $cached }; zum Abrufen `lictool cache refresh` ausführen.
+
+## Cache
+
+cache-status = { $size } in { $count ->
+    [one] einer Datei
+   *[other] { $count } Dateien
+} unter { $dir }.
+cache-age = Ältester Eintrag { $oldest } zwischengespeichert, neuester { $newest }.
+cache-empty = Der Cache unter { $dir } ist leer.
+cache-cleared = { $size } aus { $dir } gelöscht.
+cache-dry-run = Würde { $size } aus { $dir } löschen.
+cache-mirror-removed = Den importierten Datensatz unter { $dir } entfernt.
+cache-mirror-dry-run = Würde den importierten Datensatz unter { $dir } entfernen.
+cache-mirror-shadows = Lizenzen werden weiterhin aus dem unter { $dir } importierten Datensatz gelesen; führen Sie `lictool cache clear --mirror` aus, um wieder das Netzwerk zu verwenden.
+cache-refreshed = SPDX-Lizenzliste { $version } neu validiert und die Details { $count ->
+    [one] einer Lizenz
+   *[other] von { $count } Lizenzen
+} zwischengespeichert.
+age-just-now = gerade eben
+age-minutes = { $count ->
+    [one] vor einer Minute
+   *[other] vor { $count } Minuten
+}
+age-hours = { $count ->
+    [one] vor einer Stunde
+   *[other] vor { $count } Stunden
+}
+age-days = { $count ->
+    [one] vor einem Tag
+   *[other] vor { $count } Tagen
+}
+
+## Self-update
+
+update-up-to-date = lictool { $version } ist das neueste Release.
+update-available = lictool { $latest } ist verfügbar; installiert ist { $current }.
+update-would-replace = { $path } würde ersetzt.
+update-done = { $path } wurde auf lictool { $version } aktualisiert.
+
+## Diagnostics
+
+doctor-connectivity = SPDX
+doctor-connectivity-ok = { $url } hat in { $millis } ms geantwortet.
+doctor-connectivity-status = { $url } hat mit { $status } geantwortet.
+doctor-fix-connectivity = Netzwerk- und Proxy-Einstellungen wie HTTPS_PROXY prüfen; bereits abgerufene Lizenzen kommen weiterhin aus dem Cache.
+doctor-cache = Cache
+doctor-cache-size = { $size } in { $files } Dateien unter { $dir }.
+doctor-cache-unwritable = { $dir } ist nicht beschreibbar: { $error }
+doctor-fix-cache-permissions = { $dir } für den eigenen Benutzer beschreibbar machen.
+doctor-fix-cache-size = `lictool cache clear` ausführen, um Platz zu schaffen; der Cache wird bei Bedarf neu aufgebaut.
+doctor-fix-cache-corrupt = `lictool cache clear` ausführen; der Cache wird bei Bedarf neu aufgebaut.
+doctor-config = Konfiguration
+doctor-config-ok = Gelesen aus { $files }.
+doctor-fix-config = Die Datei wie beschrieben korrigieren und danach `lictool config validate` ausführen.
+doctor-git = Git
+doctor-git-ok = Repository unter { $dir }, Commits als { $name }.
+doctor-git-no-repository = Das aktuelle Verzeichnis liegt in keinem Git-Repository.
+doctor-fix-git-repository = `git init` ausführen, um --commit zu nutzen.
+doctor-git-no-identity = Weder user.name noch user.email ist konfiguriert.
+doctor-fix-git-identity = `git config --global user.name "Ihr Name"` und `git config --global user.email sie@example.com` ausführen.
+doctor-terminal = Terminal
+doctor-terminal-ok = { $columns } Spalten, { $colors ->
+    [yes] mit Farben
+   *[no] ohne Farben
+}.
+doctor-terminal-none = Kein interaktives Terminal, daher sind keine Eingabeaufforderungen möglich.
+doctor-fix-terminal = Werte stattdessen als Optionen übergeben, etwa --yes oder --no-input.
+
+## Undo
+
+undo-restored = { $file } wiederhergestellt
+undo-removed = { $file } entfernt
+undo-would-restore = { $file } würde wiederhergestellt
+undo-would-remove = { $file } würde entfernt
+undo-done = `{ $command }` vom { $time } wurde rückgängig gemacht.
+
+## Checks
+
+check-no-drift = { $file } entspricht dem kanonischen { $license }-Text.
+check-stdin = Der Lizenztext aus stdin
+check-no-placeholders = Keine offenen Platzhalter gefunden.
+check-unfilled-placeholder = offener Platzhalter { $placeholder }
+check-drift-hunk = Zeile { $line }:
+check-drift-paragraphs = { $matching } von { $total } kanonischen Absätzen sind unverändert ({ $percent } %).
+check-no-header-conflicts = Kein SPDX-Header widerspricht der Projektlizenz.
+check-header-conflict = Header nennt { $declared }, erwartet wird { $expected }
+check-ignored-file = wegen { $directive } übersprungen
+check-no-misnamed-license-files = Die Lizenzdateien folgen der Namenskonvention.
+check-misnamed-license-file = sollte { $expected } heißen
+check-license-file-renamed = { $file } wurde in { $expected } umbenannt.
+check-license-file-rename-planned = Würde { $file } in { $expected } umbenennen.
+check-license-file-name-taken = { $file } wurde nicht umbenannt, da { $expected } bereits existiert.
+check-baseline-written = { $count ->
+    [one] Ein Verstoß wurde
+   *[other] { $count } Verstöße wurden
+} in { $file } festgehalten.
+check-baseline-suppressed = { $count ->
+    [one] Ein bekannter Verstoß wurde
+   *[other] { $count } bekannte Verstöße wurden
+} laut Baseline ignoriert.
+
+## Headers
+
+header-missing = { $file } hat keinen SPDX-Header
+header-added = { $file } hat jetzt einen SPDX-Header
+header-all-present = Jede Datei, die einen Header haben kann, hat einen SPDX-Header.
+header-snippet-unterminated = { $file }:{ $line } beginnt ein SPDX-Snippet, das nie endet
+header-snippet-no-license = { $file }:{ $line } beginnt ein SPDX-Snippet ohne SPDX-License-Identifier
+header-watching = Beobachte Änderungen; Strg+C beendet.
+header-replaced = { $file } nennt jetzt { $to } statt { $from }
+header-replace-none = Kein SPDX-Header nennt { $license }.
+header-replace-skipped = { $file } wurde nicht geändert: { $reason }
+header-adding = SPDX-Header werden hinzugefügt…
+header-replacing = SPDX-Header werden ersetzt…
+header-missing-count = { $count ->
+    [0] Keiner Datei fehlt ein SPDX-Header.
+    [one] Einer Datei fehlt ein SPDX-Header.
+   *[other] { $count } Dateien fehlt ein SPDX-Header.
+}
+
+## Badges
+
+badge-injected = Der Lizenzabschnitt für { $license } steht jetzt in { $file }.
+
+## License expressions
+
+expression-unexpected-end = unerwartetes Ende des Ausdrucks
+expression-unexpected-token = unerwartetes `{ $token }`
+expression-invalid-id = `{ $id }` ist keine gültige Lizenzkennung
+expression-valid = { $expression } ist ein gültiger Lizenzausdruck.
+
+## Publishing
+
+listing-package-files = Dateien des Pakets werden aufgelistet…
+publish-license-ready = Lizenzfelder und -dateien sind bereit zur Veröffentlichung.
+publish-missing-license = Cargo.toml setzt weder license noch license-file.
+publish-invalid-license = Das Feld license { $license } ist kein gültiger SPDX-Ausdruck: { $reason }
+publish-missing-license-file = Die license-file { $path } existiert nicht.
+publish-excluded-license-file = { $path } gehört nicht zum Paket; include und exclude in Cargo.toml prüfen.
+
+## Authors
+
+authors-updated = Die Datei { $file } wurde aktualisiert.
+authors-unchanged = { $file } ist aktuell.
+file-created-partial = { $file } wurde erstellt, aber diese Platzhalter bleiben: { $placeholders }
+
+## Policy
+
+policy-all-of = erfordert alle
+policy-any-of = erfordert eine von
+policy-allowed = von der Richtlinie erlaubt
+policy-denied = von der Richtlinie verboten
+policy-not-allowed = nicht in der Erlaubt-Liste
+policy-no-allow-list = nicht verboten, und keine Erlaubt-Liste konfiguriert
+
+## Auditing dependencies
+
+audit-flag-missing = kein SPDX-Ausdruck
+audit-flag-invalid = ungültiger Ausdruck
+audit-flag-deprecated = veraltete ID
+audit-flag-not-osi-approved = nicht von der OSI anerkannt
+audit-flag-copyleft = Copyleft
+audit-flag-denied = von der Richtlinie verboten
+audit-no-license = Kein Lizenzausdruck
+audit-summary = { $packages ->
+    [one] Eine Abhängigkeit
+   *[other] { $packages } Abhängigkeiten
+} unter { $licenses ->
+    [one] einer Lizenz
+   *[other] { $licenses } Lizenzen
+}.
+
+## Placeholders
+
+placeholders-heading = Platzhalter in { $license }:
+placeholders-others = Weitere Platzhalter, die add nicht ausfüllt:
+
+## Filling existing files
+
+fill-nothing-to-do = { $file } enthält keine Platzhalter mehr.
+fill-done = Die Platzhalter von { $file } wurden ausgefüllt.
+fill-partial = { $file } wurde aktualisiert, aber diese Platzhalter bleiben: { $placeholders }
+bump-year-current = Die Urheberrechtsjahre von { $file } sind aktuell.
+bump-year-done = { $count ->
+    [one] Eine Urheberrechtszeile
+   *[other] { $count } Urheberrechtszeilen
+} von { $file } bis { $year } verlängert.
+
+## License families
+
+family-id = ID
+family-version = Version
+family-variant = Variante
+family-osi = OSI
+family-fsf = FSF
+family-deprecated = Veraltet
+family-only = nur diese
+family-or-later = oder später
+
+## Comparing licenses
+
+compare-name = Name
+compare-osi = Von der OSI anerkannt
+compare-fsf = Von der FSF als frei eingestuft
+compare-deprecated = Veraltet
+
+## Searching license texts
+
+search-no-licenses = Keine Lizenz-ID und kein Lizenzname passt zu { $query }.
+search-no-matches = Kein Lizenztext enthält { $pattern }.
+search-summary = { $matches ->
+    [one] Ein Treffer
+   *[other] { $matches } Treffer
+} in { $licenses ->
+    [one] einer Lizenz
+   *[other] { $licenses } Lizenzen
+}.
+
+## Reports
+
+report-title = Lizenzierung von { $project }
+report-summary = Übersicht
+report-files-by-license = Dateien nach Lizenz
+report-no-headers = Keine Datei hat einen SPDX-Header.
+report-file-count = { $count ->
+    [one] Eine Datei
+   *[other] { $count } Dateien
+}
+report-violations = Verstöße gegen die Lizenzrichtlinie
+report-short-format-deprecated = -f ist für report veraltet; verwenden Sie stattdessen --format.
+
+## License database
+
+db-exported = Die SPDX-Lizenzliste { $version } wurde als { $count ->
+    [one] eine Datei
+   *[other] { $count } Dateien
+} nach { $path } exportiert.
+db-imported = Die SPDX-Lizenzliste { $version } ({ $count ->
+    [one] eine Datei
+   *[other] { $count } Dateien
+}) wurde nach { $path } importiert.
+mirror-checksum-mismatch = { $file } stimmt nicht mit der Prüfsumme überein; die Datei wird stattdessen aus dem Netzwerk geladen.
+db-resuming = Ein unterbrochener Export wird mit { $count ->
+    [one] einer bereits geladenen Datei
+   *[other] { $count } bereits geladenen Dateien
+} fortgesetzt; mit --restart wird alles neu geladen.
+db-would-fetch = würde { $url } laden
+db-dry-run = Würde { $count ->
+    [one] eine Datei
+   *[other] { $count } Dateien
+} nach { $path } schreiben.
+
+## Packs
+
+pack-exported = { $settings } wurde mit { $count ->
+    [one] einer Datei
+   *[other] { $count } Dateien
+} nach { $path } exportiert.
+pack-imported = { $settings } wurde in { $path } importiert.
+pack-dry-run = Würde { $count ->
+    [one] eine Datei
+   *[other] { $count } Dateien
+} nach { $path } schreiben.
+
+## Language server
+
+lsp-unknown-license = { $id } ist keine Lizenz-ID der SPDX-Liste.
+lsp-unknown-exception = { $id } ist keine Ausnahme-ID der SPDX-Liste.
+lsp-deprecated-id = { $id } ist veraltet.
+
+## Scanning archives
+
+scan-summary = { $archive }: { $files ->
+    [one] eine Datei
+   *[other] { $files } Dateien
+} durchsucht
+scan-no-license-files = Keine Lizenzdateien gefunden.
+scan-reading = { $archive } wird gelesen…
+scan-unidentified = Nicht erkannte Lizenz
+scan-headers = SPDX-Header
+scan-no-headers = Keine SPDX-Header gefunden.
+scan-snippets = SPDX-Snippets
+scan-snippet-count = { $count ->
+    [one] ein Snippet
+   *[other] { $count } Snippets
+}
+scan-header-files = { $count ->
+    [one] in einer Datei
+   *[other] in { $count } Dateien
+}
+
+## Detecting licenses
+
+detect-likely = { $file } ist höchstwahrscheinlich { $license } ({ $confidence } % Übereinstimmung).
+detect-closest = Keine Lizenz stimmt genau mit { $file } überein; am nächsten kommt { $license } ({ $confidence } % Übereinstimmung).
+detect-no-match = Kein Lizenztext zum Vergleich mit { $file } vorhanden.
+detect-candidates = Weitere Kandidaten:
+
+## Container images
+
+oci-label-patched = Das Image von { $file } wurde mit { $license } gekennzeichnet.
+oci-label-unchanged = Das Image von { $file } ist bereits mit { $license } gekennzeichnet.
+
+## Verifying SPDX documents
+
+verify-consistent = Die Lizenzen von { $sbom } stimmen mit dem Projekt überein.
+verify-undetected = { $package } deklariert { $license }, aber keine Lizenzdatei und kein Header enthält sie.
+verify-undeclared = { $file } enthält { $license }, was das SPDX-Dokument nicht erwähnt.
+verify-missing-file = { $file } ist im SPDX-Dokument aufgeführt, existiert aber nicht.
+verify-file-mismatch = { $file } ist mit { $declared } aufgeführt, sein Header nennt aber { $found }.
+verify-file-no-header = { $file } ist mit { $declared } aufgeführt, hat aber keinen SPDX-Header.
+verify-unfilled = { $file } enthält noch die Platzhalter { $placeholders }.
+
+## Syncing manifests
+
+sync-manifest-updated = Die Lizenz von { $file } ist jetzt { $license }.
+sync-manifest-in-sync = Die Manifeste geben { $license } an.
+sync-manifest-mismatch = { $file } gibt { $declared } statt { $license } an.
+sync-manifest-missing = { $file } gibt keine Lizenz statt { $license } an.
+
+## License details
+
+info-reference = Referenz:
+info-license-id = Lizenz-ID:
+info-exception-id = Ausnahme-ID:
+info-combined-with = Häufig kombiniert mit:
+info-exception-text = Ausnahmetext:
+info-comments = Anmerkungen:
+info-see-also = Siehe auch:
+info-supported = Unterstützte Lizenz-ID:
+info-osi-approved = Von der OSI anerkannt:
+info-fsf-libre = Von der FSF als frei eingestuft:
+info-fsf-categories = FSF-Kategorien:
+info-unknown = unbekannt
+info-asset-categories = Gedacht für:
+info-link-check = Linkprüfung: { $dead ->
+    [0] alle Links erreichbar
+    [one] ein toter Link
+   *[other] { $dead } tote Links
+}
+info-debian-name = Debian (DEP-5):
+info-fedora-name = Fedora (veraltet):
+info-successors = Ersetzt durch:
+info-permissions = Erlaubt:
+info-conditions = Bedingungen:
+info-limitations = Einschränkungen:
+rule-commercial-use = kommerzielle Nutzung
+rule-modifications = Veränderung
+rule-distribution = Weitergabe
+rule-patent-use = Patentnutzung
+rule-private-use = private Nutzung
+rule-include-copyright = Urheberrechtsvermerk beifügen
+rule-document-changes = Änderungen dokumentieren
+rule-disclose-source = Quellcode offenlegen
+rule-network-use-disclose = Quellcode bei Netzwerknutzung offenlegen
+rule-same-license = gleiche Lizenz
+rule-same-license-file = gleiche Lizenz (Datei)
+rule-same-license-library = gleiche Lizenz (Bibliothek)
+rule-liability = Haftung
+rule-warranty = Gewährleistung
+rule-trademark-use = Markennutzung
+fsf-free = frei
+fsf-nonfree = unfrei
+fsf-gpl-2-compatible = GPLv2-kompatibel
+fsf-gpl-3-compatible = GPLv3-kompatibel
+fsf-fdl-compatible = FDL-kompatibel
+info-deprecated-version = Veraltet seit Version:
@@ -0,0 +1,637 @@
+# English messages for lictool.
+#
+# This catalog is the reference: every other catalog should provide
+# the same messages, and missing ones fall back to the text below.
+
+## Errors
+
+error-prefix = Error:
+error-caused-by = Caused by:
+error-not-found = No license found matching the ID provided.
+error-file-not-found = { $path } does not exist.
+error-license-not-found = No license found matching { $id }. Did you mean: { $suggestions }?
+error-already-exists = The { $file } file already exists.
+error-missing-directory = The { $dir } directory does not exist; pass --parents to create it.
+error-unencodable = The character { $ch } cannot be encoded as { $encoding }.
+error-hook-failed = The post-write hook `{ $command }` failed with { $status }.
+error-unknown-profile = The profile { $name } is not defined in any configuration file.
+error-missing-license-id = No license ID was given and no default-license is configured.
+error-license-drift = { $file } differs from the canonical { $license } text.
+error-compound-license = { $expression } combines several licenses; compare the file of each of them instead.
+error-compound-replace = { $expression } is not a single license; replace one license at a time.
+error-compound-before-with = { $expression } combines several licenses and cannot stand before WITH.
+error-invalid-expression = Invalid license expression `{ $expression }`: { $reason }
+error-policy-violation = { $expression } is not accepted by the license policy.
+error-unknown-licenses = Unknown license IDs: { $ids }
+error-unfilled-placeholders = The license still contains placeholders: { $placeholders }
+error-missing-field-value = No { $field } was found without prompting; pass { $flag } or set it in the configuration.
+error-placeholder-violations = { $count ->
+    [one] One unfilled placeholder was found.
+   *[other] { $count } unfilled placeholders were found.
+}
+error-header-conflicts = { $count ->
+    [one] One file has
+   *[other] { $count } files have
+} an SPDX header conflicting with the project license.
+error-missing-asset-dirs = None of { $dirs } exists; pass the files or directories to license.
+error-misnamed-license-files = { $count ->
+    [one] One license file is
+   *[other] { $count } license files are
+} not named by the convention; pass --fix to rename them.
+error-missing-license-ref-text = { $id } is a custom license; add its text as [overrides.{ $id }] with text = "<file>".
+error-invalid-license-ref = { $id } is not a valid custom license ID; it must start with LicenseRef- followed by letters, digits, dots or dashes.
+error-unsupported-file-type = No comment style is known for { $file }; configure one under [header.languages].
+error-empty-family = No license ID starts with { $prefix }.
+error-missing-release-asset = Release { $version } has no { $name } asset for this platform; install it with `cargo install lictool` instead.
+error-checksum-mismatch = The downloaded binary does not match its checksum (expected { $expected }, got { $actual }); nothing was replaced.
+error-diagnostics-failed = { $count ->
+    [one] One check
+   *[other] { $count } checks
+} failed.
+error-unsupported-archive = { $path } is not a supported archive; expected a .tar, .tar.gz, .tgz, .crate, .zip, .jar or .whl file.
+error-not-ready-to-publish = { $count ->
+    [one] One problem has
+   *[other] { $count } problems have
+} to be fixed before publishing.
+error-cargo-package = Could not list the files of the package with `cargo package --list`
+error-cargo-metadata = Could not list the dependencies of the project with `cargo metadata`
+error-invalid-pattern = Invalid pattern { $pattern }: { $reason }
+error-missing-headers = { $count ->
+    [one] One file lacks
+   *[other] { $count } files lack
+} an SPDX header.
+error-malformed-snippets = { $count ->
+    [one] One SPDX snippet is
+   *[other] { $count } SPDX snippets are
+} not marked correctly.
+error-invalid-dataset = { $path } is not a license dataset exported with `lictool db export`.
+error-corrupted-dataset = { $file } of { $path } does not match its checksum; the dataset was not imported.
+error-corrupted-mirror = { $file } of the imported mirror does not match its checksum; run `lictool db import` again.
+error-unknown-command = No command { $name } exists, and no lictool-{ $name } plugin was found on the PATH.
+error-unknown-command-suggestion = No command { $name } exists, and no lictool-{ $name } plugin was found on the PATH. Did you mean { $suggestion }?
+error-run-plugin = Could not run the plugin { $plugin }
+error-plugin-failed = The plugin lictool-{ $name } failed with exit code { $code }.
+error-invalid-script = The placeholder script { $path } failed: { $message }
+error-lictool-not-runnable = Could not run lictool. Install it with `cargo install lictool`.
+error-journal = Could not record the changes in the undo journal.
+error-unsupported-format = --format { $format } is not supported by this command or its options.
+error-invalid-sbom = { $path } is not an SPDX document in JSON form.
+error-sbom-discrepancies = { $count ->
+    [one] One discrepancy was
+   *[other] { $count } discrepancies were
+} found between the SPDX document and the project.
+error-invalid-pack = { $path } is not a pack exported with `lictool pack export`.
+error-aborted = Aborted.
+error-no-manifest = No Cargo.toml, package.json or pyproject.toml was found.
+error-undetected-license = The license of { $file } could not be detected; pass its SPDX expression instead.
+error-manifests-out-of-sync = { $count ->
+    [one] One manifest does
+   *[other] { $count } manifests do
+} not declare the license of the license file; run `lictool sync-manifest` to update them.
+error-denied-dependencies = { $count ->
+    [one] One dependency has
+   *[other] { $count } dependencies have
+} a license the policy rejects.
+error-unknown = An unknown error occurred: { $error }
+error-read-git-history = Could not read the authors from the git history
+error-current-dir = Failed to read the current directory
+error-read-file = Failed to read { $path }
+error-parse-file = Failed to parse { $path }
+error-confirmation-required = Refusing to remove files without confirmation; pass --yes to proceed.
+error-remove-file = Failed to remove { $path }
+error-nothing-to-undo = There is no operation to undo.
+error-no-license-file = No license file was found; add one with `lictool add` first.
+error-changed-since-operation = Changed since the last operation, use --force to revert anyway: { $files }
+error-config-not-found = The configuration file { $path } does not exist.
+error-config-key-not-set = { $key } is not set.
+error-unknown-config-keys = { $file } has unknown keys:
+    { $keys }
+error-write-file = Failed to write { $path }
+error-invalid-baseline = { $path } is not a valid baseline file
+error-request = Failed to request { $url }
+error-proxy = Invalid proxy URL { $url }
+error-ca-bundle = Failed to load the CA certificates of { $path }
+error-http-status = { $url } responded with { $status } ({ $origin })
+error-decode-response = Failed to decode the response from { $url } ({ $status }, { $origin })
+http-origin-cache = served from cache
+http-origin-network = fetched from the network
+http-origin-mirror = read from the imported mirror
+
+## Validation
+
+invalid-year = `{ $year }` is not a year between { $min } and { $max }
+invalid-year-range = The range `{ $range }` ends before it starts
+year-no-git-history = from-git needs a git repository with at least one commit
+invalid-email = `{ $email }` is not a valid email address
+invalid-mode = `{ $mode }` is not a valid octal file mode
+invalid-variable = `{ $variable }` is not of the form KEY=VALUE
+invalid-source = `{ $source }` is not spdx, github or the URL of a mirror
+
+## Prompts
+
+prompt-select-license = Select a license
+prompt-field = Please enter the { $field }
+prompt-create-directory = The { $dir } directory does not exist. Create it?
+prompt-new-file-name = Please specify a new file name to avoid overwriting.
+prompt-confirm-remove = Remove the license?
+field-owner = author's name
+field-year = year of creation
+field-repo = program's name
+field-email = email
+review-prompt = Does the preview look right?
+review-write = Write the license
+review-edit = Edit the { $field }
+review-change-license = Choose a different license
+review-abort = Abort
+init-public-domain-note = { $license } places no conditions on reuse: anyone may copy, modify and distribute the work for any purpose, without giving credit. There are no details to fill in.
+init-prompt-another = Add another license, such as for dual licensing?
+
+## Progress
+
+fetching-licenses = Fetching SPDX license list…
+fetching-exceptions = Fetching SPDX exception list…
+fetching-license-texts = Fetching license texts…
+fetching-license = Fetching the { $license } license…
+fetching-license-source = Downloading the license text…
+fetching-pack = Downloading the pack…
+fetching-latest-release = Checking for a newer release…
+downloading-release = Downloading lictool { $version }…
+fetching-licenses-details = Fetching { $count ->
+    [one] one license…
+   *[other] { $count } licenses…
+}
+
+## Listing licenses
+
+list-column-id = ID
+list-column-osi = OSI
+list-column-fsf = FSF
+list-column-supported = Supported
+list-column-name = Name
+
+## License picker
+
+picker-group-custom = Custom templates
+picker-group-default = Default
+picker-group-favorites = Favorites
+picker-group-recent = Recently used
+picker-group-all = All licenses
+picker-filter-osi = OSI only
+picker-filter-deprecated = hide deprecated
+picker-filter-popular = popular only
+browse-search = Search:
+browse-keys = ↑↓ move  PgUp/PgDn scroll  ^O/^D/^P filter  Enter initialize  Esc quit
+browse-no-details = The details of this license could not be fetched.
+
+## Deprecated licenses
+
+deprecated-prompt = { $license } is deprecated; which license should be used instead?
+deprecated-keep = Keep { $license }
+deprecated-warning = { $license } is deprecated; consider { $successors } instead
+
+## Summaries
+
+file-created = Successfully created { $file } file.
+file-backed-up = Backed up { $file } to { $backup }.
+license-written-stdout = Wrote the license to stdout.
+favorite-added = Added { $license } to favorites.
+favorite-removed = Removed { $license } from favorites.
+license-ref-registered = Registered { $id } with its text in { $file }.
+license-committed = Committed the { $license } license as { $commit }.
+
+## Configuration
+
+config-unknown-key = unknown key { $key }
+config-unknown-key-suggestion = unknown key { $key }, did you mean { $suggestion }?
+config-valid = { $file } is valid.
+config-no-files = No configuration file was found.
+config-set = Set { $key } in { $file }.
+
+## Choosing a license
+
+choose-prompt-commercial = May others use the project commercially?
+choose-prompt-permissive = May others include the project in proprietary software without sharing their changes?
+choose-prompt-attribution = Must reusers keep your copyright notice?
+choose-prompt-library = Is the project a library that software under other licenses should be able to use?
+choose-prompt-network = Must the source be shared when the software is offered over a network?
+choose-prompt-patents = Do you need contributors to grant their patents explicitly?
+choose-prompt-license = Recommended licenses
+
+## Creative Commons
+
+cc-prompt-attribution = Must reusers give you credit?
+cc-prompt-commercial = May the work be used commercially?
+cc-prompt-adaptations = May adapted versions of the work be shared?
+cc-prompt-share-alike = Must adaptations be shared under the same license?
+cc-chosen = { $license } fits these conditions.
+cc-mapped = Mapped { $pattern } to { $license }.
+cc-remapped = Mapped { $pattern } to { $license } instead of { $previous }.
+
+## Dry runs
+
+dry-run-create = would be created ({ $lines ->
+    [one] one line
+   *[other] { $lines } lines
+})
+dry-run-modify = would change (+{ $added } -{ $removed })
+dry-run-unchanged = would stay unchanged
+
+## Removing licenses
+
+rm-plan = The following changes will be made:
+rm-manifest-license = remove the license field
+rm-config-default-license = remove default-license
+rm-nothing = No license files or license fields were found.
+rm-cancelled = Nothing was removed.
+rm-done = Removed the license.
+
+## Switching licenses
+
+switch-manifest-license = set the license field to { $license }
+switch-config-default-license = set default-license to { $license }
+switch-kept = { $field }: { $value }
+switch-done = Switched the license to { $license }.
+
+## Status
+
+status-license = License
+status-manifests = Manifests
+status-headers = Headers
+status-policy = Policy
+status-no-license-file = no license file
+status-undetected = unrecognized license text
+status-no-manifests = no license declared
+status-no-source-files = no files that can hold a header
+status-header-coverage = { $percent }% ({ $covered }/{ $total } files)
+status-no-violations = no violations
+
+## License list updates
+
+list-update-available = SPDX license list { $upstream } is out, while { $cached } is cached; run `lictool cache refresh` to fetch it.
+
+## Cache
+
+cache-status = { $size } in { $count ->
+    [one] one file
+   *[other] { $count } files
+} at { $dir }.
+cache-age = Oldest entry cached { $oldest }, newest { $newest }.
+cache-empty = The cache at { $dir } is empty.
+cache-cleared = Cleared { $size } from { $dir }.
+cache-dry-run = Would delete { $size } from { $dir }.
+cache-mirror-removed = Removed the imported dataset at { $dir }.
+cache-mirror-dry-run = Would remove the imported dataset at { $dir }.
+cache-mirror-shadows = Licenses are still read from the dataset imported at { $dir }; run `lictool cache clear --mirror` to use the network again.
+cache-refreshed = Revalidated the SPDX license list { $version } and cached the details of { $count ->
+    [one] one license
+   *[other] { $count } licenses
+}.
+age-just-now = just now
+age-minutes = { $count ->
+    [one] a minute ago
+   *[other] { $count } minutes ago
+}
+age-hours = { $count ->
+    [one] an hour ago
+   *[other] { $count } hours ago
+}
+age-days = { $count ->
+    [one] a day ago
+   *[other] { $count } days ago
+}
+
+## Self-update
+
+update-up-to-date = lictool { $version } is the latest release.
+update-available = lictool { $latest } is available; this is { $current }.
+update-would-replace = Would replace { $path }.
+update-done = Updated { $path } to lictool { $version }.
+
+## Diagnostics
+
+doctor-connectivity = SPDX
+doctor-connectivity-ok = { $url } responded in { $millis } ms.
+doctor-connectivity-status = { $url } responded with { $status }.
+doctor-fix-connectivity = Check your network and proxy settings, such as HTTPS_PROXY; licenses fetched before are still served from the cache.
+doctor-cache = Cache
+doctor-cache-size = { $size } in { $files } files at { $dir }.
+doctor-cache-unwritable = { $dir } cannot be written to: { $error }
+doctor-fix-cache-permissions = Make { $dir } writable by your user.
+doctor-fix-cache-size = Run `lictool cache clear` to free space; the cache is rebuilt as needed.
+doctor-fix-cache-corrupt = Run `lictool cache clear`; the cache is rebuilt as needed.
+doctor-config = Configuration
+doctor-config-ok = Read from { $files }.
+doctor-fix-config = Fix the file as described, then run `lictool config validate`.
+doctor-git = Git
+doctor-git-ok = Repository at { $dir }, committing as { $name }.
+doctor-git-no-repository = The current directory is not in a git repository.
+doctor-fix-git-repository = Run `git init` to use --commit.
+doctor-git-no-identity = No user.name or user.email is configured.
+doctor-fix-git-identity = Run `git config --global user.name "Your Name"` and `git config --global user.email you@example.com`.
+doctor-terminal = Terminal
+doctor-terminal-ok = { $columns } columns, { $colors ->
+    [yes] with colors
+   *[no] without colors
+}.
+doctor-terminal-none = Not an interactive terminal, so prompts are unavailable.
+doctor-fix-terminal = Pass values as flags instead, such as --yes or --no-input.
+
+## Undo
+
+undo-restored = restored { $file }
+undo-removed = removed { $file }
+undo-would-restore = would restore { $file }
+undo-would-remove = would remove { $file }
+undo-done = Reverted `{ $command }` from { $time }.
+
+## Checks
+
+check-no-drift = { $file } matches the canonical { $license } text.
+check-stdin = The license text from stdin
+check-no-placeholders = No unfilled placeholders were found.
+check-unfilled-placeholder = unfilled placeholder { $placeholder }
+check-drift-hunk = Line { $line }:
+check-drift-paragraphs = { $matching } of { $total } canonical paragraphs are unchanged ({ $percent }%).
+check-no-header-conflicts = No SPDX header conflicts with the project license.
+check-header-conflict = header declares { $declared }, expected { $expected }
+check-ignored-file = skipped because of { $directive }
+check-no-misnamed-license-files = License files follow the naming convention.
+check-misnamed-license-file = should be named { $expected }
+check-license-file-renamed = Renamed { $file } to { $expected }.
+check-license-file-rename-planned = Would rename { $file } to { $expected }.
+check-license-file-name-taken = { $file } was not renamed, as { $expected } already exists.
+check-baseline-written = Recorded { $count ->
+    [one] one violation
+   *[other] { $count } violations
+} in { $file }.
+check-baseline-suppressed = { $count ->
+    [one] One known violation was
+   *[other] { $count } known violations were
+} ignored per the baseline.
+
+## Headers
+
+header-missing = { $file } has no SPDX header
+header-added = { $file } now has an SPDX header
+header-all-present = Every file that can hold a header has an SPDX one.
+header-snippet-unterminated = { $file }:{ $line } begins an SPDX snippet that never ends
+header-snippet-no-license = { $file }:{ $line } begins an SPDX snippet without an SPDX-License-Identifier
+header-watching = Watching for changes; press Ctrl+C to stop.
+header-replaced = { $file } now names { $to } instead of { $from }
+header-replace-none = No SPDX header names { $license }.
+header-replace-skipped = { $file } was left unchanged: { $reason }
+header-adding = Adding SPDX headers…
+header-replacing = Replacing SPDX headers…
+header-missing-count = { $count ->
+    [0] No file lacks an SPDX header.
+    [one] One file lacks an SPDX header.
+   *[other] { $count } files lack an SPDX header.
+}
+
+## Badges
+
+badge-injected = Put the { $license } license section into { $file }.
+
+## License expressions
+
+expression-unexpected-end = unexpected end of expression
+expression-unexpected-token = unexpected `{ $token }`
+expression-invalid-id = `{ $id }` is not a valid license identifier
+expression-valid = { $expression } is a valid license expression.
+
+## Publishing
+
+listing-package-files = Listing the files of the package…
+publish-license-ready = The license fields and files are ready for publishing.
+publish-missing-license = Cargo.toml sets neither license nor license-file.
+publish-invalid-license = The license field { $license } is not a valid SPDX expression: { $reason }
+publish-missing-license-file = The license-file { $path } does not exist.
+publish-excluded-license-file = { $path } is not part of the package; check include and exclude in Cargo.toml.
+
+## Authors
+
+authors-updated = Successfully updated { $file } file.
+authors-unchanged = { $file } is up to date.
+file-created-partial = Created { $file }, but these placeholders are left: { $placeholders }
+
+## Policy
+
+policy-all-of = requires all of
+policy-any-of = requires any of
+policy-allowed = allowed by policy
+policy-denied = denied by policy
+policy-not-allowed = not in the allow list
+policy-no-allow-list = not denied, and no allow list is configured
+
+## Auditing dependencies
+
+audit-flag-missing = no SPDX expression
+audit-flag-invalid = invalid expression
+audit-flag-deprecated = deprecated ID
+audit-flag-not-osi-approved = not OSI approved
+audit-flag-copyleft = copyleft
+audit-flag-denied = denied by policy
+audit-no-license = No license expression
+audit-summary = { $packages ->
+    [one] One dependency
+   *[other] { $packages } dependencies
+} under { $licenses ->
+    [one] one license
+   *[other] { $licenses } licenses
+}.
+
+## Placeholders
+
+placeholders-heading = Placeholders in { $license }:
+placeholders-others = Other placeholders, not filled by add:
+
+## Filling existing files
+
+fill-nothing-to-do = { $file } has no placeholders left.
+fill-done = Filled the placeholders of { $file }.
+fill-partial = Updated { $file }, but these placeholders are left: { $placeholders }
+bump-year-current = The copyright years of { $file } are up to date.
+bump-year-done = Extended { $count ->
+    [one] one copyright line
+   *[other] { $count } copyright lines
+} of { $file } to { $year }.
+
+## License families
+
+family-id = ID
+family-version = Version
+family-variant = Variant
+family-osi = OSI
+family-fsf = FSF
+family-deprecated = Deprecated
+family-only = only
+family-or-later = or later
+
+## Comparing licenses
+
+compare-name = Name
+compare-osi = OSI approved
+compare-fsf = FSF free/libre
+compare-deprecated = Deprecated
+
+## Searching license texts
+
+search-no-licenses = No license ID or name matches { $query }.
+search-no-matches = No license text matches { $pattern }.
+search-summary = { $matches ->
+    [one] One match
+   *[other] { $matches } matches
+} in { $licenses ->
+    [one] one license
+   *[other] { $licenses } licenses
+}.
+
+## Reports
+
+report-title = Licensing of { $project }
+report-summary = Summary
+report-files-by-license = Files by license
+report-no-headers = No file has an SPDX header.
+report-file-count = { $count ->
+    [one] One file
+   *[other] { $count } files
+}
+report-violations = Policy violations
+report-short-format-deprecated = -f is deprecated for report; use --format instead.
+
+## License database
+
+db-exported = Exported the SPDX license list { $version } as { $count ->
+    [one] one file
+   *[other] { $count } files
+} to { $path }.
+db-imported = Imported the SPDX license list { $version } ({ $count ->
+    [one] one file
+   *[other] { $count } files
+}) into { $path }.
+mirror-checksum-mismatch = { $file } does not match its checksum; fetching it from the network instead.
+db-resuming = Resuming an interrupted export with { $count ->
+    [one] one file
+   *[other] { $count } files
+} already fetched; pass --restart to fetch everything again.
+db-would-fetch = would fetch { $url }
+db-dry-run = Would write { $count ->
+    [one] one file
+   *[other] { $count } files
+} to { $path }.
+
+## Packs
+
+pack-exported = Exported { $settings } with { $count ->
+    [one] one file
+   *[other] { $count } files
+} to { $path }.
+pack-imported = Imported { $settings } into { $path }.
+pack-dry-run = Would write { $count ->
+    [one] one file
+   *[other] { $count } files
+} to { $path }.
+
+## Language server
+
+lsp-unknown-license = { $id } is not a license ID of the SPDX list.
+lsp-unknown-exception = { $id } is not an exception ID of the SPDX list.
+lsp-deprecated-id = { $id } is deprecated.
+
+## Scanning archives
+
+scan-summary = { $archive }: { $files ->
+    [one] one file
+   *[other] { $files } files
+} scanned
+scan-no-license-files = No license files found.
+scan-reading = Reading { $archive }…
+scan-unidentified = Unidentified license
+scan-headers = SPDX headers
+scan-no-headers = No SPDX headers found.
+scan-snippets = SPDX snippets
+scan-snippet-count = { $count ->
+    [one] one snippet
+   *[other] { $count } snippets
+}
+scan-header-files = { $count ->
+    [one] in one file
+   *[other] in { $count } files
+}
+
+## Detecting licenses
+
+detect-likely = { $file } is most likely { $license } ({ $confidence }% confidence).
+detect-closest = No license matches { $file } closely; the closest is { $license } ({ $confidence }% confidence).
+detect-no-match = No license text to compare { $file } with.
+detect-candidates = Other candidates:
+
+## Container images
+
+oci-label-patched = Labelled the image of { $file } with { $license }.
+oci-label-unchanged = The image of { $file } is already labelled with { $license }.
+
+## Verifying SPDX documents
+
+verify-consistent = The licenses of { $sbom } match the project.
+verify-undetected = { $package } declares { $license }, but no license file or header has it.
+verify-undeclared = { $file } has { $license }, which the SPDX document does not mention.
+verify-missing-file = { $file } is listed by the SPDX document but does not exist.
+verify-file-mismatch = { $file } is listed with { $declared }, but its header has { $found }.
+verify-file-no-header = { $file } is listed with { $declared }, but has no SPDX header.
+verify-unfilled = { $file } still contains the placeholders { $placeholders }.
+
+## Syncing manifests
+
+sync-manifest-updated = Set the license of { $file } to { $license }.
+sync-manifest-in-sync = The manifests declare { $license }.
+sync-manifest-mismatch = { $file } declares { $declared } instead of { $license }.
+sync-manifest-missing = { $file } declares no license instead of { $license }.
+
+## License details
+
+info-reference = Reference:
+info-license-id = License ID:
+info-exception-id = Exception ID:
+info-combined-with = Commonly Combined With:
+info-exception-text = Exception Text:
+info-comments = License Comments:
+info-see-also = See Also:
+info-supported = Is Supported License ID:
+info-osi-approved = Is OSI Approved:
+info-fsf-libre = Is FSF Free/Libre:
+info-fsf-categories = FSF Categories:
+info-unknown = unknown
+info-asset-categories = Intended for:
+info-link-check = Link Check: { $dead ->
+    [0] all links resolve
+    [one] one dead link
+   *[other] { $dead } dead links
+}
+info-debian-name = Debian (DEP-5):
+info-fedora-name = Fedora (legacy):
+info-successors = Replaced By:
+info-permissions = Permissions:
+info-conditions = Conditions:
+info-limitations = Limitations:
+rule-commercial-use = commercial use
+rule-modifications = modification
+rule-distribution = distribution
+rule-patent-use = patent use
+rule-private-use = private use
+rule-include-copyright = include copyright
+rule-document-changes = document changes
+rule-disclose-source = disclose source
+rule-network-use-disclose = disclose source for network use
+rule-same-license = same license
+rule-same-license-file = same license (file)
+rule-same-license-library = same license (library)
+rule-liability = liability
+rule-warranty = warranty
+rule-trademark-use = trademark use
+fsf-free = free
+fsf-nonfree = nonfree
+fsf-gpl-2-compatible = GPLv2-compatible
+fsf-gpl-3-compatible = GPLv3-compatible
+fsf-fdl-compatible = FDL-compatible
+info-deprecated-version = Deprecated Version:
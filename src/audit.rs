@@ -0,0 +1,476 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+use anyhow::{anyhow, Context};
+use color_print::{cformat, cprintln};
+use serde::{Deserialize, Serialize};
+use tokio::process::Command;
+
+use crate::{
+    expression::{Expression, LicenseTerm},
+    i18n::t,
+    policy::PolicyConfig,
+    spdx::License,
+    util::errors::{Error, LictoolResult},
+};
+
+/// The prefixes of the IDs of copyleft licenses, which require
+/// derived works or changes to be shared under the same terms.
+const COPYLEFT_PREFIXES: [&str; 12] = [
+    "GPL-",
+    "LGPL-",
+    "AGPL-",
+    "MPL-",
+    "EPL-",
+    "EUPL-",
+    "CDDL-",
+    "OSL-",
+    "CPL-",
+    "MS-RL",
+    "SSPL-",
+    "CC-BY-SA-",
+];
+
+/// The output of `cargo metadata`, reduced to the licensing of the
+/// packages.
+#[derive(Debug, Deserialize)]
+struct CargoMetadata {
+    packages: Vec<CargoPackage>,
+    /// The IDs of the packages of the workspace itself.
+    workspace_members: Vec<String>,
+    /// The resolved dependency graph.
+    resolve: Option<CargoResolve>,
+}
+
+/// The dependency graph of `cargo metadata`.
+#[derive(Debug, Deserialize)]
+struct CargoResolve {
+    nodes: Vec<CargoNode>,
+}
+
+/// A package of the dependency graph with its direct dependencies.
+#[derive(Debug, Deserialize)]
+struct CargoNode {
+    id: String,
+    #[serde(default)]
+    deps: Vec<CargoNodeDep>,
+}
+
+/// A direct dependency of a package.
+#[derive(Debug, Deserialize)]
+struct CargoNodeDep {
+    pkg: String,
+    #[serde(default)]
+    dep_kinds: Vec<CargoDepKind>,
+}
+
+/// How a package depends on another: `None` for a normal dependency,
+/// or `dev` or `build`.
+#[derive(Debug, Deserialize)]
+struct CargoDepKind {
+    kind: Option<String>,
+}
+
+impl CargoMetadata {
+    /// Returns the IDs of the packages the workspace members need at
+    /// run time, following normal dependencies only, or `None`
+    /// without a dependency graph.
+    fn runtime_packages(&self) -> Option<BTreeSet<&str>> {
+        let nodes: BTreeMap<&str, &CargoNode> = self
+            .resolve
+            .as_ref()?
+            .nodes
+            .iter()
+            .map(|node| (node.id.as_str(), node))
+            .collect();
+        let mut reached: BTreeSet<&str> = BTreeSet::new();
+        let mut pending: Vec<&str> = self.workspace_members.iter().map(String::as_str).collect();
+        while let Some(id) = pending.pop() {
+            if !reached.insert(id) {
+                continue;
+            }
+            let deps = nodes
+                .get(id)
+                .map(|node| node.deps.as_slice())
+                .unwrap_or_default();
+            for dep in deps {
+                if dep.dep_kinds.iter().any(|kind| kind.kind.is_none()) {
+                    pending.push(&dep.pkg);
+                }
+            }
+        }
+        Some(reached)
+    }
+
+    /// Returns the dependencies, without the packages of the
+    /// workspace and, unless `include_dev` is set, without those
+    /// only needed to build or test it.
+    fn dependencies(
+        self,
+        include_dev: bool,
+    ) -> Vec<CargoPackage> {
+        let runtime = if include_dev {
+            None
+        } else {
+            self.runtime_packages()
+        };
+        let mut packages: Vec<CargoPackage> = self
+            .packages
+            .iter()
+            .filter(|package| !self.workspace_members.contains(&package.id))
+            .filter(|package| {
+                runtime
+                    .as_ref()
+                    .is_none_or(|runtime| runtime.contains(package.id.as_str()))
+            })
+            .cloned()
+            .collect();
+        packages.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        packages
+    }
+}
+
+/// A package of `cargo metadata`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct CargoPackage {
+    pub(crate) id: String,
+    pub(crate) name: String,
+    pub(crate) version: String,
+    pub(crate) license: Option<String>,
+}
+
+/// A concern about the license of a dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum AuditFlag {
+    /// The package declares no SPDX expression, at most a license
+    /// file.
+    Missing,
+    /// The expression does not parse or uses unknown IDs.
+    Invalid,
+    /// A license of the expression has a deprecated ID.
+    Deprecated,
+    /// The package cannot be used without a license the OSI has not
+    /// approved.
+    NotOsiApproved,
+    /// The package cannot be used without a copyleft license.
+    Copyleft,
+    /// The configured policy rejects the expression.
+    Denied,
+}
+
+impl AuditFlag {
+    /// Returns the label of the flag shown next to a license.
+    pub fn label(&self) -> String {
+        match self {
+            AuditFlag::Missing => t!("audit-flag-missing"),
+            AuditFlag::Invalid => t!("audit-flag-invalid"),
+            AuditFlag::Deprecated => t!("audit-flag-deprecated"),
+            AuditFlag::NotOsiApproved => t!("audit-flag-not-osi-approved"),
+            AuditFlag::Copyleft => t!("audit-flag-copyleft"),
+            AuditFlag::Denied => t!("audit-flag-denied"),
+        }
+    }
+}
+
+/// A dependency with the concerns about its license.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct AuditedPackage {
+    pub(crate) name: String,
+    pub(crate) version: String,
+    /// The declared expression, in its canonical form if it is
+    /// valid.
+    pub(crate) license: Option<String>,
+    pub(crate) flags: Vec<AuditFlag>,
+}
+
+/// Lists the dependencies of the Cargo project in `dir`, without the
+/// packages of its workspace.
+///
+/// Dev- and build-dependencies are not distributed, so they are left
+/// out unless `include_dev` is set.
+///
+/// # Errors
+/// - If cargo cannot be run or fails, such as outside of a project.
+pub(crate) async fn cargo_dependencies(
+    dir: &Path,
+    include_dev: bool,
+) -> LictoolResult<Vec<CargoPackage>> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--format-version", "1", "--quiet"])
+        .current_dir(dir)
+        .output()
+        .await
+        .with_context(|| t!("error-cargo-metadata"))?;
+    if !output.status.success() {
+        return Err(
+            anyhow!(String::from_utf8_lossy(&output.stderr).trim().to_string())
+                .context(t!("error-cargo-metadata")),
+        );
+    }
+    let metadata: CargoMetadata =
+        serde_json::from_slice(&output.stdout).with_context(|| t!("error-cargo-metadata"))?;
+    Ok(metadata.dependencies(include_dev))
+}
+
+/// Checks whether an expression cannot be satisfied without a license
+/// for which `matches` holds, such as `GPL-3.0-only` but not
+/// `MIT OR GPL-3.0-only`.
+fn unavoidable(
+    expression: &Expression,
+    matches: &impl Fn(&LicenseTerm) -> bool,
+) -> bool {
+    match expression {
+        Expression::License(term) => matches(term),
+        Expression::And(operands) => operands.iter().any(|operand| unavoidable(operand, matches)),
+        Expression::Or(operands) => operands.iter().all(|operand| unavoidable(operand, matches)),
+    }
+}
+
+/// Audits the declared licenses of dependencies against the SPDX
+/// license list and the policy.
+///
+/// The legacy `MIT/Apache-2.0` form crates.io still accepts is read
+/// as `MIT OR Apache-2.0`. Without an expression, a package is only
+/// rejected by a policy with an allow list.
+pub(crate) fn audit(
+    packages: &[CargoPackage],
+    licenses: &[License],
+    policy: &PolicyConfig,
+) -> Vec<AuditedPackage> {
+    packages
+        .iter()
+        .map(|package| {
+            let declared = package.license.as_deref().map(|license| {
+                license
+                    .split('/')
+                    .map(str::trim)
+                    .collect::<Vec<_>>()
+                    .join(" OR ")
+            });
+            let mut flags = Vec::new();
+            let find = |term: &LicenseTerm| licenses.iter().find(|license| license.id == term.id);
+            let canonical = declared.as_deref().map(|declared| {
+                let expression = declared.parse::<Expression>()?;
+                // Canonical IDs replace deprecated ones, so these are
+                // looked for in the declared expression.
+                let deprecated = expression
+                    .terms()
+                    .iter()
+                    .any(|term| find(term).is_some_and(|license| license.is_deprecated_license_id));
+                Ok::<_, Error>((expression.canonicalize(licenses)?, deprecated))
+            });
+            let license = match canonical {
+                None => {
+                    flags.push(AuditFlag::Missing);
+                    None
+                }
+                Some(Err(_)) => {
+                    flags.push(AuditFlag::Invalid);
+                    declared
+                }
+                Some(Ok((expression, deprecated))) => {
+                    if deprecated {
+                        flags.push(AuditFlag::Deprecated);
+                    }
+                    if unavoidable(&expression, &|term| {
+                        find(term).is_some_and(|license| license.is_osi_approved == Some(false))
+                    }) {
+                        flags.push(AuditFlag::NotOsiApproved);
+                    }
+                    if unavoidable(&expression, &|term| {
+                        COPYLEFT_PREFIXES
+                            .iter()
+                            .any(|prefix| term.id.starts_with(prefix))
+                    }) {
+                        flags.push(AuditFlag::Copyleft);
+                    }
+                    if !policy.evaluate(&expression).satisfied {
+                        flags.push(AuditFlag::Denied);
+                    }
+                    Some(expression.to_string())
+                }
+            };
+            if (license.is_none() || flags.contains(&AuditFlag::Invalid))
+                && !policy.allow.is_empty()
+            {
+                flags.push(AuditFlag::Denied);
+            }
+            AuditedPackage {
+                name: package.name.clone(),
+                version: package.version.clone(),
+                license,
+                flags,
+            }
+        })
+        .collect()
+}
+
+/// Prints the audited dependencies grouped by license, the most
+/// common license first.
+pub(crate) fn display_audit(packages: &[AuditedPackage]) {
+    let mut groups: BTreeMap<Option<&str>, Vec<&AuditedPackage>> = BTreeMap::new();
+    for package in packages {
+        groups
+            .entry(package.license.as_deref())
+            .or_default()
+            .push(package);
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by_key(|(_, packages)| std::cmp::Reverse(packages.len()));
+    for (license, members) in &groups {
+        let license = license.map_or_else(|| t!("audit-no-license"), str::to_string);
+        let flags: Vec<String> = members[0]
+            .flags
+            .iter()
+            .map(|flag| {
+                match flag {
+                    AuditFlag::Denied | AuditFlag::Invalid => {
+                        cformat!("<red>[{}]</>", flag.label())
+                    }
+                    _ => cformat!("<yellow>[{}]</>", flag.label()),
+                }
+            })
+            .collect();
+        cprintln!(
+            "<bold>{}</> <dim>({})</> {}",
+            license,
+            members.len(),
+            flags.join(" ")
+        );
+        let names: Vec<String> = members
+            .iter()
+            .map(|package| format!("{} {}", package.name, package.version))
+            .collect();
+        cprintln!("  <dim>{}</>", names.join(", "));
+    }
+    cprintln!(
+        "<bold>{}</>",
+        t!(
+            "audit-summary",
+            packages = packages.len(),
+            licenses = groups.len()
+        )
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{audit, AuditFlag, CargoMetadata, CargoPackage};
+    use crate::{policy::PolicyConfig, spdx::License};
+
+    fn license(
+        id: &str,
+        is_osi_approved: bool,
+        is_deprecated_license_id: bool,
+    ) -> License {
+        License {
+            is_deprecated_license_id,
+            details_url: String::new(),
+            name: id.to_string(),
+            id: id.to_string(),
+            is_osi_approved: Some(is_osi_approved),
+            is_fsf_libre: None,
+        }
+    }
+
+    #[test]
+    fn test_audit_dependencies() {
+        let licenses = [
+            license("MIT", true, false),
+            license("Apache-2.0", true, false),
+            license("GPL-3.0-only", true, false),
+            license("GPL-2.0", true, true),
+            license("GPL-2.0-only", true, false),
+            license("JSON", false, false),
+        ];
+        let package = |name: &str, license: Option<&str>| {
+            CargoPackage {
+                id: name.to_string(),
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                license: license.map(str::to_string),
+            }
+        };
+        let packages = [
+            package("dual", Some("MIT/Apache-2.0")),
+            package("either", Some("MIT OR GPL-3.0-only")),
+            package("gpl", Some("GPL-3.0-only")),
+            package("old", Some("GPL-2.0")),
+            package("json", Some("JSON")),
+            package("custom", None),
+            package("typo", Some("MTI")),
+        ];
+        let policy = PolicyConfig {
+            allow: Vec::new(),
+            deny: vec!["GPL-3.0-only".to_string()],
+        };
+        let flags: Vec<(Option<String>, Vec<AuditFlag>)> = audit(&packages, &licenses, &policy)
+            .into_iter()
+            .map(|audited| (audited.license, audited.flags))
+            .collect();
+        assert_eq!(
+            flags,
+            [
+                (Some("MIT OR Apache-2.0".to_string()), vec![]),
+                (Some("MIT OR GPL-3.0-only".to_string()), vec![]),
+                (
+                    Some("GPL-3.0-only".to_string()),
+                    vec![AuditFlag::Copyleft, AuditFlag::Denied]
+                ),
+                (
+                    Some("GPL-2.0-only".to_string()),
+                    vec![AuditFlag::Deprecated, AuditFlag::Copyleft]
+                ),
+                (Some("JSON".to_string()), vec![AuditFlag::NotOsiApproved]),
+                (None, vec![AuditFlag::Missing]),
+                (Some("MTI".to_string()), vec![AuditFlag::Invalid]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_runtime_dependencies() {
+        let metadata: CargoMetadata = serde_json::from_str(
+            r#"{
+                "packages": [
+                    {"id": "app", "name": "app", "version": "0.1.0", "license": "MIT"},
+                    {"id": "serde", "name": "serde", "version": "1.0.0", "license": "MIT"},
+                    {"id": "cc", "name": "cc", "version": "1.0.0", "license": "MIT"},
+                    {"id": "mockito", "name": "mockito", "version": "1.0.0", "license": "MIT"}
+                ],
+                "workspace_members": ["app"],
+                "resolve": {"nodes": [
+                    {"id": "app", "deps": [
+                        {"pkg": "serde", "dep_kinds": [{"kind": null}]},
+                        {"pkg": "cc", "dep_kinds": [{"kind": "build"}]},
+                        {"pkg": "mockito", "dep_kinds": [{"kind": "dev"}]}
+                    ]},
+                    {"id": "serde", "deps": []},
+                    {"id": "cc", "deps": []},
+                    {"id": "mockito", "deps": []}
+                ]}
+            }"#,
+        )
+        .unwrap();
+        let names = |packages: Vec<CargoPackage>| -> Vec<String> {
+            packages.into_iter().map(|package| package.name).collect()
+        };
+        assert_eq!(names(metadata.dependencies(false)), ["serde"]);
+        let metadata: CargoMetadata = serde_json::from_str(
+            r#"{
+                "packages": [
+                    {"id": "app", "name": "app", "version": "0.1.0", "license": "MIT"},
+                    {"id": "cc", "name": "cc", "version": "1.0.0", "license": "MIT"}
+                ],
+                "workspace_members": ["app"]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(names(metadata.dependencies(false)), ["cc"]);
+    }
+}
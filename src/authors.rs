@@ -0,0 +1,127 @@
+use serde::Deserialize;
+
+use crate::util::{git::Contributor, line_ending::glob_matches};
+
+/// The file the authors are written to by default.
+const DEFAULT_AUTHORS_FILE: &str = "AUTHORS";
+
+/// The format of an author line by default.
+const DEFAULT_AUTHOR_FORMAT: &str = "{{name}} <{{email}}>";
+
+/// Settings of the `[authors]` configuration section.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct AuthorsConfig {
+    /// The file the authors are written to; `AUTHORS` by default.
+    pub(crate) file: Option<String>,
+    /// The format of each line, which may use `{{name}}`, `{{email}}`
+    /// and `{{commits}}`.
+    pub(crate) format: Option<String>,
+    /// Text written above the authors, such as a comment explaining
+    /// how the file is generated.
+    pub(crate) header: Option<String>,
+    /// Patterns of names or emails left out, such as `*[bot]` or
+    /// `*@users.noreply.github.com`.
+    pub(crate) exclude: Vec<String>,
+    /// The number of commits an author needs to be listed.
+    pub(crate) min_commits: Option<usize>,
+}
+
+impl AuthorsConfig {
+    /// Returns the file the authors are written to.
+    pub fn file(&self) -> &str {
+        self.file.as_deref().unwrap_or(DEFAULT_AUTHORS_FILE)
+    }
+
+    /// Checks whether a contributor is left out by an `exclude`
+    /// pattern, comparing emails without regard to case.
+    fn excludes(
+        &self,
+        contributor: &Contributor,
+    ) -> bool {
+        self.exclude.iter().any(|pattern| {
+            glob_matches(pattern, &contributor.name)
+                || glob_matches(&pattern.to_lowercase(), &contributor.email.to_lowercase())
+        })
+    }
+
+    /// Renders the authors file listing the contributors, in the
+    /// order given, that have at least `min_commits` commits and are
+    /// not excluded.
+    pub fn render(
+        &self,
+        contributors: &[Contributor],
+        min_commits: Option<usize>,
+    ) -> String {
+        let min_commits = min_commits.or(self.min_commits).unwrap_or(1);
+        let format = self.format.as_deref().unwrap_or(DEFAULT_AUTHOR_FORMAT);
+        let mut content = String::new();
+        if let Some(header) = &self.header {
+            content.push_str(header.trim_end());
+            content.push_str("\n\n");
+        }
+        for contributor in contributors
+            .iter()
+            .filter(|contributor| contributor.commits >= min_commits)
+            .filter(|contributor| !self.excludes(contributor))
+        {
+            let line = format
+                .replace("{{name}}", &contributor.name)
+                .replace("{{email}}", &contributor.email)
+                .replace("{{commits}}", &contributor.commits.to_string());
+            // A line for an author without an email drops the empty
+            // brackets of the default format.
+            content.push_str(line.replace(" <>", "").trim_end());
+            content.push('\n');
+        }
+        content
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::AuthorsConfig;
+    use crate::util::git::Contributor;
+
+    #[test]
+    fn test_render_authors() {
+        let contributor = |name: &str, email: &str, commits: usize| {
+            Contributor {
+                name: name.to_string(),
+                email: email.to_string(),
+                commits,
+            }
+        };
+        let contributors = [
+            contributor("Jane Doe", "jane@example.com", 12),
+            contributor(
+                "dependabot[bot]",
+                "49699333+dependabot[bot]@users.noreply.github.com",
+                7,
+            ),
+            contributor("John Roe", "", 2),
+            contributor("Drive By", "drive@example.com", 1),
+        ];
+        let config = AuthorsConfig {
+            header: Some("# Generated by lictool authors\n".to_string()),
+            exclude: vec!["*[bot]".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.render(&contributors, Some(2)),
+            "# Generated by lictool authors\n\nJane Doe <jane@example.com>\nJohn Roe\n"
+        );
+        let config = AuthorsConfig {
+            format: Some("{{name}} ({{commits}})".to_string()),
+            exclude: vec!["*@USERS.NOREPLY.GITHUB.COM".to_string()],
+            min_commits: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.render(&contributors, None),
+            "Jane Doe (12)\nJohn Roe (2)\n"
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use clap::ValueEnum;
+
+/// The markers around the license section of a README, so that it is
+/// updated in place by later runs.
+const SECTION_START: &str = "lictool:license:start";
+const SECTION_END: &str = "lictool:license:end";
+
+/// The markup a badge and README section are written in.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub(crate) enum Markup {
+    Markdown,
+    Html,
+    /// reStructuredText.
+    Rst,
+}
+
+impl Markup {
+    /// Guesses the markup of a README from its extension, such as
+    /// `Rst` for `README.rst`, defaulting to Markdown.
+    pub(crate) fn from_path(path: &str) -> Self {
+        let path = path.to_lowercase();
+        if path.ends_with(".rst") {
+            Markup::Rst
+        } else if path.ends_with(".html") || path.ends_with(".htm") {
+            Markup::Html
+        } else {
+            Markup::Markdown
+        }
+    }
+
+    /// Wraps a marker in a comment of the markup.
+    fn comment(
+        self,
+        marker: &str,
+    ) -> String {
+        match self {
+            Markup::Markdown | Markup::Html => format!("<!-- {} -->", marker),
+            Markup::Rst => format!(".. {}", marker),
+        }
+    }
+}
+
+/// Returns the URL of a shields.io badge for a license expression.
+///
+/// Dashes and underscores are doubled and spaces turned into
+/// underscores, as shields.io expects in the path of static badges.
+pub(crate) fn badge_url(license: &str) -> String {
+    let message = license
+        .replace('-', "--")
+        .replace('_', "__")
+        .replace(' ', "_");
+    format!("https://img.shields.io/badge/license-{}-blue.svg", message)
+}
+
+/// Renders a license badge linking to the license file.
+pub(crate) fn badge(
+    license: &str,
+    license_file: &str,
+    markup: Markup,
+) -> String {
+    let url = badge_url(license);
+    let alt = format!("License: {}", license);
+    match markup {
+        Markup::Markdown => format!("[![{}]({})]({})", alt, url, license_file),
+        Markup::Html => {
+            format!(
+                "<a href=\"{}\"><img src=\"{}\" alt=\"{}\"></a>",
+                license_file, url, alt
+            )
+        }
+        Markup::Rst => {
+            format!(
+                ".. image:: {}\n   :alt: {}\n   :target: {}",
+                url, alt, license_file
+            )
+        }
+    }
+}
+
+/// Renders a "License" section for a README with the badge of the
+/// license and a sentence linking to the license file, naming the
+/// license with `name`, such as `the MIT License`.
+pub(crate) fn readme_section(
+    license: &str,
+    name: &str,
+    license_file: &str,
+    markup: Markup,
+) -> String {
+    let badge = badge(license, license_file, markup);
+    match markup {
+        Markup::Markdown => {
+            format!(
+                "## License\n\n{}\n\nThis project is licensed under {}. See [{}]({}) for \
+                 details.\n",
+                badge, name, license_file, license_file
+            )
+        }
+        Markup::Html => {
+            format!(
+                "<h2>License</h2>\n\n<p>{}</p>\n\n<p>This project is licensed under {}. See <a \
+                 href=\"{}\">{}</a> for details.</p>\n",
+                badge, name, license_file, license_file
+            )
+        }
+        Markup::Rst => {
+            format!(
+                "License\n=======\n\n{}\n\nThis project is licensed under {}. See `{} <{}>`_ for \
+                 details.\n",
+                badge, name, license_file, license_file
+            )
+        }
+    }
+}
+
+/// Puts a section between the license markers of a README, replacing
+/// the section already there, or appends it with its markers.
+pub(crate) fn inject_section(
+    content: &str,
+    section: &str,
+    markup: Markup,
+) -> String {
+    let start = markup.comment(SECTION_START);
+    let end = markup.comment(SECTION_END);
+    let block = format!("{}\n{}{}", start, section, end);
+    if let Some(from) = content.find(&start) {
+        if let Some(to) = content[from..].find(&end) {
+            return format!(
+                "{}{}{}",
+                &content[..from],
+                block,
+                &content[from + to + end.len()..]
+            );
+        }
+    }
+    let content = content.trim_end();
+    if content.is_empty() {
+        format!("{}\n", block)
+    } else {
+        format!("{}\n\n{}\n", content, block)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{badge, badge_url, inject_section, readme_section, Markup};
+
+    #[test]
+    fn test_inject_section() {
+        assert_eq!(
+            badge_url("GPL-3.0-only WITH GCC_exception"),
+            "https://img.shields.io/badge/license-GPL--3.0--only_WITH_GCC__exception-blue.svg"
+        );
+        assert_eq!(
+            badge("MIT", "LICENSE", Markup::Rst),
+            ".. image:: https://img.shields.io/badge/license-MIT-blue.svg\n   :alt: License: \
+             MIT\n   :target: LICENSE"
+        );
+        let section = readme_section("MIT", "the MIT License", "LICENSE.md", Markup::Markdown);
+        let injected = inject_section("# Tool\n\nDoes things.\n", &section, Markup::Markdown);
+        assert_eq!(
+            injected,
+            format!(
+                "# Tool\n\nDoes things.\n\n<!-- lictool:license:start -->\n{}<!-- \
+                 lictool:license:end -->\n",
+                section
+            )
+        );
+        let updated = readme_section("ISC", "the ISC License", "LICENSE.md", Markup::Markdown);
+        assert_eq!(
+            inject_section(
+                &format!("{}## Contributing\n", injected),
+                &updated,
+                Markup::Markdown
+            ),
+            format!(
+                "# Tool\n\nDoes things.\n\n<!-- lictool:license:start -->\n{}<!-- \
+                 lictool:license:end -->\n## Contributing\n",
+                updated
+            )
+        );
+    }
+}
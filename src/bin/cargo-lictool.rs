@@ -0,0 +1,101 @@
+//! The `cargo lictool` subcommand, which runs lictool from the root
+//! of the current cargo workspace.
+//!
+//! Cargo runs `cargo-lictool lictool <args>` for `cargo lictool
+//! <args>`, so the subcommand name is dropped before the arguments
+//! are handed to the `lictool` binary installed alongside this one.
+//!
+//! Arguments naming relative paths, such as `header add src/lib.rs`,
+//! mean paths from the current directory, so lictool then runs there
+//! instead.
+
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf, MAIN_SEPARATOR},
+    process::{self, Command},
+};
+
+use lictool::report_unrunnable_lictool;
+
+/// The entry point of the cargo subcommand.
+fn main() {
+    let mut args: Vec<OsString> = env::args_os().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "lictool") {
+        args.remove(0);
+    }
+    let mut command = Command::new(lictool_binary());
+    if !args.iter().any(|arg| names_relative_path(arg)) {
+        if let Some(root) = workspace_root() {
+            command.current_dir(root);
+        }
+    }
+    command.args(args);
+    match command.status() {
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(error) => process::exit(report_unrunnable_lictool(error)),
+    }
+}
+
+/// Checks whether an argument, or the value of a `--flag=value`
+/// argument, names a relative path: one that exists, or one with a
+/// directory such as `docs/LICENSE`.
+fn names_relative_path(arg: &OsStr) -> bool {
+    let arg = arg.to_string_lossy();
+    let value = if arg.starts_with('-') {
+        match arg.split_once('=') {
+            Some((_, value)) => value,
+            None => return false,
+        }
+    } else {
+        &arg
+    };
+    let path = Path::new(value);
+    !value.is_empty()
+        && path.is_relative()
+        && (value.contains(['/', MAIN_SEPARATOR]) || path.exists())
+}
+
+/// Returns the `lictool` binary next to this one, as `cargo install`
+/// places them, or else the one on the `PATH`.
+fn lictool_binary() -> PathBuf {
+    let name = format!("lictool{}", env::consts::EXE_SUFFIX);
+    env::current_exe()
+        .ok()
+        .and_then(|exe| Some(exe.parent()?.join(&name)))
+        .filter(|path| path.is_file())
+        .unwrap_or_else(|| PathBuf::from(name))
+}
+
+/// Finds the root of the current workspace with the cargo binary that
+/// invoked the subcommand, which cargo passes in `CARGO`.
+fn workspace_root() -> Option<PathBuf> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args(["locate-project", "--workspace", "--message-format", "plain"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let manifest = String::from_utf8(output.stdout).ok()?;
+    Path::new(manifest.trim()).parent().map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+
+    use super::names_relative_path;
+
+    #[test]
+    fn test_names_relative_path() {
+        assert!(names_relative_path(OsStr::new("docs/LICENSE")));
+        assert!(names_relative_path(OsStr::new("--path=docs/LICENSE")));
+        assert!(names_relative_path(OsStr::new("src")));
+        assert!(!names_relative_path(OsStr::new("MIT OR Apache-2.0")));
+        assert!(!names_relative_path(OsStr::new("--owner")));
+        assert!(!names_relative_path(OsStr::new("--owner=Jane")));
+        assert!(!names_relative_path(OsStr::new("/tmp/LICENSE")));
+    }
+}
@@ -0,0 +1,307 @@
+use std::{cmp::Reverse, collections::HashMap, io};
+
+use console::{pad_str, style, truncate_str, Alignment, Key, Term};
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+use crate::{
+    i18n::t,
+    picker::{PickerFilters, TOGGLE_DEPRECATED, TOGGLE_OSI, TOGGLE_POPULAR},
+    rules::{display_license_rules, license_rules},
+    spdx::{License, LicenseDetails},
+};
+
+/// The widest the license list gets, in columns.
+const MAX_LIST_WIDTH: usize = 40;
+
+/// Switches to the alternate screen of the terminal, leaving the
+/// scrollback untouched.
+const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?1049l";
+
+/// Keeps the terminal on the alternate screen with the cursor hidden,
+/// restoring both when dropped, even if the browser fails midway.
+struct FullScreen<'t>(&'t Term);
+
+impl<'t> FullScreen<'t> {
+    fn enter(term: &'t Term) -> io::Result<Self> {
+        term.write_str(ENTER_ALTERNATE_SCREEN)?;
+        let screen = FullScreen(term);
+        term.hide_cursor()?;
+        Ok(screen)
+    }
+}
+
+impl Drop for FullScreen<'_> {
+    fn drop(&mut self) {
+        let _ = self.0.show_cursor();
+        let _ = self.0.write_str(LEAVE_ALTERNATE_SCREEN);
+    }
+}
+
+/// A full-screen browser over licenses, with a filterable list on the
+/// left and the details and text of the highlighted license on the
+/// right.
+///
+/// Details are fetched with `details` when a license is first
+/// highlighted and kept for the rest of the session.
+pub(crate) struct LicenseBrowser<'a, F> {
+    licenses: &'a [License],
+    labels: Vec<String>,
+    details: F,
+    /// The preview lines of each license, by index and width.
+    previews: HashMap<(usize, usize), Vec<String>>,
+}
+
+impl<'a, F> LicenseBrowser<'a, F>
+where
+    F: FnMut(&License) -> Option<LicenseDetails>,
+{
+    pub(crate) fn new(
+        licenses: &'a [License],
+        details: F,
+    ) -> Self {
+        Self {
+            licenses,
+            labels: licenses.iter().map(License::picker_label).collect(),
+            details,
+            previews: HashMap::new(),
+        }
+    }
+
+    /// Returns the indices of the licenses matching the query and the
+    /// filters, best matches first.
+    fn candidates(
+        &self,
+        matcher: &SkimMatcherV2,
+        query: &str,
+        filters: &PickerFilters,
+    ) -> Vec<usize> {
+        let mut scored: Vec<(usize, i64)> = self
+            .licenses
+            .iter()
+            .enumerate()
+            .filter(|(_, license)| filters.matches(license))
+            .filter_map(|(idx, _)| {
+                matcher
+                    .fuzzy_match(&self.labels[idx], query)
+                    .map(|score| (idx, score))
+            })
+            .collect();
+        scored.sort_by_key(|&(idx, score)| (Reverse(score), idx));
+        scored.into_iter().map(|(idx, _)| idx).collect()
+    }
+
+    /// Returns the preview of a license wrapped to `width`, fetching
+    /// its details the first time.
+    fn preview(
+        &mut self,
+        idx: usize,
+        width: usize,
+    ) -> &[String] {
+        if !self.previews.contains_key(&(idx, width)) {
+            let lines = match (self.details)(&self.licenses[idx]) {
+                Some(details) => preview_lines(&details, width),
+                None => vec![style(t!("browse-no-details")).dim().to_string()],
+            };
+            self.previews.insert((idx, width), lines);
+        }
+        &self.previews[&(idx, width)]
+    }
+
+    /// Runs the browser on stderr and returns the license picked with
+    /// `Enter`.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(None)` if the user left the browser with `Esc` or
+    /// `Ctrl-C`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if stderr is not a
+    /// terminal, or if the terminal cannot be read from or
+    /// written to.
+    pub(crate) fn interact_opt(mut self) -> io::Result<Option<&'a License>> {
+        let term = Term::stderr();
+        if !term.is_term() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotConnected,
+                "not a terminal",
+            ));
+        }
+        let matcher = SkimMatcherV2::default();
+        let mut filters = PickerFilters::default();
+        let mut query = String::new();
+        let (mut sel, mut offset, mut scroll) = (0, 0, 0);
+
+        let screen = FullScreen::enter(&term)?;
+        let picked = loop {
+            let (height, width) = term.size();
+            let (height, width) = (height as usize, width as usize);
+            // The search line and the key hints take a row each.
+            let rows = height.saturating_sub(2).max(1);
+            let list_width = (width / 3).min(MAX_LIST_WIDTH);
+            let preview_width = width.saturating_sub(list_width + 3).max(1);
+
+            let candidates = self.candidates(&matcher, &query, &filters);
+            sel = sel.min(candidates.len().saturating_sub(1));
+            if sel < offset {
+                offset = sel;
+            } else if sel >= offset + rows {
+                offset = sel + 1 - rows;
+            }
+            let preview: Vec<String> = match candidates.get(sel) {
+                Some(&idx) => self.preview(idx, preview_width).to_vec(),
+                None => Vec::new(),
+            };
+            scroll = scroll.min(preview.len().saturating_sub(rows));
+
+            let mut frame = String::from("\x1b[H");
+            frame.push_str(&truncate_str(
+                &format!(
+                    "{} {}{}",
+                    style(t!("browse-search")).bold(),
+                    query,
+                    filters.hint()
+                ),
+                width,
+                "…",
+            ));
+            for row in 0..rows {
+                let item = match candidates.get(offset + row) {
+                    Some(&idx) if offset + row == sel => {
+                        style(format!("❯ {}", self.licenses[idx].id))
+                            .cyan()
+                            .bold()
+                            .to_string()
+                    }
+                    Some(&idx) => format!("  {}", self.licenses[idx].id),
+                    None => String::new(),
+                };
+                let line = preview.get(scroll + row).map_or("", String::as_str);
+                frame.push_str(&format!(
+                    "\x1b[K\r\n{} {} {}",
+                    pad_str(&item, list_width, Alignment::Left, Some("…")),
+                    style("│").dim(),
+                    truncate_str(line, preview_width, "…")
+                ));
+            }
+            frame.push_str(&format!(
+                "\x1b[K\r\n{}\x1b[K\x1b[J",
+                truncate_str(&style(t!("browse-keys")).dim().to_string(), width, "…")
+            ));
+            term.write_str(&frame)?;
+
+            // Raw reads keep Ctrl-C from killing the process before the
+            // terminal is restored.
+            match term.read_key_raw()? {
+                Key::Escape | Key::CtrlC => break None,
+                Key::Enter if !candidates.is_empty() => break Some(candidates[sel]),
+                Key::ArrowUp | Key::BackTab if !candidates.is_empty() => {
+                    sel = sel.checked_sub(1).unwrap_or(candidates.len() - 1);
+                    scroll = 0;
+                }
+                Key::ArrowDown | Key::Tab if !candidates.is_empty() => {
+                    sel = (sel + 1) % candidates.len();
+                    scroll = 0;
+                }
+                Key::PageDown => scroll += rows,
+                Key::PageUp => scroll = scroll.saturating_sub(rows),
+                Key::Backspace => {
+                    query.pop();
+                    sel = 0;
+                }
+                Key::Char(TOGGLE_OSI) => filters.osi_only = !filters.osi_only,
+                Key::Char(TOGGLE_DEPRECATED) => filters.hide_deprecated = !filters.hide_deprecated,
+                Key::Char(TOGGLE_POPULAR) => filters.popular_only = !filters.popular_only,
+                Key::Char(c) if !c.is_control() => {
+                    query.push(c);
+                    sel = 0;
+                }
+                _ => {}
+            }
+        };
+        drop(screen);
+        Ok(picked.map(|idx| &self.licenses[idx]))
+    }
+}
+
+/// Renders the details of a license for the preview pane: its name,
+/// status, rules and text wrapped to `width`.
+fn preview_lines(
+    details: &LicenseDetails,
+    width: usize,
+) -> Vec<String> {
+    let yes_no = |value: bool| if value { "✔" } else { "✘" };
+    let mut lines = vec![
+        style(&details.name).bold().to_string(),
+        format!("{} {}", t!("info-license-id"), details.license_id),
+        format!(
+            "{} {}",
+            t!("info-osi-approved"),
+            details.is_osi_approved.map_or("—", yes_no)
+        ),
+        format!(
+            "{} {}",
+            t!("info-fsf-libre"),
+            details.is_fsf_libre.map_or("—", yes_no)
+        ),
+        format!(
+            "{} {}",
+            t!("info-supported"),
+            yes_no(!details.is_deprecated_license_id)
+        ),
+    ];
+    if let Some(rules) = license_rules(&details.license_id) {
+        lines.extend(display_license_rules(&rules).lines().map(str::to_string));
+    }
+    lines.push(String::new());
+    lines.extend(wrap(&details.license_text, width));
+    lines
+}
+
+/// Wraps each line of a text at word boundaries so that none is
+/// wider than `width`, keeping blank lines.
+fn wrap(
+    text: &str,
+    width: usize,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        let mut current = String::new();
+        for word in line.split_whitespace() {
+            if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::wrap;
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrap("Permission is hereby granted,\n\nfree of charge", 12),
+            [
+                "Permission",
+                "is hereby",
+                "granted,",
+                "",
+                "free of",
+                "charge"
+            ]
+        );
+        assert_eq!(wrap("averyveryverylongword", 5), ["averyveryverylongword"]);
+    }
+}
@@ -0,0 +1,132 @@
+use clap::ValueEnum;
+
+use crate::spdx::{License, Licenses};
+
+/// A kind of non-code asset with licenses of its own.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub(crate) enum AssetCategory {
+    /// Font files, usually under the SIL Open Font License.
+    Fonts,
+    /// Datasets and databases.
+    Data,
+    /// Documentation, articles and other prose.
+    Docs,
+}
+
+impl AssetCategory {
+    /// Returns the IDs of the licenses intended for the category.
+    pub fn license_ids(self) -> &'static [&'static str] {
+        match self {
+            AssetCategory::Fonts => {
+                &[
+                    "OFL-1.1",
+                    "OFL-1.1-RFN",
+                    "OFL-1.1-no-RFN",
+                    "OFL-1.0",
+                    "OFL-1.0-RFN",
+                    "OFL-1.0-no-RFN",
+                    "Apache-2.0",
+                    "Ubuntu-font-1.0",
+                    "Bitstream-Vera",
+                    "Arphic-1999",
+                ]
+            }
+            AssetCategory::Data => {
+                &[
+                    "ODbL-1.0",
+                    "ODC-By-1.0",
+                    "PDDL-1.0",
+                    "CDLA-Permissive-2.0",
+                    "CDLA-Permissive-1.0",
+                    "CDLA-Sharing-1.0",
+                    "C-UDA-1.0",
+                    "O-UDA-1.0",
+                    "CC0-1.0",
+                    "CC-BY-4.0",
+                    "CC-BY-SA-4.0",
+                ]
+            }
+            AssetCategory::Docs => {
+                &[
+                    "CC-BY-4.0",
+                    "CC-BY-SA-4.0",
+                    "CC-BY-NC-4.0",
+                    "CC-BY-ND-4.0",
+                    "CC-BY-NC-SA-4.0",
+                    "CC-BY-NC-ND-4.0",
+                    "CC0-1.0",
+                    "GFDL-1.3-or-later",
+                    "GFDL-1.3-only",
+                    "GFDL-1.3-no-invariants-or-later",
+                ]
+            }
+        }
+    }
+
+    /// Returns the name of the category as given to `--for`.
+    pub fn name(self) -> String {
+        self.to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    }
+
+    /// Returns the file name licenses of the category are written to
+    /// by default, keeping them apart from the license of the code.
+    pub fn default_filename(self) -> &'static str {
+        match self {
+            AssetCategory::Fonts => "OFL.txt",
+            AssetCategory::Data => "LICENSE-DATA.md",
+            AssetCategory::Docs => "LICENSE-DOCS.md",
+        }
+    }
+
+    /// Checks whether a license is intended for the category.
+    pub fn includes(
+        self,
+        license: &License,
+    ) -> bool {
+        self.license_ids().contains(&license.id.as_str())
+    }
+
+    /// Returns the licenses of the list intended for the category.
+    pub fn licenses(
+        self,
+        licenses: &Licenses,
+    ) -> Licenses {
+        Licenses {
+            body: licenses
+                .body
+                .iter()
+                .filter(|license| self.includes(license))
+                .cloned()
+                .collect(),
+            version: licenses.version.clone(),
+        }
+    }
+
+    /// Returns the categories a license is intended for.
+    pub fn of(license_id: &str) -> Vec<AssetCategory> {
+        AssetCategory::value_variants()
+            .iter()
+            .copied()
+            .filter(|category| category.license_ids().contains(&license_id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::AssetCategory;
+
+    #[test]
+    fn test_asset_categories() {
+        assert_eq!(AssetCategory::of("OFL-1.1"), [AssetCategory::Fonts]);
+        assert_eq!(
+            AssetCategory::of("CC-BY-4.0"),
+            [AssetCategory::Data, AssetCategory::Docs]
+        );
+        assert!(AssetCategory::of("MIT").is_empty());
+    }
+}
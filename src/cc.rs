@@ -0,0 +1,131 @@
+use std::path::Path;
+
+use dialoguer::{theme::Theme, Confirm};
+
+use crate::{i18n::t, util::errors::LictoolResult};
+
+/// The version of the Creative Commons licenses offered by `cc`.
+const CC_VERSION: &str = "4.0";
+
+/// The directories of non-code assets mapped when `cc` is given no
+/// paths, if they exist.
+pub(crate) const ASSET_DIRS: [&str; 4] = ["docs", "doc", "assets", "media"];
+
+/// The conditions a Creative Commons license is chosen by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CcChoice {
+    /// Whether reusers must give credit; without it, the work is
+    /// dedicated to the public domain with CC0.
+    pub(crate) attribution: bool,
+    pub(crate) commercial: bool,
+    /// Whether adapted works may be shared.
+    pub(crate) adaptations: bool,
+    /// Whether adapted works must be shared under the same license.
+    pub(crate) share_alike: bool,
+}
+
+impl CcChoice {
+    /// Asks for the conditions one at a time, skipping those that no
+    /// longer matter.
+    ///
+    /// # Returns
+    ///
+    /// `None` if a prompt was cancelled.
+    ///
+    /// # Errors
+    /// - If a prompt cannot be displayed or read.
+    pub fn prompt(theme: &dyn Theme) -> LictoolResult<Option<Self>> {
+        let confirm = |prompt: String, default: bool| {
+            Confirm::with_theme(theme)
+                .with_prompt(prompt)
+                .default(default)
+                .interact_opt()
+        };
+        let Some(attribution) = confirm(t!("cc-prompt-attribution"), true)? else {
+            return Ok(None);
+        };
+        let mut choice = Self {
+            attribution,
+            commercial: true,
+            adaptations: true,
+            share_alike: false,
+        };
+        if !attribution {
+            return Ok(Some(choice));
+        }
+        let Some(commercial) = confirm(t!("cc-prompt-commercial"), true)? else {
+            return Ok(None);
+        };
+        let Some(adaptations) = confirm(t!("cc-prompt-adaptations"), true)? else {
+            return Ok(None);
+        };
+        choice.commercial = commercial;
+        choice.adaptations = adaptations;
+        if adaptations {
+            let Some(share_alike) = confirm(t!("cc-prompt-share-alike"), false)? else {
+                return Ok(None);
+            };
+            choice.share_alike = share_alike;
+        }
+        Ok(Some(choice))
+    }
+
+    /// Returns the SPDX ID of the license matching the conditions,
+    /// such as `CC-BY-NC-SA-4.0`.
+    pub fn license_id(&self) -> String {
+        if !self.attribution {
+            return "CC0-1.0".to_string();
+        }
+        let mut id = "CC-BY".to_string();
+        if !self.commercial {
+            id.push_str("-NC");
+        }
+        if !self.adaptations {
+            id.push_str("-ND");
+        } else if self.share_alike {
+            id.push_str("-SA");
+        }
+        format!("{}-{}", id, CC_VERSION)
+    }
+}
+
+/// Returns the `[paths]` pattern of a file, or the one covering
+/// everything below a directory, such as `docs/*`.
+pub(crate) fn path_pattern(path: &Path) -> String {
+    let relative = path.strip_prefix(".").unwrap_or(path);
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    if path.is_dir() {
+        format!("{}/*", relative.trim_end_matches('/'))
+    } else {
+        relative
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+
+    use super::{path_pattern, CcChoice};
+
+    #[test]
+    fn test_cc_license_ids() {
+        let choice = |attribution, commercial, adaptations, share_alike| {
+            CcChoice {
+                attribution,
+                commercial,
+                adaptations,
+                share_alike,
+            }
+            .license_id()
+        };
+        assert_eq!(choice(false, true, true, false), "CC0-1.0");
+        assert_eq!(choice(true, true, true, false), "CC-BY-4.0");
+        assert_eq!(choice(true, false, true, true), "CC-BY-NC-SA-4.0");
+        assert_eq!(choice(true, false, false, true), "CC-BY-NC-ND-4.0");
+        assert_eq!(choice(true, true, false, false), "CC-BY-ND-4.0");
+        assert_eq!(path_pattern(Path::new("./src/")), "src/*");
+        assert_eq!(path_pattern(Path::new("./logo.svg")), "logo.svg");
+    }
+}
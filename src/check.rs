@@ -0,0 +1,1304 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use chrono::{Datelike, Local};
+use color_print::{cformat, cprintln};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    consts::{EMAIL, OWNER, REPO, YEAR},
+    expression::Expression,
+    fix::{
+        drift_fix, header_conflict_fixes, misnamed_license_fixes, placeholder_fixes, CheckFormat,
+        Fix,
+    },
+    header::spdx_header_line,
+    i18n::t,
+    license_set::LicenseSet,
+    placeholders::unfilled_placeholders,
+    project::{conventional_license_file_name, license_file_reference_edits, license_files},
+    spdx::Licenses,
+    template::markdown_plain_text,
+    util::{
+        defaults::FieldDefaults,
+        diff::{diff, Edit},
+        dry_run::{is_dry_run, print_planned_write},
+        errors::{Error, LictoolResult},
+        events::{emit, Event},
+        input::{read_input, STDIN_PATH},
+        journal,
+        matching::MatchNormalization,
+        output::{note, success},
+        progress::with_spinner,
+        walk::project_files,
+    },
+};
+
+/// Files larger than this are not linted, as they are unlikely to be
+/// license texts or sources with headers.
+const MAX_LINTED_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Comments excluding a file from the lints when found near its top.
+const IGNORE_DIRECTIVES: [&str; 2] = ["lictool:ignore", "reuse:ignore"];
+
+/// How many lines from the top of a file are searched for an ignore
+/// directive.
+const IGNORE_DIRECTIVE_LINES: usize = 10;
+
+/// A run of lines where a license file departs from the canonical
+/// text.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Hunk {
+    /// The line of the license file where the hunk starts.
+    pub(crate) line: usize,
+    /// Canonical lines missing from the license file.
+    pub(crate) removed: Vec<String>,
+    /// Lines of the license file absent from the canonical text.
+    pub(crate) added: Vec<String>,
+}
+
+impl Default for Hunk {
+    fn default() -> Self {
+        Self {
+            // Lowered to the first line of the hunk once known.
+            line: usize::MAX,
+            removed: Vec::new(),
+            added: Vec::new(),
+        }
+    }
+}
+
+/// Compares a license file against the canonical license text.
+///
+/// Runs of whitespace and blank lines are ignored, and placeholders
+/// of the canonical text match any filled in value. Further
+/// differences are ignored as enabled in `normalization`, and the
+/// heading and escapes of a file written as Markdown are undone.
+///
+/// Lines are compared first. A file re-wrapped with `--wrap` departs
+/// from the canonical text on most lines, so its paragraphs are then
+/// compared instead, and their hunks are kept when there are fewer.
+///
+/// # Returns
+///
+/// The drifted hunks, empty if the file matches.
+pub(crate) fn find_drift(
+    canonical: &str,
+    actual: &str,
+    normalization: &MatchNormalization,
+) -> Vec<Hunk> {
+    let actual = markdown_plain_text(actual);
+    let lines = diff_hunks(
+        &compared(significant_lines(canonical), normalization),
+        &compared(significant_lines(&actual), normalization),
+        normalization,
+    );
+    if lines.is_empty() {
+        return lines;
+    }
+    let paragraphs = find_paragraph_drift(canonical, &actual, normalization).hunks;
+    if paragraphs.len() < lines.len() {
+        paragraphs
+    } else {
+        lines
+    }
+}
+
+/// How a license file departs from the canonical text, by paragraph.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ParagraphDrift {
+    /// The drifted paragraphs, each hunk line holding a whole
+    /// paragraph.
+    pub(crate) hunks: Vec<Hunk>,
+    /// How many canonical paragraphs are kept unchanged.
+    pub(crate) matching: usize,
+    /// How many paragraphs the canonical text has.
+    pub(crate) total: usize,
+}
+
+/// Compares a license file against the canonical license text
+/// paragraph by paragraph, so that reviewers see each modified clause
+/// in full.
+///
+/// Paragraphs are separated by blank lines and compared like lines
+/// are by `find_drift`, Markdown included.
+pub(crate) fn find_paragraph_drift(
+    canonical: &str,
+    actual: &str,
+    normalization: &MatchNormalization,
+) -> ParagraphDrift {
+    let expected = compared(paragraphs(canonical), normalization);
+    let found = compared(paragraphs(&markdown_plain_text(actual)), normalization);
+    let hunks = diff_hunks(&expected, &found, normalization);
+    let removed: usize = hunks.iter().map(|hunk| hunk.removed.len()).sum();
+    ParagraphDrift {
+        hunks,
+        matching: expected.len() - removed,
+        total: expected.len(),
+    }
+}
+
+/// Drops the units, lines or paragraphs, that `normalization` skips.
+fn compared(
+    units: Vec<(usize, String)>,
+    normalization: &MatchNormalization,
+) -> Vec<(usize, String)> {
+    units
+        .into_iter()
+        .filter(|(_, text)| !normalization.skips(text))
+        .collect()
+}
+
+/// Groups the differences between numbered canonical and actual
+/// units, lines or paragraphs, into hunks.
+///
+/// Units are compared once normalized, while hunks keep their
+/// original text.
+fn diff_hunks(
+    expected: &[(usize, String)],
+    found: &[(usize, String)],
+    normalization: &MatchNormalization,
+) -> Vec<Hunk> {
+    let patterns: Vec<Vec<String>> = expected
+        .iter()
+        .map(|(_, pattern)| {
+            literal_segments(pattern)
+                .into_iter()
+                .map(|segment| normalization.apply(segment))
+                .collect()
+        })
+        .collect();
+    let lines: Vec<String> = found
+        .iter()
+        .map(|(_, line)| normalization.apply(line))
+        .collect();
+    let mut hunks = Vec::new();
+    let mut current: Option<Hunk> = None;
+    for edit in diff(&patterns, &lines, |segments, line| {
+        line_matches(segments, line)
+    }) {
+        match edit {
+            Edit::Keep(_, idx) => {
+                if let Some(mut hunk) = current.take() {
+                    // A hunk only removing lines points at the line
+                    // following them.
+                    hunk.line = hunk.line.min(found[idx].0);
+                    hunks.push(hunk);
+                }
+            }
+            Edit::Remove(idx) => {
+                current
+                    .get_or_insert_with(Hunk::default)
+                    .removed
+                    .push(expected[idx].1.clone());
+            }
+            Edit::Add(idx) => {
+                let hunk = current.get_or_insert_with(Hunk::default);
+                hunk.line = hunk.line.min(found[idx].0);
+                hunk.added.push(found[idx].1.clone());
+            }
+        }
+    }
+    if let Some(mut hunk) = current {
+        hunk.line = hunk.line.min(found.last().map_or(1, |(line, _)| line + 1));
+        hunks.push(hunk);
+    }
+    hunks
+}
+
+/// A placeholder left unfilled in a file of the project.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PlaceholderViolation {
+    pub(crate) path: PathBuf,
+    /// The line of the placeholder, starting at 1.
+    pub(crate) line: usize,
+    pub(crate) placeholder: &'static str,
+    /// The content of the line, without surrounding whitespace.
+    pub(crate) text: String,
+}
+
+impl PlaceholderViolation {
+    /// Returns the baseline entry recording this violation.
+    fn baseline_entry(&self) -> BaselineEntry {
+        BaselineEntry {
+            path: self
+                .path
+                .strip_prefix(".")
+                .unwrap_or(&self.path)
+                .display()
+                .to_string(),
+            placeholder: self.placeholder.to_string(),
+            text: self.text.clone(),
+        }
+    }
+
+    /// Returns the event reporting this violation with `--log-json`.
+    pub fn event(&self) -> Event {
+        Event::ViolationFound {
+            check: "unfilled-placeholder",
+            file: self.path.display().to_string(),
+            line: Some(self.line),
+            message: t!("check-unfilled-placeholder", placeholder = self.placeholder),
+        }
+    }
+}
+
+/// A file excluded from the lints by an ignore directive.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct IgnoredFile {
+    pub(crate) path: PathBuf,
+    /// The line of the directive, starting at 1.
+    pub(crate) line: usize,
+    pub(crate) directive: &'static str,
+}
+
+/// The outcome of the placeholder lint.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct PlaceholderLint {
+    pub(crate) violations: Vec<PlaceholderViolation>,
+    /// Files skipped because of an ignore directive.
+    pub(crate) ignored: Vec<IgnoredFile>,
+}
+
+/// Finds a `lictool:ignore` or `reuse:ignore` directive among the
+/// first lines of a file, returning its line and the directive.
+pub(crate) fn ignore_directive(content: &str) -> Option<(usize, &'static str)> {
+    content
+        .lines()
+        .take(IGNORE_DIRECTIVE_LINES)
+        .enumerate()
+        .find_map(|(idx, line)| {
+            let line = line.to_lowercase();
+            IGNORE_DIRECTIVES
+                .into_iter()
+                .find(|directive| line.contains(directive))
+                .map(|directive| (idx + 1, directive))
+        })
+}
+
+/// Known violations that checks ignore, so that lints can be adopted
+/// without fixing every existing file first.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Baseline {
+    pub(crate) placeholders: Vec<BaselineEntry>,
+}
+
+/// A violation recorded in the baseline.
+///
+/// Violations are matched by the content of their line rather than
+/// its number, so that edits elsewhere in a file keep them
+/// suppressed.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) struct BaselineEntry {
+    pub(crate) path: String,
+    pub(crate) placeholder: String,
+    pub(crate) text: String,
+}
+
+impl Baseline {
+    /// Records the given violations.
+    pub(crate) fn from_violations(violations: &[PlaceholderViolation]) -> Self {
+        let mut placeholders: Vec<BaselineEntry> = violations
+            .iter()
+            .map(PlaceholderViolation::baseline_entry)
+            .collect();
+        placeholders.sort();
+        placeholders.dedup();
+        Self {
+            placeholders,
+        }
+    }
+
+    /// Loads a baseline, which is empty if the file does not exist.
+    ///
+    /// # Errors
+    /// - If the file cannot be read or is not a valid baseline.
+    pub(crate) fn load(path: &Path) -> LictoolResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| t!("error-read-file", path = path.display().to_string()))?;
+        let baseline = serde_json::from_str(&content)
+            .with_context(|| t!("error-invalid-baseline", path = path.display().to_string()))?;
+        Ok(baseline)
+    }
+
+    /// Writes the baseline as JSON.
+    ///
+    /// # Errors
+    /// - If the file cannot be written.
+    pub(crate) fn save(
+        &self,
+        path: &Path,
+    ) -> io::Result<()> {
+        let content = serde_json::to_string_pretty(self)? + "\n";
+        if is_dry_run() {
+            print_planned_write(path, &content);
+            return Ok(());
+        }
+        journal::write(path, content)
+    }
+
+    /// Removes the violations recorded in the baseline.
+    ///
+    /// # Returns
+    ///
+    /// The remaining violations and how many were suppressed.
+    pub(crate) fn filter(
+        &self,
+        violations: Vec<PlaceholderViolation>,
+    ) -> (Vec<PlaceholderViolation>, usize) {
+        let total = violations.len();
+        let remaining: Vec<_> = violations
+            .into_iter()
+            .filter(|violation| !self.placeholders.contains(&violation.baseline_entry()))
+            .collect();
+        let suppressed = total - remaining.len();
+        (remaining, suppressed)
+    }
+}
+
+/// Finds raw placeholder tokens such as `[yyyy]` or `<copyright
+/// holders>` in the given files.
+///
+/// Files that are not UTF-8 text, or too large, are skipped, as are
+/// files with an ignore directive.
+pub(crate) fn find_unfilled_placeholders(files: &[PathBuf]) -> PlaceholderLint {
+    let mut lint = PlaceholderLint::default();
+    for path in files {
+        if fs::metadata(path).map_or(true, |metadata| metadata.len() > MAX_LINTED_FILE_SIZE) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        if let Some((line, directive)) = ignore_directive(&content) {
+            lint.ignored.push(IgnoredFile {
+                path: path.clone(),
+                line,
+                directive,
+            });
+            continue;
+        }
+        for (idx, line) in content.lines().enumerate() {
+            for placeholder in unfilled_placeholders(line) {
+                lint.violations.push(PlaceholderViolation {
+                    path: path.clone(),
+                    line: idx + 1,
+                    placeholder,
+                    text: line.trim().to_string(),
+                });
+            }
+        }
+    }
+    lint
+}
+
+/// A file whose SPDX header declares licenses beyond those expected
+/// for it, such as a GPL header in an MIT project.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct HeaderConflict {
+    pub(crate) path: PathBuf,
+    /// The line of the header, starting at 1.
+    pub(crate) line: usize,
+    /// The license expression of the header.
+    pub(crate) declared: String,
+    /// The expression of the `[paths]` mapping or default license.
+    pub(crate) expected: String,
+}
+
+impl HeaderConflict {
+    /// Returns the event reporting this conflict with `--log-json`.
+    pub fn event(&self) -> Event {
+        Event::ViolationFound {
+            check: "header-conflict",
+            file: self.path.display().to_string(),
+            line: Some(self.line),
+            message: t!(
+                "check-header-conflict",
+                declared = self.declared.as_str(),
+                expected = self.expected.as_str()
+            ),
+        }
+    }
+}
+
+/// Finds the files whose SPDX header names a license absent from the
+/// expression expected for them by the `[paths]` mapping or the
+/// default license, or else by `fallback`, such as the license of the
+/// project.
+///
+/// A header choosing some of the expected licenses, such as `MIT` in
+/// an `MIT OR Apache-2.0` project, is accepted. Headers that do not
+/// parse are left to `validate`, and files are skipped like
+/// `find_unfilled_placeholders` does.
+pub(crate) fn find_header_conflicts(
+    files: &[PathBuf],
+    config: &Config,
+    fallback: Option<&str>,
+) -> Vec<HeaderConflict> {
+    let mut conflicts = Vec::new();
+    for path in files {
+        let Some(expected) = config.license_for(path).or(fallback) else {
+            continue;
+        };
+        if fs::metadata(path).map_or(true, |metadata| metadata.len() > MAX_LINTED_FILE_SIZE) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        if ignore_directive(&content).is_some() {
+            continue;
+        }
+        let Some((line, declared)) = spdx_header_line(&content) else {
+            continue;
+        };
+        let (Ok(declared_expression), Ok(expected_expression)) = (
+            declared.parse::<Expression>(),
+            expected.parse::<Expression>(),
+        ) else {
+            continue;
+        };
+        let expected_ids: Vec<String> = expected_expression
+            .terms()
+            .iter()
+            .map(|term| term.id.to_lowercase())
+            .collect();
+        if declared_expression
+            .terms()
+            .iter()
+            .any(|term| !expected_ids.contains(&term.id.to_lowercase()))
+        {
+            conflicts.push(HeaderConflict {
+                path: path.clone(),
+                line,
+                declared: declared.to_string(),
+                expected: expected.to_string(),
+            });
+        }
+    }
+    conflicts
+}
+
+/// Prints header conflicts as `path:line: declared, expected`.
+pub(crate) fn display_header_conflicts(conflicts: &[HeaderConflict]) {
+    for conflict in conflicts {
+        cprintln!(
+            "<red>✘</> <bold>{}:{}</> {}",
+            conflict.path.display(),
+            conflict.line,
+            t!(
+                "check-header-conflict",
+                declared = conflict.declared.as_str(),
+                expected = conflict.expected.as_str()
+            )
+        );
+    }
+}
+
+/// A license file whose name departs from the convention, such as
+/// `license.TXT` or `Licence.md`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MisnamedLicenseFile {
+    pub(crate) path: PathBuf,
+    /// The path of the file with its conventional name.
+    pub(crate) expected: PathBuf,
+}
+
+impl MisnamedLicenseFile {
+    /// Returns the name the file should have.
+    fn expected_name(&self) -> String {
+        self.expected
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// Describes the finding, naming the expected name.
+    pub fn message(&self) -> String {
+        t!(
+            "check-misnamed-license-file",
+            expected = self.expected_name()
+        )
+    }
+
+    /// Returns the event reporting this file with `--log-json`.
+    pub fn event(&self) -> Event {
+        Event::ViolationFound {
+            check: "license-filename",
+            file: self.path.display().to_string(),
+            line: None,
+            message: self.message(),
+        }
+    }
+}
+
+/// Finds the license files in `dir` not named by the convention of
+/// `conventional_license_file_name`.
+pub(crate) fn find_misnamed_license_files(
+    dir: &Path,
+    default_filename: &str,
+) -> Vec<MisnamedLicenseFile> {
+    license_files(dir, default_filename)
+        .into_iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let expected = conventional_license_file_name(name, default_filename)?;
+            (expected != name).then(|| {
+                MisnamedLicenseFile {
+                    expected: path.with_file_name(expected),
+                    path,
+                }
+            })
+        })
+        .collect()
+}
+
+/// Prints misnamed license files as `path: expected name`.
+pub(crate) fn display_misnamed_license_files(files: &[MisnamedLicenseFile]) {
+    for file in files {
+        cprintln!(
+            "<red>✘</> <bold>{}</> {}",
+            file.path.display(),
+            file.message()
+        );
+    }
+}
+
+/// Renames a license file to its conventional name, along with the
+/// `license-file` references of the manifests in `dir`.
+///
+/// The file is left alone if another file already has that name,
+/// telling apart a file differing only in case on case-sensitive file
+/// systems.
+///
+/// # Returns
+///
+/// `false` if the file was left alone.
+///
+/// # Errors
+/// - If the file or a manifest cannot be written.
+pub(crate) fn rename_license_file(
+    file: &MisnamedLicenseFile,
+    dir: &Path,
+) -> LictoolResult<bool> {
+    let old = file.path.file_name().unwrap_or_default().to_string_lossy();
+    let new = file.expected_name();
+    let taken = fs::read_dir(dir)?
+        .flatten()
+        .any(|entry| entry.file_name().to_string_lossy() == new);
+    if taken {
+        return Ok(false);
+    }
+    let edits = license_file_reference_edits(dir, &old, &new);
+    let path = file.path.display().to_string();
+    if is_dry_run() {
+        cprintln!(
+            "<dim>{}</>",
+            t!(
+                "check-license-file-rename-planned",
+                file = path,
+                expected = new
+            )
+        );
+        for edit in &edits {
+            print_planned_write(&edit.path, &edit.content);
+        }
+        return Ok(true);
+    }
+    journal::rename(&file.path, &file.expected).with_context(|| {
+        t!(
+            "error-write-file",
+            path = file.expected.display().to_string()
+        )
+    })?;
+    for edit in &edits {
+        journal::write(&edit.path, &edit.content)
+            .with_context(|| t!("error-write-file", path = edit.path.display().to_string()))?;
+    }
+    success!(
+        "<green>✔</> {}",
+        t!("check-license-file-renamed", file = path, expected = new)
+    );
+    Ok(true)
+}
+
+/// Prints placeholder violations as `path:line: placeholder`.
+pub(crate) fn display_placeholder_violations(violations: &[PlaceholderViolation]) {
+    for violation in violations {
+        cprintln!(
+            "<red>✘</> <bold>{}:{}</> {}",
+            violation.path.display(),
+            violation.line,
+            t!(
+                "check-unfilled-placeholder",
+                placeholder = violation.placeholder
+            )
+        );
+    }
+}
+
+/// Prints the files skipped because of an ignore directive.
+pub(crate) fn display_ignored_files(ignored: &[IgnoredFile]) {
+    for file in ignored {
+        note!(format!(
+            "{}:{} {}",
+            file.path.display(),
+            file.line,
+            t!("check-ignored-file", directive = file.directive)
+        ));
+    }
+}
+
+/// Prints drifted hunks as a diff, canonical lines first.
+pub(crate) fn display_drift(hunks: &[Hunk]) {
+    for hunk in hunks {
+        cprintln!("<cyan>{}</>", t!("check-drift-hunk", line = hunk.line));
+        for line in &hunk.removed {
+            cprintln!("<red>- {}</>", line);
+        }
+        for line in &hunk.added {
+            cprintln!("<green>+ {}</>", line);
+        }
+    }
+}
+
+/// The checks `check` runs, selected by its flags, and how their
+/// findings are reported.
+pub(crate) struct CheckOptions<'a> {
+    pub(crate) drift: bool,
+    /// Whether drift is compared by paragraph rather than by line.
+    pub(crate) paragraphs: bool,
+    pub(crate) placeholders: bool,
+    pub(crate) headers: bool,
+    pub(crate) filenames: bool,
+    /// Whether misnamed license files are renamed.
+    pub(crate) fix: bool,
+    /// The license compared with, instead of the default license.
+    pub(crate) license: Option<&'a str>,
+    /// The license file compared, or `-` for stdin.
+    pub(crate) path: &'a str,
+    /// The baseline of accepted placeholder violations.
+    pub(crate) baseline: &'a Path,
+    /// Whether the files excluded by an ignore directive are listed.
+    pub(crate) verbose: bool,
+    pub(crate) matching: &'a MatchNormalization,
+    pub(crate) format: CheckFormat,
+}
+
+impl CheckOptions<'_> {
+    /// Returns whether no check was selected, in which case every
+    /// check runs.
+    fn runs_all(&self) -> bool {
+        !self.drift && !self.placeholders && !self.headers && !self.filenames
+    }
+
+    /// Returns whether the findings are printed as fixes.
+    fn prints_fixes(&self) -> bool {
+        self.format == CheckFormat::FixJson
+    }
+}
+
+/// Runs the checks selected by `options`, printing their findings,
+/// or the fixes for all of them with `--format fix-json`.
+///
+/// # Errors
+/// - `Error::LicenseDrift`, `Error::PlaceholderViolations`,
+///   `Error::HeaderConflicts` or `Error::MisnamedLicenseFiles`, in
+///   that order, if a check found problems.
+/// - If the license file or text cannot be read or fetched, or a
+///   misnamed file cannot be renamed.
+pub(crate) async fn run_checks(
+    options: &CheckOptions<'_>,
+    config: &Config,
+) -> LictoolResult<()> {
+    let all = options.runs_all();
+    let mut fixes = Vec::new();
+    let violations = if options.placeholders || all {
+        check_placeholders(options, config, &mut fixes)?
+    } else {
+        0
+    };
+    let conflicts = if options.headers || all {
+        check_headers(options, config, &mut fixes)
+    } else {
+        0
+    };
+    let misnamed = if options.filenames || all {
+        check_filenames(options, config, &mut fixes)?
+    } else {
+        0
+    };
+    let drift_error = if options.drift || all {
+        check_drift(options, config, &mut fixes).await?
+    } else {
+        None
+    };
+    if options.prints_fixes() {
+        println!("{}", serde_json::to_string_pretty(&fixes)?);
+    }
+    if let Some(error) = drift_error {
+        Err(error)?
+    }
+    if violations > 0 {
+        Err(Error::PlaceholderViolations {
+            count: violations,
+        })?
+    }
+    if conflicts > 0 {
+        Err(Error::HeaderConflicts {
+            count: conflicts,
+        })?
+    }
+    if misnamed > 0 {
+        Err(Error::MisnamedLicenseFiles {
+            count: misnamed,
+        })?
+    }
+    Ok(())
+}
+
+/// Records the unfilled placeholders of the project as accepted in
+/// the baseline at `path`, for `check --write-baseline`.
+///
+/// # Errors
+/// - If the baseline cannot be written.
+pub(crate) fn write_placeholder_baseline(
+    path: &Path,
+    verbose: bool,
+) -> LictoolResult<()> {
+    let violations = lint_project_placeholders(verbose);
+    let file = path.display().to_string();
+    Baseline::from_violations(&violations)
+        .save(path)
+        .with_context(|| t!("error-write-file", path = file.as_str()))?;
+    success!(
+        "<green>✔</> <bold>{}</>",
+        t!(
+            "check-baseline-written",
+            file = file,
+            count = violations.len()
+        )
+    );
+    Ok(())
+}
+
+/// Lints the placeholders of the project files, listing the files
+/// excluded by an ignore directive if `verbose`.
+fn lint_project_placeholders(verbose: bool) -> Vec<PlaceholderViolation> {
+    let lint = find_unfilled_placeholders(&project_files(Path::new(".")));
+    if verbose {
+        display_ignored_files(&lint.ignored);
+    }
+    lint.violations
+}
+
+/// Returns the values fixes fill placeholders with, the current year
+/// among them.
+fn fix_defaults(config: &Config) -> FieldDefaults {
+    let mut defaults = FieldDefaults::load(config);
+    defaults
+        .year
+        .get_or_insert_with(|| Local::now().year().to_string());
+    defaults
+}
+
+/// Reports the unfilled placeholders of the project not accepted by
+/// the baseline.
+///
+/// # Returns
+///
+/// The number of violations.
+///
+/// # Errors
+/// - If the baseline cannot be read.
+fn check_placeholders(
+    options: &CheckOptions<'_>,
+    config: &Config,
+    fixes: &mut Vec<Fix>,
+) -> LictoolResult<usize> {
+    let (violations, suppressed) =
+        Baseline::load(options.baseline)?.filter(lint_project_placeholders(options.verbose));
+    violations
+        .iter()
+        .for_each(|violation| emit(violation.event()));
+    if options.prints_fixes() {
+        fixes.extend(placeholder_fixes(&violations, &fix_defaults(config)));
+        return Ok(violations.len());
+    }
+    if suppressed > 0 {
+        note!(t!("check-baseline-suppressed", count = suppressed));
+    }
+    if violations.is_empty() {
+        success!("<green>✔</> <bold>{}</>", t!("check-no-placeholders"));
+    } else {
+        display_placeholder_violations(&violations);
+    }
+    Ok(violations.len())
+}
+
+/// Reports the files whose SPDX header contradicts the license
+/// configured for their path.
+///
+/// # Returns
+///
+/// The number of conflicts.
+fn check_headers(
+    options: &CheckOptions<'_>,
+    config: &Config,
+    fixes: &mut Vec<Fix>,
+) -> usize {
+    let conflicts = find_header_conflicts(&project_files(Path::new(".")), config, None);
+    conflicts.iter().for_each(|conflict| emit(conflict.event()));
+    if options.prints_fixes() {
+        fixes.extend(header_conflict_fixes(&conflicts));
+    } else if conflicts.is_empty() {
+        success!("<green>✔</> <bold>{}</>", t!("check-no-header-conflicts"));
+    } else {
+        display_header_conflicts(&conflicts);
+    }
+    conflicts.len()
+}
+
+/// Reports the license files without a conventional name, renaming
+/// them with `--fix`.
+///
+/// # Returns
+///
+/// The number of files left misnamed.
+///
+/// # Errors
+/// - If a file cannot be renamed.
+fn check_filenames(
+    options: &CheckOptions<'_>,
+    config: &Config,
+    fixes: &mut Vec<Fix>,
+) -> LictoolResult<usize> {
+    let root = Path::new(".");
+    let misnamed = find_misnamed_license_files(root, config.default_filename());
+    misnamed.iter().for_each(|file| emit(file.event()));
+    if options.prints_fixes() {
+        fixes.extend(misnamed_license_fixes(&misnamed));
+        return Ok(misnamed.len());
+    }
+    if misnamed.is_empty() {
+        success!(
+            "<green>✔</> <bold>{}</>",
+            t!("check-no-misnamed-license-files")
+        );
+        return Ok(0);
+    }
+    if !options.fix {
+        display_misnamed_license_files(&misnamed);
+        return Ok(misnamed.len());
+    }
+    let mut skipped = 0;
+    for file in &misnamed {
+        if !rename_license_file(file, root)? {
+            eprintln!(
+                "{}",
+                cformat!(
+                    "<yellow>{}</>",
+                    t!(
+                        "check-license-file-name-taken",
+                        file = file.path.display().to_string(),
+                        expected = file.expected.display().to_string()
+                    )
+                )
+            );
+            skipped += 1;
+        }
+    }
+    Ok(skipped)
+}
+
+/// Compares the license file with the canonical text of its license.
+///
+/// The licenses of an expression such as `MIT OR Apache-2.0` are each
+/// compared with their own file, such as `LICENSE-MIT`, as `add`
+/// writes them.
+///
+/// # Returns
+///
+/// `Error::LicenseDrift` for the first file that drifted, to be
+/// returned once the other checks ran.
+///
+/// # Errors
+/// - `Error::MissingLicenseId` if no license is given or configured.
+/// - `Error::CompoundLicense` for an expression checked on stdin.
+/// - If a file cannot be read or a license text fetched.
+async fn check_drift(
+    options: &CheckOptions<'_>,
+    config: &Config,
+    fixes: &mut Vec<Fix>,
+) -> LictoolResult<Option<Error>> {
+    let license_id = options
+        .license
+        .or(config.default_license())
+        .ok_or(Error::MissingLicenseId)?;
+    let expression = license_id.parse::<Expression>()?;
+    let terms = expression.terms();
+    let files = if terms.len() == 1 {
+        vec![(options.path.to_string(), license_id.to_string())]
+    } else {
+        if options.path == STDIN_PATH {
+            Err(Error::CompoundLicense {
+                expression: expression.to_string(),
+            })?
+        }
+        let set = LicenseSet::from_terms(
+            terms
+                .iter()
+                .map(|term| (term.id.clone(), term.exception.clone()))
+                .collect(),
+        );
+        set.targets(&[options.path.to_string()], config.default_filename())
+            .into_iter()
+            .map(|targets| targets.concat())
+            .zip(set.ids())
+            .collect()
+    };
+    let mut drift = None;
+    for (path, license_id) in &files {
+        let error = check_file_drift(options, config, path, license_id, fixes).await?;
+        drift = drift.or(error);
+    }
+    Ok(drift)
+}
+
+/// Compares one license file with the canonical text of `license_id`.
+///
+/// # Returns
+///
+/// `Error::LicenseDrift` if the file drifted.
+///
+/// # Errors
+/// - If the file cannot be read or the license text fetched.
+async fn check_file_drift(
+    options: &CheckOptions<'_>,
+    config: &Config,
+    path: &str,
+    license_id: &str,
+    fixes: &mut Vec<Fix>,
+) -> LictoolResult<Option<Error>> {
+    let actual = read_input(path)?;
+    let file = if path == STDIN_PATH {
+        t!("check-stdin")
+    } else {
+        path.to_string()
+    };
+    let (license_id, canonical) = canonical_text(license_id, config).await?;
+    let hunks = if options.prints_fixes() {
+        find_drift(&canonical, &actual, options.matching)
+    } else if options.paragraphs {
+        let drift = find_paragraph_drift(&canonical, &actual, options.matching);
+        if !drift.hunks.is_empty() {
+            display_drift(&drift.hunks);
+            cprintln!(
+                "<yellow>{}</>",
+                t!(
+                    "check-drift-paragraphs",
+                    matching = drift.matching,
+                    total = drift.total,
+                    percent = drift.matching * 100 / drift.total.max(1)
+                )
+            );
+        }
+        drift.hunks
+    } else {
+        let hunks = find_drift(&canonical, &actual, options.matching);
+        display_drift(&hunks);
+        hunks
+    };
+    if hunks.is_empty() {
+        if !options.prints_fixes() {
+            success!(
+                "<green>✔</> <bold>{}</>",
+                t!("check-no-drift", file = file, license = license_id)
+            );
+        }
+        return Ok(None);
+    }
+    emit(Event::ViolationFound {
+        check: "license-drift",
+        file: file.clone(),
+        line: None,
+        message: t!(
+            "error-license-drift",
+            file = file.as_str(),
+            license = license_id.as_str()
+        ),
+    });
+    if options.prints_fixes() {
+        fixes.push(drift_fix(
+            path,
+            &actual,
+            &canonical,
+            &license_id,
+            &fix_defaults(config),
+        ));
+    }
+    Ok(Some(Error::LicenseDrift {
+        file,
+        license: license_id,
+    }))
+}
+
+/// Returns the ID and canonical text of a license, with the
+/// configured override applied.
+///
+/// Custom licenses, such as those registered by `init --from`, are
+/// only known by their override. A `+` or exception given with the
+/// license is ignored.
+///
+/// # Errors
+/// - `Error::InvalidExpression` if `license_id` does not parse.
+/// - `Error::CompoundLicense` if it combines several licenses.
+/// - `Error::NotFound` if the license is not on the SPDX list.
+/// - If the license texts cannot be fetched or an override cannot be
+///   read.
+pub(crate) async fn canonical_text(
+    license_id: &str,
+    config: &Config,
+) -> LictoolResult<(String, String)> {
+    let Expression::License(term) = license_id.parse::<Expression>()? else {
+        Err(Error::CompoundLicense {
+            expression: license_id.to_string(),
+        })?
+    };
+    let license_id = term.id.as_str();
+    if license_id.starts_with("LicenseRef-") {
+        if let Some(text) = config.override_text(license_id)? {
+            return Ok((license_id.to_string(), text));
+        }
+    }
+    let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+    let license = licenses.find(license_id).ok_or(Error::NotFound)?;
+    let mut details = with_spinner(
+        t!("fetching-license", license = license.id),
+        license.details(),
+    )
+    .await?;
+    config.apply_override(&mut details)?;
+    Ok((license.id.clone(), details.license_text))
+}
+
+/// Returns the non-blank lines of `text` with their line numbers,
+/// with runs of whitespace collapsed.
+fn significant_lines(text: &str) -> Vec<(usize, String)> {
+    text.lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            (
+                idx + 1,
+                line.split_whitespace().collect::<Vec<_>>().join(" "),
+            )
+        })
+        .filter(|(_, line)| !line.is_empty())
+        .collect()
+}
+
+/// Returns the paragraphs of `text` with the line numbers they start
+/// at, each joined into a single line with runs of whitespace
+/// collapsed.
+fn paragraphs(text: &str) -> Vec<(usize, String)> {
+    let mut paragraphs: Vec<(usize, String)> = Vec::new();
+    let mut in_paragraph = false;
+    for (idx, line) in text.lines().enumerate() {
+        let words = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if words.is_empty() {
+            in_paragraph = false;
+        } else if in_paragraph {
+            let (_, paragraph) = paragraphs.last_mut().expect("a paragraph is open");
+            paragraph.push(' ');
+            paragraph.push_str(&words);
+        } else {
+            paragraphs.push((idx + 1, words));
+            in_paragraph = true;
+        }
+    }
+    paragraphs
+}
+
+/// Checks whether a line matches the literal segments of a canonical
+/// line, between which placeholders match any text.
+fn line_matches(
+    segments: &[String],
+    line: &str,
+) -> bool {
+    let [first, middle @ .., last] = segments else {
+        return segments.iter().all(|segment| segment == line);
+    };
+    if line.len() < first.len() + last.len() || !line.starts_with(first) || !line.ends_with(last) {
+        return false;
+    }
+    let mut rest = &line[first.len()..line.len() - last.len()];
+    for segment in middle {
+        match rest.find(segment) {
+            Some(idx) => rest = &rest[idx + segment.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Splits a canonical line on its placeholders.
+fn literal_segments(pattern: &str) -> Vec<&str> {
+    let placeholders = || YEAR.iter().chain(&OWNER).chain(&REPO).chain(&EMAIL);
+    let mut segments = Vec::new();
+    let mut rest = pattern;
+    while let Some((idx, placeholder)) = placeholders()
+        .filter_map(|placeholder| rest.find(placeholder).map(|idx| (idx, placeholder)))
+        .min_by_key(|(idx, placeholder)| (*idx, usize::MAX - placeholder.len()))
+    {
+        segments.push(&rest[..idx]);
+        rest = &rest[idx + placeholder.len()..];
+    }
+    segments.push(rest);
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::{
+        find_drift, find_header_conflicts, find_paragraph_drift, ignore_directive, Baseline, Hunk,
+        PlaceholderViolation,
+    };
+    use crate::{config::Config, util::matching::MatchNormalization};
+
+    #[test]
+    fn test_find_drift() {
+        let canonical = "MIT License\n\nCopyright (c) <year> <copyright holders>\n\nPermission is \
+                         hereby granted, free of charge.\nThe above copyright notice shall be \
+                         included.\n";
+        let filled = "MIT License\n\nCopyright (c) 2024   Jane Doe\n\n\n\nPermission is hereby \
+                      granted, free of charge.\nThe above copyright notice shall be included.";
+        assert_eq!(find_drift(canonical, filled, &Default::default()), vec![]);
+
+        let modified = "MIT License\n\nCopyright (c) 2024 Jane Doe\n\nPermission is hereby \
+                        granted, for a fee.\nThe above copyright notice shall be included.\n";
+        assert_eq!(
+            find_drift(canonical, modified, &Default::default()),
+            vec![Hunk {
+                line: 5,
+                removed: vec!["Permission is hereby granted, free of charge.".to_string()],
+                added: vec!["Permission is hereby granted, for a fee.".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_find_paragraph_drift() {
+        let canonical = "Copyright (c) <year> <copyright holders>\n\nPermission is hereby \
+                         granted,\nfree of charge.\n\nThe software is provided as is.\n";
+        let modified = "Copyright (c) 2024 Jane Doe\n\nPermission is hereby granted,\nfor a \
+                        fee.\n\nThe software is provided as is.\n";
+        let drift = find_paragraph_drift(canonical, modified, &Default::default());
+        assert_eq!(
+            drift.hunks,
+            vec![Hunk {
+                line: 3,
+                removed: vec!["Permission is hereby granted, free of charge.".to_string()],
+                added: vec!["Permission is hereby granted, for a fee.".to_string()],
+            }]
+        );
+        assert_eq!((drift.matching, drift.total), (2, 3));
+
+        let reformatted = "Copyright © 2024 Jane Doe\n\nPermission is hereby granted,\nfree of \
+                           charge .\n\nThe Software is provided “as is”.\n";
+        let normalization = MatchNormalization {
+            spdx_matching: true,
+            ..Default::default()
+        };
+        let canonical = canonical.replace("as is", "\"as is\"");
+        let drift = find_paragraph_drift(&canonical, reformatted, &normalization);
+        assert_eq!(drift.hunks, vec![]);
+        assert_eq!((drift.matching, drift.total), (2, 2));
+    }
+
+    #[test]
+    fn test_baseline() {
+        let violation = |line: usize, text: &str| {
+            PlaceholderViolation {
+                path: PathBuf::from("./LICENSE"),
+                line,
+                placeholder: "<year>",
+                text: text.to_string(),
+            }
+        };
+        let baseline = Baseline::from_violations(&[violation(3, "Copyright <year>")]);
+        assert_eq!(baseline.placeholders[0].path, "LICENSE");
+        let (remaining, suppressed) = baseline.filter(vec![
+            violation(5, "Copyright <year>"),
+            violation(9, "Updated <year>"),
+        ]);
+        assert_eq!(suppressed, 1);
+        assert_eq!(remaining, vec![violation(9, "Updated <year>")]);
+    }
+
+    #[test]
+    fn test_ignore_directive() {
+        assert_eq!(
+            ignore_directive("#!/bin/sh\n# lictool:ignore\necho <year>\n"),
+            Some((2, "lictool:ignore"))
+        );
+        assert_eq!(
+            ignore_directive("<!-- REUSE:Ignore -->"),
+            Some((1, "reuse:ignore"))
+        );
+        let late = format!("{}// lictool:ignore\n", "\n".repeat(10));
+        assert_eq!(ignore_directive(&late), None);
+    }
+
+    #[test]
+    fn test_find_header_conflicts() {
+        let dir = TempDir::new().unwrap();
+        let vendor = dir.path().join("vendor");
+        fs::create_dir(&vendor).unwrap();
+        let file = |path: PathBuf, header: &str| {
+            fs::write(&path, format!("// SPDX-License-Identifier: {}\n", header)).unwrap();
+            path
+        };
+        let files = [
+            file(dir.path().join("lib.rs"), "mit"),
+            file(dir.path().join("gpl.rs"), "GPL-3.0-only"),
+            file(vendor.join("zlib.c"), "Zlib"),
+            file(vendor.join("mit.c"), "MIT"),
+        ];
+        let mut config = Config {
+            default_license: Some("MIT OR Apache-2.0".to_string()),
+            ..Default::default()
+        };
+        config.paths.insert(
+            format!("{}/vendor/*", dir.path().display()),
+            "Zlib".to_string(),
+        );
+        let conflicts: Vec<_> = find_header_conflicts(&files, &config, None)
+            .into_iter()
+            .map(|conflict| (conflict.path, conflict.declared, conflict.expected))
+            .collect();
+        assert_eq!(
+            conflicts,
+            [
+                (
+                    files[1].clone(),
+                    "GPL-3.0-only".to_string(),
+                    "MIT OR Apache-2.0".to_string()
+                ),
+                (files[3].clone(), "MIT".to_string(), "Zlib".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use dialoguer::{theme::Theme, Confirm};
+
+use crate::{i18n::t, util::errors::LictoolResult};
+
+/// How far a license requires derived works to be shared under the
+/// same terms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Copyleft {
+    /// Derived works may use any license.
+    None,
+    /// Changes to the licensed files or library must be shared, but
+    /// the works using them may use any license.
+    Weak,
+    /// Derived works must be shared under the same license when
+    /// distributed.
+    Strong,
+    /// Like `Strong`, but offering the software over a network
+    /// counts as distributing it.
+    Network,
+}
+
+/// The properties of a license the recommendations are based on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LicenseTraits {
+    pub(crate) id: &'static str,
+    pub(crate) copyleft: Copyleft,
+    /// Whether contributors explicitly grant their patents.
+    pub(crate) patent_grant: bool,
+    /// Whether copies must keep the copyright notice.
+    pub(crate) attribution: bool,
+    pub(crate) commercial: bool,
+}
+
+/// The licenses recommended by `choose`, the more common first.
+pub(crate) const LICENSE_TRAITS: [LicenseTraits; 15] = [
+    traits("MIT", Copyleft::None, false, true),
+    traits("Apache-2.0", Copyleft::None, true, true),
+    traits("BSD-3-Clause", Copyleft::None, false, true),
+    traits("BSD-2-Clause", Copyleft::None, false, true),
+    traits("ISC", Copyleft::None, false, true),
+    traits("0BSD", Copyleft::None, false, false),
+    traits("Unlicense", Copyleft::None, false, false),
+    traits("MPL-2.0", Copyleft::Weak, true, true),
+    traits("LGPL-3.0-or-later", Copyleft::Weak, true, true),
+    traits("LGPL-2.1-or-later", Copyleft::Weak, false, true),
+    traits("EPL-2.0", Copyleft::Weak, true, true),
+    traits("GPL-3.0-or-later", Copyleft::Strong, true, true),
+    traits("GPL-2.0-or-later", Copyleft::Strong, false, true),
+    traits("AGPL-3.0-or-later", Copyleft::Network, true, true),
+    LicenseTraits {
+        id: "PolyForm-Noncommercial-1.0.0",
+        copyleft: Copyleft::None,
+        patent_grant: true,
+        attribution: true,
+        commercial: false,
+    },
+];
+
+/// Returns the traits of an open source license, which all allow
+/// commercial use.
+const fn traits(
+    id: &'static str,
+    copyleft: Copyleft,
+    patent_grant: bool,
+    attribution: bool,
+) -> LicenseTraits {
+    LicenseTraits {
+        id,
+        copyleft,
+        patent_grant,
+        attribution,
+        commercial: true,
+    }
+}
+
+/// The needs of a project a license is chosen by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct LicenseNeeds {
+    pub(crate) commercial: bool,
+    pub(crate) copyleft: Copyleft,
+    pub(crate) patent_grant: bool,
+    /// Whether reusers must credit the authors.
+    pub(crate) attribution: bool,
+}
+
+impl LicenseNeeds {
+    /// Asks for the needs one at a time, skipping those that no
+    /// longer matter.
+    ///
+    /// # Returns
+    ///
+    /// `None` if a prompt was cancelled.
+    ///
+    /// # Errors
+    /// - If a prompt cannot be displayed or read.
+    pub fn prompt(theme: &dyn Theme) -> LictoolResult<Option<Self>> {
+        let confirm = |prompt: String, default: bool| {
+            Confirm::with_theme(theme)
+                .with_prompt(prompt)
+                .default(default)
+                .interact_opt()
+        };
+        let Some(commercial) = confirm(t!("choose-prompt-commercial"), true)? else {
+            return Ok(None);
+        };
+        let mut needs = Self {
+            commercial,
+            copyleft: Copyleft::None,
+            patent_grant: false,
+            attribution: true,
+        };
+        if !commercial {
+            return Ok(Some(needs));
+        }
+        let Some(permissive) = confirm(t!("choose-prompt-permissive"), true)? else {
+            return Ok(None);
+        };
+        if permissive {
+            let Some(attribution) = confirm(t!("choose-prompt-attribution"), true)? else {
+                return Ok(None);
+            };
+            needs.attribution = attribution;
+        } else {
+            let Some(library) = confirm(t!("choose-prompt-library"), false)? else {
+                return Ok(None);
+            };
+            needs.copyleft = if library {
+                Copyleft::Weak
+            } else {
+                let Some(network) = confirm(t!("choose-prompt-network"), false)? else {
+                    return Ok(None);
+                };
+                if network {
+                    Copyleft::Network
+                } else {
+                    Copyleft::Strong
+                }
+            };
+        }
+        let Some(patent_grant) = confirm(t!("choose-prompt-patents"), false)? else {
+            return Ok(None);
+        };
+        needs.patent_grant = patent_grant;
+        Ok(Some(needs))
+    }
+
+    /// Returns the IDs of the licenses meeting the needs, the more
+    /// common first.
+    ///
+    /// A patent grant is only required if one of the otherwise
+    /// fitting licenses has one, such as for none of the licenses
+    /// without attribution.
+    pub fn recommend(&self) -> Vec<&'static str> {
+        let fitting: Vec<&LicenseTraits> = LICENSE_TRAITS
+            .iter()
+            .filter(|license| license.commercial == self.commercial)
+            .filter(|license| !self.commercial || license.copyleft == self.copyleft)
+            .filter(|license| license.attribution == self.attribution)
+            .collect();
+        let with_grant = fitting.iter().any(|license| license.patent_grant);
+        fitting
+            .into_iter()
+            .filter(|license| !(self.patent_grant && with_grant) || license.patent_grant)
+            .map(|license| license.id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{Copyleft, LicenseNeeds};
+
+    #[test]
+    fn test_recommend_licenses() {
+        let needs = |copyleft, patent_grant, attribution| {
+            LicenseNeeds {
+                commercial: true,
+                copyleft,
+                patent_grant,
+                attribution,
+            }
+            .recommend()
+        };
+        assert_eq!(
+            needs(Copyleft::None, false, true),
+            ["MIT", "Apache-2.0", "BSD-3-Clause", "BSD-2-Clause", "ISC"]
+        );
+        assert_eq!(needs(Copyleft::None, true, true), ["Apache-2.0"]);
+        assert_eq!(needs(Copyleft::None, true, false), ["0BSD", "Unlicense"]);
+        assert_eq!(
+            needs(Copyleft::Weak, true, true),
+            ["MPL-2.0", "LGPL-3.0-or-later", "EPL-2.0"]
+        );
+        assert_eq!(needs(Copyleft::Network, false, true), ["AGPL-3.0-or-later"]);
+        let noncommercial = LicenseNeeds {
+            commercial: false,
+            copyleft: Copyleft::Strong,
+            patent_grant: false,
+            attribution: true,
+        };
+        assert_eq!(noncommercial.recommend(), ["PolyForm-Noncommercial-1.0.0"]);
+    }
+}
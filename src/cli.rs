@@ -1,16 +1,129 @@
-use std::io;
+use std::{
+    ffi::{OsStr, OsString},
+    fs,
+    io::{self, BufWriter, IsTerminal, Write},
+    path::{Path, PathBuf},
+};
 
 use anstyle::AnsiColor;
-use clap::{CommandFactory, Parser, Subcommand};
-use clap_complete::{generate, Shell};
-use dialoguer::{theme::ColorfulTheme, FuzzySelect};
+use anyhow::Context;
+use chrono::{Datelike, Local};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::{
+    engine::{ArgValueCompleter, CompletionCandidate},
+    env::Shells,
+    generate, Shell,
+};
+use color_print::{cformat, cprintln};
+use dialoguer::{theme::Theme, Confirm, Select};
+use futures::future::{join, try_join, try_join_all};
+use serde::Serialize;
+use tokio::{runtime::Handle, task::block_in_place};
 
 use crate::{
-    spdx::{display_license_ids, Licenses},
-    template::{fill_license_forms, interact_write_template, write_template, Template},
-    util::errors::{Error, LictoolResult},
+    audit::{audit, cargo_dependencies, display_audit, AuditFlag},
+    badge::{badge, inject_section, readme_section, Markup},
+    browser::LicenseBrowser,
+    category::AssetCategory,
+    cc::{path_pattern, CcChoice, ASSET_DIRS},
+    check::{
+        canonical_text, display_drift, display_header_conflicts, find_drift, find_header_conflicts,
+        run_checks, write_placeholder_baseline, CheckOptions,
+    },
+    choose::LicenseNeeds,
+    compare::{display_metadata, display_word_diff, word_diff},
+    config::{lookup, parse_setting, Config, ConfigFile, Scope},
+    consts::{DEFAULT_BASELINE_FILE, LICENSE_REF_DIR, POPULAR, PUBLIC_DOMAIN_DEDICATIONS},
+    contribution::ContributionDocument,
+    custom::{custom_templates, CustomTemplate},
+    db::{is_archive_path, Dataset},
+    debian::build_copyright,
+    deprecation::{prompt_successor, successors, warn_deprecated},
+    detect::{detect_license, display_detections, LIKELY_CONFIDENCE},
+    display::{
+        display_exception_details, display_exception_ids, display_license_details,
+        display_license_ids, display_license_table,
+    },
+    distro::{debian_name, display_distro_names, fedora_name},
+    doctor::{diagnose, display_diagnoses, Health},
+    expression::Expression,
+    family::{display_family, family},
+    fix::CheckFormat,
+    fsf::FsfLicense,
+    header::{
+        declared_expression, display_missing_headers, insert_header, malformed_snippets,
+        missing_headers, replace_header_license, HeaderConfig, HeaderVars,
+    },
+    i18n::{self, t},
+    license_set::LicenseSet,
+    links::{self, display_link_checks, LinkCheck},
+    lsp,
+    notice::{third_party_notices, NOTICE_FILENAME, NOTICE_TEMPLATE},
+    oci::{label_instruction, patch_dockerfile},
+    pack::{pack_dir, Pack},
+    picker::LicensePicker,
+    placeholders::{display_placeholders, find_placeholders, unfilled_placeholders},
+    plugin::{run_plugin, PluginContext},
+    policy::{display_verdict, PolicyConfig},
+    popularity::{self, display_popular, most_popular, with_shares},
+    project::{
+        license_files, main_license_file, manifest_declarations, manifest_edits, manifest_licenses,
+        manifest_names,
+    },
+    publish::check_publish,
+    report::{files_by_license, find_violations, project_name, Report, ReportFormat},
+    rules::{display_license_rules, license_rules, LicenseRules},
+    sbom::{display_discrepancies, ProjectSbom, SpdxDocument},
+    scan::{display_scan, scan_archive, scan_dir},
+    search::{build_pattern, display_matches, display_name_matches, search_names, search_text},
+    spdx::{
+        set_source, ExceptionDetails, Exceptions, FilterOptions, License, LicenseDetails, Licenses,
+        Source, DETAILS_CONCURRENCY,
+    },
+    status::{display_status, project_license, same_file, Status},
+    template::{
+        ensure_targets_free, fill_fields, fill_license_forms, fill_placeholders,
+        fill_shared_license_forms, fill_variables, interact_write_template, is_markdown_file,
+        is_stdout, replace_template, review_template, write_template, write_templates, Field,
+        FileNameConvention, Review, Template, WriteOptions,
+    },
+    text_index::TextIndex,
+    update::{binary_asset_name, is_newer, replace_executable, verify_checksum, Release},
+    util::{
+        cache::{
+            dir_size, format_age, format_size, http_cache_dir, modified_range, partial_export_dir,
+            text_index_path,
+        },
+        defaults::{file_years, FieldDefaults},
+        dry_run::{self, is_dry_run, print_planned_write},
+        encoding::Encoding,
+        errors::{Error, LictoolResult},
+        events::{self, emit, Event},
+        git::{commit_files, contributors},
+        history::History,
+        http::{self, HttpConfig},
+        input::{read_file, read_input, STDIN_PATH},
+        integrity,
+        journal::{self, Journal},
+        line_ending::{glob_matches, LineEnding},
+        list_updates::check_list_update,
+        matching::MatchNormalization,
+        mirror::mirror_dir,
+        normalize::Normalization,
+        output::{note, print_json, success, OutputFormat},
+        pager::page,
+        progress::{self, byte_progress_bar, progress_bar, with_spinner},
+        validate::parse_year,
+        walk::project_files,
+        watch::watch_files,
+    },
+    years::bump_years,
 };
 
+/// The environment variable with which completion scripts ask
+/// lictool for the completions of a command line.
+pub(crate) const COMPLETE_VAR: &str = "COMPLETE";
+
 #[derive(Parser, Debug)]
 #[command(author, version,styles=get_styles())]
 #[clap(arg_required_else_help = true)]
@@ -18,6 +131,144 @@ pub struct Cli {
     /// A field that holds the specific subcommand to be executed.
     #[clap(subcommand)]
     subcommand: CliCommand,
+    /// Use the named [profile.<name>] settings from the configuration
+    #[arg(long, global = true)]
+    profile: Option<String>,
+    /// Read settings only from this configuration file, ignoring the
+    /// user and project files
+    #[arg(long, global = true, env = "LICTOOL_CONFIG")]
+    config: Option<PathBuf>,
+    /// The language of messages and prompts, such as en or de; read
+    /// from LC_ALL, LC_MESSAGES or LANG by default
+    #[arg(long, global = true, env = "LICTOOL_LANG")]
+    lang: Option<String>,
+    /// Print the changes to files instead of making them
+    #[arg(long, global = true)]
+    dry_run: bool,
+    /// Hide spinners, progress bars and confirmations, keeping the
+    /// results, warnings and errors, e.g. in CI logs and scripts
+    #[arg(long, short, global = true)]
+    quiet: bool,
+    /// Refuse imported license data not matching its checksum instead
+    /// of warning and fetching it from spdx.org
+    #[arg(long, global = true)]
+    verify_integrity: bool,
+    /// Print events such as fetches, written files and violations to
+    /// stderr as JSON lines
+    #[arg(long, global = true)]
+    log_json: bool,
+    /// Print the results of list, info and detect as JSON instead of
+    /// text, those of check as fix-json, or a report as Markdown or
+    /// HTML; defaults to the configured format
+    #[arg(long, global = true, value_enum)]
+    format: Option<FormatArg>,
+    /// Fetch license metadata from spdx, github or the URL of an SPDX
+    /// mirror, such as when spdx.org is blocked; defaults to the
+    /// configured source
+    #[arg(
+        long,
+        global = true,
+        env = "LICTOOL_SOURCE",
+        value_name = "spdx|github|URL"
+    )]
+    source: Option<Source>,
+    #[command(flatten)]
+    http_args: HttpArgs,
+}
+
+/// The formats of the global `--format` flag, shared by the commands
+/// printing their results in several formats, since a subcommand
+/// cannot have a `--format` flag of its own.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum FormatArg {
+    Text,
+    Json,
+    /// The text edits fixing the findings of `check`.
+    FixJson,
+    Markdown,
+    Html,
+}
+
+impl FormatArg {
+    /// Returns the format of `list`, `info` and `detect` this is.
+    fn output_format(self) -> Option<OutputFormat> {
+        match self {
+            FormatArg::Text => Some(OutputFormat::Text),
+            FormatArg::Json => Some(OutputFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// Returns the format of `check` this is.
+    fn check_format(self) -> Option<CheckFormat> {
+        match self {
+            FormatArg::Text => Some(CheckFormat::Text),
+            FormatArg::FixJson => Some(CheckFormat::FixJson),
+            _ => None,
+        }
+    }
+
+    /// Returns the format of `report` this is.
+    fn report_format(self) -> Option<ReportFormat> {
+        match self {
+            FormatArg::Markdown => Some(ReportFormat::Markdown),
+            FormatArg::Html => Some(ReportFormat::Html),
+            _ => None,
+        }
+    }
+
+    /// Returns the error of a command not printing this format.
+    fn unsupported(self) -> Error {
+        Error::UnsupportedFormat {
+            format: self
+                .to_possible_value()
+                .map(|value| value.get_name().to_string())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// Options of the requests fetching license data, overriding the
+// `[http]` settings. Not a doc comment: clap would take it as the
+// about text of the whole command line once flattened into `Cli`.
+#[derive(Args, Debug)]
+#[command(next_help_heading = "HTTP")]
+struct HttpArgs {
+    /// Seconds to wait for a connection to be established
+    #[arg(long, global = true, value_name = "SECONDS")]
+    connect_timeout: Option<f64>,
+    /// Seconds to wait for data once connected
+    #[arg(long, global = true, value_name = "SECONDS")]
+    read_timeout: Option<f64>,
+    /// How many times to retry requests failing transiently, such as
+    /// with a timeout or a 503 status
+    #[arg(long, global = true, value_name = "COUNT")]
+    retries: Option<u32>,
+    /// Send requests through this proxy instead of the one of
+    /// HTTPS_PROXY
+    #[arg(long, global = true, value_name = "URL")]
+    proxy: Option<String>,
+    /// Also trust the CA certificates of this PEM file, such as that
+    /// of a corporate proxy
+    #[arg(long, global = true, value_name = "FILE")]
+    ca_bundle: Option<PathBuf>,
+}
+
+impl HttpArgs {
+    /// Returns `config` with the settings given as flags replaced.
+    fn apply(
+        &self,
+        config: &HttpConfig,
+    ) -> HttpConfig {
+        HttpConfig {
+            connect_timeout: self.connect_timeout.or(config.connect_timeout),
+            read_timeout: self.read_timeout.or(config.read_timeout),
+            retries: self.retries.or(config.retries),
+            proxy: self.proxy.clone().or(config.proxy.clone()),
+            ca_bundle: self.ca_bundle.clone().or(config.ca_bundle.clone()),
+            ..config.clone()
+        }
+    }
 }
 
 impl Cli {
@@ -34,155 +285,4121 @@ impl Cli {
     /// This function will return an error if the execution of the
     /// command fails.
     pub async fn exec_command(&self) -> LictoolResult<()> {
+        i18n::init(self.lang.as_deref());
+        progress::set_quiet(self.quiet);
+        dry_run::set_dry_run(self.dry_run);
+        integrity::set_strict(self.verify_integrity);
+        events::set_log_json(self.log_json);
+        let config = Config::load(self.profile.as_deref(), self.config.as_deref());
+        if let CliCommand::External(args) = &self.subcommand {
+            let context = PluginContext::new(
+                config.as_ref().ok(),
+                self.profile.as_deref(),
+                self.config.as_deref(),
+                self.dry_run,
+                self.quiet,
+                self.verify_integrity,
+                self.log_json,
+            );
+            // Typos are suggested a command, as clap does for
+            // built-in ones.
+            let commands: Vec<String> = Cli::command()
+                .get_subcommands()
+                .filter(|command| !command.is_hide_set())
+                .flat_map(|command| {
+                    std::iter::once(command.get_name())
+                        .chain(command.get_visible_aliases())
+                        .map(str::to_string)
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            return run_plugin(args, &context, &commands);
+        }
+        if let CliCommand::Doctor = &self.subcommand {
+            // An invalid configuration is diagnosed rather than
+            // aborting, so it is only unwrapped by other commands.
+            if let Ok(config) = &config {
+                http::configure(&self.http_args.apply(&config.http))?;
+            }
+            let diagnoses = diagnose(&config).await;
+            display_diagnoses(&diagnoses);
+            let count = diagnoses
+                .iter()
+                .filter(|diagnosis| diagnosis.health == Health::Failure)
+                .count();
+            if count > 0 {
+                Err(Error::DiagnosticsFailed {
+                    count,
+                })?
+            }
+            return Ok(());
+        }
+        let config = config?;
+        // Check and report read the formats of their own from the flag.
+        let format = match self.format {
+            Some(arg)
+                if !matches!(
+                    self.subcommand,
+                    CliCommand::Check { .. } | CliCommand::Report { .. }
+                ) =>
+            {
+                arg.output_format().ok_or_else(|| arg.unsupported())?
+            }
+            _ => config.format.unwrap_or_default(),
+        };
+        http::configure(&self.http_args.apply(&config.http))?;
+        set_source(
+            self.source
+                .clone()
+                .or(config.source.clone())
+                .unwrap_or_default(),
+        );
+        if config.notifies_list_updates() {
+            if let Some(update) = check_list_update().await {
+                // Printed to stderr, keeping the output of the command
+                // itself intact.
+                eprintln!(
+                    "{}",
+                    cformat!(
+                        "<dim>{}</>",
+                        t!(
+                            "list-update-available",
+                            upstream = update.upstream,
+                            cached = update.cached
+                        )
+                    )
+                );
+            }
+        }
         match &self.subcommand {
             CliCommand::Completions {
                 shell,
+                static_script,
             } => {
-                generate(
-                    shell.to_owned(),
-                    &mut Cli::command(),
-                    env!("CARGO_BIN_NAME"),
-                    &mut io::stdout().lock(),
-                );
+                let name = env!("CARGO_PKG_NAME");
+                let mut out = io::stdout().lock();
+                // The script registers lictool itself as the completer,
+                // run with COMPLETE set by `run`.
+                match Shells::builtins().completer(&shell.to_string()) {
+                    Some(completer) if !static_script => {
+                        completer.write_registration(COMPLETE_VAR, name, name, name, &mut out)?
+                    }
+                    _ => generate(shell.to_owned(), &mut Cli::command(), name, &mut out),
+                }
                 Ok(())
             }
             CliCommand::Init {
                 path,
+                fallback,
+                category,
+                from,
+                license_ref,
+                yes,
+                multiple,
+                owner,
+                email,
+                repo,
+                year,
+                owner_args,
+                write_args,
+            } => {
+                let interactive = !*yes && io::stdin().is_terminal();
+                let flags = FieldDefaults {
+                    owner: owner.clone(),
+                    email: email.clone(),
+                    repo: repo.clone(),
+                    year: year.clone(),
+                };
+                let defaults = flags
+                    .or(owner_args.defaults()?)
+                    .or(FieldDefaults::load(&config));
+                if let Some(source) = from {
+                    if let Some(id) = license_ref {
+                        validate_license_ref(id)?;
+                    }
+                    let path = path
+                        .as_deref()
+                        .unwrap_or(write_args.default_filename(&config));
+                    let text = read_license_source(source).await?;
+                    let theme = config.theme.build();
+                    let mut template = Template::new(text.clone());
+                    let interactive = interactive && source != STDIN_PATH;
+                    let fields = fill_placeholders(&mut template, &theme, &defaults, interactive)?;
+                    if !interactive {
+                        fill_fields(&mut template, &fields, &defaults)?;
+                    }
+                    let options = write_args.options(&config)?;
+                    let written = if interactive {
+                        interact_write_template(path, &mut template, &options, &theme)?
+                    } else {
+                        write_template(path, &mut template, &options)?
+                    };
+                    let mut paths = vec![written];
+                    if let Some(id) = license_ref {
+                        paths.extend(register_license_ref(id, &text, &config)?);
+                    }
+                    if write_args.commit {
+                        let name = license_ref.as_deref().unwrap_or(source);
+                        commit_license(&paths, name, &config)?;
+                    }
+                    return Ok(());
+                }
+                let path = path
+                    .as_deref()
+                    .or(category.map(AssetCategory::default_filename))
+                    .unwrap_or(write_args.default_filename(&config));
+                let options = write_args.options(&config)?;
+                let mut licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                if let Some(category) = category {
+                    licenses = category.licenses(&licenses);
+                }
+                // Custom templates are picked like SPDX licenses, unless
+                // the licenses are limited to a category.
+                let custom = if category.is_none() {
+                    custom_templates()?
+                } else {
+                    Vec::new()
+                };
+                licenses
+                    .body
+                    .extend(custom.iter().map(CustomTemplate::license));
+                if !interactive {
+                    let id = fallback
+                        .as_deref()
+                        .or(config.default_license())
+                        .ok_or(Error::MissingLicenseId)?;
+                    // An expression such as `MIT OR Apache-2.0` writes
+                    // each license to its own file, as `add` does.
+                    let set = match LicenseSet::from_expression(&[id.to_string()], &licenses)? {
+                        Some(set) => set,
+                        None => {
+                            let id = custom
+                                .iter()
+                                .find(|template| template.matches(id))
+                                .map_or(id, |template| template.id.as_str());
+                            let license = licenses.find(id).ok_or(Error::NotFound)?;
+                            if license.is_deprecated_license_id {
+                                warn_deprecated(&license.id, &successors(&license.id, &licenses));
+                            }
+                            LicenseSet::from_terms(vec![(license.id.clone(), None)])
+                        }
+                    };
+                    let paths = vec![path.to_string()];
+                    let targets = set.targets(&paths, &options.default_filename);
+                    ensure_targets_free(&set.all_targets(&targets, &paths), &options)?;
+                    let details = set.details(&licenses, &custom, &config).await?;
+                    let mut written = Vec::new();
+                    for (details, paths) in details.into_iter().zip(&targets) {
+                        let fields =
+                            if PUBLIC_DOMAIN_DEDICATIONS.contains(&details.license_id.as_str()) {
+                                Vec::new()
+                            } else {
+                                Field::present_in(&details)
+                            };
+                        let mut template = Template {
+                            license_text: details.license_text,
+                            variables: options.variables.clone(),
+                            ..Default::default()
+                        };
+                        fill_fields(&mut template, &fields, &defaults)?;
+                        fill_placeholders(&mut template, &config.theme.build(), &defaults, false)?;
+                        fill_variables(&mut template, None)?;
+                        written.extend(write_templates(paths, &mut template, &options)?);
+                    }
+                    written.extend(set.write_stub(&paths, &targets, &options)?);
+                    if write_args.commit {
+                        commit_license(&written, &set.name(), &config)?;
+                    }
+                    set.ids().iter().for_each(|id| remember_license(id));
+                    return Ok(());
+                }
+                let theme = config.theme.build();
+                if *multiple {
+                    loop {
+                        let mut picked: Vec<&License> = Vec::new();
+                        loop {
+                            // Cancelling a later picker keeps the licenses
+                            // picked so far.
+                            let fallback = fallback.as_deref().filter(|_| picked.is_empty());
+                            match pick_init_license(&licenses, fallback, &config, &theme)? {
+                                Some(license) => {
+                                    if !picked.iter().any(|picked| picked.id == license.id) {
+                                        picked.push(license);
+                                    }
+                                }
+                                None if picked.is_empty() => Err(Error::Aborted)?,
+                                None => break,
+                            }
+                            let another = Confirm::with_theme(&theme)
+                                .with_prompt(t!("init-prompt-another"))
+                                .default(false)
+                                .interact()?;
+                            if !another {
+                                break;
+                            }
+                        }
+                        if init_licenses(
+                            &picked,
+                            &licenses,
+                            &custom,
+                            path,
+                            &options,
+                            write_args.commit,
+                            &config,
+                            &theme,
+                            &defaults,
+                        )
+                        .await?
+                        {
+                            return Ok(());
+                        }
+                    }
+                }
+                loop {
+                    let license =
+                        pick_init_license(&licenses, fallback.as_deref(), &config, &theme)?
+                            .ok_or(Error::Aborted)?;
+                    if init_license(
+                        license,
+                        &custom,
+                        path,
+                        &options,
+                        write_args.commit,
+                        &config,
+                        &theme,
+                        &defaults,
+                    )
+                    .await?
+                    {
+                        return Ok(());
+                    }
+                }
+            }
+            CliCommand::Choose {
+                path,
+                owner_args,
+                write_args,
+            } => {
+                let theme = config.theme.build();
+                let needs = LicenseNeeds::prompt(&theme)?.ok_or(Error::Aborted)?;
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let recommended: Vec<&License> = needs
+                    .recommend()
+                    .into_iter()
+                    .filter_map(|id| licenses.find(id))
+                    .collect();
+                if recommended.is_empty() {
+                    Err(Error::NotFound)?
+                }
+                let labels: Vec<String> = recommended
+                    .iter()
+                    .map(|license| license.picker_label())
+                    .collect();
+                let path = path
+                    .as_deref()
+                    .unwrap_or(write_args.default_filename(&config));
+                let options = write_args.options(&config)?;
+                let defaults = owner_args.defaults()?.or(FieldDefaults::load(&config));
+                loop {
+                    let selection = Select::with_theme(&theme)
+                        .with_prompt(t!("choose-prompt-license"))
+                        .items(&labels)
+                        .default(0)
+                        .interact_opt()?
+                        .ok_or(Error::Aborted)?;
+                    if init_license(
+                        recommended[selection],
+                        &[],
+                        path,
+                        &options,
+                        write_args.commit,
+                        &config,
+                        &theme,
+                        &defaults,
+                    )
+                    .await?
+                    {
+                        return Ok(());
+                    }
+                }
+            }
+            CliCommand::Browse {
+                path,
+                owner_args,
+                write_args,
             } => {
-                let licenses = Licenses::new().await?;
-                let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
-                    .with_prompt("Select a license")
-                    .items(&licenses.body)
-                    .max_length(7)
-                    .interact_opt()?;
-                let license = &licenses.body[selection.unwrap_or(0)];
-                let mut details = license.details().await?;
-                let mut template = fill_license_forms(&mut details, &ColorfulTheme::default())?;
-                Ok(interact_write_template(path, &mut template)?)
+                let mut licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let custom = custom_templates()?;
+                licenses
+                    .body
+                    .extend(custom.iter().map(CustomTemplate::license));
+                let path = path
+                    .as_deref()
+                    .unwrap_or(write_args.default_filename(&config));
+                let options = write_args.options(&config)?;
+                let defaults = owner_args.defaults()?.or(FieldDefaults::load(&config));
+                let theme = config.theme.build();
+                loop {
+                    // The browser is synchronous, so details are fetched
+                    // on this thread as licenses get highlighted.
+                    let browser = LicenseBrowser::new(&licenses.body, |license| {
+                        block_in_place(|| {
+                            Handle::current().block_on(async {
+                                match custom.iter().find(|template| template.id == license.id) {
+                                    Some(template) => template.details(),
+                                    None => license.details().await,
+                                }
+                            })
+                        })
+                        .ok()
+                    });
+                    let license = browser.interact_opt()?.ok_or(Error::Aborted)?;
+                    if init_license(
+                        license,
+                        &custom,
+                        path,
+                        &options,
+                        write_args.commit,
+                        &config,
+                        &theme,
+                        &defaults,
+                    )
+                    .await?
+                    {
+                        return Ok(());
+                    }
+                }
             }
             CliCommand::List {
                 deprecated,
                 supported,
+                no_deprecated,
                 osi_approved,
+                no_osi_approved,
                 fsf_libre,
+                no_fsf_libre,
+                favorites,
+                popular,
+                plain,
+                fancy,
+                category,
+                long,
+                exceptions,
             } => {
-                let licenses = Licenses::new().await?;
-                let mut filtered =
-                    licenses.filter_by(*deprecated, *supported, *osi_approved, *fsf_libre);
-                display_license_ids(&mut filtered)
+                let plain = *plain || (!*fancy && !io::stdout().is_terminal());
+                let criterion = |only: bool, not: bool| {
+                    if only {
+                        Some(true)
+                    } else if not {
+                        Some(false)
+                    } else {
+                        None
+                    }
+                };
+                let options = FilterOptions {
+                    deprecated: criterion(*deprecated, *supported || *no_deprecated),
+                    osi_approved: criterion(*osi_approved, *no_osi_approved),
+                    fsf_libre: criterion(*fsf_libre, *no_fsf_libre),
+                };
+                if *exceptions {
+                    let exceptions =
+                        with_spinner(t!("fetching-exceptions"), Exceptions::new()).await?;
+                    let mut filtered = exceptions.filter_by(options.deprecated);
+                    if format == OutputFormat::Json {
+                        return print_json(&filtered);
+                    }
+                    return display_exception_ids(&mut filtered, plain);
+                }
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let mut filtered = licenses.filter_by(&options);
+                if *favorites {
+                    filtered.retain(|license| config.favorites.contains(&license.id));
+                }
+                if let Some(category) = category {
+                    filtered.retain(|license| category.includes(license));
+                }
+                if format == OutputFormat::Json {
+                    return if *popular {
+                        print_json(&with_shares(&most_popular(&filtered)))
+                    } else {
+                        print_json(&filtered)
+                    };
+                }
+                if *popular {
+                    display_popular(&most_popular(&filtered), plain)
+                } else if *long {
+                    display_license_table(&mut filtered, plain)
+                } else {
+                    display_license_ids(&mut filtered, plain)
+                }
             }
             CliCommand::Add {
-                license_id,
+                license_ids,
                 owner,
                 email,
                 repo,
                 year,
                 path,
+                owner_args,
+                write_args,
             } => {
-                let licenses = Licenses::new().await?;
-                if let Some(license) = licenses
+                let git_owners = owner_args.defaults()?;
+                let paths = if path.is_empty() {
+                    vec![write_args.default_filename(&config).to_string()]
+                } else {
+                    path.clone()
+                };
+                let options = write_args.options(&config)?;
+                let license_ids = if license_ids.is_empty() {
+                    vec![config
+                        .default_license()
+                        .ok_or(Error::MissingLicenseId)?
+                        .to_string()]
+                } else {
+                    license_ids.clone()
+                };
+                let profile = config.profile_defaults().or(config.field_defaults());
+                let mut licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let custom = custom_templates()?;
+                licenses
                     .body
-                    .iter()
-                    .find(|lic| lic.to_string() == *license_id)
-                {
-                    let details = license.details().await?;
-                    Ok(write_template(
+                    .extend(custom.iter().map(CustomTemplate::license));
+                let set = match LicenseSet::from_expression(&license_ids, &licenses)? {
+                    Some(set) => set,
+                    None => {
+                        let mut terms = Vec::with_capacity(license_ids.len());
+                        for id in &license_ids {
+                            terms.push(match custom.iter().find(|template| template.matches(id)) {
+                                Some(template) => (template.id.clone(), None),
+                                None => {
+                                    replace_deprecated(licenses.resolve(id)?, &licenses, &config)?
+                                }
+                            });
+                        }
+                        LicenseSet::from_terms(terms)
+                    }
+                };
+                let license_ids = set.ids();
+                for license_id in &license_ids {
+                    licenses.find(license_id).ok_or(Error::NotFound)?;
+                }
+                let targets = set.targets(&paths, &options.default_filename);
+                ensure_targets_free(&set.all_targets(&targets, &paths), &options)?;
+                let details = set.details(&licenses, &custom, &config).await?;
+                let mut written = Vec::new();
+                for (details, paths) in details.into_iter().zip(&targets) {
+                    let mut template = Template {
+                        license_text: details.license_text,
+                        year: year.clone(),
+                        owner: owner
+                            .clone()
+                            .or(git_owners.owner.clone())
+                            .or(profile.owner.clone()),
+                        repo: repo.clone(),
+                        email: email.clone().or(profile.email.clone()),
+                        variables: options.variables.clone(),
+                        ..Default::default()
+                    };
+                    fill_variables(&mut template, None)?;
+                    written.extend(write_templates(paths, &mut template, &options)?);
+                }
+                written.extend(set.write_stub(&paths, &targets, &options)?);
+                if write_args.commit {
+                    commit_license(&written, &set.name(), &config)?;
+                }
+                license_ids.iter().for_each(|id| remember_license(id));
+                Ok(())
+            }
+            CliCommand::Fav {
+                action,
+            } => {
+                match action {
+                    FavCommand::Add {
+                        license_ids,
+                        project,
+                    } => {
+                        let licenses =
+                            with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                        let mut file = ConfigFile::open(Scope::from_project_flag(*project))?;
+                        for license_id in license_ids {
+                            if licenses.find(license_id).is_none() {
+                                Err(Error::NotFound)?
+                            }
+                            if file.push_to_list("favorites", license_id) {
+                                success!(
+                                    "<green>✔</> <bold>{}</>",
+                                    t!("favorite-added", license = license_id)
+                                );
+                            }
+                        }
+                        file.save()
+                    }
+                    FavCommand::Remove {
+                        license_ids,
+                        project,
+                    } => {
+                        let mut file = ConfigFile::open(Scope::from_project_flag(*project))?;
+                        for license_id in license_ids {
+                            if file.remove_from_list("favorites", license_id) {
+                                success!(
+                                    "<green>✔</> <bold>{}</>",
+                                    t!("favorite-removed", license = license_id)
+                                );
+                            }
+                        }
+                        file.save()
+                    }
+                    FavCommand::List => {
+                        let mut out = BufWriter::new(io::stdout().lock());
+                        for license_id in &config.favorites {
+                            writeln!(out, "{}", license_id)?;
+                        }
+                        out.flush()?;
+                        Ok(())
+                    }
+                }
+            }
+            CliCommand::Config {
+                action,
+            } => {
+                match action {
+                    ConfigCommand::Validate => {
+                        // The files were already validated when loading
+                        // the configuration, so only report them.
+                        if config.sources.is_empty() {
+                            note!(t!("config-no-files"));
+                        }
+                        for path in &config.sources {
+                            success!(
+                                "<green>✔</> <bold>{}</>",
+                                t!("config-valid", file = path.display().to_string())
+                            );
+                        }
+                        Ok(())
+                    }
+                    ConfigCommand::Get {
+                        key,
+                    } => {
+                        let (settings, _) = Config::merged_settings(self.config.as_deref())?;
+                        match lookup(&settings, key).ok_or_else(|| {
+                            Error::ConfigKeyNotSet {
+                                key: key.clone(),
+                            }
+                        })? {
+                            toml::Value::String(value) => println!("{}", value),
+                            toml::Value::Table(table) => print!("{}", toml::to_string(table)?),
+                            value => println!("{}", value),
+                        }
+                        Ok(())
+                    }
+                    ConfigCommand::Set {
+                        key,
+                        value,
+                        project,
+                    } => {
+                        let mut file = ConfigFile::open(Scope::from_project_flag(*project))?;
+                        file.set_dotted(key, parse_setting(value));
+                        file.check()?;
+                        file.save()?;
+                        success!(
+                            "<green>✔</> <bold>{}</>",
+                            t!(
+                                "config-set",
+                                key = key.as_str(),
+                                file = file.path().display().to_string()
+                            )
+                        );
+                        Ok(())
+                    }
+                }
+            }
+            CliCommand::Rm {
+                yes,
+            } => {
+                let files = license_files(Path::new("."), config.default_filename());
+                let manifests = manifest_edits(Path::new("."), None);
+                let mut project_config = ConfigFile::open(Scope::Project)?;
+                let config_path = Scope::Project.path();
+                let edits_config =
+                    config_path.is_file() && project_config.remove_key("default-license");
+                if files.is_empty() && manifests.is_empty() && !edits_config {
+                    note!(t!("rm-nothing"));
+                    return Ok(());
+                }
+                cprintln!("<bold>{}</>", t!("rm-plan"));
+                for file in &files {
+                    cprintln!("  <red>- {}</>", file.display());
+                }
+                for manifest in &manifests {
+                    cprintln!(
+                        "  <yellow>~ {}</> <dim>{}</>",
+                        manifest.path.display(),
+                        t!("rm-manifest-license")
+                    );
+                }
+                if edits_config {
+                    cprintln!(
+                        "  <yellow>~ {}</> <dim>{}</>",
+                        config_path.display(),
+                        t!("rm-config-default-license")
+                    );
+                }
+                if is_dry_run() {
+                    return Ok(());
+                }
+                if !*yes {
+                    if !io::stdin().is_terminal() {
+                        Err(Error::ConfirmationRequired)?
+                    }
+                    let confirmed = Confirm::with_theme(&config.theme.build())
+                        .with_prompt(t!("prompt-confirm-remove"))
+                        .default(false)
+                        .interact()?;
+                    if !confirmed {
+                        note!(t!("rm-cancelled"));
+                        return Ok(());
+                    }
+                }
+                for file in &files {
+                    journal::remove(file).with_context(|| {
+                        t!("error-remove-file", path = file.display().to_string())
+                    })?;
+                }
+                for manifest in &manifests {
+                    journal::write(&manifest.path, &manifest.content).with_context(|| {
+                        t!(
+                            "error-write-file",
+                            path = manifest.path.display().to_string()
+                        )
+                    })?;
+                }
+                if edits_config {
+                    project_config.save()?;
+                }
+                success!("<green>✔</> <bold>{}</>", t!("rm-done"));
+                Ok(())
+            }
+            CliCommand::Switch {
+                license_id,
+                write_args,
+            } => {
+                let options = write_args.options(&config)?;
+                let path = main_license_file(Path::new("."), config.default_filename())
+                    .ok_or(Error::NoLicenseFile)?;
+                let previous = fs::read_to_string(&path)
+                    .with_context(|| t!("error-read-file", path = path.display().to_string()))?;
+                // The values of the current license text win over the
+                // configured ones, so the copyright line survives.
+                let carried =
+                    FieldDefaults::from_license_text(&previous).or(FieldDefaults::load(&config));
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let license = licenses.find(license_id).ok_or(Error::NotFound)?;
+                let mut details = with_spinner(
+                    t!("fetching-license", license = license.id),
+                    license.details(),
+                )
+                .await?;
+                config.apply_override(&mut details)?;
+                let manifests = manifest_edits(Path::new("."), Some(&license.id));
+                let mut project_config = ConfigFile::open(Scope::Project)?;
+                let config_path = Scope::Project.path();
+                let edits_config = config_path.is_file()
+                    && project_config.replace_string("default-license", &license.id);
+                replace_template(
+                    &path,
+                    &mut Template {
+                        license_text: details.license_text,
+                        year: carried.year.clone(),
+                        owner: carried.owner.clone(),
+                        repo: carried.repo.clone(),
+                        email: carried.email.clone(),
+                        variables: options.variables.clone(),
+                        ..Default::default()
+                    },
+                    &options,
+                )?;
+                for manifest in &manifests {
+                    if is_dry_run() {
+                        print_planned_write(&manifest.path, &manifest.content);
+                        continue;
+                    }
+                    journal::write(&manifest.path, &manifest.content).with_context(|| {
+                        t!(
+                            "error-write-file",
+                            path = manifest.path.display().to_string()
+                        )
+                    })?;
+                }
+                if edits_config {
+                    project_config.save()?;
+                }
+                if is_dry_run() {
+                    return Ok(());
+                }
+                if write_args.commit {
+                    let written: Vec<PathBuf> = [path.clone()]
+                        .into_iter()
+                        .chain(manifests.iter().map(|manifest| manifest.path.clone()))
+                        .chain(edits_config.then_some(config_path.clone()))
+                        .collect();
+                    commit_license(&written, &license.id, &config)?;
+                }
+                remember_license(&license.id);
+                success!(
+                    "<green>✔</> <bold>{}</>",
+                    t!("switch-done", license = license.id)
+                );
+                cprintln!("  <yellow>~ {}</>", path.display());
+                for (field, value) in [
+                    (Field::Owner, &carried.owner),
+                    (Field::Year, &carried.year),
+                    (Field::Email, &carried.email),
+                ] {
+                    if let Some(value) = value {
+                        cprintln!(
+                            "    <dim>{}</>",
+                            t!("switch-kept", field = field.label(), value = value)
+                        );
+                    }
+                }
+                for manifest in &manifests {
+                    cprintln!(
+                        "  <yellow>~ {}</> <dim>{}</>",
+                        manifest.path.display(),
+                        t!("switch-manifest-license", license = license.id)
+                    );
+                }
+                if edits_config {
+                    cprintln!(
+                        "  <yellow>~ {}</> <dim>{}</>",
+                        config_path.display(),
+                        t!("switch-config-default-license", license = license.id)
+                    );
+                }
+                Ok(())
+            }
+            CliCommand::Status {
+                json,
+            } => {
+                let status = Status::collect(Path::new("."), &config).await?;
+                if *json {
+                    println!("{}", serde_json::to_string_pretty(&status)?);
+                } else {
+                    display_status(&status);
+                }
+                Ok(())
+            }
+            CliCommand::Report {
+                output,
+                short_format,
+            } => {
+                let root = Path::new(".");
+                let status = Status::collect(root, &config).await?;
+                let files_by_license = files_by_license(
+                    &project_files(root),
+                    &license_files(root, config.default_filename()),
+                );
+                let report = Report {
+                    project: project_name(root),
+                    violations: find_violations(
+                        &status.declared,
+                        &files_by_license,
+                        &config.policy,
+                    ),
+                    files_by_license,
+                    status,
+                };
+                let format = match (self.format, short_format) {
+                    (Some(arg), _) => arg.report_format().ok_or_else(|| arg.unsupported())?,
+                    (None, Some(format)) => {
+                        eprintln!(
+                            "{}",
+                            cformat!("<dim>{}</>", t!("report-short-format-deprecated"))
+                        );
+                        *format
+                    }
+                    (None, None) => ReportFormat::for_output(output.as_deref()),
+                };
+                let rendered = match format {
+                    ReportFormat::Markdown => report.to_markdown(),
+                    ReportFormat::Html => report.to_html(),
+                };
+                let Some(output) = output else {
+                    print!("{}", rendered);
+                    return Ok(());
+                };
+                if is_dry_run() {
+                    print_planned_write(Path::new(output), &rendered);
+                    return Ok(());
+                }
+                journal::write(Path::new(output), rendered)
+                    .with_context(|| t!("error-write-file", path = output.as_str()))?;
+                success!("<green>✔</> <bold>{}</>", t!("file-created", file = output));
+                Ok(())
+            }
+            CliCommand::Db {
+                action,
+            } => {
+                match action {
+                    DbCommand::Export {
                         path,
-                        &mut Template {
-                            license_text: details.license_text,
-                            year: *year,
-                            owner: owner.clone(),
-                            repo: repo.clone(),
-                            email: email.clone(),
-                        },
-                    )?)
+                        restart,
+                    } => {
+                        let (licenses, exceptions) = with_spinner(
+                            t!("fetching-licenses"),
+                            try_join(Licenses::new(), Exceptions::new()),
+                        )
+                        .await?;
+                        let staging = partial_export_dir();
+                        if is_dry_run() {
+                            let staged = if *restart {
+                                Dataset::default()
+                            } else {
+                                Dataset::staged(&staging, &licenses.version)
+                            };
+                            let missing = staged.missing_urls(&licenses, &exceptions);
+                            for (_, url) in &missing {
+                                cprintln!("<dim>{}</>", t!("db-would-fetch", url = url.as_str()));
+                            }
+                            cprintln!(
+                                "<dim>{}</>",
+                                t!(
+                                    "db-dry-run",
+                                    path = path.display().to_string(),
+                                    count = staged.files.len() + missing.len()
+                                )
+                            );
+                            return Ok(());
+                        }
+                        if *restart && staging.exists() {
+                            fs::remove_dir_all(&staging).with_context(|| {
+                                t!("error-write-file", path = staging.display().to_string())
+                            })?;
+                        }
+                        let staged = Dataset::staged(&staging, &licenses.version);
+                        if !staged.files.is_empty() {
+                            note!(t!("db-resuming", count = staged.files.len()));
+                        }
+                        // The two lists are staged along with the details.
+                        let missing = (licenses.body.len() + exceptions.body.len() + 2)
+                            .saturating_sub(staged.files.len());
+                        let progress =
+                            progress_bar(t!("fetching-licenses-details", count = missing), missing);
+                        let dataset = staged
+                            .fetch_missing(&licenses, &exceptions, &staging, || progress.inc(1))
+                            .await;
+                        progress.finish_and_clear();
+                        let dataset = dataset?;
+                        let target = path.display().to_string();
+                        if is_archive_path(path) {
+                            fs::write(path, dataset.to_archive()?)
+                                .with_context(|| t!("error-write-file", path = target.as_str()))?;
+                            emit(Event::FileWritten {
+                                path: target.clone(),
+                            });
+                        } else {
+                            dataset.write_dir(path)?;
+                        }
+                        success!(
+                            "<green>✔</> <bold>{}</>",
+                            t!(
+                                "db-exported",
+                                version = licenses.version,
+                                count = dataset.files.len(),
+                                path = target
+                            )
+                        );
+                        Ok(())
+                    }
+                    DbCommand::Import {
+                        path,
+                    } => {
+                        let dataset = Dataset::read(path)?;
+                        let version = dataset
+                            .licenses()
+                            .map(|licenses| licenses.version)
+                            .unwrap_or_default();
+                        let dir = mirror_dir();
+                        let target = dir.display().to_string();
+                        if is_dry_run() {
+                            cprintln!(
+                                "<dim>{}</>",
+                                t!("db-dry-run", path = target, count = dataset.files.len())
+                            );
+                            return Ok(());
+                        }
+                        if dir.exists() {
+                            fs::remove_dir_all(&dir)
+                                .with_context(|| t!("error-write-file", path = target.as_str()))?;
+                        }
+                        dataset.write_dir(&dir)?;
+                        success!(
+                            "<green>✔</> <bold>{}</>",
+                            t!(
+                                "db-imported",
+                                version = version,
+                                count = dataset.files.len(),
+                                path = target
+                            )
+                        );
+                        Ok(())
+                    }
+                }
+            }
+            CliCommand::Cache {
+                action,
+            } => {
+                let dir = http_cache_dir();
+                let path = dir.display().to_string();
+                let (mut bytes, mut files) =
+                    dir_size(&dir).with_context(|| t!("error-read-file", path = path.as_str()))?;
+                // The text index of `grep` is cached next to the HTTP
+                // cache.
+                let index = text_index_path();
+                let index_metadata = fs::metadata(&index).ok();
+                if let Some(metadata) = &index_metadata {
+                    bytes += metadata.len();
+                    files += 1;
+                }
+                match action {
+                    CacheCommand::Status => {
+                        let mut range = modified_range(&dir)
+                            .with_context(|| t!("error-read-file", path = path.as_str()))?;
+                        if let Some(modified) =
+                            index_metadata.and_then(|metadata| metadata.modified().ok())
+                        {
+                            range = Some(range.map_or((modified, modified), |(oldest, newest)| {
+                                (oldest.min(modified), newest.max(modified))
+                            }));
+                        }
+                        let Some((oldest, newest)) = range else {
+                            note!(t!("cache-empty", dir = path));
+                            return Ok(());
+                        };
+                        cprintln!(
+                            "<bold>{}</>",
+                            t!(
+                                "cache-status",
+                                size = format_size(bytes),
+                                count = files,
+                                dir = path
+                            )
+                        );
+                        note!(t!(
+                            "cache-age",
+                            oldest = format_age(oldest),
+                            newest = format_age(newest)
+                        ));
+                        Ok(())
+                    }
+                    CacheCommand::Clear {
+                        mirror,
+                    } => {
+                        let mirror_dir = mirror_dir();
+                        let mirror_path = mirror_dir.display().to_string();
+                        let remove_mirror = *mirror && mirror_dir.exists();
+                        if is_dry_run() {
+                            cprintln!(
+                                "<dim>{}</>",
+                                t!("cache-dry-run", size = format_size(bytes), dir = path)
+                            );
+                            if remove_mirror {
+                                cprintln!(
+                                    "<dim>{}</>",
+                                    t!("cache-mirror-dry-run", dir = mirror_path)
+                                );
+                            }
+                            return Ok(());
+                        }
+                        if dir.exists() {
+                            fs::remove_dir_all(&dir)
+                                .with_context(|| t!("error-write-file", path = path.as_str()))?;
+                        }
+                        if index_metadata.is_some() {
+                            fs::remove_file(&index).with_context(|| {
+                                t!("error-write-file", path = index.display().to_string())
+                            })?;
+                        }
+                        success!(
+                            "<green>✔</> <bold>{}</>",
+                            t!("cache-cleared", size = format_size(bytes), dir = path)
+                        );
+                        if remove_mirror {
+                            fs::remove_dir_all(&mirror_dir).with_context(|| {
+                                t!("error-write-file", path = mirror_path.as_str())
+                            })?;
+                            success!(
+                                "<green>✔</> <bold>{}</>",
+                                t!("cache-mirror-removed", dir = mirror_path)
+                            );
+                        }
+                        Ok(())
+                    }
+                    CacheCommand::Refresh => {
+                        let licenses =
+                            with_spinner(t!("fetching-licenses"), Licenses::revalidate()).await?;
+                        let popular: Vec<&str> = POPULAR
+                            .iter()
+                            .copied()
+                            .filter(|id| licenses.find(id).is_some())
+                            .collect();
+                        let progress = progress_bar(
+                            t!("fetching-licenses-details", count = popular.len()),
+                            popular.len(),
+                        );
+                        let fetched = licenses
+                            .details_for(&popular, DETAILS_CONCURRENCY, || progress.inc(1))
+                            .await;
+                        progress.finish_and_clear();
+                        fetched?;
+                        success!(
+                            "<green>✔</> <bold>{}</>",
+                            t!(
+                                "cache-refreshed",
+                                version = licenses.version,
+                                count = popular.len()
+                            )
+                        );
+                        // Lookups keep reading the imported dataset
+                        // over what was just revalidated.
+                        let mirror_dir = mirror_dir();
+                        if mirror_dir.exists() {
+                            note!(t!(
+                                "cache-mirror-shadows",
+                                dir = mirror_dir.display().to_string()
+                            ));
+                        }
+                        Ok(())
+                    }
+                }
+            }
+            CliCommand::Pack {
+                action,
+            } => {
+                match action {
+                    PackCommand::Export {
+                        path,
+                        project,
+                    } => {
+                        let config_path = Scope::from_project_flag(*project).path();
+                        if !config_path.is_file() {
+                            Err(Error::ConfigNotFound {
+                                path: config_path.display().to_string(),
+                            })?
+                        }
+                        let pack = Pack::from_config_file(&config_path)?;
+                        let target = path.display().to_string();
+                        if is_dry_run() {
+                            cprintln!(
+                                "<dim>{}</>",
+                                t!("pack-dry-run", path = target, count = pack.files.len())
+                            );
+                            return Ok(());
+                        }
+                        fs::write(path, pack.to_archive()?)
+                            .with_context(|| t!("error-write-file", path = target.as_str()))?;
+                        emit(Event::FileWritten {
+                            path: target.clone(),
+                        });
+                        success!(
+                            "<green>✔</> <bold>{}</>",
+                            t!(
+                                "pack-exported",
+                                settings = pack_setting_names(&pack.settings),
+                                count = pack.files.len(),
+                                path = target
+                            )
+                        );
+                        Ok(())
+                    }
+                    PackCommand::Import {
+                        source,
+                        project,
+                    } => {
+                        let archive =
+                            if source.starts_with("https://") || source.starts_with("http://") {
+                                with_spinner(t!("fetching-pack"), http::download(source)).await?
+                            } else {
+                                fs::read(source)
+                                    .with_context(|| t!("error-read-file", path = source))?
+                            };
+                        let pack = Pack::from_archive(&archive, source)?;
+                        let scope = Scope::from_project_flag(*project);
+                        let dir = pack_dir(scope);
+                        if is_dry_run() {
+                            cprintln!(
+                                "<dim>{}</>",
+                                t!(
+                                    "pack-dry-run",
+                                    path = dir.display().to_string(),
+                                    count = pack.files.len()
+                                )
+                            );
+                            return Ok(());
+                        }
+                        let settings = pack.install(&dir)?;
+                        let mut config_file = ConfigFile::open(scope)?;
+                        config_file.merge(&settings)?;
+                        config_file.save()?;
+                        success!(
+                            "<green>✔</> <bold>{}</>",
+                            t!(
+                                "pack-imported",
+                                settings = pack_setting_names(&settings),
+                                path = scope.path().display().to_string()
+                            )
+                        );
+                        Ok(())
+                    }
+                }
+            }
+            CliCommand::Lsp => {
+                let (licenses, exceptions) = try_join(Licenses::new(), Exceptions::new()).await?;
+                tokio::task::spawn_blocking(move || lsp::serve(&licenses.body, &exceptions.body))
+                    .await?
+            }
+            CliCommand::Detect {
+                path,
+                candidates,
+            } => {
+                let path = path.clone().unwrap_or_else(|| PathBuf::from("."));
+                let file = if path.is_dir() {
+                    main_license_file(&path, config.default_filename())
+                        .ok_or(Error::NoLicenseFile)?
                 } else {
-                    Err(Error::NotFound)?
+                    path
+                };
+                let text = read_file(&file)?;
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let details = licenses.all_details().await?;
+                let detections = detect_license(&text, &details, *candidates);
+                if format == OutputFormat::Json {
+                    return print_json(&detections);
                 }
+                display_detections(&file.display().to_string(), &detections);
+                Ok(())
             }
-            CliCommand::Info {
+            CliCommand::Scan {
+                archive,
+                json,
+            } => {
+                let progress = byte_progress_bar(
+                    t!("scan-reading", archive = archive.display().to_string()),
+                    0,
+                );
+                let scan = scan_archive(archive, &progress);
+                progress.finish_and_clear();
+                let mut scan = scan?;
+                // License texts are only fetched for license files
+                // without an SPDX identifier.
+                if scan.needs_identification() {
+                    let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                    let details = licenses.all_details().await?;
+                    scan.identify(&details);
+                }
+                if *json {
+                    println!("{}", serde_json::to_string_pretty(&scan)?);
+                } else {
+                    display_scan(&archive.display().to_string(), &scan);
+                }
+                Ok(())
+            }
+            CliCommand::OciLabel {
+                license,
+                dockerfile,
+            } => {
+                let expression = license
+                    .clone()
+                    .or_else(|| config.default_license().map(str::to_string))
+                    .or_else(|| {
+                        manifest_licenses(Path::new("."))
+                            .into_iter()
+                            .next()
+                            .map(|(_, license)| license)
+                    })
+                    .ok_or(Error::MissingLicenseId)?;
+                let expression = expression.parse::<Expression>()?.to_string();
+                let Some(path) = dockerfile else {
+                    println!("{}", label_instruction(&expression));
+                    return Ok(());
+                };
+                let file = path.display().to_string();
+                let content = read_file(path)?;
+                let Some(patched) = patch_dockerfile(&content, &expression) else {
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!("oci-label-unchanged", file = file, license = expression)
+                    );
+                    return Ok(());
+                };
+                if is_dry_run() {
+                    print_planned_write(path, &patched);
+                    return Ok(());
+                }
+                journal::write(path, patched)
+                    .with_context(|| t!("error-write-file", path = file.as_str()))?;
+                success!(
+                    "<green>✔</> <bold>{}</>",
+                    t!("oci-label-patched", file = file, license = expression)
+                );
+                Ok(())
+            }
+            CliCommand::Verify {
+                path,
                 license_id,
+                sbom: None,
             } => {
-                let licenses = Licenses::new().await?;
-                if let Some(license) = licenses
-                    .body
+                let path = match path {
+                    Some(path) => path.clone(),
+                    None => {
+                        main_license_file(Path::new("."), config.default_filename())
+                            .ok_or(Error::NoLicenseFile)?
+                            .display()
+                            .to_string()
+                    }
+                };
+                let path = path.strip_prefix("./").unwrap_or(&path);
+                let license_id = license_id
+                    .as_deref()
+                    .or(config.default_license())
+                    .ok_or(Error::MissingLicenseId)?;
+                let actual = read_input(path)?;
+                let file = if path == STDIN_PATH {
+                    t!("check-stdin")
+                } else {
+                    path.to_string()
+                };
+                let (license_id, canonical) = canonical_text(license_id, &config).await?;
+                let hunks = find_drift(
+                    &canonical,
+                    &actual,
+                    &MatchNormalization {
+                        spdx_matching: true,
+                        ..Default::default()
+                    },
+                );
+                display_drift(&hunks);
+                let unfilled = unfilled_placeholders(&actual);
+                // Without drift, the error lists the placeholders.
+                if !unfilled.is_empty() && !hunks.is_empty() {
+                    cprintln!(
+                        "<red>✘</> {}",
+                        t!(
+                            "verify-unfilled",
+                            file = file.as_str(),
+                            placeholders = unfilled.join(", ")
+                        )
+                    );
+                }
+                if !hunks.is_empty() {
+                    Err(Error::LicenseDrift {
+                        file,
+                        license: license_id,
+                    })?
+                } else if !unfilled.is_empty() {
+                    Err(Error::UnfilledPlaceholders {
+                        placeholders: unfilled.join(", "),
+                    })?
+                } else {
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!("check-no-drift", file = file, license = license_id)
+                    );
+                }
+                Ok(())
+            }
+            CliCommand::Verify {
+                sbom: Some(sbom), ..
+            } => {
+                let document = SpdxDocument::read(sbom)?;
+                let root = Path::new(".");
+                let mut scan = scan_dir(root);
+                if scan.needs_identification() {
+                    let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                    let details = licenses.all_details().await?;
+                    scan.identify(&details);
+                }
+                let discrepancies = document.verify(&scan, root);
+                for discrepancy in &discrepancies {
+                    emit(discrepancy.event());
+                }
+                display_discrepancies(&sbom.display().to_string(), &discrepancies);
+                match discrepancies.len() {
+                    0 => Ok(()),
+                    count => {
+                        Err(Error::SbomDiscrepancies {
+                            count,
+                        })?
+                    }
+                }
+            }
+            CliCommand::Sbom {
+                files,
+                output,
+            } => {
+                let sbom = ProjectSbom::collect(Path::new("."), &config, *files);
+                let json = format == OutputFormat::Json
+                    || output
+                        .as_deref()
+                        .is_some_and(|output| output.ends_with(".json"));
+                let rendered = if json {
+                    format!("{:#}\n", sbom.to_json())
+                } else {
+                    sbom.to_tag_value()
+                };
+                let Some(output) = output else {
+                    print!("{}", rendered);
+                    return Ok(());
+                };
+                if is_dry_run() {
+                    print_planned_write(Path::new(output), &rendered);
+                    return Ok(());
+                }
+                journal::write(Path::new(output), rendered)
+                    .with_context(|| t!("error-write-file", path = output.as_str()))?;
+                success!("<green>✔</> <bold>{}</>", t!("file-created", file = output));
+                Ok(())
+            }
+            CliCommand::SelfUpdate {
+                check,
+            } => {
+                let current = env!("CARGO_PKG_VERSION");
+                let release =
+                    with_spinner(t!("fetching-latest-release"), Release::latest()).await?;
+                let latest = release.version();
+                if !is_newer(latest, current) {
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!("update-up-to-date", version = current)
+                    );
+                    return Ok(());
+                }
+                cprintln!(
+                    "<bold>{}</>",
+                    t!("update-available", latest = latest, current = current)
+                );
+                if *check {
+                    return Ok(());
+                }
+                let name = binary_asset_name();
+                let binary_asset = release.asset(&name)?;
+                let checksum_asset = release.asset(&format!("{}.sha256", name))?;
+                if is_dry_run() {
+                    let exe = std::env::current_exe()?;
+                    cprintln!(
+                        "<dim>{}</>",
+                        t!("update-would-replace", path = exe.display().to_string())
+                    );
+                    return Ok(());
+                }
+                let (binary, checksum) = with_spinner(
+                    t!("downloading-release", version = latest),
+                    try_join(
+                        http::download(&binary_asset.browser_download_url),
+                        http::download(&checksum_asset.browser_download_url),
+                    ),
+                )
+                .await?;
+                verify_checksum(&binary, &String::from_utf8_lossy(&checksum))?;
+                let exe = replace_executable(&binary)?;
+                success!(
+                    "<green>✔</> <bold>{}</>",
+                    t!(
+                        "update-done",
+                        path = exe.display().to_string(),
+                        version = latest
+                    )
+                );
+                Ok(())
+            }
+            CliCommand::Doctor => unreachable!("handled before loading the configuration"),
+            CliCommand::External(_) => unreachable!("handled before loading the configuration"),
+            CliCommand::Undo {
+                force,
+            } => {
+                let mut journal = Journal::load();
+                let operation = journal.operations.pop().ok_or(Error::NothingToUndo)?;
+                let changed: Vec<String> = operation
+                    .files
                     .iter()
-                    .find(|lic| lic.to_string() == *license_id)
-                {
-                    let details = license.details().await?;
-                    println!("{}", details);
+                    .filter(|file| !file.is_unchanged())
+                    .map(|file| file.path.display().to_string())
+                    .collect();
+                if !changed.is_empty() && !*force {
+                    Err(Error::ChangedSinceOperation {
+                        files: changed.join(", "),
+                    })?
+                }
+                for file in operation.files.iter().rev() {
+                    let path = file.path.display().to_string();
+                    let message = match (&file.previous, is_dry_run()) {
+                        (Some(_), false) => t!("undo-restored", file = path),
+                        (None, false) => t!("undo-removed", file = path),
+                        (Some(_), true) => t!("undo-would-restore", file = path),
+                        (None, true) => t!("undo-would-remove", file = path),
+                    };
+                    if !is_dry_run() {
+                        file.revert()
+                            .with_context(|| t!("error-write-file", path = path.as_str()))?;
+                    }
+                    cprintln!("  {}", message);
+                }
+                if !is_dry_run() {
+                    journal.save()?;
+                    journal.discard_backups(&operation);
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!(
+                            "undo-done",
+                            command = operation.command.as_str(),
+                            time = operation.time.as_str()
+                        )
+                    );
+                }
+                Ok(())
+            }
+            CliCommand::Check {
+                drift,
+                paragraphs,
+                placeholders,
+                headers,
+                filenames,
+                fix,
+                publish,
+                license,
+                path,
+                baseline,
+                write_baseline,
+                verbose,
+                matching,
+            } => {
+                let format = match self.format {
+                    Some(arg) => arg.check_format().ok_or_else(|| arg.unsupported())?,
+                    None => CheckFormat::Text,
+                };
+                if format == CheckFormat::FixJson && (*publish || *write_baseline) {
+                    Err(FormatArg::FixJson.unsupported())?
+                }
+                let baseline = Path::new(baseline);
+                if *publish {
+                    return check_publish(Path::new("."), &config, baseline).await;
+                }
+                if *write_baseline {
+                    return write_placeholder_baseline(baseline, *verbose);
+                }
+                run_checks(
+                    &CheckOptions {
+                        drift: *drift,
+                        paragraphs: *paragraphs,
+                        placeholders: *placeholders,
+                        headers: *headers,
+                        filenames: *filenames,
+                        fix: *fix,
+                        license: license.as_deref(),
+                        path: path.as_deref().unwrap_or(config.default_filename()),
+                        baseline,
+                        verbose: *verbose,
+                        matching,
+                        format,
+                    },
+                    &config,
+                )
+                .await
+            }
+            CliCommand::Audit {
+                allow,
+                deny,
+                include_dev,
+            } => {
+                let mut policy = config.policy.clone();
+                policy.allow.extend(allow.iter().cloned());
+                policy.deny.extend(deny.iter().cloned());
+                let (packages, licenses) = with_spinner(
+                    t!("fetching-licenses"),
+                    try_join(
+                        cargo_dependencies(Path::new("."), *include_dev),
+                        Licenses::new(),
+                    ),
+                )
+                .await?;
+                let audited = audit(&packages, &licenses.body, &policy);
+                if format == OutputFormat::Json {
+                    print_json(&audited)?;
                 } else {
-                    Err(Error::NotFound)?
+                    display_audit(&audited);
+                }
+                let denied = audited
+                    .iter()
+                    .filter(|package| package.flags.contains(&AuditFlag::Denied))
+                    .count();
+                if denied > 0 {
+                    Err(Error::DeniedDependencies {
+                        count: denied,
+                    })?
                 }
                 Ok(())
             }
-        }
-    }
-}
-
-#[derive(Subcommand, Debug)]
-/// Available commands
-enum CliCommand {
-    /// Initializes a license, prompting for details to fill
-    /// placeholders
-    Init {
-        #[clap(short, long)]
-        #[clap(default_value_t = String::from("LICENSE.md"))]
-        path: String,
-    },
-    /// Add a license in the current directory without prompting for
-    /// individual details
-    Add {
-        license_id: String,
-        #[arg(short, long, alias = "author")]
-        owner: Option<String>,
-        #[arg(short, long)]
-        email: Option<String>,
-        #[arg(short, long)]
-        repo: Option<String>,
-        #[arg(short, long)]
-        year: Option<i32>,
-        #[clap(short, long)]
-        #[clap(default_value_t = String::from("LICENSE.md"))]
-        path: String,
-    },
-    /// Lists all available licenses
-    List {
-        /// Only deprecated
-        #[arg(short, long)]
-        deprecated: bool,
-        #[arg(short, long)]
-        /// Only supported
-        supported: bool,
-        #[arg(short, long)]
-        /// Only OSI Approved
-        osi_approved: bool,
-        #[arg(short, long)]
-        /// Only FSF Free/Libre
-        fsf_libre: bool,
-    },
-    /// Get info about license
-    Info { license_id: String },
-    /// Generate completion scripts for your shell
-    Completions {
-        #[clap(value_enum)]
-        shell: Shell,
-    },
-}
-
-/// Retrieves the styles to be used in the command-line interface
-/// (CLI) output.
-///
-/// # Returns
-///
-/// * `clap::builder::Styles` - The styles configured for usage and
-///   header display in the CLI, including
-fn get_styles() -> clap::builder::Styles {
-    clap::builder::Styles::styled()
-        .usage(
-            anstyle::Style::new()
-                .bold()
-                .underline()
+            CliCommand::Policy {
+                action,
+            } => {
+                match action {
+                    PolicyCommand::Eval {
+                        expression,
+                    } => {
+                        let verdict = config.policy.evaluate(&expression.parse()?);
+                        display_verdict(&verdict, 0);
+                        if !verdict.satisfied {
+                            Err(Error::PolicyViolation {
+                                expression: expression.clone(),
+                            })?
+                        }
+                        Ok(())
+                    }
+                }
+            }
+            CliCommand::Validate {
+                expression,
+                normalize,
+            } => {
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let canonical = expression
+                    .parse::<Expression>()?
+                    .canonicalize(&licenses.body)?;
+                if *normalize {
+                    println!("{}", canonical);
+                } else {
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!("expression-valid", expression = expression)
+                    );
+                }
+                Ok(())
+            }
+            CliCommand::Dco(args) => {
+                write_document(
+                    ContributionDocument::Dco.text(),
+                    ContributionDocument::Dco.default_filename(),
+                    args,
+                    &config,
+                )
+            }
+            CliCommand::Cla(args) => {
+                write_document(
+                    ContributionDocument::Cla.text(),
+                    ContributionDocument::Cla.default_filename(),
+                    args,
+                    &config,
+                )
+            }
+            CliCommand::Notice {
+                document,
+                third_party,
+                include_dev,
+            } => {
+                let mut text = NOTICE_TEMPLATE.to_string();
+                if *third_party {
+                    let (packages, licenses) = with_spinner(
+                        t!("fetching-licenses"),
+                        try_join(
+                            cargo_dependencies(Path::new("."), *include_dev),
+                            Licenses::new(),
+                        ),
+                    )
+                    .await?;
+                    let audited = audit(&packages, &licenses.body, &PolicyConfig::default());
+                    if let Some(notices) = third_party_notices(&audited) {
+                        text.push('\n');
+                        text.push_str(&notices);
+                    }
+                }
+                write_document(&text, NOTICE_FILENAME, document, &config)
+            }
+            CliCommand::Authors {
+                output,
+                min_commits,
+            } => {
+                let output = output.as_deref().unwrap_or(config.authors.file());
+                let contributors =
+                    contributors(Path::new(".")).with_context(|| t!("error-read-git-history"))?;
+                let content = config.authors.render(&contributors, *min_commits);
+                if fs::read_to_string(output).is_ok_and(|existing| existing == content) {
+                    note!(t!("authors-unchanged", file = output));
+                    return Ok(());
+                }
+                if is_dry_run() {
+                    print_planned_write(Path::new(output), &content);
+                    return Ok(());
+                }
+                let existed = Path::new(output).exists();
+                journal::write(Path::new(output), content)
+                    .with_context(|| t!("error-write-file", path = output))?;
+                if existed {
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!("authors-updated", file = output)
+                    );
+                } else {
+                    success!("<green>✔</> <bold>{}</>", t!("file-created", file = output));
+                }
+                Ok(())
+            }
+            CliCommand::DebianCopyright {
+                output,
+                force,
+            } => {
+                if !*force && Path::new(output).exists() {
+                    Err(Error::AlreadyExists {
+                        file: output.clone(),
+                    })?
+                }
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let copyright = with_spinner(
+                    t!("fetching-license-texts"),
+                    build_copyright(&config, &licenses),
+                )
+                .await?;
+                if is_dry_run() {
+                    print_planned_write(Path::new(output), &copyright);
+                    return Ok(());
+                }
+                if let Some(parent) = Path::new(output).parent() {
+                    fs::create_dir_all(parent)
+                        .with_context(|| t!("error-write-file", path = output))?;
+                }
+                journal::write(Path::new(output), copyright)
+                    .with_context(|| t!("error-write-file", path = output))?;
+                success!("<green>✔</> <bold>{}</>", t!("file-created", file = output));
+                Ok(())
+            }
+            CliCommand::Fill {
+                path,
+                fields,
+            } => {
+                let path = path.as_deref().unwrap_or(config.default_filename());
+                let mut template = fields.template(read_file(Path::new(path))?, &config)?;
+                let theme = config.theme.build();
+                let interactive = fields.interactive();
+                let present = fill_placeholders(
+                    &mut template,
+                    &theme,
+                    &FieldDefaults::load(&config),
+                    interactive,
+                )?;
+                if present.is_empty() {
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!("fill-nothing-to-do", file = path)
+                    );
+                    return Ok(());
+                }
+                let filled = template.render();
+                if is_dry_run() {
+                    print_planned_write(Path::new(path), &filled);
+                    return Ok(());
+                }
+                journal::write(Path::new(path), &filled)
+                    .with_context(|| t!("error-write-file", path = path))?;
+                let unfilled = unfilled_placeholders(&filled);
+                if unfilled.is_empty() {
+                    success!("<green>✔</> <bold>{}</>", t!("fill-done", file = path));
+                } else {
+                    cprintln!(
+                        "<y, bold>\u{f421}</> <bold>{}</>",
+                        t!(
+                            "fill-partial",
+                            file = path,
+                            placeholders = unfilled.join(", ")
+                        )
+                    );
+                }
+                Ok(())
+            }
+            CliCommand::BumpYear {
+                path,
+            } => {
+                let path = path.as_deref().unwrap_or(config.default_filename());
+                let text = read_file(Path::new(path))?;
+                let year = Local::now().year();
+                let (bumped, count) = bump_years(&text, year);
+                if count == 0 {
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!("bump-year-current", file = path)
+                    );
+                    return Ok(());
+                }
+                if is_dry_run() {
+                    print_planned_write(Path::new(path), &bumped);
+                    return Ok(());
+                }
+                journal::write(Path::new(path), &bumped)
+                    .with_context(|| t!("error-write-file", path = path))?;
+                success!(
+                    "<green>✔</> <bold>{}</>",
+                    t!("bump-year-done", file = path, count = count, year = year)
+                );
+                Ok(())
+            }
+            CliCommand::SyncManifest {
+                expression,
+                check,
+            } => {
+                let root = Path::new(".");
+                let manifests = manifest_names(root);
+                if manifests.is_empty() {
+                    Err(Error::NoManifest)?
+                }
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let canonical = |expression: &str| {
+                    expression
+                        .parse::<Expression>()
+                        .and_then(|parsed| parsed.canonicalize(&licenses.body))
+                        .map(|parsed| parsed.to_string())
+                };
+                let license = match expression {
+                    Some(expression) => canonical(expression)?,
+                    None => license_file_expression(root, &licenses, &config).await?,
+                };
+                if *check {
+                    let declared = manifest_licenses(root);
+                    let mut out_of_sync = 0;
+                    for name in manifests {
+                        let found = declared.iter().find(|(path, _)| path.ends_with(name)).map(
+                            |(_, declared)| {
+                                canonical(declared).unwrap_or_else(|_| declared.clone())
+                            },
+                        );
+                        match found {
+                            Some(found) if found == license => {}
+                            Some(found) => {
+                                out_of_sync += 1;
+                                cprintln!(
+                                    "<red>✘</> {}",
+                                    t!(
+                                        "sync-manifest-mismatch",
+                                        file = name,
+                                        declared = found,
+                                        license = license.as_str()
+                                    )
+                                );
+                            }
+                            None => {
+                                out_of_sync += 1;
+                                cprintln!(
+                                    "<red>✘</> {}",
+                                    t!(
+                                        "sync-manifest-missing",
+                                        file = name,
+                                        license = license.as_str()
+                                    )
+                                );
+                            }
+                        }
+                    }
+                    if out_of_sync > 0 {
+                        Err(Error::ManifestsOutOfSync {
+                            count: out_of_sync,
+                        })?
+                    }
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!("sync-manifest-in-sync", license = license.as_str())
+                    );
+                    return Ok(());
+                }
+                let edits = manifest_declarations(root, &license);
+                if edits.is_empty() {
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!("sync-manifest-in-sync", license = license.as_str())
+                    );
+                    return Ok(());
+                }
+                for edit in edits {
+                    if is_dry_run() {
+                        print_planned_write(&edit.path, &edit.content);
+                        continue;
+                    }
+                    let file = edit
+                        .path
+                        .strip_prefix(root)
+                        .unwrap_or(&edit.path)
+                        .display()
+                        .to_string();
+                    journal::write(&edit.path, &edit.content)
+                        .with_context(|| t!("error-write-file", path = file.as_str()))?;
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!(
+                            "sync-manifest-updated",
+                            file = file,
+                            license = license.as_str()
+                        )
+                    );
+                }
+                Ok(())
+            }
+            CliCommand::Header {
+                action,
+            } => {
+                match action {
+                    HeaderCommand::Check {
+                        watch,
+                        filter,
+                    } => {
+                        let root = Path::new(".");
+                        let license_files = license_files(root, config.default_filename());
+                        let is_checked = |path: &Path| {
+                            filter.matches(path)
+                                && !license_files.iter().any(|license| same_file(path, license))
+                        };
+                        let files: Vec<PathBuf> = project_files(root)
+                            .into_iter()
+                            .filter(|path| is_checked(path))
+                            .collect();
+                        let mut missing = missing_headers(&files, &config.header);
+                        display_missing_headers(&missing);
+                        let malformed = malformed_snippets(&files);
+                        for (path, snippet) in &malformed {
+                            if let Some(problem) = snippet.problem(&path.display().to_string()) {
+                                cprintln!("<red>✘</> {}", problem);
+                            }
+                        }
+                        let conflicts = find_header_conflicts(
+                            &files,
+                            &config,
+                            project_license(root, &config).as_deref(),
+                        );
+                        display_header_conflicts(&conflicts);
+                        if !watch {
+                            return match (missing.len(), malformed.len(), conflicts.len()) {
+                                (0, 0, 0) => Ok(()),
+                                (0, 0, count) => {
+                                    Err(Error::HeaderConflicts {
+                                        count,
+                                    })?
+                                }
+                                (0, count, _) => {
+                                    Err(Error::MalformedSnippets {
+                                        count,
+                                    })?
+                                }
+                                (count, ..) => {
+                                    Err(Error::MissingHeaders {
+                                        count,
+                                    })?
+                                }
+                            };
+                        }
+                        note!(t!("header-watching"));
+                        watch_files(root, |changed| {
+                            for path in changed.iter().filter(|path| is_checked(path)) {
+                                let file = path.display().to_string();
+                                if config.header.has_header(path) == Some(false) {
+                                    if missing.insert(path.clone()) {
+                                        cprintln!(
+                                            "<red>✘</> {}",
+                                            t!("header-missing", file = file)
+                                        );
+                                    }
+                                } else if missing.remove(path) && path.is_file() {
+                                    success!("<green>✔</> {}", t!("header-added", file = file));
+                                }
+                            }
+                            note!(t!("header-missing-count", count = missing.len()));
+                        })
+                    }
+                    HeaderCommand::Add {
+                        paths,
+                        license,
+                        years_from_git,
+                        no_copyright,
+                        check,
+                        filter,
+                    } => {
+                        if let Some(license) = license {
+                            license.parse::<Expression>()?;
+                        }
+                        let files = header_files(paths, filter, &config);
+                        let missing = missing_headers(&files, &config.header);
+                        if *check || missing.is_empty() {
+                            display_missing_headers(&missing);
+                            return match missing.len() {
+                                0 => Ok(()),
+                                count => {
+                                    Err(Error::MissingHeaders {
+                                        count,
+                                    })?
+                                }
+                            };
+                        }
+                        // Without --license, each file gets the license
+                        // [paths] maps it to.
+                        let license_ids = missing
+                            .iter()
+                            .map(|path| {
+                                let license_id = license
+                                    .as_deref()
+                                    .or_else(|| config.license_for(path))
+                                    .ok_or(Error::MissingLicenseId)?;
+                                Ok(license_id.parse::<Expression>()?.to_string())
+                            })
+                            .collect::<LictoolResult<Vec<String>>>()?;
+                        let defaults = FieldDefaults::load(&config);
+                        let owner = defaults.owner.as_deref().filter(|_| !no_copyright);
+                        let progress = progress_bar(t!("header-adding"), missing.len());
+                        for (path, license_id) in missing.iter().zip(&license_ids) {
+                            progress.inc(1);
+                            let header = file_header(
+                                path,
+                                license_id,
+                                owner,
+                                &defaults,
+                                *years_from_git,
+                                &config.header,
+                            )?;
+                            let file = path.display().to_string();
+                            let content = fs::read_to_string(path)
+                                .with_context(|| t!("error-read-file", path = file.as_str()))?;
+                            let content = insert_header(&content, &header);
+                            if is_dry_run() {
+                                progress.suspend(|| print_planned_write(path, &content));
+                                continue;
+                            }
+                            journal::write(path, content)
+                                .with_context(|| t!("error-write-file", path = file.as_str()))?;
+                            progress.suspend(|| {
+                                success!("<green>✔</> {}", t!("header-added", file = file))
+                            });
+                        }
+                        progress.finish_and_clear();
+                        Ok(())
+                    }
+                    HeaderCommand::Replace {
+                        paths,
+                        from,
+                        to,
+                        filter,
+                    } => {
+                        // Headers are matched term by term, so only a
+                        // single license can be replaced.
+                        match from.parse::<Expression>()? {
+                            Expression::License(term) if term.exception.is_none() => {}
+                            _ => {
+                                Err(Error::CompoundReplace {
+                                    expression: from.clone(),
+                                })?
+                            }
+                        }
+                        let to = to.parse::<Expression>()?.to_string();
+                        let mut replaced = 0;
+                        let files = header_files(paths, filter, &config);
+                        let progress = progress_bar(t!("header-replacing"), files.len());
+                        for path in files {
+                            progress.inc(1);
+                            if config.header.comment_style(&path).is_none() {
+                                continue;
+                            }
+                            let Ok(content) = fs::read_to_string(&path) else {
+                                continue;
+                            };
+                            let content = match replace_header_license(&content, from, &to) {
+                                Ok(Some(content)) => content,
+                                Ok(None) => continue,
+                                Err(error) => {
+                                    progress.suspend(|| {
+                                        eprintln!(
+                                            "{}",
+                                            cformat!(
+                                                "<y>{}</>",
+                                                t!(
+                                                    "header-replace-skipped",
+                                                    file = path.display().to_string(),
+                                                    reason = error.to_string()
+                                                )
+                                            )
+                                        )
+                                    });
+                                    continue;
+                                }
+                            };
+                            replaced += 1;
+                            if is_dry_run() {
+                                progress.suspend(|| print_planned_write(&path, &content));
+                                continue;
+                            }
+                            let file = path.display().to_string();
+                            journal::write(&path, content)
+                                .with_context(|| t!("error-write-file", path = file.as_str()))?;
+                            progress.suspend(|| {
+                                success!(
+                                    "<green>✔</> {}",
+                                    t!(
+                                        "header-replaced",
+                                        file = file,
+                                        from = from.as_str(),
+                                        to = to.as_str()
+                                    )
+                                )
+                            });
+                        }
+                        progress.finish_and_clear();
+                        if replaced == 0 {
+                            success!(
+                                "<green>✔</> <bold>{}</>",
+                                t!("header-replace-none", license = from.as_str())
+                            );
+                        }
+                        Ok(())
+                    }
+                    HeaderCommand::Preview {
+                        path,
+                        years_from_git,
+                    } => {
+                        let license_id = config.default_license().ok_or(Error::MissingLicenseId)?;
+                        let defaults = FieldDefaults::load(&config);
+                        let header = file_header(
+                            Path::new(path),
+                            license_id,
+                            defaults.owner.as_deref(),
+                            &defaults,
+                            *years_from_git,
+                            &config.header,
+                        )?;
+                        print!("{}", header);
+                        Ok(())
+                    }
+                }
+            }
+            CliCommand::Placeholders {
+                license_id,
+            } => {
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let license = licenses.find(license_id).ok_or(Error::NotFound)?;
+                let mut details = with_spinner(
+                    t!("fetching-license", license = license.id),
+                    license.details(),
+                )
+                .await?;
+                config.apply_override(&mut details)?;
+                display_placeholders(&license.id, &find_placeholders(&details));
+                Ok(())
+            }
+            CliCommand::Search {
+                pattern,
+                text,
+                regex,
+                ignore_case,
+            } => {
+                if !*text {
+                    let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                    let found = search_names(&licenses.body, pattern);
+                    if format == OutputFormat::Json {
+                        return print_json(&found);
+                    }
+                    display_name_matches(&found, pattern);
+                    return Ok(());
+                }
+                let matcher = build_pattern(pattern, *regex, *ignore_case)?;
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let details = licenses.all_details().await?;
+                let mut results: Vec<(String, Vec<_>)> = licenses
+                    .body
+                    .iter()
+                    .zip(&details)
+                    .map(|(license, details)| {
+                        (
+                            license.id.clone(),
+                            search_text(&matcher, &details.license_text),
+                        )
+                    })
+                    .filter(|(_, matches)| !matches.is_empty())
+                    .collect();
+                results.sort_by(|(a, _), (b, _)| a.cmp(b));
+                display_matches(&results, pattern);
+                Ok(())
+            }
+            CliCommand::Grep {
+                phrase,
+            } => {
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let path = text_index_path();
+                let index = match TextIndex::load(&path, &licenses.version) {
+                    Some(index) => index,
+                    None => {
+                        let details = licenses.all_details().await?;
+                        let index = TextIndex::build(&licenses.version, &details);
+                        index.save(&path)?;
+                        index
+                    }
+                };
+                let results = index.search(phrase)?;
+                if format == OutputFormat::Json {
+                    let results: Vec<_> = results
+                        .iter()
+                        .map(|(license, matches)| {
+                            serde_json::json!({
+                                "license": license,
+                                "matches": matches,
+                            })
+                        })
+                        .collect();
+                    return print_json(&results);
+                }
+                display_matches(&results, phrase);
+                Ok(())
+            }
+            CliCommand::Family {
+                prefix,
+            } => {
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                display_family(&family(&licenses, prefix)?)
+            }
+            CliCommand::Badge {
+                license_id,
+                markup,
+                inject,
+            } => {
+                let root = Path::new(".");
+                let license_id = match license_id {
+                    Some(license_id) => license_id.clone(),
+                    None => project_license(root, &config).ok_or(Error::MissingLicenseId)?,
+                };
+                let expression = license_id.parse::<Expression>()?;
+                let (license, name) = match &expression {
+                    Expression::License(term) if !term.or_later && term.exception.is_none() => {
+                        let licenses =
+                            with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                        let license = licenses.resolve(&term.id)?;
+                        (license.id.clone(), format!("the {}", license.name))
+                    }
+                    _ => (expression.to_string(), expression.to_string()),
+                };
+                let license_file = main_license_file(root, config.default_filename())
+                    .map(|path| {
+                        path.strip_prefix(root)
+                            .unwrap_or(&path)
+                            .to_string_lossy()
+                            .replace('\\', "/")
+                    })
+                    .unwrap_or_else(|| config.default_filename().to_string());
+                let markup = markup
+                    .or(inject.as_deref().map(Markup::from_path))
+                    .unwrap_or(Markup::Markdown);
+                let section = readme_section(&license, &name, &license_file, markup);
+                if let Some(readme) = inject {
+                    let content = match fs::read_to_string(readme) {
+                        Ok(content) => content,
+                        Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+                        Err(error) => {
+                            Err(error).with_context(|| t!("error-read-file", path = readme))?
+                        }
+                    };
+                    let content = inject_section(&content, &section, markup);
+                    if is_dry_run() {
+                        print_planned_write(Path::new(readme), &content);
+                        return Ok(());
+                    }
+                    journal::write(Path::new(readme), content)
+                        .with_context(|| t!("error-write-file", path = readme))?;
+                    success!(
+                        "<green>✔</> <bold>{}</>",
+                        t!("badge-injected", license = license, file = readme)
+                    );
+                    return Ok(());
+                }
+                let badge = badge(&license, &license_file, markup);
+                if format == OutputFormat::Json {
+                    return print_json(&serde_json::json!({
+                        "license": license,
+                        "badge": badge,
+                        "section": section,
+                    }));
+                }
+                println!("{}\n\n{}", badge, section);
+                Ok(())
+            }
+            CliCommand::Diff {
+                first,
+                second,
+            } => {
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let (first, second) = (licenses.resolve(first)?, licenses.resolve(second)?);
+                let first = license_details(first, &[], &config).await?;
+                let second = license_details(second, &[], &config).await?;
+                let segments = word_diff(&first.license_text, &second.license_text);
+                if format == OutputFormat::Json {
+                    return print_json(&serde_json::json!({
+                        "first": first.license_id,
+                        "second": second.license_id,
+                        "diff": segments,
+                    }));
+                }
+                page(&format!(
+                    "{}\n\n{}\n",
+                    display_metadata(&first, &second),
+                    display_word_diff(&segments)
+                ))?;
+                Ok(())
+            }
+            CliCommand::Cc {
+                paths,
+            } => {
+                let paths: Vec<PathBuf> = if paths.is_empty() {
+                    ASSET_DIRS
+                        .iter()
+                        .map(PathBuf::from)
+                        .filter(|dir| dir.is_dir())
+                        .collect()
+                } else {
+                    paths.clone()
+                };
+                if paths.is_empty() {
+                    Err(Error::MissingAssetDirs {
+                        dirs: ASSET_DIRS.join(", "),
+                    })?
+                }
+                let choice = CcChoice::prompt(&config.theme.build())?.ok_or(Error::Aborted)?;
+                let license_id = choice.license_id();
+                cprintln!(
+                    "<bold>{}</>",
+                    t!("cc-chosen", license = license_id.as_str())
+                );
+                let mut file = ConfigFile::open(Scope::Project)?;
+                for path in &paths {
+                    let pattern = path_pattern(path);
+                    match file.set_in_table(&["paths"], &pattern, &license_id) {
+                        Some(previous) if previous != license_id => {
+                            success!(
+                                "<green>✔</> {}",
+                                t!(
+                                    "cc-remapped",
+                                    pattern = pattern,
+                                    license = license_id.as_str(),
+                                    previous = previous
+                                )
+                            )
+                        }
+                        _ => {
+                            success!(
+                                "<green>✔</> {}",
+                                t!(
+                                    "cc-mapped",
+                                    pattern = pattern,
+                                    license = license_id.as_str()
+                                )
+                            )
+                        }
+                    }
+                }
+                file.save()
+            }
+            CliCommand::Info {
+                license_ids,
+                distro,
+                verify_links,
+            } => {
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                let selected = if license_ids.is_empty() {
+                    let theme = config.theme.build();
+                    let license =
+                        pick_license(&licenses, &config, &theme)?.ok_or(Error::Aborted)?;
+                    vec![license.id.clone()]
+                } else {
+                    license_ids.clone()
+                };
+                // Exceptions are only fetched for IDs that are not
+                // licenses.
+                let exceptions = if selected.iter().any(|id| licenses.resolve(id).is_err()) {
+                    Some(with_spinner(t!("fetching-exceptions"), Exceptions::new()).await?)
+                } else {
+                    None
+                };
+                let selected = selected
+                    .iter()
+                    .map(|id| {
+                        match licenses.resolve(id) {
+                            Ok(license) => Ok(license.id.clone()),
+                            Err(error) => {
+                                exceptions
+                                    .as_ref()
+                                    .and_then(|exceptions| exceptions.find(id))
+                                    .map(|exception| exception.id.clone())
+                                    .ok_or(error)
+                            }
+                        }
+                    })
+                    .collect::<LictoolResult<Vec<_>>>()?;
+                let details = with_spinner(
+                    t!("fetching-licenses-details", count = selected.len()),
+                    try_join_all(selected.iter().map(|id| {
+                        describe(id, &licenses, exceptions.as_ref(), *distro, *verify_links)
+                    })),
+                )
+                .await?;
+                if format == OutputFormat::Json {
+                    return print_json(&details);
+                }
+                let details: Vec<String> = details.iter().map(Description::to_string).collect();
+                println!("{}", details.join("\n\n"));
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+/// Available commands
+enum CliCommand {
+    /// Initializes a license, prompting for details to fill
+    /// placeholders and previewing the result before writing it
+    Init {
+        /// Where to write the license, or - for stdout; defaults to
+        /// the configured file name, which directories also receive
+        #[clap(short, long)]
+        path: Option<String>,
+        /// The license to use when the picker is cancelled, instead
+        /// of aborting
+        #[arg(long = "default", value_name = "ID")]
+        fallback: Option<String>,
+        /// Only offer licenses intended for fonts, data or
+        /// documentation, written to OFL.txt, LICENSE-DATA.md or
+        /// LICENSE-DOCS.md unless a path is given
+        #[arg(long = "for", value_enum, value_name = "ASSETS")]
+        category: Option<AssetCategory>,
+        /// Start from the license text at this URL or file, or - for
+        /// stdin, instead of picking an SPDX license
+        #[arg(long, value_name = "URL|FILE", conflicts_with_all = ["fallback", "category"])]
+        from: Option<String>,
+        /// Register the text given with --from as this custom
+        /// license, such as LicenseRef-Acme, so that check
+        /// can compare against it
+        #[arg(long, value_name = "ID", requires = "from")]
+        license_ref: Option<String>,
+        /// Never prompt, such as in CI: write the --default or
+        /// configured default license, filling its fields from the
+        /// flags, the configuration, git and the current year, and
+        /// fail if one has no value; implied when stdin is not a
+        /// terminal
+        #[arg(long, visible_alias = "non-interactive")]
+        yes: bool,
+        /// Pick several licenses one after the other, such as MIT and
+        /// Apache-2.0 for a dual-licensed project, each written to
+        /// its own LICENSE-<ID> file with its details asked for once
+        #[arg(long, conflicts_with_all = ["from", "yes"])]
+        multiple: bool,
+        #[arg(
+            short,
+            long,
+            alias = "author",
+            env = "LICTOOL_OWNER",
+            conflicts_with = "owners_from_git"
+        )]
+        owner: Option<String>,
+        #[arg(short, long, env = "LICTOOL_EMAIL")]
+        email: Option<String>,
+        #[arg(short, long, env = "LICTOOL_REPO")]
+        repo: Option<String>,
+        /// A year or year range such as 2019-2024, or from-git for
+        /// the years since the first commit of the repository
+        #[arg(short, long, env = "LICTOOL_YEAR", value_parser = parse_year)]
+        year: Option<String>,
+        #[command(flatten)]
+        owner_args: GitOwnerArgs,
+        #[command(flatten)]
+        write_args: WriteArgs,
+    },
+    /// Recommend licenses from a few questions about the project,
+    /// such as whether it is a library or needs a patent grant, and
+    /// initialize the one chosen
+    Choose {
+        /// Where to write the license, or - for stdout; defaults to
+        /// the configured file name, which directories also receive
+        #[clap(short, long)]
+        path: Option<String>,
+        #[command(flatten)]
+        owner_args: GitOwnerArgs,
+        #[command(flatten)]
+        write_args: WriteArgs,
+    },
+    /// Browse the licenses in a full-screen view, with a filterable
+    /// list beside the details and text of the highlighted license,
+    /// and initialize the one picked with Enter
+    Browse {
+        /// Where to write the license, or - for stdout; defaults to
+        /// the configured file name, which directories also receive
+        #[clap(short, long)]
+        path: Option<String>,
+        #[command(flatten)]
+        owner_args: GitOwnerArgs,
+        #[command(flatten)]
+        write_args: WriteArgs,
+    },
+    /// Add a license in the current directory without prompting for
+    /// individual details
+    Add {
+        /// The licenses to add, each to its own LICENSE-<ID> file
+        /// when there are several, or an SPDX expression such as "MIT
+        /// OR Apache-2.0", explained by a stub in the license file;
+        /// defaults to the configured default-license
+        #[arg(add = ArgValueCompleter::new(complete_license_ids))]
+        license_ids: Vec<String>,
+        #[arg(
+            short,
+            long,
+            alias = "author",
+            env = "LICTOOL_OWNER",
+            conflicts_with = "owners_from_git"
+        )]
+        owner: Option<String>,
+        #[arg(short, long, env = "LICTOOL_EMAIL")]
+        email: Option<String>,
+        #[arg(short, long, env = "LICTOOL_REPO")]
+        repo: Option<String>,
+        /// A year or year range such as 2019-2024, or from-git for
+        /// the years since the first commit of the repository
+        #[arg(short, long, env = "LICTOOL_YEAR", value_parser = parse_year)]
+        year: Option<String>,
+        /// Where to write the license, or - for stdout; can be
+        /// repeated or given as a comma-separated list, and defaults
+        /// to the configured file name, which directories also
+        /// receive
+        #[clap(short, long, value_delimiter = ',')]
+        path: Vec<String>,
+        #[command(flatten)]
+        owner_args: GitOwnerArgs,
+        #[command(flatten)]
+        write_args: WriteArgs,
+    },
+    /// Lists all available licenses
+    List {
+        /// Only deprecated
+        #[arg(short, long, conflicts_with_all = ["supported", "no_deprecated"])]
+        deprecated: bool,
+        #[arg(short, long)]
+        /// Only supported, the same as --no-deprecated
+        supported: bool,
+        /// Exclude deprecated
+        #[arg(long)]
+        no_deprecated: bool,
+        #[arg(short, long, conflicts_with = "no_osi_approved")]
+        /// Only OSI Approved
+        osi_approved: bool,
+        /// Exclude OSI Approved
+        #[arg(long)]
+        no_osi_approved: bool,
+        #[arg(short, long, conflicts_with = "no_fsf_libre")]
+        /// Only FSF Free/Libre
+        fsf_libre: bool,
+        /// Exclude FSF Free/Libre, including licenses the FSF has not
+        /// rated
+        #[arg(long)]
+        no_fsf_libre: bool,
+        /// Only favorites
+        #[arg(long)]
+        favorites: bool,
+        /// Only popular licenses, most popular first, with their
+        /// share of GitHub repositories where known
+        #[arg(long)]
+        popular: bool,
+        /// Print bare IDs without color or sorting (default when
+        /// piped)
+        #[arg(long, conflicts_with = "fancy")]
+        plain: bool,
+        /// Force colored output even when stdout is not a terminal
+        #[arg(long)]
+        fancy: bool,
+        /// Only licenses intended for fonts, data or documentation
+        #[arg(long = "for", value_enum, value_name = "ASSETS")]
+        category: Option<AssetCategory>,
+        /// Show a table of the licenses with their OSI approval, FSF
+        /// libre status, support status and name
+        #[arg(short, long, conflicts_with = "popular")]
+        long: bool,
+        /// List the license exceptions, such as LLVM-exception,
+        /// instead of the licenses
+        #[arg(
+            long,
+            conflicts_with_all = ["osi_approved", "no_osi_approved", "fsf_libre", "no_fsf_libre", "favorites", "popular", "category", "long"]
+        )]
+        exceptions: bool,
+    },
+    /// Check the project's license file; --format fix-json describes
+    /// the text edits fixing the findings, with lines and columns
+    /// starting at 1
+    Check {
+        /// Compare the license file against the canonical SPDX text,
+        /// ignoring filled in placeholders
+        #[arg(long)]
+        drift: bool,
+        /// Report drift by paragraph, showing each modified clause in
+        /// full along with how much of the canonical text is kept
+        #[arg(long)]
+        paragraphs: bool,
+        /// Report files of the project still containing raw
+        /// placeholders such as [yyyy] or <copyright holders>
+        #[arg(long)]
+        placeholders: bool,
+        /// Report files whose SPDX header names a license other than
+        /// the one [paths] or the default license expects for them
+        #[arg(long)]
+        headers: bool,
+        /// Report license files not named by the convention, such as
+        /// license.TXT or Licence.md for a LICENSE.md default file
+        /// name
+        #[arg(long)]
+        filenames: bool,
+        /// Rename misnamed license files, updating the license-file
+        /// references of Cargo.toml and package.json
+        #[arg(long, conflicts_with = "format")]
+        fix: bool,
+        /// Check what crates.io and downstream packagers expect
+        /// before `cargo publish`: a valid SPDX license
+        /// field, packaged license files and no unfilled
+        /// placeholders in the package
+        #[arg(long, conflicts_with_all = ["drift", "write_baseline"])]
+        publish: bool,
+        /// The license the file should contain; defaults to the
+        /// configured default-license
+        #[arg(short, long)]
+        license: Option<String>,
+        /// The license file to check, or - to read the license text
+        /// from stdin; defaults to the configured file name
+        #[arg(short, long)]
+        path: Option<String>,
+        /// The baseline file of known violations to ignore
+        #[arg(long, default_value = DEFAULT_BASELINE_FILE)]
+        baseline: String,
+        /// Record the current placeholder violations in the baseline
+        /// file instead of reporting them
+        #[arg(long)]
+        write_baseline: bool,
+        /// List the files skipped because of a lictool:ignore or
+        /// reuse:ignore comment near their top
+        #[arg(short, long)]
+        verbose: bool,
+        #[command(flatten)]
+        matching: MatchNormalization,
+    },
+    /// Audit the declared licenses of the dependencies of a Cargo
+    /// project, grouped by license, flagging copyleft, deprecated
+    /// and non-OSI licenses and those the [policy] rejects
+    Audit {
+        /// Accept these licenses in addition to the [policy] allow
+        /// list
+        #[arg(long, value_delimiter = ',', value_name = "IDS")]
+        allow: Vec<String>,
+        /// Reject these licenses in addition to the [policy] deny
+        /// list
+        #[arg(long, value_delimiter = ',', value_name = "IDS")]
+        deny: Vec<String>,
+        /// Include dev- and build-dependencies, which are not
+        /// distributed with the project
+        #[arg(long)]
+        include_dev: bool,
+    },
+    /// Evaluate license expressions against the configured policy
+    Policy {
+        #[clap(subcommand)]
+        action: PolicyCommand,
+    },
+    /// Check that an SPDX expression is well-formed and only uses
+    /// known license IDs
+    Validate {
+        expression: String,
+        /// Print the canonical form of the expression instead, with
+        /// the SPDX casing, -only/-or-later IDs and no redundant
+        /// parentheses
+        #[arg(long)]
+        normalize: bool,
+    },
+    /// Generate a Debian machine-readable copyright file (DEP-5)
+    /// from the default license and the [paths] license mapping
+    DebianCopyright {
+        /// Where to write the file
+        #[arg(short, long, default_value = "debian/copyright")]
+        output: String,
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Write a Developer Certificate of Origin file, asking
+    /// contributors to sign off their commits
+    Dco(DocumentArgs),
+    /// Write an individual Contributor License Agreement template for
+    /// contributors to sign
+    Cla(DocumentArgs),
+    /// Write a NOTICE file with the project name and copyright line,
+    /// as expected alongside Apache-2.0 and similar licenses
+    Notice {
+        #[command(flatten)]
+        document: DocumentArgs,
+        /// Attribute the dependencies of the Cargo project, with the
+        /// license each declares
+        #[arg(long)]
+        third_party: bool,
+        /// With --third-party, include dev- and build-dependencies,
+        /// which are not distributed with the project
+        #[arg(long, requires = "third_party")]
+        include_dev: bool,
+    },
+    /// Generate or refresh an AUTHORS file from the git history,
+    /// formatted as configured in the [authors] section
+    Authors {
+        /// Where to write the file; defaults to the configured file,
+        /// or AUTHORS
+        #[arg(short, long)]
+        output: Option<String>,
+        /// The number of commits an author needs to be listed;
+        /// defaults to the configured min-commits, or 1
+        #[arg(long)]
+        min_commits: Option<usize>,
+    },
+    /// Fill in the placeholders left in an existing license file,
+    /// prompting for the values not given as flags
+    Fill {
+        /// The license file; defaults to the configured file name
+        #[arg(short, long)]
+        path: Option<String>,
+        #[command(flatten)]
+        fields: FieldArgs,
+    },
+    /// Extend the years of the copyright lines of a license file to
+    /// the current year, such as 2021 to 2021-2025
+    BumpYear {
+        /// The license file; defaults to the configured file name
+        path: Option<String>,
+    },
+    /// Set the license field of Cargo.toml, package.json and
+    /// pyproject.toml to the license of the license file
+    SyncManifest {
+        /// The SPDX expression to declare instead of the license of
+        /// the license file
+        expression: Option<String>,
+        /// Only check that the manifests declare the license, without
+        /// updating them
+        #[arg(long)]
+        check: bool,
+    },
+    /// Remove the license files of the project, along with the
+    /// license fields of its manifests and the configured default
+    /// license
+    Rm {
+        /// Remove without asking for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Replace the license of the project with another one, keeping
+    /// the owner, year and email of the current license file and
+    /// updating the license fields of its manifests
+    Switch {
+        /// The SPDX ID of the new license
+        license_id: String,
+        #[command(flatten)]
+        write_args: WriteArgs,
+    },
+    /// Summarize the licensing of the project: its detected license,
+    /// whether its manifests agree, how many files have an SPDX
+    /// header and which declared licenses the policy rejects
+    Status {
+        /// Print the summary as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write a Markdown report of the licensing of the project, with
+    /// a summary table, the files of each license and the policy
+    /// violations, such as for release documentation; --format html
+    /// renders it as HTML, the default for an .html output
+    Report {
+        /// Where to write the report; printed when omitted
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Deprecated short form of --format for the report, kept
+        /// for existing scripts
+        #[arg(short = 'f', value_enum, hide = true)]
+        short_format: Option<ReportFormat>,
+    },
+    /// Export the license dataset for machines without internet
+    /// access, or import such an export
+    Db {
+        #[clap(subcommand)]
+        action: DbCommand,
+    },
+    /// Inspect, clear or refresh the cache of responses from spdx.org
+    /// and other sources
+    Cache {
+        #[clap(subcommand)]
+        action: CacheCommand,
+    },
+    /// Bundle the custom license texts, placeholder script, policy,
+    /// profiles and header styles into a single file, or import such
+    /// a bundle, so that a team shares one lictool setup
+    Pack {
+        #[clap(subcommand)]
+        action: PackCommand,
+    },
+    /// Run a language server over stdio that completes SPDX IDs in
+    /// SPDX headers and the license fields of Cargo.toml and
+    /// package.json, and flags unknown or deprecated ones
+    Lsp,
+    /// Print the org.opencontainers.image.licenses LABEL of the
+    /// project's license expression, or patch it into a Dockerfile
+    OciLabel {
+        /// The license expression; defaults to the configured default
+        /// license, or else the license of Cargo.toml or package.json
+        #[arg(short, long)]
+        license: Option<String>,
+        /// Patch the label into this Dockerfile instead of printing
+        /// it; defaults to ./Dockerfile
+        #[arg(long, num_args = 0..=1, default_missing_value = "Dockerfile")]
+        dockerfile: Option<PathBuf>,
+    },
+    /// Verify that the license file matches the canonical SPDX text
+    /// with every placeholder filled in, or cross-check an SPDX
+    /// document against the project with --sbom
+    Verify {
+        /// The license file to verify, or - to read the license text
+        /// from stdin; defaults to the main license file
+        #[arg(conflicts_with = "sbom")]
+        path: Option<String>,
+        /// The license the file should contain; defaults to the
+        /// configured default-license
+        #[arg(long, conflicts_with = "sbom")]
+        license_id: Option<String>,
+        /// Verify the licenses of an SPDX document in JSON form, such
+        /// as project.spdx.json, against the project instead
+        #[arg(long)]
+        sbom: Option<PathBuf>,
+    },
+    /// Generate a minimal SPDX document describing the project: its
+    /// name, version, declared license and copyright
+    Sbom {
+        /// Also list the files with an SPDX header and their licenses
+        #[arg(long)]
+        files: bool,
+        /// Where to write the document; printed when omitted. SPDX
+        /// JSON is written with --format json or a .json output, the
+        /// tag-value format otherwise
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Identify the license of an existing license file, such as one
+    /// in vendored code, by comparing its text with the SPDX license
+    /// texts
+    Detect {
+        /// The license file, or a directory whose main license file
+        /// to read; defaults to the current directory
+        path: Option<PathBuf>,
+        /// How many of the most similar licenses to show
+        #[arg(long, default_value_t = 3)]
+        candidates: usize,
+    },
+    /// Find the license files and SPDX headers inside a tar or zip
+    /// archive, such as a release tarball or a vendored bundle
+    Scan {
+        /// The archive to scan: a .tar, .tar.gz, .tgz, .crate, .zip,
+        /// .jar or .whl file
+        archive: PathBuf,
+        /// Print the findings as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Replace this executable with the latest GitHub release, after
+    /// verifying its checksum; for installs outside of a package
+    /// manager
+    ///
+    /// Releases are not signed. The checksum is published with the
+    /// release itself, so it catches corrupted downloads but not a
+    /// tampered release or GitHub account.
+    SelfUpdate {
+        /// Only report whether a newer release exists
+        #[arg(long)]
+        check: bool,
+    },
+    /// Check the connection to SPDX, the cache, the configuration,
+    /// git and the terminal, suggesting fixes for any problem
+    Doctor,
+    /// Revert the files written by the last command, such as a
+    /// license added by mistake
+    Undo {
+        /// Revert even files that were changed since
+        #[arg(long)]
+        force: bool,
+    },
+    /// Inspect the user and project configuration files
+    Config {
+        #[clap(subcommand)]
+        action: ConfigCommand,
+    },
+    /// Work with the SPDX headers of source files
+    Header {
+        #[clap(subcommand)]
+        action: HeaderCommand,
+    },
+    /// Show which placeholders a license contains, and the add flags
+    /// filling them
+    Placeholders { license_id: String },
+    /// Search licenses by ID and name, such as all Apache licenses,
+    /// or with --text the texts of all licenses, such as for a clause
+    /// across the SPDX corpus; texts are cached after the first run
+    Search {
+        /// The part of an ID or name to find, matched fuzzily if no
+        /// license contains it; with --text, the text to find, or a
+        /// regular expression with --regex, where line breaks in
+        /// license texts match a single space
+        pattern: String,
+        /// Search the texts of the licenses instead of their IDs and
+        /// names
+        #[arg(short, long)]
+        text: bool,
+        /// Read the pattern as a regular expression
+        #[arg(short, long, requires = "text")]
+        regex: bool,
+        /// Match without regard to letter case
+        #[arg(short, long, requires = "text")]
+        ignore_case: bool,
+    },
+    /// Search the full texts of all SPDX licenses for a phrase,
+    /// ignoring case and line breaks, from an index kept in the cache
+    /// directory once built
+    Grep {
+        /// The phrase to find, such as a clause half-remembered
+        phrase: String,
+    },
+    /// Compare the variants of a license family, such as GPL, CC or
+    /// BSD
+    Family {
+        /// The start of the license IDs of the family, ignoring case
+        prefix: String,
+    },
+    /// Print a shields.io badge of a license and a "License" section
+    /// for the README linking to the license file, or put the section
+    /// into a README
+    Badge {
+        /// The license ID or expression; defaults to the license of
+        /// the project
+        #[arg(add = ArgValueCompleter::new(complete_license_ids))]
+        license_id: Option<String>,
+        /// The markup to write; guessed from the extension of the
+        /// README with --inject, and Markdown otherwise
+        #[arg(short, long, value_enum)]
+        markup: Option<Markup>,
+        /// Put the section into this README between lictool markers,
+        /// replacing the one a previous run put there
+        #[arg(long, value_name = "README")]
+        inject: Option<String>,
+    },
+    /// Compare the texts of two licenses word by word, along with
+    /// their OSI, FSF and deprecation status
+    Diff {
+        /// The license to compare from
+        #[arg(add = ArgValueCompleter::new(complete_license_ids))]
+        first: String,
+        /// The license to compare to
+        #[arg(add = ArgValueCompleter::new(complete_license_ids))]
+        second: String,
+    },
+    /// Choose a Creative Commons license for documentation and other
+    /// non-code assets by answering a few questions, and map it to
+    /// their paths in the [paths] table of .lictool.toml
+    Cc {
+        /// The files or directories to license; defaults to the
+        /// existing ones of docs, doc, assets and media
+        paths: Vec<PathBuf>,
+    },
+    /// Get info about one or more licenses or license exceptions
+    Info {
+        /// The licenses or exceptions to describe, fetched
+        /// concurrently; a license is picked interactively when
+        /// omitted
+        #[arg(add = ArgValueCompleter::new(complete_license_ids))]
+        license_ids: Vec<String>,
+        /// Also show the Debian (DEP-5) and legacy Fedora names of
+        /// the licenses
+        #[arg(long)]
+        distro: bool,
+        /// Check that the See Also links still resolve, flagging dead
+        /// ones
+        #[arg(long)]
+        verify_links: bool,
+    },
+    /// Manage favorite licenses, which are listed first in the picker
+    Fav {
+        #[clap(subcommand)]
+        action: FavCommand,
+    },
+    /// Generate completion scripts for your shell, which complete
+    /// license IDs from the cached license list by calling back into
+    /// lictool
+    Completions {
+        #[clap(value_enum)]
+        shell: Shell,
+        /// Generate a self-contained script instead, which completes
+        /// commands and flags but not license IDs
+        #[arg(long = "static")]
+        static_script: bool,
+    },
+    /// Run the `lictool-<name>` executable on the PATH for other
+    /// commands, passing the settings as JSON in
+    /// LICTOOL_PLUGIN_CONTEXT
+    #[command(external_subcommand)]
+    External(Vec<OsString>),
+}
+
+#[derive(Subcommand, Debug)]
+/// Favorite license actions
+enum FavCommand {
+    /// Add licenses to the favorites
+    Add {
+        #[arg(required = true)]
+        license_ids: Vec<String>,
+        /// Store the favorites in the project's .lictool.toml instead
+        /// of the user configuration
+        #[arg(long)]
+        project: bool,
+    },
+    /// Remove licenses from the favorites
+    Remove {
+        #[arg(required = true)]
+        license_ids: Vec<String>,
+        /// Remove the favorites from the project's .lictool.toml
+        /// instead of the user configuration
+        #[arg(long)]
+        project: bool,
+    },
+    /// List the favorite licenses
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Check the configuration files for unknown keys and values of
+    /// the wrong type
+    Validate,
+    /// Print the value of a setting, such as owner or policy.deny, as
+    /// merged from the configuration files and environment
+    Get { key: String },
+    /// Set a setting in the user configuration, such as `config set
+    /// default-license MIT`; values are read as TOML when they parse,
+    /// such as true or ["MIT"], and as a string otherwise
+    Set {
+        key: String,
+        value: String,
+        /// Set it in the project's .lictool.toml instead of the user
+        /// configuration
+        #[arg(long)]
+        project: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum HeaderCommand {
+    /// Check that every file that can hold a header has an SPDX one
+    /// and that it names the license expected for the file, from the
+    /// [paths] mapping, the default-license or the project's license
+    Check {
+        /// Keep running and re-check files as they change, printing
+        /// the files that gain or lose a header
+        #[arg(short, long)]
+        watch: bool,
+        #[command(flatten)]
+        filter: FileFilterArgs,
+    },
+    /// Insert SPDX headers, in the comment style of their language,
+    /// into the files that have none
+    Add {
+        /// The files or directories to add headers to; defaults to
+        /// the current directory
+        paths: Vec<PathBuf>,
+        /// The license expression of the headers; defaults to the
+        /// license [paths] maps each file to, or the default-license
+        #[arg(short, long)]
+        license: Option<String>,
+        /// Use the years of the oldest and newest commits in the git
+        /// blame of each file instead of the current year
+        #[arg(long)]
+        years_from_git: bool,
+        /// Leave out the SPDX-FileCopyrightText line
+        #[arg(long)]
+        no_copyright: bool,
+        /// Only list the files without a header, failing if there are
+        /// any, such as in CI
+        #[arg(long)]
+        check: bool,
+        #[command(flatten)]
+        filter: FileFilterArgs,
+    },
+    /// Replace a license in the SPDX headers of files, such as when
+    /// relicensing a project
+    Replace {
+        /// The files or directories whose headers to rewrite;
+        /// defaults to the current directory
+        paths: Vec<PathBuf>,
+        /// The license ID to replace
+        #[arg(long, value_name = "ID", add = ArgValueCompleter::new(complete_license_ids))]
+        from: String,
+        /// The license expression to replace it with
+        #[arg(long, value_name = "EXPRESSION", add = ArgValueCompleter::new(complete_license_ids))]
+        to: String,
+        #[command(flatten)]
+        filter: FileFilterArgs,
+    },
+    /// Print the header a file would get, in the comment style of its
+    /// language
+    Preview {
+        path: String,
+        /// Use the years of the oldest and newest commits in the git
+        /// blame of the file instead of the current year
+        #[arg(long)]
+        years_from_git: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum PackCommand {
+    /// Write the pack settings of the user configuration, along with
+    /// the files they reference, to a .tar.gz file
+    Export {
+        path: PathBuf,
+        /// Export the project's .lictool.toml instead of the user
+        /// configuration
+        #[arg(long)]
+        project: bool,
+    },
+    /// Merge a pack exported with `pack export`, given as a file or a
+    /// URL, into the user configuration
+    Import {
+        // Named apart from the global --source flag, whose values
+        // would otherwise be read as this argument.
+        #[arg(id = "pack", value_name = "SOURCE")]
+        source: String,
+        /// Import the pack into the project's .lictool.toml instead
+        /// of the user configuration
+        #[arg(long)]
+        project: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum DbCommand {
+    /// Download the license and exception lists with every license
+    /// text into a directory, or a .tar.gz file
+    ///
+    /// An interrupted export resumes where it stopped on the next
+    /// run.
+    Export {
+        path: PathBuf,
+        /// Discard the files fetched by an interrupted export and
+        /// fetch everything again
+        #[arg(long)]
+        restart: bool,
+    },
+    /// Load a dataset exported with `db export`, which is then used
+    /// instead of spdx.org until `cache clear --mirror`
+    Import { path: PathBuf },
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheCommand {
+    /// Show the size of the cache and the age of its entries
+    Status,
+    /// Delete the cache, which is rebuilt as licenses are fetched
+    /// again
+    Clear {
+        /// Also delete the dataset imported with `db import`, so that
+        /// licenses are fetched from the network again
+        #[arg(long)]
+        mirror: bool,
+    },
+    /// Revalidate the SPDX license list with spdx.org and cache the
+    /// details of commonly used licenses
+    Refresh,
+}
+
+#[derive(Subcommand, Debug)]
+enum PolicyCommand {
+    /// Check whether an SPDX expression such as "MIT OR GPL-3.0-only"
+    /// has at least one branch accepted by the [policy] allow and
+    /// deny lists
+    Eval { expression: String },
+}
+
+/// Options shared by the commands that write license files.
+#[derive(Args, Debug)]
+struct WriteArgs {
+    /// Create missing parent directories without asking
+    #[arg(long)]
+    parents: bool,
+    /// Line endings to write; detected from .gitattributes or
+    /// existing files when omitted
+    #[arg(long, value_enum, visible_alias = "line-ending")]
+    line_endings: Option<LineEnding>,
+    /// The name to write the license file with when no path is
+    /// given, in plain text or, for LICENSE.md, formatted as
+    /// Markdown; defaults to the configured default-filename
+    #[arg(long, value_enum, ignore_case = true)]
+    filename: Option<FileNameConvention>,
+    /// Character encoding of the written file
+    #[arg(long, value_enum, default_value_t = Encoding::Utf8)]
+    encoding: Encoding,
+    #[command(flatten)]
+    normalization: Normalization,
+    /// Permissions of the written file, in octal (e.g. 0644)
+    #[arg(long, value_parser = parse_mode)]
+    mode: Option<u32>,
+    /// Stage the written files and create a git commit
+    #[arg(long)]
+    commit: bool,
+    /// Fail instead of writing a license that still contains
+    /// placeholders
+    #[arg(long)]
+    strict: bool,
+    /// Overwrite existing license files
+    #[arg(short, long)]
+    force: bool,
+    /// Copy existing license files to <file>.bak before overwriting
+    /// them
+    #[arg(long)]
+    backup: bool,
+    /// Fill a {{key}} placeholder of a custom template, such as
+    /// --set department=Research; may be repeated
+    #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_variable)]
+    variables: Vec<(String, String)>,
+}
+
+impl WriteArgs {
+    /// Returns the file name to write the license with when no path
+    /// is given: that of --filename, or else the configured one.
+    fn default_filename<'a>(
+        &self,
+        config: &'a Config,
+    ) -> &'a str {
+        match self.filename {
+            Some(filename) => filename.file_name(),
+            None => config.default_filename(),
+        }
+    }
+
+    /// Builds the `WriteOptions` for these arguments, taking defaults
+    /// from the configuration.
+    fn options(
+        &self,
+        config: &Config,
+    ) -> LictoolResult<WriteOptions> {
+        Ok(WriteOptions {
+            parents: self.parents,
+            default_filename: self.default_filename(config).to_string(),
+            line_ending: self.line_endings,
+            encoding: self.encoding,
+            normalization: self.normalization,
+            markdown: is_markdown_file(Path::new(self.default_filename(config))),
+            mode: self.mode,
+            post_write_hooks: config.hooks.post_write.clone(),
+            theme: config.theme.clone(),
+            strict: self.strict,
+            force: self.force,
+            backup: self.backup,
+            variables: config
+                .variables
+                .clone()
+                .into_iter()
+                .chain(self.variables.iter().cloned())
+                .collect(),
+            script: config.placeholder_script()?,
+        })
+    }
+}
+
+/// Arguments of `dco`, `cla` and `notice`, filling the same fields as
+/// license templates.
+#[derive(Args, Debug)]
+struct DocumentArgs {
+    /// Where to write the document; defaults to DCO for `dco`, CLA.md
+    /// for `cla` and NOTICE for `notice`
+    #[arg(short = 'p', long)]
+    path: Option<String>,
+    /// Overwrite the file if it already exists
+    #[arg(short, long)]
+    force: bool,
+    #[command(flatten)]
+    fields: FieldArgs,
+}
+
+/// The values of the fields of a template, for the commands filling
+/// one without picking a license.
+#[derive(Args, Debug)]
+struct FieldArgs {
+    #[arg(short, long, alias = "author", env = "LICTOOL_OWNER")]
+    owner: Option<String>,
+    #[arg(short, long, env = "LICTOOL_EMAIL")]
+    email: Option<String>,
+    #[arg(short, long, env = "LICTOOL_REPO")]
+    repo: Option<String>,
+    /// A year or year range such as 2019-2024, or from-git for the
+    /// years since the first commit of the repository
+    #[arg(short, long, env = "LICTOOL_YEAR", value_parser = parse_year)]
+    year: Option<String>,
+    /// Use the configured defaults instead of prompting for the
+    /// values not given as flags
+    #[arg(long)]
+    no_input: bool,
+}
+
+impl FieldArgs {
+    /// Returns a template of `text` with the fields given as flags.
+    ///
+    /// # Errors
+    /// - If the configured placeholder script cannot be loaded.
+    fn template(
+        &self,
+        text: String,
+        config: &Config,
+    ) -> LictoolResult<Template> {
+        Ok(Template {
+            license_text: text,
+            year: self.year.clone(),
+            owner: self.owner.clone(),
+            repo: self.repo.clone(),
+            email: self.email.clone(),
+            variables: config.variables.clone(),
+            script: config.placeholder_script()?,
+        })
+    }
+
+    /// Checks whether to prompt for the values not given as flags.
+    fn interactive(&self) -> bool {
+        !self.no_input && io::stdin().is_terminal()
+    }
+}
+
+/// Glob filters on the files a header command walks, matched against
+/// their path from the current directory, or their name for patterns
+/// without a `/`.
+#[derive(Args, Debug)]
+struct FileFilterArgs {
+    /// Only walk the files matching this glob, such as 'src/*'; may
+    /// be repeated
+    #[arg(long, value_name = "GLOB")]
+    include: Vec<String>,
+    /// Skip the files matching this glob, such as '*.min.js'; may be
+    /// repeated
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<String>,
+}
+
+impl FileFilterArgs {
+    /// Checks whether a file is included and not excluded.
+    fn matches(
+        &self,
+        path: &Path,
+    ) -> bool {
+        let relative = path.strip_prefix(".").unwrap_or(path);
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        let matches = |pattern: &String| {
+            let pattern = pattern.trim_start_matches("./");
+            glob_matches(pattern, &relative)
+                || (!pattern.contains('/') && glob_matches(pattern, &name))
+        };
+        (self.include.is_empty() || self.include.iter().any(matches))
+            && !self.exclude.iter().any(matches)
+    }
+}
+
+/// Arguments naming the authors from the git history as the copyright
+/// holders.
+#[derive(Args, Debug)]
+struct GitOwnerArgs {
+    /// Name every author of the git history as a copyright holder,
+    /// merging their identities with .mailmap
+    #[arg(long)]
+    owners_from_git: bool,
+    /// The number of commits an author needs to be named by
+    /// --owners-from-git
+    #[arg(long, default_value_t = 1, requires = "owners_from_git")]
+    min_commits: usize,
+}
+
+impl GitOwnerArgs {
+    /// Returns the defaults naming the git authors, or no defaults
+    /// without --owners-from-git.
+    ///
+    /// # Errors
+    /// - If the git history cannot be read.
+    fn defaults(&self) -> LictoolResult<FieldDefaults> {
+        if !self.owners_from_git {
+            return Ok(FieldDefaults::default());
+        }
+        let contributors =
+            contributors(Path::new(".")).with_context(|| t!("error-read-git-history"))?;
+        Ok(FieldDefaults::from_contributors(
+            &contributors,
+            self.min_commits,
+        ))
+    }
+}
+
+/// Parses the value of a template placeholder such as
+/// `department=Research`.
+fn parse_variable(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.to_string()))
+        }
+        _ => Err(t!("invalid-variable", variable = value)),
+    }
+}
+
+/// Parses an octal file mode such as `0644` or `755`.
+fn parse_mode(value: &str) -> Result<u32, String> {
+    let digits = value.trim_start_matches("0o");
+    match u32::from_str_radix(digits, 8) {
+        Ok(mode) if mode <= 0o7777 => Ok(mode),
+        _ => Err(t!("invalid-mode", mode = value)),
+    }
+}
+
+/// Commits the written license files to the current git repository.
+fn commit_license(
+    paths: &[PathBuf],
+    license_id: &str,
+    config: &Config,
+) -> LictoolResult<()> {
+    let paths: Vec<PathBuf> = paths
+        .iter()
+        .filter(|path| !is_stdout(path))
+        .cloned()
+        .collect();
+    if is_dry_run() || paths.is_empty() {
+        return Ok(());
+    }
+    let oid = commit_files(&paths, &config.commit_message(license_id))?;
+    let short = oid.to_string();
+    success!(
+        "<green>✔</> <bold>{}</>",
+        t!(
+            "license-committed",
+            license = license_id,
+            commit = &short[..7]
+        )
+    );
+    Ok(())
+}
+
+/// Fetches the details of a license, along with its FSF
+/// categorization, or else of a license exception, formatted for
+/// display.
+///
+/// With `distro`, the distribution names of licenses are appended,
+/// and with `verify_links`, the status of each See Also link.
+///
+/// # Errors
+/// - `Error::NotFound` if `id` is neither a license nor an exception.
+async fn describe(
+    id: &str,
+    licenses: &Licenses,
+    exceptions: Option<&Exceptions>,
+    distro: bool,
+    verify_links: bool,
+) -> LictoolResult<Description> {
+    // The FSF list is an extra; `info` works without it.
+    let mut fsf_unavailable = false;
+    let details = if let Some(license) = licenses.find(id) {
+        let (details, fsf) = join(license.details(), FsfLicense::fetch(&license.id)).await;
+        let mut details = details?;
+        match fsf {
+            Ok(fsf) => details.fsf = fsf,
+            Err(_) => fsf_unavailable = true,
+        }
+        Described::License(details)
+    } else {
+        let exception = exceptions
+            .and_then(|exceptions| exceptions.find(id))
+            .ok_or(Error::NotFound)?;
+        let mut details = exception.details().await?;
+        details.resolve_combined_with(licenses);
+        Described::Exception(details)
+    };
+    let distro_names = match &details {
+        Described::License(details) if distro => {
+            Some(DistroNames {
+                debian: debian_name(&details.license_id).to_string(),
+                fedora: fedora_name(&details.license_id).to_string(),
+            })
+        }
+        _ => None,
+    };
+    let link_checks = if verify_links {
+        let see_also = match &details {
+            Described::License(details) => &details.see_also,
+            Described::Exception(details) => &details.see_also,
+        };
+        Some(links::verify_links(see_also).await)
+    } else {
+        None
+    };
+    let rules = match &details {
+        Described::License(details) => license_rules(&details.license_id),
+        Described::Exception(_) => None,
+    };
+    let successors = match &details {
+        Described::License(details) if details.is_deprecated_license_id => {
+            successors(&details.license_id, licenses)
+        }
+        _ => Vec::new(),
+    };
+    Ok(Description {
+        details,
+        distro_names,
+        link_checks,
+        rules,
+        successors,
+        fsf_unavailable,
+    })
+}
+
+/// What `info` shows of a license or an exception.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Description {
+    #[serde(flatten)]
+    details: Described,
+    /// The names of the license in Linux distributions, with
+    /// `--distro`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distro_names: Option<DistroNames>,
+    /// The checks of the links of the license, with `--verify-links`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link_checks: Option<Vec<LinkCheck>>,
+    /// The permissions, conditions and limitations of the license,
+    /// for the common licenses lictool knows them of.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rules: Option<LicenseRules>,
+    /// The licenses to use instead of a deprecated one.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    successors: Vec<String>,
+    /// Whether the FSF license list could not be fetched.
+    #[serde(skip)]
+    fsf_unavailable: bool,
+}
+
+/// The details of a license or an exception.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum Described {
+    License(LicenseDetails),
+    Exception(ExceptionDetails),
+}
+
+/// The names Linux distributions use for a license, falling back to
+/// its SPDX ID.
+#[derive(Debug, Serialize)]
+struct DistroNames {
+    debian: String,
+    fedora: String,
+}
+
+impl std::fmt::Display for Description {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match &self.details {
+            Described::License(details) => {
+                write!(f, "{}", display_license_details(details))?;
+                if self.fsf_unavailable {
+                    write!(
+                        f,
+                        "\n{}",
+                        cformat!("<s>{}</> {}", t!("info-fsf-categories"), t!("info-unknown"))
+                    )?;
+                }
+                if !self.successors.is_empty() {
+                    write!(
+                        f,
+                        "\n{}",
+                        cformat!(
+                            "<s>{}</> <yellow>{}</>",
+                            t!("info-successors"),
+                            self.successors.join(", ")
+                        )
+                    )?;
+                }
+                if let Some(rules) = &self.rules {
+                    write!(f, "\n{}", display_license_rules(rules))?;
+                }
+                if self.distro_names.is_some() {
+                    write!(f, "\n{}", display_distro_names(&details.license_id))?;
+                }
+            }
+            Described::Exception(details) => write!(f, "{}", display_exception_details(details))?,
+        }
+        if let Some(checks) = &self.link_checks {
+            write!(f, "\n{}", display_link_checks(checks))?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders the header of a file in the comment style of its
+/// language, dated with the years of its git history if
+/// `years_from_git` is set.
+///
+/// # Errors
+/// - `Error::UnsupportedFileType` if no comment style covers the
+///   file.
+fn file_header(
+    path: &Path,
+    license_id: &str,
+    owner: Option<&str>,
+    defaults: &FieldDefaults,
+    years_from_git: bool,
+    header: &HeaderConfig,
+) -> LictoolResult<String> {
+    let year = years_from_git
+        .then(|| file_years(path))
+        .flatten()
+        .or(defaults.year.clone())
+        .unwrap_or_else(|| Local::now().year().to_string());
+    let vars = HeaderVars {
+        spdx_id: license_id,
+        year: &year,
+        owner,
+        filename: &path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default(),
+    };
+    let lines = header.header_lines(path, &vars);
+    Ok(header
+        .comment_style(path)
+        .and_then(|style| style.comment(&lines))
+        .ok_or_else(|| {
+            Error::UnsupportedFileType {
+                file: path.display().to_string(),
+            }
+        })?)
+}
+
+/// Lists the files under `paths`, or the current directory without
+/// any, that pass `filter`, leaving out license files.
+fn header_files(
+    paths: &[PathBuf],
+    filter: &FileFilterArgs,
+    config: &Config,
+) -> Vec<PathBuf> {
+    let license_files = license_files(Path::new("."), config.default_filename());
+    let roots = if paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        paths.to_vec()
+    };
+    let mut files = Vec::new();
+    for root in roots {
+        if root.is_dir() {
+            files.extend(project_files(&root));
+        } else {
+            files.push(root);
+        }
+    }
+    files.retain(|path| {
+        filter.matches(path) && !license_files.iter().any(|license| same_file(path, license))
+    });
+    files
+}
+
+/// Completes the license IDs starting with the word being completed,
+/// ignoring case, from the cached license list so that completion
+/// never waits on the network; deprecated IDs are only offered when
+/// no other matches.
+fn complete_license_ids(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(current) = current.to_str() else {
+        return Vec::new();
+    };
+    let Ok(licenses) = block_in_place(|| Handle::current().block_on(Licenses::cached())) else {
+        return Vec::new();
+    };
+    let prefix = current.to_lowercase();
+    licenses
+        .body
+        .iter()
+        .filter(|license| license.id.to_lowercase().starts_with(&prefix))
+        .map(|license| {
+            CompletionCandidate::new(&license.id)
+                .help(Some(license.name.clone().into()))
+                .hide(license.is_deprecated_license_id)
+        })
+        .collect()
+}
+
+/// Offers the successors of a deprecated license for `add`, or warns
+/// about them when stdin is no terminal.
+///
+/// # Returns
+///
+/// The ID of the license to add, with the exception of a successor
+/// such as `GPL-2.0-only WITH Classpath-exception-2.0`.
+///
+/// # Errors
+/// - `Error::Aborted` if the prompt was cancelled.
+fn replace_deprecated(
+    license: &License,
+    licenses: &Licenses,
+    config: &Config,
+) -> LictoolResult<(String, Option<String>)> {
+    let successors = successors(&license.id, licenses);
+    if !license.is_deprecated_license_id || successors.is_empty() {
+        return Ok((license.id.clone(), None));
+    }
+    if !io::stdin().is_terminal() {
+        warn_deprecated(&license.id, &successors);
+        return Ok((license.id.clone(), None));
+    }
+    let chosen = prompt_successor(license, &successors, &config.theme.build())?;
+    Ok(match chosen.split_once(" WITH ") {
+        Some((id, exception)) => (id.to_string(), Some(exception.to_string())),
+        None => (chosen, None),
+    })
+}
+
+/// Fetches a license, prompts for the values of its fields and
+/// writes it once the preview is confirmed, as the interactive `init`
+/// does.
+///
+/// # Returns
+///
+/// `false` if another license was asked for in the preview.
+///
+/// # Errors
+/// - `Error::Aborted` if the preview was aborted.
+/// - If the license cannot be fetched, prompted for or written.
+#[allow(clippy::too_many_arguments)]
+async fn init_license(
+    license: &License,
+    custom: &[CustomTemplate],
+    path: &str,
+    options: &WriteOptions,
+    commit: bool,
+    config: &Config,
+    theme: &dyn Theme,
+    defaults: &FieldDefaults,
+) -> LictoolResult<bool> {
+    let mut details = license_details(license, custom, config).await?;
+    let (fields, mut template) = if PUBLIC_DOMAIN_DEDICATIONS.contains(&license.id.as_str()) {
+        // Nothing is prompted for, though defaults still fill
+        // placeholders of overrides.
+        note!(t!("init-public-domain-note", license = license.id.as_str()));
+        let mut template = Template {
+            license_text: details.license_text,
+            ..Default::default()
+        };
+        fill_placeholders(&mut template, theme, defaults, false)?;
+        (Vec::new(), template)
+    } else {
+        let fields = Field::present_in(&details);
+        (fields, fill_license_forms(&mut details, theme, defaults)?)
+    };
+    template.variables = options.variables.clone();
+    fill_variables(&mut template, Some(theme))?;
+    // A preview would end up in the output piped from stdout.
+    let review = if is_stdout(Path::new(path)) {
+        Review::Confirm
+    } else {
+        review_template(&mut template, &fields, theme, defaults, options)?
+    };
+    match review {
+        Review::Confirm => {
+            let written = interact_write_template(path, &mut template, options, theme)?;
+            if commit {
+                commit_license(&[written], &license.id, config)?;
+            }
+            remember_license(&license.id);
+            Ok(true)
+        }
+        Review::ChangeLicense => Ok(false),
+        Review::Abort => Err(Error::Aborted)?,
+    }
+}
+
+/// Fetches several licenses, prompts once for the values of the
+/// fields they have and writes each to its own file once the previews
+/// are confirmed, such as `LICENSE-MIT` and `LICENSE-APACHE`, with a
+/// stub explaining the expression in place of the license file.
+///
+/// Licenses picked together are alternatives, as in the dual
+/// `MIT OR Apache-2.0` licensing common to Rust crates.
+///
+/// # Returns
+///
+/// `false` if other licenses were asked for in a preview.
+///
+/// # Errors
+/// - `Error::AlreadyExists` if a file exists, before any prompt.
+/// - `Error::Aborted` if a preview was aborted.
+/// - If a license cannot be fetched, prompted for or written.
+#[allow(clippy::too_many_arguments)]
+async fn init_licenses(
+    picked: &[&License],
+    licenses: &Licenses,
+    custom: &[CustomTemplate],
+    path: &str,
+    options: &WriteOptions,
+    commit: bool,
+    config: &Config,
+    theme: &dyn Theme,
+    defaults: &FieldDefaults,
+) -> LictoolResult<bool> {
+    let ids: Vec<String> = picked.iter().map(|license| license.id.clone()).collect();
+    let set = match LicenseSet::from_expression(&[ids.join(" OR ")], licenses)? {
+        Some(set) => set,
+        None => LicenseSet::from_terms(ids.into_iter().map(|id| (id, None)).collect()),
+    };
+    let paths = vec![path.to_string()];
+    let targets = set.targets(&paths, &options.default_filename);
+    ensure_targets_free(&set.all_targets(&targets, &paths), options)?;
+    let details = set.details(licenses, custom, config).await?;
+    let mut forms = Vec::with_capacity(details.len());
+    for details in details {
+        let fields = if PUBLIC_DOMAIN_DEDICATIONS.contains(&details.license_id.as_str()) {
+            note!(t!(
+                "init-public-domain-note",
+                license = details.license_id.as_str()
+            ));
+            Vec::new()
+        } else {
+            Field::present_in(&details)
+        };
+        forms.push((details.license_text, fields));
+    }
+    let fields: Vec<Vec<Field>> = forms.iter().map(|(_, fields)| fields.clone()).collect();
+    let mut templates = fill_shared_license_forms(forms, theme, defaults)?;
+    for ((id, _), (template, fields)) in set.terms.iter().zip(templates.iter_mut().zip(&fields)) {
+        if PUBLIC_DOMAIN_DEDICATIONS.contains(&id.as_str()) {
+            fill_placeholders(template, theme, defaults, false)?;
+        }
+        template.variables = options.variables.clone();
+        fill_variables(template, Some(theme))?;
+        // A preview would end up in the output piped from stdout.
+        if is_stdout(Path::new(path)) {
+            continue;
+        }
+        match review_template(template, fields, theme, defaults, options)? {
+            Review::Confirm => {}
+            Review::ChangeLicense => return Ok(false),
+            Review::Abort => Err(Error::Aborted)?,
+        }
+    }
+    let mut written = Vec::new();
+    for (mut template, paths) in templates.into_iter().zip(&targets) {
+        written.extend(write_templates(paths, &mut template, options)?);
+    }
+    written.extend(set.write_stub(&paths, &targets, options)?);
+    if commit {
+        commit_license(&written, &set.name(), config)?;
+    }
+    set.ids().iter().for_each(|id| remember_license(id));
+    Ok(true)
+}
+
+/// Opens the license picker for `init`, falling back to `fallback`
+/// when it is cancelled, and offers the successors of a deprecated
+/// license picked.
+///
+/// # Returns
+///
+/// `None` if the picker was cancelled without a fallback.
+///
+/// # Errors
+/// - `Error::NotFound` if the fallback is not among `licenses`.
+/// - `Error::Aborted` if the successor prompt was cancelled.
+fn pick_init_license<'a>(
+    licenses: &'a Licenses,
+    fallback: Option<&str>,
+    config: &Config,
+    theme: &dyn Theme,
+) -> LictoolResult<Option<&'a License>> {
+    let license = match (pick_license(licenses, config, theme)?, fallback) {
+        (Some(license), _) => license,
+        (None, Some(fallback)) => licenses.find(fallback).ok_or(Error::NotFound)?,
+        (None, None) => return Ok(None),
+    };
+    // Only successors without an exception can be initialized in
+    // place of the license.
+    let replacements: Vec<String> = successors(&license.id, licenses)
+        .into_iter()
+        .filter(|id| licenses.find(id).is_some())
+        .collect();
+    if license.is_deprecated_license_id && !replacements.is_empty() {
+        let id = prompt_successor(license, &replacements, theme)?;
+        return Ok(Some(licenses.find(&id).ok_or(Error::NotFound)?));
+    }
+    Ok(Some(license))
+}
+
+/// Returns the details of a license to write: the text of the custom
+/// template among `custom` with its `LicenseRef-` ID as-is, or else
+/// the SPDX details with the configured override applied.
+///
+/// # Errors
+/// - If the template cannot be read, the details cannot be fetched,
+///   or an override cannot be read.
+async fn license_details(
+    license: &License,
+    custom: &[CustomTemplate],
+    config: &Config,
+) -> LictoolResult<LicenseDetails> {
+    if let Some(template) = custom.iter().find(|template| template.id == license.id) {
+        return template.details();
+    }
+    let mut details = with_spinner(
+        t!("fetching-license", license = license.id),
+        license.details(),
+    )
+    .await?;
+    config.apply_override(&mut details)?;
+    Ok(details)
+}
+
+/// Opens the license picker, with the custom templates among
+/// `licenses`, the default license, favorites and recently used
+/// licenses pinned at the top, and popular licenses
+/// first among the rest unless disabled.
+///
+/// # Returns
+///
+/// `None` if the user cancelled the selection.
+fn pick_license<'a>(
+    licenses: &'a Licenses,
+    config: &Config,
+    theme: &dyn Theme,
+) -> LictoolResult<Option<&'a License>> {
+    let history = History::load();
+    let default_license: Vec<String> = config
+        .default_license()
+        .map(str::to_string)
+        .into_iter()
+        .collect();
+    let custom: Vec<String> = custom_templates()?
+        .into_iter()
+        .map(|template| template.id)
+        .filter(|id| licenses.find(id).is_some())
+        .collect();
+    let mut picker = LicensePicker::new(theme, &licenses.body)
+        .with_prompt(t!("prompt-select-license"))
+        .group(t!("picker-group-custom"), &custom)
+        .group(t!("picker-group-default"), &default_license)
+        .group(t!("picker-group-favorites"), &config.favorites)
+        .group(t!("picker-group-recent"), &history.recent)
+        .max_length(7);
+    if config.ranks_by_popularity() {
+        picker = picker.rank_by(|license| popularity::rank(&license.id));
+    }
+    Ok(picker.interact_opt()?)
+}
+
+/// Reads a license text from a URL, a file, or stdin for `-`.
+///
+/// # Errors
+/// - If the text cannot be downloaded or read.
+async fn read_license_source(source: &str) -> LictoolResult<String> {
+    if source.starts_with("https://") || source.starts_with("http://") {
+        let body = with_spinner(t!("fetching-license-source"), http::download(source)).await?;
+        return Ok(String::from_utf8_lossy(&body).into_owned());
+    }
+    read_input(source)
+}
+
+/// Returns the SPDX expression of the main license file in `root`:
+/// that of its `SPDX-License-Identifier`, such as in a license stub,
+/// or else the license its text matches.
+///
+/// # Errors
+/// - `Error::NoLicenseFile` if the project has no license file.
+/// - `Error::UndetectedLicense` if no license matches the text
+///   closely.
+/// - If the file cannot be read or the license texts cannot be
+///   fetched.
+async fn license_file_expression(
+    root: &Path,
+    licenses: &Licenses,
+    config: &Config,
+) -> LictoolResult<String> {
+    let file = main_license_file(root, config.default_filename()).ok_or(Error::NoLicenseFile)?;
+    let text = fs::read_to_string(&file)
+        .with_context(|| t!("error-read-file", path = file.display().to_string()))?;
+    if let Some(expression) = declared_expression(&text) {
+        return Ok(expression
+            .parse::<Expression>()?
+            .canonicalize(&licenses.body)?
+            .to_string());
+    }
+    let details = licenses.all_details().await?;
+    detect_license(&text, &details, 1)
+        .into_iter()
+        .find(|detection| detection.confidence >= LIKELY_CONFIDENCE)
+        .map(|detection| detection.license_id)
+        .ok_or_else(|| {
+            Error::UndetectedLicense {
+                file: file.display().to_string(),
+            }
+            .into()
+        })
+}
+
+/// Writes a document such as a DCO, CLA or NOTICE file with its
+/// fields filled in like those of a license, prompting for the values
+/// not given as flags.
+///
+/// # Errors
+/// - `Error::AlreadyExists` if the file exists and `--force` is not
+///   given.
+/// - If a prompt fails or the file cannot be written.
+fn write_document(
+    text: &str,
+    default_filename: &str,
+    args: &DocumentArgs,
+    config: &Config,
+) -> LictoolResult<()> {
+    let path = args.path.as_deref().unwrap_or(default_filename);
+    if !args.force && Path::new(path).exists() {
+        Err(Error::AlreadyExists {
+            file: path.to_string(),
+        })?
+    }
+    let mut template = args.fields.template(text.to_string(), config)?;
+    let interactive = args.fields.interactive();
+    fill_placeholders(
+        &mut template,
+        &config.theme.build(),
+        &FieldDefaults::load(config),
+        interactive,
+    )?;
+    let content = template.render();
+    if is_dry_run() {
+        print_planned_write(Path::new(path), &content);
+        return Ok(());
+    }
+    journal::write(Path::new(path), &content)
+        .with_context(|| t!("error-write-file", path = path))?;
+    let unfilled = unfilled_placeholders(&content);
+    if unfilled.is_empty() {
+        success!("<green>✔</> <bold>{}</>", t!("file-created", file = path));
+    } else {
+        cprintln!(
+            "<y, bold>\u{f421}</> <bold>{}</>",
+            t!(
+                "file-created-partial",
+                file = path,
+                placeholders = unfilled.join(", ")
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Returns the names of the settings of a pack, such as `policy,
+/// profile`.
+fn pack_setting_names(settings: &toml::Table) -> String {
+    settings
+        .keys()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Checks that `id` is a valid `LicenseRef-` ID.
+///
+/// # Errors
+/// - `Error::InvalidLicenseRef` if it is not.
+fn validate_license_ref(id: &str) -> LictoolResult<()> {
+    let valid = id.strip_prefix("LicenseRef-").is_some_and(|name| {
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    });
+    if !valid {
+        Err(Error::InvalidLicenseRef {
+            id: id.to_string(),
+        })?
+    }
+    Ok(())
+}
+
+/// Stores a custom license text as `LICENSES/<id>.txt` and registers
+/// it in the project configuration as the override of `id`, which
+/// also becomes the default license if there is none.
+///
+/// # Errors
+/// - `Error::InvalidLicenseRef` if `id` is no valid `LicenseRef-` ID.
+/// - If the text or the configuration cannot be written.
+///
+/// # Returns
+///
+/// The paths of the text and the configuration file.
+fn register_license_ref(
+    id: &str,
+    text: &str,
+    config: &Config,
+) -> LictoolResult<Vec<PathBuf>> {
+    validate_license_ref(id)?;
+    let text_path = PathBuf::from(LICENSE_REF_DIR).join(format!("{}.txt", id));
+    if is_dry_run() {
+        print_planned_write(&text_path, text);
+    } else {
+        fs::create_dir_all(LICENSE_REF_DIR)
+            .with_context(|| t!("error-write-file", path = LICENSE_REF_DIR))?;
+        journal::write(&text_path, text)
+            .with_context(|| t!("error-write-file", path = text_path.display().to_string()))?;
+    }
+    let mut file = ConfigFile::open(Scope::Project)?;
+    file.set_in_table(
+        &["overrides", id],
+        "text",
+        &text_path.to_string_lossy().replace('\\', "/"),
+    );
+    if config.default_license().is_none() {
+        file.set_string("default-license", id);
+    }
+    file.save()?;
+    success!(
+        "<green>✔</> <bold>{}</>",
+        t!(
+            "license-ref-registered",
+            id = id,
+            file = text_path.display().to_string()
+        )
+    );
+    Ok(vec![text_path, file.path().to_path_buf()])
+}
+
+/// Records a license in the recently used history.
+///
+/// Failing to persist the history is not fatal, since the license
+/// itself has already been written.
+fn remember_license(license_id: &str) {
+    if is_dry_run() {
+        return;
+    }
+    let _ = History::load().record(license_id).save();
+}
+
+/// Retrieves the styles to be used in the command-line interface
+/// (CLI) output.
+///
+/// # Returns
+///
+/// * `clap::builder::Styles` - The styles configured for usage and
+///   header display in the CLI, including
+fn get_styles() -> clap::builder::Styles {
+    clap::builder::Styles::styled()
+        .usage(
+            anstyle::Style::new()
+                .bold()
+                .underline()
                 .fg_color(Some(anstyle::Color::Ansi(AnsiColor::Cyan))),
         )
         .header(
@@ -210,3 +4427,15 @@ fn get_styles() -> clap::builder::Styles {
         )
         .placeholder(anstyle::Style::new().fg_color(Some(anstyle::Color::Ansi(AnsiColor::Yellow))))
 }
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::Cli;
+
+    #[test]
+    fn test_cli() {
+        Cli::command().debug_assert();
+    }
+}
@@ -0,0 +1,261 @@
+use color_print::cformat;
+use serde::Serialize;
+
+use crate::{
+    i18n::t,
+    spdx::LicenseDetails,
+    util::diff::{diff, Edit},
+};
+
+/// The most words compared at once, as removed times added words;
+/// larger rewrites are shown as whole removed and added paragraphs
+/// to bound the memory of the diff.
+const MAX_WORD_DIFF_CELLS: usize = 4_000_000;
+
+/// How a part of the new text relates to the old one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Change {
+    Same,
+    Removed,
+    Added,
+}
+
+/// A run of words of a word diff sharing their change.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Segment {
+    pub(crate) change: Change,
+    pub(crate) text: String,
+}
+
+/// Builds the segments of a word diff, joining words with spaces and
+/// paragraphs with blank lines.
+#[derive(Debug, Default)]
+struct Segments(Vec<Segment>);
+
+impl Segments {
+    fn push(
+        &mut self,
+        change: Change,
+        text: &str,
+    ) {
+        match self.0.last_mut() {
+            Some(last) if last.change == change => last.text.push_str(text),
+            _ => {
+                self.0.push(Segment {
+                    change,
+                    text: text.to_string(),
+                })
+            }
+        }
+    }
+
+    fn word(
+        &mut self,
+        change: Change,
+        word: &str,
+    ) {
+        if let Some(last) = self.0.last() {
+            if !last.text.ends_with('\n') {
+                // The space only belongs to a change within one.
+                let separator = if last.change == change {
+                    change
+                } else {
+                    Change::Same
+                };
+                self.push(separator, " ");
+            }
+        }
+        self.push(change, word);
+    }
+
+    fn paragraph_break(&mut self) {
+        if !self.0.is_empty() {
+            self.push(Change::Same, "\n\n");
+        }
+    }
+}
+
+/// Splits a text into paragraphs separated by blank lines, each made
+/// of its words.
+fn paragraphs(text: &str) -> Vec<Vec<&str>> {
+    let mut paragraphs = vec![Vec::new()];
+    for line in text.lines() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if words.is_empty() {
+            if paragraphs
+                .last()
+                .is_some_and(|paragraph| !paragraph.is_empty())
+            {
+                paragraphs.push(Vec::new());
+            }
+        } else if let Some(paragraph) = paragraphs.last_mut() {
+            paragraph.extend(words);
+        }
+    }
+    paragraphs.retain(|paragraph| !paragraph.is_empty());
+    paragraphs
+}
+
+/// Computes a word diff turning `old` into `new`, ignoring how the
+/// words are wrapped.
+///
+/// Paragraphs are compared first, and the words of the paragraphs
+/// that differ next.
+pub(crate) fn word_diff(
+    old: &str,
+    new: &str,
+) -> Vec<Segment> {
+    let (old, new) = (paragraphs(old), paragraphs(new));
+    let edits = diff(&old, &new, |a, b| a == b);
+    let mut segments = Segments::default();
+    let mut index = 0;
+    while index < edits.len() {
+        segments.paragraph_break();
+        if let Edit::Keep(kept, _) = edits[index] {
+            for word in &old[kept] {
+                segments.word(Change::Same, word);
+            }
+            index += 1;
+            continue;
+        }
+        let start = index;
+        while index < edits.len() && !matches!(edits[index], Edit::Keep(..)) {
+            index += 1;
+        }
+        let mut removed: Vec<&str> = Vec::new();
+        let mut added: Vec<&str> = Vec::new();
+        for edit in &edits[start..index] {
+            match *edit {
+                Edit::Remove(paragraph) => removed.extend(&old[paragraph]),
+                Edit::Add(paragraph) => added.extend(&new[paragraph]),
+                Edit::Keep(..) => {}
+            }
+        }
+        if removed.len() * added.len() > MAX_WORD_DIFF_CELLS {
+            for word in &removed {
+                segments.word(Change::Removed, word);
+            }
+            for word in &added {
+                segments.word(Change::Added, word);
+            }
+            continue;
+        }
+        for edit in diff(&removed, &added, |a, b| a == b) {
+            match edit {
+                Edit::Keep(word, _) => segments.word(Change::Same, removed[word]),
+                Edit::Remove(word) => segments.word(Change::Removed, removed[word]),
+                Edit::Add(word) => segments.word(Change::Added, added[word]),
+            }
+        }
+    }
+    segments.0
+}
+
+/// Renders a word diff with removed words as `[-…-]` in red and added
+/// ones as `{+…+}` in green, as `git diff --word-diff` does.
+pub(crate) fn display_word_diff(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .map(|segment| {
+            match segment.change {
+                Change::Same => segment.text.clone(),
+                Change::Removed => cformat!("<red>[-{}-]</>", segment.text),
+                Change::Added => cformat!("<green>{{+{}+}}</>", segment.text),
+            }
+        })
+        .collect()
+}
+
+/// Renders the metadata of two licenses side by side, highlighting
+/// the rows that differ.
+pub(crate) fn display_metadata(
+    old: &LicenseDetails,
+    new: &LicenseDetails,
+) -> String {
+    let yes_no = |value: bool| if value { "✔" } else { "✘" };
+    let rows: [[String; 3]; 5] = [
+        [
+            String::new(),
+            old.license_id.clone(),
+            new.license_id.clone(),
+        ],
+        [t!("compare-name"), old.name.clone(), new.name.clone()],
+        [
+            t!("compare-osi"),
+            old.is_osi_approved.map_or("—", yes_no).to_string(),
+            new.is_osi_approved.map_or("—", yes_no).to_string(),
+        ],
+        [
+            t!("compare-fsf"),
+            old.is_fsf_libre.map_or("—", yes_no).to_string(),
+            new.is_fsf_libre.map_or("—", yes_no).to_string(),
+        ],
+        [
+            t!("compare-deprecated"),
+            yes_no(old.is_deprecated_license_id).to_string(),
+            yes_no(new.is_deprecated_license_id).to_string(),
+        ],
+    ];
+    let mut widths = [0; 3];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let lines: Vec<String> = rows
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let line = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string();
+            if index == 0 {
+                cformat!("<bold>{}</>", line)
+            } else if row[1] != row[2] {
+                cformat!("<yellow>{}</>", line)
+            } else {
+                line
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{word_diff, Change, Segment};
+
+    #[test]
+    fn test_word_diff() {
+        let segment = |change, text: &str| {
+            Segment {
+                change,
+                text: text.to_string(),
+            }
+        };
+        let old =
+            "Copyright (c) <year>\n\nRedistribution of source code\nis permitted.\n\nNo warranty.";
+        let new = "Copyright (c) <year>\n\nRedistribution of source and binary code \
+                   is\npermitted.\n\nNo warranty.";
+        assert_eq!(
+            word_diff(old, new),
+            [
+                segment(
+                    Change::Same,
+                    "Copyright (c) <year>\n\nRedistribution of source "
+                ),
+                segment(Change::Added, "and binary"),
+                segment(Change::Same, " code is permitted.\n\nNo warranty."),
+            ]
+        );
+        assert_eq!(word_diff("a b", "a b"), [segment(Change::Same, "a b")]);
+    }
+}
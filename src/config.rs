@@ -0,0 +1,838 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+use toml_edit::{Array, DocumentMut, Item, Table, TableLike, Value};
+
+use crate::{
+    authors::AuthorsConfig,
+    consts::DEFAULT_LICENSE_FILE,
+    header::HeaderConfig,
+    i18n::t,
+    policy::PolicyConfig,
+    script::PlaceholderScript,
+    spdx::{LicenseDetails, Source},
+    util::{
+        defaults::FieldDefaults,
+        dry_run::{is_dry_run, print_planned_write},
+        errors::{Error, LictoolResult},
+        http::HttpConfig,
+        journal,
+        line_ending::glob_matches,
+        output::OutputFormat,
+        remote::ForgeConfig,
+        schema::{deserialize_checked, env_overrides},
+        theme::ThemeConfig,
+    },
+};
+
+/// The name of the per-project configuration file.
+pub(crate) const PROJECT_CONFIG_FILE: &str = ".lictool.toml";
+
+/// The prefix of environment variables overriding settings.
+const ENV_PREFIX: &str = "LICTOOL_";
+
+/// The default message used by `--commit`.
+const DEFAULT_COMMIT_MESSAGE: &str = "chore: add {license} license";
+
+/// Settings loaded from the user and project configuration files.
+///
+/// Values from the project file (`.lictool.toml` in the current
+/// directory) take precedence over the user file
+/// (`~/.config/lictool/config.toml`), and `LICTOOL_*` environment
+/// variables over both.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct Config {
+    /// License IDs shown first in the picker and by `list
+    /// --favorites`.
+    pub(crate) favorites: Vec<String>,
+    /// The file name licenses are written to when no path is given.
+    pub(crate) default_filename: Option<String>,
+    /// Commands run at various points of lictool's workflow.
+    pub(crate) hooks: HooksConfig,
+    /// The message used by `--commit`, where `{license}` is replaced
+    /// with the license ID.
+    pub(crate) commit_message: Option<String>,
+    /// The default copyright owner.
+    pub(crate) owner: Option<String>,
+    /// The default owner email.
+    pub(crate) email: Option<String>,
+    /// The license used by `add` when no ID is given, and pinned at
+    /// the top of the `init` picker.
+    pub(crate) default_license: Option<String>,
+    /// Named sets of identity settings selected with `--profile`.
+    pub(crate) profile: BTreeMap<String, Profile>,
+    /// The configuration files the settings were read from.
+    #[serde(skip)]
+    pub(crate) sources: Vec<PathBuf>,
+    /// The profile selected with `--profile`, if any.
+    #[serde(skip)]
+    pub(crate) active_profile: Option<Profile>,
+    /// The symbols and colors of the interactive prompts.
+    pub(crate) theme: ThemeConfig,
+    /// Limits on the requests sent to SPDX and mirrors.
+    pub(crate) http: HttpConfig,
+    /// Local texts used instead of the SPDX text of specific
+    /// licenses.
+    pub(crate) overrides: BTreeMap<String, LicenseOverride>,
+    /// The licenses accepted by the project.
+    pub(crate) policy: PolicyConfig,
+    /// License expressions of files not covered by the default
+    /// license, keyed by a pattern such as `vendor/*`.
+    pub(crate) paths: BTreeMap<String, String>,
+    /// Whether the picker lists popular licenses first among equally
+    /// good matches; enabled by default.
+    pub(crate) rank_by_popularity: Option<bool>,
+    /// Whether to hint, at most once a day, that a newer SPDX license
+    /// list than the cached one was released; disabled by default.
+    pub(crate) notify_list_updates: Option<bool>,
+    /// The comment styles of source file headers.
+    pub(crate) header: HeaderConfig,
+    /// How the AUTHORS file is generated.
+    pub(crate) authors: AuthorsConfig,
+    /// A Rhai script computing placeholder values when licenses are
+    /// rendered, relative to the configuration file declaring it.
+    pub(crate) placeholder_script: Option<PathBuf>,
+    /// Values of the user-defined `{{key}}` placeholders of custom
+    /// templates, such as `department = "Research"`.
+    pub(crate) variables: BTreeMap<String, String>,
+    /// Self-hosted GitHub, GitLab and Bitbucket instances, whose
+    /// remote URLs give the owner and repository defaults.
+    pub(crate) forges: ForgeConfig,
+    /// The format `list`, `info` and `detect` print results in when
+    /// `--format` is not given.
+    pub(crate) format: Option<OutputFormat>,
+    /// Where license metadata is fetched from when `--source` is not
+    /// given: `spdx`, `github` or the URL of an SPDX mirror.
+    pub(crate) source: Option<Source>,
+}
+
+/// A vetted local text for a license, such as `[overrides.MIT]`.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct LicenseOverride {
+    /// The file holding the license text, relative to the
+    /// configuration file declaring it.
+    pub(crate) text: PathBuf,
+}
+
+/// A named identity, such as `[profile.work]`, overriding the
+/// top-level settings when selected.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct Profile {
+    /// The copyright owner for this profile.
+    pub(crate) owner: Option<String>,
+    /// The owner email for this profile.
+    pub(crate) email: Option<String>,
+    /// The default license for this profile.
+    pub(crate) default_license: Option<String>,
+}
+
+/// Commands run after lictool performs certain actions.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct HooksConfig {
+    /// Shell commands run after files are written, receiving the
+    /// written paths as arguments.
+    pub(crate) post_write: Vec<String>,
+}
+
+impl Config {
+    /// Loads and merges the user and project configuration files,
+    /// activating the given profile.
+    ///
+    /// With `file`, only that file is read and the user and project
+    /// files are ignored. `LICTOOL_*` environment variables, such as
+    /// `LICTOOL_POLICY_DENY`, take precedence over the files.
+    ///
+    /// # Errors
+    /// - `Error::ConfigNotFound` if `file` does not exist.
+    /// - If a configuration file exists but cannot be read or parsed.
+    /// - `Error::UnknownConfigKeys` if a configuration file has keys
+    ///   that no setting reads.
+    /// - If the profile is not defined in any configuration file.
+    pub fn load(
+        profile: Option<&str>,
+        file: Option<&Path>,
+    ) -> LictoolResult<Self> {
+        let (merged, sources) = Self::merged_settings(file)?;
+        let mut config: Config = merged.try_into()?;
+        config.sources = sources;
+        if let Some(name) = profile {
+            config.active_profile = Some(config.profile.get(name).cloned().ok_or_else(|| {
+                Error::UnknownProfile {
+                    name: name.to_string(),
+                }
+            })?);
+        }
+        Ok(config)
+    }
+
+    /// Reads the settings of the configuration files and environment
+    /// variables, merged as `load` does.
+    ///
+    /// # Returns
+    ///
+    /// The merged settings and the files they were read from.
+    ///
+    /// # Errors
+    /// - `Error::ConfigNotFound` if `file` does not exist.
+    /// - If a configuration file exists but cannot be read or parsed.
+    /// - `Error::UnknownConfigKeys` if a configuration file has keys
+    ///   that no setting reads.
+    pub fn merged_settings(file: Option<&Path>) -> LictoolResult<(toml::Table, Vec<PathBuf>)> {
+        let paths = match file {
+            Some(file) if !file.is_file() => {
+                Err(Error::ConfigNotFound {
+                    path: file.display().to_string(),
+                })?
+            }
+            Some(file) => vec![file.to_path_buf()],
+            None => vec![Scope::User.path(), Scope::Project.path()],
+        };
+        let mut merged = toml::Table::new();
+        let mut sources = Vec::new();
+        for path in paths {
+            if let Some(table) = Self::read_file(&path)? {
+                merge_tables(&mut merged, table);
+                sources.push(path);
+            }
+        }
+        merge_tables(
+            &mut merged,
+            env_overrides::<Config>(env::vars(), ENV_PREFIX),
+        );
+        Ok((merged, sources))
+    }
+
+    /// Reads and validates a configuration file.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the file does not exist.
+    ///
+    /// # Errors
+    /// - If the file cannot be read or parsed, or a value has the
+    ///   wrong type.
+    /// - `Error::UnknownConfigKeys` if the file has keys that no
+    ///   setting reads.
+    fn read_file(path: &Path) -> LictoolResult<Option<toml::Table>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| t!("error-read-file", path = path.display()))?;
+        let mut table: toml::Table = toml::from_str(&content)
+            .with_context(|| t!("error-parse-file", path = path.display()))?;
+        Self::check_table(&table, path)?;
+        resolve_paths(&mut table, path.parent().unwrap_or(Path::new("")));
+        Ok(Some(table))
+    }
+
+    /// Checks that every key of a configuration file is a setting
+    /// with a value of the right type.
+    ///
+    /// # Errors
+    /// - If a value has the wrong type.
+    /// - `Error::UnknownConfigKeys` if the file has keys that no
+    ///   setting reads.
+    fn check_table(
+        table: &toml::Table,
+        path: &Path,
+    ) -> LictoolResult<()> {
+        let (_, unknown) = deserialize_checked::<Config>(table.clone())
+            .with_context(|| t!("error-parse-file", path = path.display()))?;
+        if !unknown.is_empty() {
+            let keys = unknown
+                .iter()
+                .map(|key| {
+                    match key.suggestion {
+                        Some(suggestion) => {
+                            t!(
+                                "config-unknown-key-suggestion",
+                                key = key.path.as_str(),
+                                suggestion = suggestion
+                            )
+                        }
+                        None => t!("config-unknown-key", key = key.path.as_str()),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            Err(Error::UnknownConfigKeys {
+                file: path.display().to_string(),
+                keys,
+            })?
+        }
+        Ok(())
+    }
+
+    /// Returns the field defaults set by the active profile.
+    pub fn profile_defaults(&self) -> FieldDefaults {
+        self.active_profile
+            .as_ref()
+            .map(|profile| {
+                FieldDefaults {
+                    owner: profile.owner.clone(),
+                    email: profile.email.clone(),
+                    ..Default::default()
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the field defaults set at the top level of the
+    /// configuration.
+    pub fn field_defaults(&self) -> FieldDefaults {
+        FieldDefaults {
+            owner: self.owner.clone(),
+            email: self.email.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the default license of the active profile, falling
+    /// back to the top-level `default-license`.
+    pub fn default_license(&self) -> Option<&str> {
+        self.active_profile
+            .as_ref()
+            .and_then(|profile| profile.default_license.as_deref())
+            .or(self.default_license.as_deref())
+    }
+
+    /// Returns the license expression expected for a file: that of
+    /// the most specific `[paths]` pattern matching it, or else
+    /// the default license.
+    pub fn license_for(
+        &self,
+        path: &Path,
+    ) -> Option<&str> {
+        let relative = path.strip_prefix(".").unwrap_or(path);
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        self.paths
+            .iter()
+            .filter(|(pattern, _)| glob_matches(pattern.trim_start_matches("./"), &relative))
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, license)| license.as_str())
+            .or(self.default_license())
+    }
+
+    /// Returns the commit message for adding the given license.
+    pub fn commit_message(
+        &self,
+        license_id: &str,
+    ) -> String {
+        self.commit_message
+            .as_deref()
+            .unwrap_or(DEFAULT_COMMIT_MESSAGE)
+            .replace("{license}", license_id)
+    }
+
+    /// Replaces the text of `details` with the configured override
+    /// for its license, if there is one.
+    ///
+    /// # Errors
+    /// - If the override file cannot be read.
+    pub fn apply_override(
+        &self,
+        details: &mut LicenseDetails,
+    ) -> LictoolResult<()> {
+        if let Some(text) = self.override_text(&details.license_id)? {
+            details.license_text = text;
+        }
+        Ok(())
+    }
+
+    /// Reads the configured override text of a license, which is how
+    /// custom `LicenseRef-` licenses get a text.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the license has no override.
+    ///
+    /// # Errors
+    /// - If the override file cannot be read.
+    pub fn override_text(
+        &self,
+        license_id: &str,
+    ) -> LictoolResult<Option<String>> {
+        let Some(license_override) = self.overrides.get(license_id) else {
+            return Ok(None);
+        };
+        let text = fs::read_to_string(&license_override.text)
+            .with_context(|| t!("error-read-file", path = license_override.text.display()))?;
+        Ok(Some(text))
+    }
+
+    /// Compiles the configured placeholder script, if any.
+    ///
+    /// # Errors
+    /// - If the script cannot be read or does not compile.
+    pub fn placeholder_script(&self) -> LictoolResult<Option<PlaceholderScript>> {
+        self.placeholder_script
+            .as_deref()
+            .map(PlaceholderScript::load)
+            .transpose()
+    }
+
+    /// Returns whether the picker ranks licenses by popularity.
+    pub fn ranks_by_popularity(&self) -> bool {
+        self.rank_by_popularity.unwrap_or(true)
+    }
+
+    /// Whether newer SPDX license list releases are announced.
+    pub fn notifies_list_updates(&self) -> bool {
+        self.notify_list_updates.unwrap_or(false)
+    }
+
+    /// Returns the configured default license file name, falling back
+    /// to `LICENSE.md`.
+    pub fn default_filename(&self) -> &str {
+        self.default_filename
+            .as_deref()
+            .unwrap_or(DEFAULT_LICENSE_FILE)
+    }
+}
+
+/// Resolves the relative paths of a configuration file in `dir`, the
+/// directory of the file, so that a user configuration can name a
+/// script or override text next to it from any project.
+pub(crate) fn resolve_paths(
+    table: &mut toml::Table,
+    dir: &Path,
+) {
+    for value in referenced_paths(table) {
+        let path = value.as_str().unwrap_or_default();
+        if Path::new(path).is_relative() {
+            *value = toml::Value::String(dir.join(path).to_string_lossy().into_owned());
+        }
+    }
+}
+
+/// Returns the settings holding the path of a file: the text of each
+/// override and the placeholder script.
+pub(crate) fn referenced_paths(settings: &mut toml::Table) -> Vec<&mut toml::Value> {
+    let mut paths = Vec::new();
+    for (key, value) in settings.iter_mut() {
+        match (key.as_str(), value) {
+            ("placeholder-script", value @ toml::Value::String(_)) => paths.push(value),
+            ("overrides", toml::Value::Table(overrides)) => {
+                paths.extend(
+                    overrides
+                        .iter_mut()
+                        .map(|(_, license_override)| license_override)
+                        .filter_map(|license_override| license_override.get_mut("text"))
+                        .filter(|text| text.is_str()),
+                );
+            }
+            _ => {}
+        }
+    }
+    paths
+}
+
+/// Recursively merges `overlay` into `base`, with values from
+/// `overlay` taking precedence.
+fn merge_tables(
+    base: &mut toml::Table,
+    overlay: toml::Table,
+) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) => {
+                merge_tables(base, overlay)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// The location of a configuration file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Scope {
+    /// The configuration file in the user's config directory.
+    User,
+    /// The configuration file in the current directory.
+    Project,
+}
+
+impl Scope {
+    /// Returns the project scope if `project` is set, and the user
+    /// scope otherwise.
+    pub(crate) fn from_project_flag(project: bool) -> Self {
+        if project {
+            Scope::Project
+        } else {
+            Scope::User
+        }
+    }
+
+    /// Returns the path of the configuration file for this scope.
+    ///
+    /// # Panics
+    /// - If the user config directory cannot be found.
+    pub(crate) fn path(&self) -> PathBuf {
+        match self {
+            Scope::User => {
+                dirs::config_dir()
+                    .expect("Config directory not found.")
+                    .join(env!("CARGO_PKG_NAME"))
+                    .join("config.toml")
+            }
+            Scope::Project => PathBuf::from(PROJECT_CONFIG_FILE),
+        }
+    }
+}
+
+/// A configuration file opened for editing.
+///
+/// Edits preserve the existing formatting and comments of the file.
+pub(crate) struct ConfigFile {
+    path: PathBuf,
+    document: DocumentMut,
+}
+
+impl ConfigFile {
+    /// Opens the configuration file of the given scope, starting from
+    /// an empty document if it does not exist yet.
+    ///
+    /// # Errors
+    /// - If the file exists but cannot be read or parsed.
+    pub fn open(scope: Scope) -> LictoolResult<Self> {
+        let path = scope.path();
+        let document = if path.is_file() {
+            fs::read_to_string(&path)
+                .with_context(|| t!("error-read-file", path = path.display()))?
+                .parse::<DocumentMut>()
+                .with_context(|| t!("error-parse-file", path = path.display()))?
+        } else {
+            DocumentMut::new()
+        };
+        Ok(Self {
+            path,
+            document,
+        })
+    }
+
+    /// Appends a value to the string array stored under `key`.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the value was already present.
+    pub fn push_to_list(
+        &mut self,
+        key: &str,
+        value: &str,
+    ) -> bool {
+        let array = self.list_mut(key);
+        if array.iter().any(|item| item.as_str() == Some(value)) {
+            return false;
+        }
+        array.push(value);
+        true
+    }
+
+    /// Replaces the value stored under `key` with a string, keeping
+    /// its position in the file.
+    ///
+    /// # Returns
+    ///
+    /// `false` if there was no such value.
+    pub fn replace_string(
+        &mut self,
+        key: &str,
+        value: &str,
+    ) -> bool {
+        if !self.document.contains_key(key) {
+            return false;
+        }
+        self.document[key] = toml_edit::value(value);
+        true
+    }
+
+    /// Sets `key` to a string, replacing any previous value.
+    pub fn set_string(
+        &mut self,
+        key: &str,
+        value: &str,
+    ) {
+        self.document[key] = toml_edit::value(value);
+    }
+
+    /// Sets `key` of the table nested along `tables`, such as
+    /// `["overrides", "MIT"]`, to a string, creating the tables if
+    /// needed.
+    ///
+    /// # Returns
+    ///
+    /// The previous value, if it was a string.
+    pub fn set_in_table(
+        &mut self,
+        tables: &[&str],
+        key: &str,
+        value: &str,
+    ) -> Option<String> {
+        let table = self.table_mut(tables);
+        let previous = table
+            .get(key)
+            .and_then(|previous| previous.as_str())
+            .map(str::to_string);
+        table.insert(key, toml_edit::value(value));
+        previous
+    }
+
+    /// Sets a dotted key such as `policy.deny` to a value, creating
+    /// the tables if needed.
+    pub fn set_dotted(
+        &mut self,
+        key: &str,
+        value: Value,
+    ) {
+        let mut path: Vec<&str> = key.split('.').collect();
+        let key = path.pop().unwrap_or_default();
+        self.table_mut(&path).insert(key, Item::Value(value));
+    }
+
+    /// Checks the settings of the file as they would be saved.
+    ///
+    /// # Errors
+    /// - If a value has the wrong type.
+    /// - `Error::UnknownConfigKeys` if the file has keys that no
+    ///   setting reads.
+    pub fn check(&self) -> LictoolResult<()> {
+        let table: toml::Table = toml::from_str(&self.document.to_string())
+            .with_context(|| t!("error-parse-file", path = self.path.display()))?;
+        Config::check_table(&table, &self.path)
+    }
+
+    /// Returns the path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the table nested along `tables`, creating the tables
+    /// if needed.
+    fn table_mut(
+        &mut self,
+        tables: &[&str],
+    ) -> &mut dyn TableLike {
+        let mut table: &mut dyn TableLike = self.document.as_table_mut();
+        for name in tables {
+            let item = table.entry(name).or_insert(Item::None);
+            if !item.is_table_like() {
+                // Implicit tables are left out of the file while they
+                // only hold other tables.
+                let mut new = Table::new();
+                new.set_implicit(true);
+                *item = Item::Table(new);
+            }
+            table = item
+                .as_table_like_mut()
+                .expect("item was just set to a table");
+        }
+        table
+    }
+
+    /// Merges settings into the file, replacing the values they share
+    /// while keeping the other entries of shared tables.
+    ///
+    /// # Errors
+    /// - If the settings cannot be converted to TOML.
+    pub fn merge(
+        &mut self,
+        settings: &toml::Table,
+    ) -> LictoolResult<()> {
+        let overlay = toml::to_string(settings)?.parse::<DocumentMut>()?;
+        merge_items(self.document.as_table_mut(), overlay.as_table());
+        Ok(())
+    }
+
+    /// Removes the value stored under `key`.
+    ///
+    /// # Returns
+    ///
+    /// `false` if there was no such value.
+    pub fn remove_key(
+        &mut self,
+        key: &str,
+    ) -> bool {
+        self.document.remove(key).is_some()
+    }
+
+    /// Removes a value from the string array stored under `key`.
+    ///
+    /// # Returns
+    ///
+    /// `false` if the value was not present.
+    pub fn remove_from_list(
+        &mut self,
+        key: &str,
+        value: &str,
+    ) -> bool {
+        let array = self.list_mut(key);
+        let len = array.len();
+        array.retain(|item| item.as_str() != Some(value));
+        array.len() != len
+    }
+
+    /// Writes the configuration file back to disk, creating its
+    /// parent directory if needed.
+    ///
+    /// # Errors
+    /// - If the file cannot be written.
+    pub fn save(&self) -> LictoolResult<()> {
+        if is_dry_run() {
+            print_planned_write(&self.path, &self.document.to_string());
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        journal::write(&self.path, self.document.to_string())
+            .with_context(|| t!("error-write-file", path = self.path.display()))?;
+        Ok(())
+    }
+
+    /// Returns the string array stored under `key`, replacing any
+    /// non-array value with an empty array.
+    fn list_mut(
+        &mut self,
+        key: &str,
+    ) -> &mut Array {
+        let item = self
+            .document
+            .entry(key)
+            .or_insert(Item::Value(Value::Array(Array::new())));
+        if !item.is_array() {
+            *item = Item::Value(Value::Array(Array::new()));
+        }
+        item.as_array_mut().expect("item was just set to an array")
+    }
+}
+
+/// Looks up a dotted key such as `policy.deny` in merged settings.
+pub(crate) fn lookup<'a>(
+    settings: &'a toml::Table,
+    key: &str,
+) -> Option<&'a toml::Value> {
+    let mut path = key.split('.');
+    let mut value = settings.get(path.next()?)?;
+    for name in path {
+        value = value.get(name)?;
+    }
+    Some(value)
+}
+
+/// Parses the value of a setting given on the command line: a TOML
+/// value such as `true` or `["MIT"]`, or else a plain string.
+pub(crate) fn parse_setting(value: &str) -> Value {
+    value
+        .parse::<Value>()
+        .unwrap_or_else(|_| Value::from(value))
+}
+
+/// Recursively merges the items of `overlay` into `base`, like
+/// `merge_tables` but keeping the formatting of `base`.
+fn merge_items(
+    base: &mut dyn TableLike,
+    overlay: &dyn TableLike,
+) {
+    for (key, item) in overlay.iter() {
+        match (base.get_mut(key), item.as_table_like()) {
+            (Some(existing), Some(table)) if existing.is_table_like() => {
+                merge_items(
+                    existing
+                        .as_table_like_mut()
+                        .expect("item was checked to be a table"),
+                    table,
+                );
+            }
+            _ => {
+                base.insert(key, item.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use pretty_assertions::assert_eq;
+
+    use super::{lookup, merge_tables, parse_setting, resolve_paths, Config, ConfigFile};
+    use crate::util::output::OutputFormat;
+
+    #[test]
+    fn test_project_config_overrides_user_config() {
+        let mut merged: toml::Table =
+            toml::from_str("favorites = [\"MIT\"]\ndefault-filename = \"LICENSE\"").unwrap();
+        merge_tables(
+            &mut merged,
+            toml::from_str("favorites = [\"Apache-2.0\"]").unwrap(),
+        );
+        let config: Config = merged.try_into().unwrap();
+        assert_eq!(
+            config,
+            Config {
+                favorites: vec!["Apache-2.0".to_string()],
+                default_filename: Some("LICENSE".to_string()),
+                ..Config::default()
+            }
+        );
+        assert_eq!(config.default_filename(), "LICENSE");
+    }
+
+    #[test]
+    fn test_resolve_paths() {
+        let mut table: toml::Table = toml::from_str(
+            "placeholder-script = \"fill.rhai\"\n[overrides.MIT]\ntext = \
+             \"texts/MIT.txt\"\n[overrides.ISC]\ntext = \"/srv/ISC.txt\"",
+        )
+        .unwrap();
+        resolve_paths(&mut table, Path::new("/home/jane/.config/lictool"));
+        let config: Config = table.try_into().unwrap();
+        assert_eq!(
+            config.placeholder_script,
+            Some(PathBuf::from("/home/jane/.config/lictool/fill.rhai"))
+        );
+        assert_eq!(
+            config.overrides["MIT"].text,
+            PathBuf::from("/home/jane/.config/lictool/texts/MIT.txt")
+        );
+        assert_eq!(config.overrides["ISC"].text, PathBuf::from("/srv/ISC.txt"));
+    }
+
+    #[test]
+    fn test_set_dotted_setting() {
+        let mut file = ConfigFile {
+            path: "config.toml".into(),
+            document: "# Defaults\nowner = \"Jane\"\n".parse().unwrap(),
+        };
+        file.set_dotted("policy.deny", parse_setting("[\"GPL-3.0-only\"]"));
+        file.set_dotted("format", parse_setting("json"));
+        file.check().unwrap();
+        assert_eq!(
+            file.document.to_string(),
+            "# Defaults\nowner = \"Jane\"\nformat = \"json\"\n\n[policy]\ndeny = \
+             [\"GPL-3.0-only\"]\n"
+        );
+        let settings: toml::Table = toml::from_str(&file.document.to_string()).unwrap();
+        assert_eq!(
+            lookup(&settings, "policy.deny").unwrap().to_string(),
+            "[\"GPL-3.0-only\"]"
+        );
+        let config: Config = settings.try_into().unwrap();
+        assert_eq!(config.format, Some(OutputFormat::Json));
+        file.set_dotted("favorites", parse_setting("MIT"));
+        assert!(file.check().is_err());
+    }
+}
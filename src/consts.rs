@@ -1,3 +1,9 @@
+/// The file name used for licenses when no explicit file is given.
+pub const DEFAULT_LICENSE_FILE: &str = "LICENSE.md";
+pub const DEFAULT_BASELINE_FILE: &str = ".lictool-baseline.json";
+/// The directory custom license texts registered by `init --from`
+/// are stored in, as in the REUSE specification.
+pub const LICENSE_REF_DIR: &str = "LICENSES";
 pub const OWNER: [&str; 11] = [
     "[fullname]",
     "<owner>",
@@ -17,4 +23,30 @@ pub const REPO: [&str; 2] = [
     "<program>",
     "<one line to give the program's name and a brief idea of what it does.>",
 ];
-pub const YEAR: [&str; 5] = ["[yyyy]", "[YEAR]", "<year>", "{YEAR}", "[Year]"];
+pub const YEAR: [&str; 6] = ["[yyyy]", "[YEAR]", "<year>", "{YEAR}", "[Year]", "[year]"];
+/// Public domain dedications and licenses placing no conditions on
+/// reuse, which need no details filled in.
+pub const PUBLIC_DOMAIN_DEDICATIONS: [&str; 5] =
+    ["CC0-1.0", "Unlicense", "0BSD", "MIT-0", "PDDL-1.0"];
+pub const POPULAR: [&str; 20] = [
+    "MIT",
+    "Apache-2.0",
+    "GPL-3.0-only",
+    "GPL-3.0-or-later",
+    "GPL-2.0-only",
+    "GPL-2.0-or-later",
+    "LGPL-3.0-only",
+    "LGPL-2.1-only",
+    "AGPL-3.0-only",
+    "MPL-2.0",
+    "BSD-3-Clause",
+    "BSD-2-Clause",
+    "0BSD",
+    "ISC",
+    "Zlib",
+    "BSL-1.0",
+    "EPL-2.0",
+    "Unlicense",
+    "CC0-1.0",
+    "CC-BY-4.0",
+];
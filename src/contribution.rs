@@ -0,0 +1,53 @@
+/// A document setting the terms contributions to a project are
+/// accepted under, which usually accompanies its license.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ContributionDocument {
+    /// The Developer Certificate of Origin, certified by signing off
+    /// each commit.
+    Dco,
+    /// An individual Contributor License Agreement, signed once by
+    /// each contributor.
+    Cla,
+}
+
+impl ContributionDocument {
+    /// Returns the file name the document is written to by default.
+    pub fn default_filename(self) -> &'static str {
+        match self {
+            ContributionDocument::Dco => "DCO",
+            ContributionDocument::Cla => "CLA.md",
+        }
+    }
+
+    /// Returns the text of the document, with the same placeholders
+    /// as license templates for the project, owner, email and year.
+    pub fn text(self) -> &'static str {
+        match self {
+            ContributionDocument::Dco => include_str!("../templates/DCO"),
+            ContributionDocument::Cla => include_str!("../templates/CLA.md"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContributionDocument;
+    use crate::{placeholders::unfilled_placeholders, template::Template};
+
+    #[test]
+    fn test_contribution_documents_are_filled() {
+        for document in [ContributionDocument::Dco, ContributionDocument::Cla] {
+            let template = Template {
+                license_text: document.text().to_string(),
+                year: Some("2024".to_string()),
+                owner: Some("Acme".to_string()),
+                repo: Some("lictool".to_string()),
+                email: Some("legal@acme.test".to_string()),
+                ..Default::default()
+            };
+            let rendered = template.render();
+            assert!(unfilled_placeholders(&rendered).is_empty());
+            assert!(rendered.contains("lictool"));
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+
+use crate::{
+    i18n::t,
+    spdx::{License, LicenseDetails},
+    util::errors::LictoolResult,
+};
+
+/// The directory of custom templates, under the user config
+/// directory of lictool.
+pub(crate) const TEMPLATES_DIR: &str = "templates";
+
+/// A license text of the user, such as a proprietary or
+/// company-specific one, stored in the templates directory.
+///
+/// Templates are known by a `LicenseRef-` ID made of their file
+/// name without extension, such as `LicenseRef-Acme` for `Acme.txt`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CustomTemplate {
+    pub(crate) id: String,
+    pub(crate) path: PathBuf,
+}
+
+impl CustomTemplate {
+    /// Returns the template for a file, named after its file stem.
+    fn from_path(path: PathBuf) -> Option<Self> {
+        let stem = path.file_stem()?.to_str()?;
+        let id = if stem.starts_with("LicenseRef-") {
+            stem.to_string()
+        } else {
+            format!("LicenseRef-{}", stem)
+        };
+        Some(CustomTemplate {
+            id,
+            path,
+        })
+    }
+
+    /// Checks whether the user means this template by `query`, either
+    /// its `LicenseRef-` ID or its name, in any case.
+    pub(crate) fn matches(
+        &self,
+        query: &str,
+    ) -> bool {
+        self.id.eq_ignore_ascii_case(query) || self.name().eq_ignore_ascii_case(query)
+    }
+
+    /// Returns the name the template is listed with, its file name
+    /// without extension.
+    pub(crate) fn name(&self) -> &str {
+        self.id.trim_start_matches("LicenseRef-")
+    }
+
+    /// Returns the template as an entry of the license list, so that
+    /// it is picked like SPDX licenses.
+    pub(crate) fn license(&self) -> License {
+        License {
+            is_deprecated_license_id: false,
+            details_url: self.path.display().to_string(),
+            name: self.name().to_string(),
+            id: self.id.clone(),
+            is_osi_approved: Some(false),
+            is_fsf_libre: None,
+        }
+    }
+
+    /// Reads the template as the details of a license.
+    ///
+    /// # Errors
+    /// - If the template cannot be read.
+    pub(crate) fn details(&self) -> LictoolResult<LicenseDetails> {
+        let text = fs::read_to_string(&self.path)
+            .with_context(|| t!("error-read-file", path = self.path.display()))?;
+        Ok(LicenseDetails {
+            is_deprecated_license_id: false,
+            license_text: text,
+            name: self.name().to_string(),
+            license_comments: None,
+            license_id: self.id.clone(),
+            see_also: Vec::new(),
+            is_osi_approved: Some(false),
+            is_fsf_libre: None,
+            deprecated_version: None,
+            fsf: None,
+        })
+    }
+}
+
+/// Returns the directory custom templates are registered in,
+/// `~/.config/lictool/templates` on Linux.
+pub(crate) fn templates_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(env!("CARGO_PKG_NAME")).join(TEMPLATES_DIR))
+}
+
+/// Lists the custom templates of the user, sorted by ID.
+///
+/// # Errors
+/// - If the templates directory exists but cannot be read.
+pub(crate) fn custom_templates() -> LictoolResult<Vec<CustomTemplate>> {
+    match templates_dir() {
+        Some(dir) => templates_in(&dir),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Lists the templates in `dir`, skipping hidden files; a missing
+/// directory has none.
+fn templates_in(dir: &Path) -> LictoolResult<Vec<CustomTemplate>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let entries = fs::read_dir(dir).with_context(|| t!("error-read-file", path = dir.display()))?;
+    let mut templates: Vec<CustomTemplate> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            !path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        })
+        .filter_map(CustomTemplate::from_path)
+        .collect();
+    templates.sort_by(|a, b| a.id.cmp(&b.id));
+    Ok(templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::templates_in;
+
+    #[test]
+    fn test_templates_in() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("Acme.txt"), "Copyright {{year}} {{owner}}").unwrap();
+        fs::write(
+            dir.path().join("LicenseRef-Internal.md"),
+            "Internal use only",
+        )
+        .unwrap();
+        fs::write(dir.path().join(".hidden"), "").unwrap();
+        let templates = templates_in(dir.path()).unwrap();
+        let ids: Vec<&str> = templates
+            .iter()
+            .map(|template| template.id.as_str())
+            .collect();
+        assert_eq!(ids, ["LicenseRef-Acme", "LicenseRef-Internal"]);
+        assert_eq!(templates[0].name(), "Acme");
+        let details = templates[0].details().unwrap();
+        assert!(details.has_year() && details.has_owner() && !details.has_email());
+        assert!(templates_in(&dir.path().join("missing"))
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,346 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::Context;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use futures::future::try_join_all;
+
+use crate::{
+    i18n::t,
+    spdx::{spdx_exceptions_url, spdx_licenses_url, Exceptions, Licenses},
+    util::{
+        errors::{Error, LictoolResult},
+        events::{emit, Event},
+        http,
+        integrity::{format_checksums, parse_checksums, sha256_hex, CHECKSUMS_FILE},
+        mirror::mirrored_path,
+    },
+};
+
+/// The file recording the license list version of staged files.
+const STAGED_VERSION_FILE: &str = "VERSION";
+/// The directory below the staging directory holding staged files.
+const STAGED_FILES_DIR: &str = "files";
+
+/// The license dataset as stored in a mirror: the JSON responses of
+/// spdx.org, keyed by their path within the mirror.
+///
+/// Exports and mirrors list the digests of the files in
+/// `CHECKSUMS_FILE`, which is not part of `files`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct Dataset {
+    pub(crate) files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl Dataset {
+    /// Reads the files an interrupted fetch of the license list
+    /// `version` left in `staging`.
+    ///
+    /// Files staged for another version are discarded, as the details
+    /// of the licenses may have changed since.
+    pub fn staged(
+        staging: &Path,
+        version: &str,
+    ) -> Self {
+        let mut dataset = Self::default();
+        let staged_version = fs::read_to_string(staging.join(STAGED_VERSION_FILE));
+        if staged_version.is_ok_and(|staged| staged == version) {
+            let _ = dataset.read_dir(&staging.join(STAGED_FILES_DIR), Path::new(""));
+            // Files being written when the fetch was interrupted.
+            dataset
+                .files
+                .retain(|path, _| path.extension().is_none_or(|extension| extension != "part"));
+        } else {
+            let _ = fs::remove_dir_all(staging);
+        }
+        dataset
+    }
+
+    /// Fetches the license and exception lists along with the details
+    /// of every license and exception that the dataset lacks.
+    ///
+    /// Each file is staged below `staging` once fetched, for
+    /// `staged` to pick up if the fetch is interrupted. The staged
+    /// files are removed once all are fetched. `on_fetched` is called
+    /// as each file is fetched.
+    ///
+    /// # Errors
+    /// - If any of the responses cannot be fetched.
+    pub async fn fetch_missing(
+        mut self,
+        licenses: &Licenses,
+        exceptions: &Exceptions,
+        staging: &Path,
+        on_fetched: impl Fn(),
+    ) -> LictoolResult<Self> {
+        // Staging is best effort, like the HTTP cache.
+        let _ = fs::create_dir_all(staging)
+            .and_then(|()| fs::write(staging.join(STAGED_VERSION_FILE), &licenses.version));
+        let urls = self.missing_urls(licenses, exceptions);
+        let on_fetched = &on_fetched;
+        let files = try_join_all(urls.into_iter().map(|(path, url)| {
+            async move {
+                let value: serde_json::Value = http::get_json(&url).await?;
+                let contents = serde_json::to_vec(&value)?;
+                stage(staging, &path, &contents);
+                on_fetched();
+                LictoolResult::Ok((path, contents))
+            }
+        }))
+        .await?;
+        self.files.extend(files);
+        let _ = fs::remove_dir_all(staging);
+        Ok(self)
+    }
+
+    /// Returns the paths and URLs of the files `fetch_missing` would
+    /// fetch: the license and exception lists and the details of
+    /// every license and exception that the dataset lacks.
+    pub fn missing_urls(
+        &self,
+        licenses: &Licenses,
+        exceptions: &Exceptions,
+    ) -> Vec<(PathBuf, String)> {
+        let urls = [spdx_licenses_url(), spdx_exceptions_url()]
+            .into_iter()
+            .chain(
+                licenses
+                    .body
+                    .iter()
+                    .map(|license| license.details_url.clone()),
+            )
+            .chain(
+                exceptions
+                    .body
+                    .iter()
+                    .map(|exception| exception.details_url.clone()),
+            );
+        // URLs outside of HTTPS cannot be looked up in a mirror.
+        urls.filter_map(|url| Some((mirrored_path(&url)?, url)))
+            .filter(|(path, _)| !self.files.contains_key(path))
+            .collect()
+    }
+
+    /// Reads a dataset exported to a directory or a `.tar.gz` file,
+    /// verifying its files against their digests.
+    ///
+    /// # Errors
+    /// - If the files cannot be read.
+    /// - `Error::InvalidDataset` if the license list or the checksums
+    ///   are missing.
+    /// - `Error::CorruptedDataset` if a file is missing or does not
+    ///   match its digest.
+    pub fn read(path: &Path) -> LictoolResult<Self> {
+        let read_error = || t!("error-read-file", path = path.display().to_string());
+        let mut dataset = Self::default();
+        if path.is_dir() {
+            dataset
+                .read_dir(path, Path::new(""))
+                .with_context(read_error)?;
+        } else {
+            let mut archive =
+                tar::Archive::new(GzDecoder::new(File::open(path).with_context(read_error)?));
+            for entry in archive.entries().with_context(read_error)? {
+                let mut entry = entry.with_context(read_error)?;
+                if !entry.header().entry_type().is_file() {
+                    continue;
+                }
+                let relative = entry.path().with_context(read_error)?.into_owned();
+                let mut contents = Vec::new();
+                entry.read_to_end(&mut contents).with_context(read_error)?;
+                dataset.insert(relative, contents);
+            }
+        }
+        let invalid = || {
+            Error::InvalidDataset {
+                path: path.display().to_string(),
+            }
+        };
+        let checksums = dataset
+            .files
+            .remove(Path::new(CHECKSUMS_FILE))
+            .ok_or_else(invalid)?;
+        let checksums = parse_checksums(&String::from_utf8_lossy(&checksums));
+        dataset.licenses().ok_or_else(invalid)?;
+        let listed = dataset.files.keys().chain(checksums.keys());
+        for file in listed {
+            let actual = dataset.files.get(file).map(|contents| sha256_hex(contents));
+            if actual.is_none() || actual.as_ref() != checksums.get(file) {
+                Err(Error::CorruptedDataset {
+                    path: path.display().to_string(),
+                    file: file.display().to_string(),
+                })?
+            }
+        }
+        Ok(dataset)
+    }
+
+    /// Adds the files below `dir` to the dataset.
+    fn read_dir(
+        &mut self,
+        dir: &Path,
+        relative: &Path,
+    ) -> LictoolResult<()> {
+        for entry in fs::read_dir(dir.join(relative))? {
+            let entry = entry?;
+            let path = relative.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                self.read_dir(dir, &path)?;
+            } else {
+                let contents = fs::read(entry.path())?;
+                self.insert(path, contents);
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds a file, leaving out paths that would escape the mirror.
+    fn insert(
+        &mut self,
+        path: PathBuf,
+        contents: Vec<u8>,
+    ) {
+        if path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            self.files.insert(path, contents);
+        }
+    }
+
+    /// Returns the license list of the dataset, if it holds one.
+    pub fn licenses(&self) -> Option<Licenses> {
+        let path = mirrored_path(&spdx_licenses_url())?;
+        serde_json::from_slice(self.files.get(&path)?).ok()
+    }
+
+    /// Returns the files of the dataset along with the checksum file.
+    fn files_with_checksums(&self) -> Vec<(PathBuf, Vec<u8>)> {
+        let checksums = format_checksums(&self.files).into_bytes();
+        self.files
+            .clone()
+            .into_iter()
+            .chain([(PathBuf::from(CHECKSUMS_FILE), checksums)])
+            .collect()
+    }
+
+    /// Writes the dataset below `dir`.
+    ///
+    /// # Errors
+    /// - If a file cannot be written.
+    pub fn write_dir(
+        &self,
+        dir: &Path,
+    ) -> LictoolResult<()> {
+        for (relative, contents) in self.files_with_checksums() {
+            let path = dir.join(relative);
+            let write_error = || t!("error-write-file", path = path.display().to_string());
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).with_context(write_error)?;
+            }
+            fs::write(&path, contents).with_context(write_error)?;
+            emit(Event::FileWritten {
+                path: path.display().to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Packs the dataset into a gzipped tarball.
+    ///
+    /// # Errors
+    /// - If the tarball cannot be built.
+    pub fn to_archive(&self) -> LictoolResult<Vec<u8>> {
+        let mut archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        for (relative, contents) in self.files_with_checksums() {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            archive.append_data(&mut header, relative, contents.as_slice())?;
+        }
+        Ok(archive.into_inner()?.finish()?)
+    }
+}
+
+/// Stages a fetched file, writing it under a temporary name first so
+/// that an interruption cannot leave it half written.
+fn stage(
+    staging: &Path,
+    relative: &Path,
+    contents: &[u8],
+) {
+    let path = staging.join(STAGED_FILES_DIR).join(relative);
+    let partial = path.with_extension("part");
+    let _ = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&partial, contents))
+        .and_then(|()| fs::rename(&partial, &path));
+}
+
+/// Checks whether a dataset is exported as a `.tar.gz` file rather
+/// than a directory.
+pub(crate) fn is_archive_path(path: &Path) -> bool {
+    let name = path.to_string_lossy().to_lowercase();
+    name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::{stage, Dataset};
+    use crate::util::errors::Error;
+
+    #[test]
+    fn test_dataset_round_trip() {
+        let mut dataset = Dataset::default();
+        dataset.files.insert(
+            "spdx.org/licenses/licenses.json".into(),
+            br#"{"licenses": [], "licenseListVersion": "3.26.0"}"#.to_vec(),
+        );
+        dataset
+            .files
+            .insert("spdx.org/licenses/MIT.json".into(), b"{}".to_vec());
+        let dir = TempDir::new().unwrap();
+        let archive = dir.path().join("db.tar.gz");
+        fs::write(&archive, dataset.to_archive().unwrap()).unwrap();
+        assert_eq!(Dataset::read(&archive).unwrap(), dataset);
+        dataset.write_dir(&dir.path().join("db")).unwrap();
+        let read = Dataset::read(&dir.path().join("db")).unwrap();
+        assert_eq!(read.licenses().unwrap().version, "3.26.0");
+        assert_eq!(read, dataset);
+        assert!(Dataset::read(&dir.path().join("db/spdx.org/licenses")).is_err());
+        fs::write(dir.path().join("db/spdx.org/licenses/MIT.json"), "[]").unwrap();
+        let error = Dataset::read(&dir.path().join("db")).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref(),
+            Some(Error::CorruptedDataset { file, .. }) if file.ends_with("MIT.json")
+        ));
+    }
+
+    #[test]
+    fn test_staged_files() {
+        let dir = TempDir::new().unwrap();
+        let staging = dir.path().join("partial");
+        fs::create_dir_all(&staging).unwrap();
+        fs::write(staging.join("VERSION"), "3.26.0").unwrap();
+        stage(&staging, "spdx.org/licenses/MIT.json".as_ref(), b"{}");
+        fs::write(staging.join("files/spdx.org/licenses/ISC.part"), "{").unwrap();
+        let staged = Dataset::staged(&staging, "3.26.0");
+        assert_eq!(
+            staged.files.keys().collect::<Vec<_>>(),
+            ["spdx.org/licenses/MIT.json"]
+        );
+        assert!(Dataset::staged(&staging, "3.27.0").files.is_empty());
+        assert!(!staging.exists());
+    }
+}
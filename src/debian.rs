@@ -0,0 +1,300 @@
+use std::env;
+
+use anyhow::Context;
+use chrono::{Datelike, Local};
+use futures::future::try_join_all;
+
+use crate::{
+    config::Config,
+    distro::debian_name,
+    expression::{Expression, LicenseTerm},
+    i18n::t,
+    spdx::Licenses,
+    util::{
+        defaults::FieldDefaults,
+        errors::{Error, LictoolResult},
+    },
+};
+
+/// The format URL of machine-readable Debian copyright files.
+const DEP5_FORMAT: &str = "https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/";
+
+/// The header of a `debian/copyright` file.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Header {
+    /// The name of the packaged project.
+    pub(crate) upstream_name: String,
+    /// Who to contact about the project, such as `Jane Doe
+    /// <jane@example.com>`.
+    pub(crate) upstream_contact: Option<String>,
+    /// Where the source of the project can be found.
+    pub(crate) source: Option<String>,
+}
+
+/// A `Files` paragraph, giving the copyright and license of the files
+/// matching its pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FilesParagraph {
+    /// The pattern of the files, such as `*` or `vendor/*`.
+    pub(crate) files: String,
+    pub(crate) copyright: String,
+    pub(crate) license: Expression,
+}
+
+/// Builds the `debian/copyright` file of the project in the current
+/// directory.
+///
+/// All files are covered by the default license, except for the
+/// patterns of the `[paths]` configuration section. The copyright
+/// holder, contact and source come from the field defaults.
+///
+/// Custom `LicenseRef-` licenses get a `License` paragraph with the
+/// text of their `[overrides]` entry.
+///
+/// # Errors
+/// - `Error::MissingLicenseId` if no default license is configured.
+/// - `Error::MissingLicenseRefText` if a `LicenseRef-` license has no
+///   override text.
+/// - If a license expression is invalid or uses an unknown license.
+/// - If a license text cannot be fetched.
+pub(crate) async fn build_copyright(
+    config: &Config,
+    licenses: &Licenses,
+) -> LictoolResult<String> {
+    let defaults = FieldDefaults::load(config);
+    let year = defaults
+        .year
+        .clone()
+        .unwrap_or_else(|| Local::now().year().to_string());
+    let copyright = match &defaults.owner {
+        Some(owner) => format!("{} {}", year, owner),
+        None => year,
+    };
+    let default_license = config.default_license().ok_or(Error::MissingLicenseId)?;
+    let mut paragraphs = vec![FilesParagraph {
+        files: "*".to_string(),
+        copyright: copyright.clone(),
+        license: default_license.parse()?,
+    }];
+    for (files, license) in &config.paths {
+        paragraphs.push(FilesParagraph {
+            files: files.clone(),
+            copyright: copyright.clone(),
+            license: license.parse()?,
+        });
+    }
+
+    // The stand-alone paragraphs are named like the terms of the
+    // `Files` paragraphs, such as `GPL-2+`.
+    let mut names: Vec<String> = Vec::new();
+    let mut terms: Vec<&LicenseTerm> = Vec::new();
+    let mut custom_terms: Vec<&LicenseTerm> = Vec::new();
+    for term in paragraphs
+        .iter()
+        .flat_map(|paragraph| paragraph.license.terms())
+    {
+        let name = debian_term(term);
+        if names.contains(&name) {
+            continue;
+        }
+        names.push(name);
+        // References to custom licenses have no SPDX text.
+        if term.id.starts_with("LicenseRef-") {
+            custom_terms.push(term);
+        } else {
+            terms.push(term);
+        }
+    }
+    let selected = terms
+        .iter()
+        .map(|term| {
+            licenses
+                .find(&term.id)
+                .map(|license| (*term, license))
+                .ok_or(Error::NotFound)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut texts = try_join_all(selected.iter().map(|(term, license)| {
+        async move {
+            let mut details = license.details().await?;
+            config.apply_override(&mut details)?;
+            LictoolResult::Ok((debian_term(term), details.license_text))
+        }
+    }))
+    .await?;
+    for term in custom_terms {
+        let text = config.override_text(&term.id)?.ok_or_else(|| {
+            Error::MissingLicenseRefText {
+                id: term.id.clone(),
+            }
+        })?;
+        texts.push((debian_term(term), text));
+    }
+
+    let upstream_name = env::current_dir()
+        .context(t!("error-current-dir"))?
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let header = Header {
+        upstream_name,
+        upstream_contact: defaults.owner.as_ref().map(|owner| {
+            match &defaults.email {
+                Some(email) => format!("{} <{}>", owner, email),
+                None => owner.clone(),
+            }
+        }),
+        source: defaults.repo.clone(),
+    };
+    Ok(render_copyright(&header, &paragraphs, &texts))
+}
+
+/// Renders a license expression with DEP-5 short names and its
+/// lowercase operators.
+pub(crate) fn debian_license(expression: &Expression) -> String {
+    match expression {
+        Expression::License(term) => debian_term(term),
+        Expression::And(operands) => {
+            operands
+                .iter()
+                .map(|operand| {
+                    match operand {
+                        Expression::Or(_) => format!("({})", debian_license(operand)),
+                        _ => debian_license(operand),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" and ")
+        }
+        Expression::Or(operands) => {
+            operands
+                .iter()
+                .map(debian_license)
+                .collect::<Vec<_>>()
+                .join(" or ")
+        }
+    }
+}
+
+/// Renders a license term with its DEP-5 short name, such as `GPL-2+`
+/// for `GPL-2.0-or-later`.
+fn debian_term(term: &LicenseTerm) -> String {
+    let mut license = debian_name(&term.id).to_string();
+    if term.or_later && !license.ends_with('+') {
+        license.push('+');
+    }
+    if let Some(exception) = &term.exception {
+        // DEP-5 names exceptions by keyword, such as `with Classpath
+        // exception`.
+        let keyword = exception.split("-exception").next().unwrap_or(exception);
+        license.push_str(&format!(" with {} exception", keyword));
+    }
+    license
+}
+
+/// Renders a `debian/copyright` file.
+///
+/// `texts` holds the full text of each license, keyed by the DEP-5
+/// short name its `Files` paragraphs use, and is written as
+/// standalone `License` paragraphs.
+pub(crate) fn render_copyright(
+    header: &Header,
+    paragraphs: &[FilesParagraph],
+    texts: &[(String, String)],
+) -> String {
+    let mut output = format!(
+        "Format: {}\nUpstream-Name: {}\n",
+        DEP5_FORMAT, header.upstream_name
+    );
+    if let Some(contact) = &header.upstream_contact {
+        output.push_str(&format!("Upstream-Contact: {}\n", contact));
+    }
+    if let Some(source) = &header.source {
+        output.push_str(&format!("Source: {}\n", source));
+    }
+    for paragraph in paragraphs {
+        output.push_str(&format!(
+            "\nFiles: {}\nCopyright: {}\nLicense: {}\n",
+            paragraph.files,
+            paragraph.copyright,
+            debian_license(&paragraph.license)
+        ));
+    }
+    for (name, text) in texts {
+        output.push_str(&format!("\nLicense: {}\n", name));
+        for line in text.trim().lines() {
+            // Empty lines of a field are written as a lone dot.
+            if line.trim().is_empty() {
+                output.push_str(" .\n");
+            } else {
+                output.push_str(&format!(" {}\n", line.trim_end()));
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{debian_license, render_copyright, FilesParagraph, Header};
+
+    #[test]
+    fn test_render_copyright() {
+        assert_eq!(
+            debian_license(
+                &"(MIT OR GPL-2.0-or-later WITH Classpath-exception-2.0) AND Zlib"
+                    .parse()
+                    .unwrap()
+            ),
+            "(Expat or GPL-2+ with Classpath exception) and Zlib"
+        );
+        let header = Header {
+            upstream_name: "lictool".to_string(),
+            upstream_contact: Some("Jane Doe <jane@example.com>".to_string()),
+            source: None,
+        };
+        let paragraphs = [
+            FilesParagraph {
+                files: "*".to_string(),
+                copyright: "2024 Jane Doe".to_string(),
+                license: "MIT".parse().unwrap(),
+            },
+            FilesParagraph {
+                files: "vendor/*".to_string(),
+                copyright: "2024 Jane Doe".to_string(),
+                license: "Zlib".parse().unwrap(),
+            },
+        ];
+        let texts = [
+            (
+                "Expat".to_string(),
+                "MIT License\n\nPermission is granted.  \n".to_string(),
+            ),
+            ("Zlib".to_string(), "zlib License".to_string()),
+        ];
+        assert_eq!(
+            render_copyright(&header, &paragraphs, &texts),
+            "Format: https://www.debian.org/doc/packaging-manuals/copyright-format/1.0/\n\
+             Upstream-Name: lictool\n\
+             Upstream-Contact: Jane Doe <jane@example.com>\n\
+             \n\
+             Files: *\n\
+             Copyright: 2024 Jane Doe\n\
+             License: Expat\n\
+             \n\
+             Files: vendor/*\n\
+             Copyright: 2024 Jane Doe\n\
+             License: Zlib\n\
+             \n\
+             License: Expat\n \
+             MIT License\n \
+             .\n \
+             Permission is granted.\n\
+             \n\
+             License: Zlib\n \
+             zlib License\n"
+        );
+    }
+}
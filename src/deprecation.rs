@@ -0,0 +1,172 @@
+use color_print::cformat;
+use dialoguer::{theme::Theme, Select};
+
+use crate::{
+    i18n::t,
+    spdx::{License, Licenses},
+    util::errors::{Error, LictoolResult},
+};
+
+/// The successors of deprecated license IDs that are not the `-only`
+/// and `-or-later` variants of their ID, as license expressions.
+const SUCCESSORS: [(&str, &[&str]); 14] = [
+    ("BSD-2-Clause-FreeBSD", &["BSD-2-Clause-Views"]),
+    ("BSD-2-Clause-NetBSD", &["BSD-2-Clause"]),
+    ("bzip2-1.0.5", &["bzip2-1.0.6"]),
+    ("eCos-2.0", &["GPL-2.0-or-later WITH eCos-exception-2.0"]),
+    (
+        "GPL-2.0-with-autoconf-exception",
+        &["GPL-2.0-only WITH Autoconf-exception-2.0"],
+    ),
+    (
+        "GPL-2.0-with-bison-exception",
+        &["GPL-2.0-or-later WITH Bison-exception-2.2"],
+    ),
+    (
+        "GPL-2.0-with-classpath-exception",
+        &["GPL-2.0-only WITH Classpath-exception-2.0"],
+    ),
+    (
+        "GPL-2.0-with-font-exception",
+        &["GPL-2.0-only WITH Font-exception-2.0"],
+    ),
+    (
+        "GPL-2.0-with-GCC-exception",
+        &["GPL-2.0-only WITH GCC-exception-2.0"],
+    ),
+    (
+        "GPL-3.0-with-autoconf-exception",
+        &["GPL-3.0-only WITH Autoconf-exception-3.0"],
+    ),
+    (
+        "GPL-3.0-with-GCC-exception",
+        &["GPL-3.0-only WITH GCC-exception-3.1"],
+    ),
+    ("Nunit", &["zlib-acknowledgement"]),
+    ("StandardML-NJ", &["SMLNJ"]),
+    (
+        "wxWindows",
+        &["LGPL-2.0-or-later WITH WxWindows-exception-3.1"],
+    ),
+];
+
+/// Returns the license expressions to use in place of a deprecated
+/// license ID, best first.
+///
+/// IDs such as `GPL-3.0` are succeeded by their `-only` and
+/// `-or-later` variants, and `GPL-3.0+` by its `-or-later` one,
+/// where `licenses` has them; other IDs are looked up in a table.
+pub(crate) fn successors(
+    id: &str,
+    licenses: &Licenses,
+) -> Vec<String> {
+    if let Some((_, successors)) = SUCCESSORS
+        .iter()
+        .find(|(deprecated, _)| deprecated.eq_ignore_ascii_case(id))
+    {
+        return successors.iter().map(|id| id.to_string()).collect();
+    }
+    let candidates = match id.strip_suffix('+') {
+        Some(base) => vec![format!("{}-or-later", base)],
+        None => vec![format!("{}-only", id), format!("{}-or-later", id)],
+    };
+    candidates
+        .into_iter()
+        .filter_map(|candidate| licenses.find(&candidate))
+        .filter(|license| !license.is_deprecated_license_id)
+        .map(|license| license.id.clone())
+        .collect()
+}
+
+/// Asks which of `successors` to use in place of a deprecated
+/// license, or whether to keep it.
+///
+/// # Returns
+///
+/// The chosen successor, or the ID of `license` if it is kept.
+///
+/// # Errors
+/// - `Error::Aborted` if the prompt was cancelled.
+/// - If the terminal cannot be read from or written to.
+pub(crate) fn prompt_successor(
+    license: &License,
+    successors: &[String],
+    theme: &dyn Theme,
+) -> LictoolResult<String> {
+    let mut items = successors.to_vec();
+    items.push(t!("deprecated-keep", license = license.id.as_str()));
+    let selection = Select::with_theme(theme)
+        .with_prompt(t!("deprecated-prompt", license = license.id.as_str()))
+        .items(&items)
+        .default(0)
+        .interact_opt()?
+        .ok_or(Error::Aborted)?;
+    Ok(successors
+        .get(selection)
+        .cloned()
+        .unwrap_or_else(|| license.id.clone()))
+}
+
+/// Warns on stderr that a deprecated license is used, naming its
+/// successors.
+pub(crate) fn warn_deprecated(
+    id: &str,
+    successors: &[String],
+) {
+    eprintln!(
+        "{}",
+        cformat!(
+            "<yellow>{}</>",
+            t!(
+                "deprecated-warning",
+                license = id,
+                successors = successors.join(", ")
+            )
+        )
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::successors;
+    use crate::spdx::{License, Licenses};
+
+    #[test]
+    fn test_successors() {
+        let license = |id: &str, deprecated: bool| {
+            License {
+                is_deprecated_license_id: deprecated,
+                details_url: String::new(),
+                name: id.to_string(),
+                id: id.to_string(),
+                is_osi_approved: Some(true),
+                is_fsf_libre: None,
+            }
+        };
+        let licenses = Licenses {
+            body: vec![
+                license("GPL-3.0", true),
+                license("GPL-3.0+", true),
+                license("GPL-3.0-only", false),
+                license("GPL-3.0-or-later", false),
+            ],
+            version: String::new(),
+        };
+        assert_eq!(
+            successors("GPL-3.0", &licenses),
+            ["GPL-3.0-only", "GPL-3.0-or-later"]
+        );
+        assert_eq!(successors("GPL-3.0+", &licenses), ["GPL-3.0-or-later"]);
+        assert_eq!(
+            successors("gpl-2.0-with-classpath-exception", &licenses),
+            ["GPL-2.0-only WITH Classpath-exception-2.0"]
+        );
+        assert_eq!(
+            successors("BSD-2-Clause-FreeBSD", &licenses),
+            ["BSD-2-Clause-Views"]
+        );
+        assert!(successors("GPL-2.0", &licenses).is_empty());
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use color_print::cprintln;
+use serde::Serialize;
+
+use crate::{
+    check::find_drift,
+    i18n::t,
+    spdx::LicenseDetails,
+    util::{
+        matching::MatchNormalization,
+        output::{note, success},
+    },
+};
+
+/// The confidence from which a license is reported as the likely
+/// license of a file.
+pub(crate) const LIKELY_CONFIDENCE: f64 = 0.9;
+
+/// A license a text may be, with how confident the match is.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Detection {
+    pub(crate) license_id: String,
+    /// The similarity of the texts, from `0.0` to `1.0` for a text
+    /// matching the license under the SPDX matching guidelines.
+    pub(crate) confidence: f64,
+}
+
+/// The pairs of consecutive words of a text, counted, after the
+/// normalizations of the SPDX matching guidelines and without its
+/// copyright lines.
+fn word_pairs(text: &str) -> HashMap<(String, String), usize> {
+    let normalization = MatchNormalization {
+        spdx_matching: true,
+        ..Default::default()
+    };
+    let words: Vec<String> = text
+        .lines()
+        .filter(|line| !normalization.skips(line))
+        .flat_map(|line| {
+            normalization
+                .apply(line)
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    let mut pairs = HashMap::new();
+    for pair in words.windows(2) {
+        *pairs.entry((pair[0].clone(), pair[1].clone())).or_default() += 1;
+    }
+    pairs
+}
+
+/// Returns the Sørensen–Dice coefficient of two counted sets of word
+/// pairs.
+fn similarity(
+    a: &HashMap<(String, String), usize>,
+    b: &HashMap<(String, String), usize>,
+) -> f64 {
+    let total: usize = a.values().sum::<usize>() + b.values().sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+    let shared: usize = a
+        .iter()
+        .filter_map(|(pair, count)| Some((*count).min(*b.get(pair)?)))
+        .sum();
+    2.0 * shared as f64 / total as f64
+}
+
+/// Ranks the licenses by how similar their canonical texts are to
+/// `text`.
+///
+/// A license whose text `text` matches under the SPDX matching
+/// guidelines, such as one with its placeholders filled, is given a
+/// confidence of `1.0`.
+///
+/// # Returns
+///
+/// Up to `count` licenses, the most likely first.
+pub(crate) fn detect_license(
+    text: &str,
+    details: &[LicenseDetails],
+    count: usize,
+) -> Vec<Detection> {
+    let pairs = word_pairs(text);
+    let mut detections: Vec<(Detection, &LicenseDetails)> = details
+        .iter()
+        .map(|details| {
+            let detection = Detection {
+                license_id: details.license_id.clone(),
+                confidence: similarity(&pairs, &word_pairs(&details.license_text)),
+            };
+            (detection, details)
+        })
+        .collect();
+    detections.sort_by(|(a, _), (b, _)| b.confidence.total_cmp(&a.confidence));
+    detections.truncate(count);
+    let normalization = MatchNormalization {
+        spdx_matching: true,
+        ..Default::default()
+    };
+    for (detection, details) in &mut detections {
+        if find_drift(&details.license_text, text, &normalization).is_empty() {
+            detection.confidence = 1.0;
+        }
+    }
+    detections.sort_by(|(a, _), (b, _)| b.confidence.total_cmp(&a.confidence));
+    detections
+        .into_iter()
+        .map(|(detection, _)| detection)
+        .collect()
+}
+
+/// Prints the most likely license of a file, followed by the other
+/// candidates.
+pub(crate) fn display_detections(
+    file: &str,
+    detections: &[Detection],
+) {
+    let Some((best, others)) = detections.split_first() else {
+        cprintln!(
+            "<y, bold>\u{f421}</> <bold>{}</>",
+            t!("detect-no-match", file = file)
+        );
+        return;
+    };
+    let confidence = format!("{:.1}", best.confidence * 100.0);
+    if best.confidence >= LIKELY_CONFIDENCE {
+        success!(
+            "<green>✔</> <bold>{}</>",
+            t!(
+                "detect-likely",
+                file = file,
+                license = best.license_id.as_str(),
+                confidence = confidence
+            )
+        );
+    } else {
+        cprintln!(
+            "<y, bold>\u{f421}</> <bold>{}</>",
+            t!(
+                "detect-closest",
+                file = file,
+                license = best.license_id.as_str(),
+                confidence = confidence
+            )
+        );
+    }
+    if others.is_empty() {
+        return;
+    }
+    let width = others
+        .iter()
+        .map(|detection| detection.license_id.len())
+        .max()
+        .unwrap_or(0);
+    note!(t!("detect-candidates"));
+    for detection in others {
+        cprintln!(
+            "  {:width$}  <dim>{:>5.1}%</>",
+            detection.license_id,
+            detection.confidence * 100.0,
+            width = width
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::detect_license;
+    use crate::spdx::LicenseDetails;
+
+    fn details(
+        id: &str,
+        text: &str,
+    ) -> LicenseDetails {
+        LicenseDetails {
+            is_deprecated_license_id: false,
+            license_text: text.to_string(),
+            name: id.to_string(),
+            license_comments: None,
+            license_id: id.to_string(),
+            see_also: Vec::new(),
+            is_osi_approved: Some(true),
+            is_fsf_libre: None,
+            deprecated_version: None,
+            fsf: None,
+        }
+    }
+
+    #[test]
+    fn test_detect_license() {
+        let licenses = [
+            details(
+                "MIT",
+                "Permission is hereby granted, free of charge, to any person obtaining a copy\nof \
+                 this software, to deal in the Software without restriction.",
+            ),
+            details(
+                "ISC",
+                "Permission to use, copy, modify, and/or distribute this software for any purpose \
+                 with or without fee is hereby granted.",
+            ),
+        ];
+        let text = "Copyright (c) 2024 Jane Doe\n\nPermission is hereby granted, free of charge, \
+                    to any person obtaining a copy\nof this software, to deal in the Software \
+                    without restriction.\n";
+        let detections = detect_license(text, &licenses, 2);
+        assert_eq!(detections[0].license_id, "MIT");
+        assert_eq!(detections[0].confidence, 1.0);
+        assert!(detections[1].confidence < 0.5);
+        let edited = text.replace("free of charge", "for a fee");
+        let detections = detect_license(&edited, &licenses, 1);
+        assert_eq!(detections.len(), 1);
+        assert!(detections[0].confidence > 0.7 && detections[0].confidence < 1.0);
+    }
+}
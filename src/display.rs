@@ -0,0 +1,332 @@
+use std::io::{self, BufWriter, Write};
+
+use color_print::{cformat, cstr};
+
+use crate::{
+    category::AssetCategory,
+    fsf::FsfLicense,
+    i18n::t,
+    spdx::{Exception, ExceptionDetails, License, LicenseDetails},
+    util::errors::LictoolResult,
+};
+
+/// Displays the IDs of licenses.
+///
+/// This function sorts the given slice of licenses by their
+/// deprecation status and then prints the ID of each license. In
+/// plain mode the IDs are printed as-is, one per line, without color
+/// and in their original order, which keeps the output stable for
+/// scripts.
+///
+/// # Arguments
+///
+/// * `licenses` - A mutable slice of references to `License` objects.
+/// * `plain` - Whether to print bare IDs instead of colored ones.
+///
+/// # Returns
+///
+/// A `LictoolResult` indicating the success or failure of the
+/// operation.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut licenses = vec![&license1, &license2];
+/// display_license_ids(&mut licenses, false)?;
+/// ```
+pub(crate) fn display_license_ids(
+    licenses: &mut [&License],
+    plain: bool,
+) -> LictoolResult<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    if plain {
+        for license in licenses.iter() {
+            writeln!(out, "{}", license.id)?;
+        }
+    } else {
+        licenses.sort_by_key(|license| license.is_deprecated_license_id);
+        for license in licenses.iter() {
+            writeln!(out, "{}", color_id(license))?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Displays licenses as a table of their ID, OSI approval, FSF
+/// libre status, support status and name.
+///
+/// Names are cut to fit the width of the terminal. In plain mode the
+/// columns are separated by tabs, without a header, colors or
+/// sorting, like `display_license_ids`.
+pub(crate) fn display_license_table(
+    licenses: &mut [&License],
+    plain: bool,
+) -> LictoolResult<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    if plain {
+        for license in licenses.iter() {
+            let unknown_or = |value: Option<bool>| {
+                value.map_or_else(|| "-".to_string(), |value| value.to_string())
+            };
+            writeln!(
+                out,
+                "{}\t{}\t{}\t{}\t{}",
+                license.id,
+                unknown_or(license.is_osi_approved),
+                unknown_or(license.is_fsf_libre),
+                !license.is_deprecated_license_id,
+                license.name
+            )?;
+        }
+        out.flush()?;
+        return Ok(());
+    }
+    licenses.sort_by_key(|license| license.is_deprecated_license_id);
+    let id_label = t!("list-column-id");
+    let labels = [
+        t!("list-column-osi"),
+        t!("list-column-fsf"),
+        t!("list-column-supported"),
+    ];
+    let id_width = licenses
+        .iter()
+        .map(|license| license.id.len())
+        .chain([id_label.chars().count()])
+        .max()
+        .unwrap_or(0);
+    let widths: Vec<usize> = labels.iter().map(|label| label.chars().count()).collect();
+    // Names take the rest of the line, after two spaces per column.
+    let used = id_width + widths.iter().map(|width| width + 2).sum::<usize>() + 2;
+    let name_width = termion::terminal_size()
+        .map(|(width, _)| (width as usize).saturating_sub(used).max(10))
+        .unwrap_or(usize::MAX);
+    let pad = |text: &str, width: usize| " ".repeat(width.saturating_sub(text.chars().count()));
+    let mut header = format!("{}{}", id_label, pad(&id_label, id_width));
+    for label in &labels {
+        header.push_str(&format!("  {}", label));
+    }
+    header.push_str(&format!("  {}", t!("list-column-name")));
+    writeln!(out, "{}", cformat!("<bold>{}</>", header))?;
+    for license in licenses.iter() {
+        let marks = [
+            license.is_osi_approved,
+            license.is_fsf_libre,
+            Some(!license.is_deprecated_license_id),
+        ];
+        let mut row = format!("{}{}", color_id(license), pad(&license.id, id_width));
+        for (mark, width) in marks.iter().zip(&widths) {
+            let mark = match mark {
+                Some(mark) => mark.as_checkbox().to_string(),
+                None => cformat!("<dim>-</>"),
+            };
+            row.push_str(&format!("  {}{}", mark, " ".repeat(width - 1)));
+        }
+        let name: String = if license.name.chars().count() > name_width {
+            let cut: String = license.name.chars().take(name_width - 1).collect();
+            format!("{}…", cut)
+        } else {
+            license.name.clone()
+        };
+        writeln!(out, "{}  {}", row, name)?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Displays the IDs of license exceptions, like
+/// `display_license_ids`.
+pub(crate) fn display_exception_ids(
+    exceptions: &mut [&Exception],
+    plain: bool,
+) -> LictoolResult<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    if plain {
+        for exception in exceptions.iter() {
+            writeln!(out, "{}", exception.id)?;
+        }
+    } else {
+        exceptions.sort_by_key(|exception| exception.is_deprecated_license_id);
+        for exception in exceptions.iter() {
+            writeln!(
+                out,
+                "{}",
+                color_deprecated(&exception.id, exception.is_deprecated_license_id)
+            )?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Returns the license ID as a colored string.
+///
+/// Deprecated IDs are displayed in red, while non-deprecated IDs are
+/// displayed in green.
+pub(crate) fn color_id(license: &License) -> String {
+    color_deprecated(&license.id, license.is_deprecated_license_id)
+}
+
+/// Returns an ID in red if it is deprecated, or in green otherwise.
+fn color_deprecated(
+    id: &str,
+    deprecated: bool,
+) -> String {
+    if deprecated {
+        cformat!("<bold, red>{}</>", id)
+    } else {
+        cformat!("<bold, green>{}</>", id)
+    }
+}
+
+/// An interface for representing a boolean value as a checkbox.
+///
+/// This trait provides a method to convert a boolean value into
+/// a string representation of a checkbox, typically for display
+/// purposes.
+///
+/// # Example
+///
+/// ```ignore
+/// let checked = true;
+/// println!("{}", checked.as_checkbox()); // Output: <green, bold>󰄲</>
+///
+/// let unchecked = false;
+/// println!("{}", unchecked.as_checkbox()); // Output: <red, bold>󰅗</>
+/// ```
+trait Checkbox {
+    fn as_checkbox(&self) -> &str;
+}
+
+impl Checkbox for bool {
+    fn as_checkbox(&self) -> &str {
+        match self {
+            true => cstr!("<green, bold>󰄲</>"),
+            false => cstr!("<red, bold>󰅗</>"),
+        }
+    }
+}
+
+/// Renders the details of a license for `info`, with its name
+/// centered on the terminal.
+pub(crate) fn display_license_details(details: &LicenseDetails) -> String {
+    let term_width = termion::terminal_size().unwrap().0 as usize;
+    let padding = (term_width - details.name.len()) / 2;
+    let mut output = format!("{:width$}", "", width = padding);
+    output.push_str(&cformat!("«<s>{}</>»\n", details.name));
+    output.push_str(&cformat!(
+        "<s>{}</> <u>https://spdx.org/licenses/{}.html</>\n",
+        t!("info-reference"),
+        details.license_id
+    ));
+    output.push_str(&cformat!(
+        "<s>{}</> {}\n",
+        t!("info-license-id"),
+        details.license_id
+    ));
+    if let Some(ref comments) = details.license_comments {
+        output.push_str(&cformat!("<s>{}</> {}\n", t!("info-comments"), comments));
+    }
+    output.push_str(&cformat!("<s>{}</>\n", t!("info-see-also")));
+    for link in &details.see_also {
+        output.push_str(&cformat!("  - <u>{}</>\n", link));
+    }
+    output.push_str(&cformat!(
+        "<s>{}</> {}\n",
+        t!("info-supported"),
+        (!details.is_deprecated_license_id).as_checkbox()
+    ));
+    if let Some(is_osi_approved) = details.is_osi_approved {
+        output.push_str(&cformat!(
+            "<s>{}</> {}",
+            t!("info-osi-approved"),
+            is_osi_approved.as_checkbox()
+        ));
+    }
+    if let Some(is_fsf_libre) = details.is_fsf_libre {
+        output.push_str(&cformat!(
+            "\n<s>{}</> {}",
+            t!("info-fsf-libre"),
+            is_fsf_libre.as_checkbox()
+        ));
+    }
+    if let Some(categories) = details
+        .fsf
+        .as_ref()
+        .map(FsfLicense::categories)
+        .filter(|categories| !categories.is_empty())
+    {
+        output.push_str(&cformat!(
+            "\n<s>{}</> {}",
+            t!("info-fsf-categories"),
+            categories.join(", ")
+        ));
+    }
+    let asset_categories: Vec<String> = AssetCategory::of(&details.license_id)
+        .iter()
+        .map(|category| category.name())
+        .collect();
+    if !asset_categories.is_empty() {
+        output.push_str(&cformat!(
+            "\n<s>{}</> {}",
+            t!("info-asset-categories"),
+            asset_categories.join(", ")
+        ));
+    }
+    if let Some(ref deprecated_version) = details.deprecated_version {
+        output.push_str(&cformat!(
+            "\n<s>{}</> {}",
+            t!("info-deprecated-version"),
+            deprecated_version
+        ));
+    }
+    output
+}
+
+/// Renders the details of a license exception for `info`, followed
+/// by its text.
+pub(crate) fn display_exception_details(details: &ExceptionDetails) -> String {
+    let mut output = cformat!("«<s>{}</>»\n", details.name);
+    output.push_str(&cformat!(
+        "<s>{}</> <u>https://spdx.org/licenses/{}.html</>\n",
+        t!("info-reference"),
+        details.license_exception_id
+    ));
+    output.push_str(&cformat!(
+        "<s>{}</> {}\n",
+        t!("info-exception-id"),
+        details.license_exception_id
+    ));
+    if let Some(ref comments) = details.license_comments {
+        output.push_str(&cformat!("<s>{}</> {}\n", t!("info-comments"), comments));
+    }
+    if !details.combined_with.is_empty() {
+        output.push_str(&cformat!(
+            "<s>{}</> {}\n",
+            t!("info-combined-with"),
+            details.combined_with.join(", ")
+        ));
+    }
+    output.push_str(&cformat!("<s>{}</>\n", t!("info-see-also")));
+    for link in &details.see_also {
+        output.push_str(&cformat!("  - <u>{}</>\n", link));
+    }
+    output.push_str(&cformat!(
+        "<s>{}</> {}",
+        t!("info-supported"),
+        (!details.is_deprecated_license_id).as_checkbox()
+    ));
+    if let Some(ref deprecated_version) = details.deprecated_version {
+        output.push_str(&cformat!(
+            "\n<s>{}</> {}",
+            t!("info-deprecated-version"),
+            deprecated_version
+        ));
+    }
+    output.push_str(&cformat!(
+        "\n\n<s>{}</>\n{}",
+        t!("info-exception-text"),
+        details.license_exception_text.trim_end()
+    ));
+    output
+}
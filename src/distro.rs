@@ -0,0 +1,116 @@
+use color_print::cformat;
+
+use crate::i18n::t;
+
+/// The names Linux distributions use for a license.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DistroNames {
+    /// The SPDX license ID.
+    pub(crate) spdx: &'static str,
+    /// The short name of Debian machine-readable copyright files
+    /// (DEP-5).
+    pub(crate) debian: &'static str,
+    /// The legacy short name of Fedora spec files, which now use SPDX
+    /// expressions.
+    pub(crate) fedora: &'static str,
+}
+
+/// Licenses whose distribution names differ from their SPDX ID.
+const DISTRO_NAMES: [DistroNames; 34] = [
+    names("0BSD", "0BSD", "0BSD"),
+    names("AGPL-3.0-only", "AGPL-3", "AGPLv3"),
+    names("AGPL-3.0-or-later", "AGPL-3+", "AGPLv3+"),
+    names("Apache-1.1", "Apache-1.1", "ASL 1.1"),
+    names("Apache-2.0", "Apache-2.0", "ASL 2.0"),
+    names("Artistic-1.0-Perl", "Artistic", "Artistic"),
+    names("Artistic-2.0", "Artistic-2.0", "Artistic 2.0"),
+    names("BSD-2-Clause", "BSD-2-clause", "BSD"),
+    names("BSD-3-Clause", "BSD-3-clause", "BSD"),
+    names("BSD-4-Clause", "BSD-4-clause", "BSD with advertising"),
+    names("BSL-1.0", "BSL-1.0", "Boost"),
+    names("CC-BY-4.0", "CC-BY-4.0", "CC-BY"),
+    names("CC-BY-SA-4.0", "CC-BY-SA-4.0", "CC-BY-SA"),
+    names("CC0-1.0", "CC0-1.0", "CC0"),
+    names("CDDL-1.0", "CDDL-1.0", "CDDL-1.0"),
+    names("EPL-1.0", "EPL-1.0", "EPL-1.0"),
+    names("EPL-2.0", "EPL-2.0", "EPL-2.0"),
+    names("GFDL-1.3-only", "GFDL-1.3", "GFDL"),
+    names("GPL-2.0-only", "GPL-2", "GPLv2"),
+    names("GPL-2.0-or-later", "GPL-2+", "GPLv2+"),
+    names("GPL-3.0-only", "GPL-3", "GPLv3"),
+    names("GPL-3.0-or-later", "GPL-3+", "GPLv3+"),
+    names("ISC", "ISC", "ISC"),
+    names("LGPL-2.1-only", "LGPL-2.1", "LGPLv2"),
+    names("LGPL-2.1-or-later", "LGPL-2.1+", "LGPLv2+"),
+    names("LGPL-3.0-only", "LGPL-3", "LGPLv3"),
+    names("LGPL-3.0-or-later", "LGPL-3+", "LGPLv3+"),
+    names("MIT", "Expat", "MIT"),
+    names("MPL-1.1", "MPL-1.1", "MPLv1.1"),
+    names("MPL-2.0", "MPL-2.0", "MPLv2.0"),
+    names("OFL-1.1", "OFL-1.1", "OFL"),
+    names("Python-2.0", "Python-2.0", "Python"),
+    names("Unlicense", "Unlicense", "Unlicense"),
+    names("Zlib", "Zlib", "zlib"),
+];
+
+const fn names(
+    spdx: &'static str,
+    debian: &'static str,
+    fedora: &'static str,
+) -> DistroNames {
+    DistroNames {
+        spdx,
+        debian,
+        fedora,
+    }
+}
+
+/// Looks up the distribution names of a license.
+pub(crate) fn distro_names(spdx_id: &str) -> Option<DistroNames> {
+    DISTRO_NAMES
+        .iter()
+        .find(|names| names.spdx.eq_ignore_ascii_case(spdx_id))
+        .copied()
+}
+
+/// Returns the DEP-5 short name of a license, which is its SPDX ID
+/// when Debian does not name it differently.
+pub(crate) fn debian_name(spdx_id: &str) -> &str {
+    distro_names(spdx_id).map_or(spdx_id, |names| names.debian)
+}
+
+/// Returns the legacy Fedora short name of a license, which is its
+/// SPDX ID when Fedora does not name it differently.
+pub(crate) fn fedora_name(spdx_id: &str) -> &str {
+    distro_names(spdx_id).map_or(spdx_id, |names| names.fedora)
+}
+
+/// Formats the distribution names of a license for `info --distro`,
+/// falling back to the SPDX ID for licenses without a mapping.
+pub(crate) fn display_distro_names(spdx_id: &str) -> String {
+    cformat!(
+        "<s>{}</> {}\n<s>{}</> {}",
+        t!("info-debian-name"),
+        debian_name(spdx_id),
+        t!("info-fedora-name"),
+        fedora_name(spdx_id)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{debian_name, distro_names, DISTRO_NAMES};
+
+    #[test]
+    fn test_distro_names() {
+        assert_eq!(debian_name("MIT"), "Expat");
+        assert_eq!(debian_name("gpl-2.0-or-later"), "GPL-2+");
+        assert_eq!(debian_name("WTFPL"), "WTFPL");
+        assert_eq!(distro_names("Apache-2.0").unwrap().fedora, "ASL 2.0");
+        assert!(DISTRO_NAMES
+            .windows(2)
+            .all(|pair| pair[0].spdx < pair[1].spdx));
+    }
+}
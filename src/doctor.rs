@@ -0,0 +1,274 @@
+use std::{
+    env, fs,
+    io::{self, IsTerminal},
+    path::Path,
+};
+
+use color_print::cprintln;
+use console::Term;
+
+use crate::{
+    config::Config,
+    i18n::t,
+    spdx::source_licenses_url,
+    util::{
+        cache::{dir_size, format_size, http_cache_dir},
+        errors::LictoolResult,
+        git::{repository_workdir, GitConfig},
+        http,
+        output::success,
+    },
+};
+
+/// Cache sizes above this are reported as worth clearing.
+const LARGE_CACHE_BYTES: u64 = 512 * 1024 * 1024;
+
+/// How healthy an aspect of the environment is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Health {
+    Ok,
+    /// Works, but some features are limited.
+    Warning,
+    /// Keeps lictool from working.
+    Failure,
+}
+
+/// The outcome of checking one aspect of the environment.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Diagnosis {
+    /// What was checked, such as `Cache`.
+    pub(crate) check: String,
+    pub(crate) health: Health,
+    pub(crate) detail: String,
+    /// What to do about a warning or failure.
+    pub(crate) fix: Option<String>,
+}
+
+impl Diagnosis {
+    fn new(
+        check: String,
+        health: Health,
+        detail: String,
+    ) -> Self {
+        Self {
+            check,
+            health,
+            detail,
+            fix: None,
+        }
+    }
+
+    fn with_fix(
+        mut self,
+        fix: String,
+    ) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// Checks the connectivity to SPDX, the HTTP cache, the
+/// configuration, git and the terminal.
+///
+/// The configuration is passed as loaded, so that an invalid one is
+/// diagnosed rather than aborting.
+pub(crate) async fn diagnose(config: &LictoolResult<Config>) -> Vec<Diagnosis> {
+    vec![
+        check_connectivity().await,
+        check_cache(),
+        check_config(config),
+        check_git(),
+        check_terminal(),
+    ]
+}
+
+/// Checks that the license list of the configured source can be
+/// reached.
+async fn check_connectivity() -> Diagnosis {
+    let check = t!("doctor-connectivity");
+    let url = source_licenses_url();
+    match http::probe(&url).await {
+        Ok((status, elapsed)) if status.is_success() || status.is_redirection() => {
+            Diagnosis::new(
+                check,
+                Health::Ok,
+                t!(
+                    "doctor-connectivity-ok",
+                    url = url,
+                    millis = elapsed.as_millis() as u64
+                ),
+            )
+        }
+        Ok((status, _)) => {
+            Diagnosis::new(
+                check,
+                Health::Failure,
+                t!(
+                    "doctor-connectivity-status",
+                    url = url,
+                    status = status.to_string()
+                ),
+            )
+            .with_fix(t!("doctor-fix-connectivity"))
+        }
+        Err(error) => {
+            Diagnosis::new(check, Health::Failure, format!("{:#}", error))
+                .with_fix(t!("doctor-fix-connectivity"))
+        }
+    }
+}
+
+/// Checks that the HTTP cache can be written to, and reports its
+/// size.
+fn check_cache() -> Diagnosis {
+    let check = t!("doctor-cache");
+    let dir = http_cache_dir();
+    let path = dir.display().to_string();
+    let writable = fs::create_dir_all(&dir).and_then(|_| {
+        let probe = dir.join(".lictool-doctor");
+        fs::write(&probe, b"")?;
+        fs::remove_file(probe)
+    });
+    if let Err(error) = writable {
+        return Diagnosis::new(
+            check,
+            Health::Failure,
+            t!(
+                "doctor-cache-unwritable",
+                dir = path.as_str(),
+                error = error.to_string()
+            ),
+        )
+        .with_fix(t!("doctor-fix-cache-permissions", dir = path.as_str()));
+    }
+    match dir_size(&dir) {
+        Ok((bytes, files)) => {
+            let detail = t!(
+                "doctor-cache-size",
+                dir = path.as_str(),
+                size = format_size(bytes),
+                files = files
+            );
+            if bytes > LARGE_CACHE_BYTES {
+                Diagnosis::new(check, Health::Warning, detail).with_fix(t!("doctor-fix-cache-size"))
+            } else {
+                Diagnosis::new(check, Health::Ok, detail)
+            }
+        }
+        Err(error) => {
+            Diagnosis::new(check, Health::Failure, error.to_string())
+                .with_fix(t!("doctor-fix-cache-corrupt"))
+        }
+    }
+}
+
+/// Reports the configuration files read, or why they are invalid.
+fn check_config(config: &LictoolResult<Config>) -> Diagnosis {
+    let check = t!("doctor-config");
+    match config {
+        Ok(config) if config.sources.is_empty() => {
+            Diagnosis::new(check, Health::Ok, t!("config-no-files"))
+        }
+        Ok(config) => {
+            let files = config
+                .sources
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            Diagnosis::new(check, Health::Ok, t!("doctor-config-ok", files = files))
+        }
+        Err(error) => {
+            Diagnosis::new(check, Health::Failure, format!("{:#}", error))
+                .with_fix(t!("doctor-fix-config"))
+        }
+    }
+}
+
+/// Checks that the current directory is in a git repository with an
+/// identity to commit with, as `--commit` needs.
+fn check_git() -> Diagnosis {
+    let check = t!("doctor-git");
+    let Some(workdir) = repository_workdir(Path::new(".")) else {
+        return Diagnosis::new(check, Health::Warning, t!("doctor-git-no-repository"))
+            .with_fix(t!("doctor-fix-git-repository"));
+    };
+    let identity = GitConfig::load();
+    if identity.username.is_empty() || identity.email.is_empty() {
+        return Diagnosis::new(check, Health::Warning, t!("doctor-git-no-identity"))
+            .with_fix(t!("doctor-fix-git-identity"));
+    }
+    Diagnosis::new(
+        check,
+        Health::Ok,
+        t!(
+            "doctor-git-ok",
+            dir = workdir.display().to_string(),
+            name = identity.username
+        ),
+    )
+}
+
+/// Checks whether prompts and colors are available.
+fn check_terminal() -> Diagnosis {
+    let check = t!("doctor-terminal");
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Diagnosis::new(check, Health::Warning, t!("doctor-terminal-none"))
+            .with_fix(t!("doctor-fix-terminal"));
+    }
+    let term = Term::stdout();
+    let (_, columns) = term.size();
+    let colors = term.features().colors_supported() && env::var_os("NO_COLOR").is_none();
+    let detail = t!(
+        "doctor-terminal-ok",
+        columns = columns,
+        colors = if colors { "yes" } else { "no" }
+    );
+    Diagnosis::new(check, Health::Ok, detail)
+}
+
+/// Prints each diagnosis with its fix, if any.
+pub(crate) fn display_diagnoses(diagnoses: &[Diagnosis]) {
+    for diagnosis in diagnoses {
+        match diagnosis.health {
+            Health::Ok => {
+                success!(
+                    "<green>✔</> <bold>{}</> {}",
+                    diagnosis.check,
+                    diagnosis.detail
+                )
+            }
+            Health::Warning => {
+                cprintln!(
+                    "<y, bold>\u{f421}</> <bold>{}</> {}",
+                    diagnosis.check,
+                    diagnosis.detail
+                )
+            }
+            Health::Failure => {
+                cprintln!(
+                    "<red>✘</> <bold>{}</> {}",
+                    diagnosis.check,
+                    diagnosis.detail
+                )
+            }
+        }
+        if let Some(fix) = &diagnosis.fix {
+            cprintln!("  <dim>→ {}</>", fix);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::format_size;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+}
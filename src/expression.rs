@@ -0,0 +1,511 @@
+use std::{
+    fmt::{self, Display},
+    iter::Peekable,
+    str::FromStr,
+    vec::IntoIter,
+};
+
+use crate::{i18n::t, spdx::License, util::errors::Error};
+
+/// A parsed SPDX license expression, such as
+/// `MIT OR (Apache-2.0 AND GPL-2.0-or-later WITH
+/// Classpath-exception-2.0)`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expression {
+    /// A single license.
+    License(LicenseTerm),
+    /// Expressions that must all be satisfied.
+    And(Vec<Expression>),
+    /// Alternatives of which one must be satisfied.
+    Or(Vec<Expression>),
+}
+
+/// A license of an expression, with its optional `+` and exception.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LicenseTerm {
+    /// The license ID, or a `LicenseRef-` reference.
+    pub(crate) id: String,
+    /// Whether later versions are accepted, written as a trailing
+    /// `+`.
+    pub(crate) or_later: bool,
+    /// The exception given with `WITH`, if any.
+    pub(crate) exception: Option<String>,
+}
+
+impl Expression {
+    /// Rewrites the expression in canonical form.
+    ///
+    /// License IDs get the casing of the SPDX list, deprecated GNU
+    /// IDs such as `GPL-2.0` or `GPL-2.0+` become their `-only` or
+    /// `-or-later` forms, and duplicate operands are dropped.
+    /// Redundant parentheses disappear when the result is displayed.
+    ///
+    /// # Errors
+    /// - `Error::UnknownLicenses` listing the IDs missing from the
+    ///   SPDX list; `LicenseRef-` references are always accepted.
+    pub fn canonicalize(
+        self,
+        licenses: &[License],
+    ) -> Result<Self, Error> {
+        let mut unknown = Vec::new();
+        let canonical = self.map_terms(&mut |term| {
+            canonicalize_term(term, licenses).unwrap_or_else(|term| {
+                unknown.push(term.id.clone());
+                term
+            })
+        });
+        if unknown.is_empty() {
+            Ok(canonical)
+        } else {
+            Err(Error::UnknownLicenses {
+                ids: unknown.join(", "),
+            })
+        }
+    }
+
+    /// Parses license arguments as an expression when they use
+    /// operators or parentheses, such as `MIT OR Apache-2.0` given as
+    /// one argument or as several.
+    ///
+    /// # Returns
+    ///
+    /// `None` for plain license IDs.
+    ///
+    /// # Errors
+    /// - `Error::InvalidExpression` if the expression is malformed.
+    pub fn from_arguments(arguments: &[String]) -> Result<Option<Self>, Error> {
+        let joined = arguments.join(" ");
+        let is_expression = joined.contains(['(', ')'])
+            || joined
+                .split_whitespace()
+                .any(|word| matches!(word.to_uppercase().as_str(), "AND" | "OR" | "WITH"));
+        if is_expression {
+            joined.parse().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the licenses of the expression, from left to right.
+    pub fn terms(&self) -> Vec<&LicenseTerm> {
+        match self {
+            Expression::License(term) => vec![term],
+            Expression::And(operands) | Expression::Or(operands) => {
+                operands.iter().flat_map(Expression::terms).collect()
+            }
+        }
+    }
+
+    /// Applies `f` to every license of the expression, dropping
+    /// operands that become duplicates.
+    fn map_terms(
+        self,
+        f: &mut impl FnMut(LicenseTerm) -> LicenseTerm,
+    ) -> Self {
+        let mut map_operands = |operands: Vec<Expression>| {
+            let mut mapped: Vec<Expression> = Vec::with_capacity(operands.len());
+            for operand in operands {
+                let operand = operand.map_terms(f);
+                if !mapped.contains(&operand) {
+                    mapped.push(operand);
+                }
+            }
+            mapped
+        };
+        match self {
+            Expression::License(term) => Expression::License(f(term)),
+            Expression::And(operands) => join(map_operands(operands), true),
+            Expression::Or(operands) => join(map_operands(operands), false),
+        }
+    }
+}
+
+/// Resolves a license against the SPDX list.
+///
+/// # Errors
+/// - The unchanged term if its ID is not in the list.
+fn canonicalize_term(
+    term: LicenseTerm,
+    licenses: &[License],
+) -> Result<LicenseTerm, LicenseTerm> {
+    if term.id.starts_with("LicenseRef-") || term.id.starts_with("DocumentRef-") {
+        return Ok(term);
+    }
+    let find = |id: &str| {
+        licenses
+            .iter()
+            .find(|license| license.id.eq_ignore_ascii_case(id))
+    };
+    let Some(license) = find(&term.id) else {
+        return Err(term);
+    };
+    let id = license.id.as_str();
+    // GNU licenses spell the "or later" choice out in their IDs.
+    let base = id
+        .strip_suffix("-only")
+        .or_else(|| id.strip_suffix("-or-later"))
+        .filter(|_| term.or_later)
+        .unwrap_or(id);
+    let suffix = if term.or_later { "-or-later" } else { "-only" };
+    let (id, or_later) = match find(&format!("{}{}", base, suffix)) {
+        Some(gnu) => (gnu.id.clone(), false),
+        None => (id.to_string(), term.or_later),
+    };
+    Ok(LicenseTerm {
+        id,
+        or_later,
+        exception: term.exception,
+    })
+}
+
+/// A lexical token of a license expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    With,
+    Word(String),
+}
+
+impl Display for Token {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+            Token::And => write!(f, "AND"),
+            Token::Or => write!(f, "OR"),
+            Token::With => write!(f, "WITH"),
+            Token::Word(word) => write!(f, "{}", word),
+        }
+    }
+}
+
+impl FromStr for Expression {
+    type Err = Error;
+
+    /// Parses an expression, where `WITH` binds tighter than `AND`,
+    /// which binds tighter than `OR`.
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: String| {
+            Error::InvalidExpression {
+                expression: expression.to_string(),
+                reason,
+            }
+        };
+        let mut tokens = tokenize(expression).into_iter().peekable();
+        let parsed = parse_or(&mut tokens).map_err(invalid)?;
+        match tokens.next() {
+            Some(token) => Err(invalid(t!("expression-unexpected-token", token = token))),
+            None => Ok(parsed),
+        }
+    }
+}
+
+impl Display for LicenseTerm {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(f, "{}", self.id)?;
+        if self.or_later {
+            write!(f, "+")?;
+        }
+        if let Some(exception) = &self.exception {
+            write!(f, " WITH {}", exception)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Expression {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Expression::License(term) => write!(f, "{}", term),
+            Expression::And(operands) => {
+                let operands: Vec<String> = operands
+                    .iter()
+                    .map(|operand| {
+                        match operand {
+                            Expression::Or(_) => format!("({})", operand),
+                            _ => operand.to_string(),
+                        }
+                    })
+                    .collect();
+                write!(f, "{}", operands.join(" AND "))
+            }
+            Expression::Or(operands) => {
+                let operands: Vec<String> = operands.iter().map(ToString::to_string).collect();
+                write!(f, "{}", operands.join(" OR "))
+            }
+        }
+    }
+}
+
+/// Splits an expression into parentheses, operators and words.
+///
+/// Operators are recognized in any case.
+fn tokenize(expression: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(match word.to_uppercase().as_str() {
+                    "AND" => Token::And,
+                    "OR" => Token::Or,
+                    "WITH" => Token::With,
+                    _ => Token::Word(word),
+                });
+            }
+        }
+    }
+    tokens
+}
+
+type Tokens = Peekable<IntoIter<Token>>;
+
+/// Parses alternatives separated by `OR`.
+fn parse_or(tokens: &mut Tokens) -> Result<Expression, String> {
+    let mut operands = vec![parse_and(tokens)?];
+    while tokens.next_if_eq(&Token::Or).is_some() {
+        operands.push(parse_and(tokens)?);
+    }
+    Ok(join(operands, false))
+}
+
+/// Parses operands separated by `AND`.
+fn parse_and(tokens: &mut Tokens) -> Result<Expression, String> {
+    let mut operands = vec![parse_atom(tokens)?];
+    while tokens.next_if_eq(&Token::And).is_some() {
+        operands.push(parse_atom(tokens)?);
+    }
+    Ok(join(operands, true))
+}
+
+/// Parses a parenthesized expression or a license with its optional
+/// exception.
+fn parse_atom(tokens: &mut Tokens) -> Result<Expression, String> {
+    match tokens.next() {
+        Some(Token::Open) => {
+            let inner = parse_or(tokens)?;
+            match tokens.next() {
+                Some(Token::Close) => Ok(inner),
+                Some(token) => Err(t!("expression-unexpected-token", token = token)),
+                None => Err(t!("expression-unexpected-end")),
+            }
+        }
+        Some(Token::Word(word)) => {
+            let (id, or_later) = match word.strip_suffix('+') {
+                Some(id) => (id.to_string(), true),
+                None => (word, false),
+            };
+            validate_id(&id)?;
+            let exception = match tokens.next_if_eq(&Token::With) {
+                Some(_) => {
+                    match tokens.next() {
+                        Some(Token::Word(exception)) => {
+                            validate_id(&exception)?;
+                            Some(exception)
+                        }
+                        Some(token) => {
+                            return Err(t!("expression-unexpected-token", token = token))
+                        }
+                        None => return Err(t!("expression-unexpected-end")),
+                    }
+                }
+                None => None,
+            };
+            Ok(Expression::License(LicenseTerm {
+                id,
+                or_later,
+                exception,
+            }))
+        }
+        Some(token) => Err(t!("expression-unexpected-token", token = token)),
+        None => Err(t!("expression-unexpected-end")),
+    }
+}
+
+/// Builds an `AND` or `OR` node, unwrapping single operands and
+/// merging nested nodes of the same operator.
+fn join(
+    operands: Vec<Expression>,
+    and: bool,
+) -> Expression {
+    if operands.len() == 1 {
+        return operands.into_iter().next().expect("one operand");
+    }
+    let merged = operands
+        .into_iter()
+        .flat_map(|operand| {
+            match operand {
+                Expression::And(inner) if and => inner,
+                Expression::Or(inner) if !and => inner,
+                operand => vec![operand],
+            }
+        })
+        .collect();
+    if and {
+        Expression::And(merged)
+    } else {
+        Expression::Or(merged)
+    }
+}
+
+/// Checks that an ID only uses the characters allowed by SPDX.
+fn validate_id(id: &str) -> Result<(), String> {
+    if !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | ':'))
+    {
+        Ok(())
+    } else {
+        Err(t!("expression-invalid-id", id = id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{Expression, LicenseTerm};
+    use crate::spdx::License;
+
+    fn license(id: &str) -> Expression {
+        Expression::License(LicenseTerm {
+            id: id.to_string(),
+            or_later: false,
+            exception: None,
+        })
+    }
+
+    #[test]
+    fn test_expression_from_arguments() {
+        let arguments = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+            Expression::from_arguments(&args).map(|parsed| parsed.map(|e| e.to_string()))
+        };
+        assert_eq!(arguments(&["MIT", "ISC"]).unwrap(), None);
+        assert_eq!(
+            arguments(&["MIT OR Apache-2.0"]).unwrap().as_deref(),
+            Some("MIT OR Apache-2.0")
+        );
+        assert_eq!(
+            arguments(&["GPL-2.0-only", "with", "Classpath-exception-2.0"])
+                .unwrap()
+                .as_deref(),
+            Some("GPL-2.0-only WITH Classpath-exception-2.0")
+        );
+        assert!(arguments(&["MIT", "OR"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        let parsed: Expression = "MIT or (Apache-2.0 AND GPL-2.0+ WITH Classpath-exception-2.0) \
+                                  OR ISC"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            parsed,
+            Expression::Or(vec![
+                license("MIT"),
+                Expression::And(vec![
+                    license("Apache-2.0"),
+                    Expression::License(LicenseTerm {
+                        id: "GPL-2.0".to_string(),
+                        or_later: true,
+                        exception: Some("Classpath-exception-2.0".to_string()),
+                    }),
+                ]),
+                license("ISC"),
+            ])
+        );
+        assert_eq!(
+            parsed.to_string(),
+            "MIT OR Apache-2.0 AND GPL-2.0+ WITH Classpath-exception-2.0 OR ISC"
+        );
+        assert_eq!(
+            "(MIT OR ISC) AND Zlib"
+                .parse::<Expression>()
+                .unwrap()
+                .to_string(),
+            "(MIT OR ISC) AND Zlib"
+        );
+        assert!("MIT OR".parse::<Expression>().is_err());
+        assert!("(MIT".parse::<Expression>().is_err());
+        assert!("MIT Apache-2.0".parse::<Expression>().is_err());
+        assert!("MIT/Apache-2.0".parse::<Expression>().is_err());
+    }
+
+    #[test]
+    fn test_canonicalize_expression() {
+        let licenses: Vec<License> = [
+            "MIT",
+            "Apache-2.0",
+            "GPL-2.0",
+            "GPL-2.0+",
+            "GPL-2.0-only",
+            "GPL-2.0-or-later",
+        ]
+        .into_iter()
+        .map(|id| {
+            License {
+                is_deprecated_license_id: false,
+                details_url: String::new(),
+                name: id.to_string(),
+                id: id.to_string(),
+                is_osi_approved: Some(true),
+                is_fsf_libre: None,
+            }
+        })
+        .collect();
+        let canonicalize = |expression: &str| {
+            expression
+                .parse::<Expression>()
+                .unwrap()
+                .canonicalize(&licenses)
+                .map(|expression| expression.to_string())
+        };
+        assert_eq!(
+            canonicalize("(mit OR (gpl-2.0 OR MIT)) and (Apache-2.0+)").unwrap(),
+            "(MIT OR GPL-2.0-only) AND Apache-2.0+"
+        );
+        assert_eq!(
+            canonicalize("GPL-2.0+ OR GPL-2.0-only+ OR GPL-2.0-or-later").unwrap(),
+            "GPL-2.0-or-later"
+        );
+        assert_eq!(
+            canonicalize("LicenseRef-Custom AND GPL-2.0-only").unwrap(),
+            "LicenseRef-Custom AND GPL-2.0-only"
+        );
+        assert!(canonicalize("MIT OR Unknown-1.0").is_err());
+    }
+}
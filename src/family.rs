@@ -0,0 +1,163 @@
+use std::io::{self, BufWriter, Write};
+
+use color_print::cformat;
+
+use crate::{
+    i18n::t,
+    spdx::{License, Licenses},
+    util::errors::{Error, LictoolResult},
+};
+
+/// A license of a family with the attributes compared across its
+/// variants.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Variant<'a> {
+    pub(crate) license: &'a License,
+    /// The version in the license ID, such as `2.0` for
+    /// `GPL-2.0-only`.
+    pub(crate) version: Option<&'a str>,
+    /// Whether later versions are accepted, for IDs spelling it out
+    /// with `-only`, `-or-later` or `+`.
+    pub(crate) or_later: Option<bool>,
+}
+
+impl<'a> Variant<'a> {
+    fn new(license: &'a License) -> Self {
+        let id = license.id.as_str();
+        let or_later = if id.ends_with("-or-later") || id.ends_with('+') {
+            Some(true)
+        } else if id.ends_with("-only") {
+            Some(false)
+        } else {
+            None
+        };
+        let version = id.split('-').find(|part| {
+            part.trim_end_matches('+')
+                .split('.')
+                .all(|number| !number.is_empty() && number.chars().all(|c| c.is_ascii_digit()))
+        });
+        Self {
+            license,
+            version: version.map(|version| version.trim_end_matches('+')),
+            or_later,
+        }
+    }
+}
+
+/// Collects the licenses whose ID starts with `prefix`, ignoring
+/// case, sorted by ID.
+///
+/// # Errors
+/// - `Error::EmptyFamily` if no license ID starts with `prefix`.
+pub(crate) fn family<'a>(
+    licenses: &'a Licenses,
+    prefix: &str,
+) -> Result<Vec<Variant<'a>>, Error> {
+    let lowercase = prefix.to_lowercase();
+    let mut variants: Vec<Variant> = licenses
+        .body
+        .iter()
+        .filter(|license| license.id.to_lowercase().starts_with(&lowercase))
+        .map(Variant::new)
+        .collect();
+    if variants.is_empty() {
+        return Err(Error::EmptyFamily {
+            prefix: prefix.to_string(),
+        });
+    }
+    variants.sort_by(|a, b| a.license.id.cmp(&b.license.id));
+    Ok(variants)
+}
+
+/// Prints the variants of a family as a comparison table.
+pub(crate) fn display_family(variants: &[Variant]) -> LictoolResult<()> {
+    let yes_no = |value: bool| if value { "✔" } else { "✘" };
+    let rows: Vec<[String; 6]> = variants
+        .iter()
+        .map(|variant| {
+            let license = variant.license;
+            [
+                license.id.clone(),
+                variant.version.unwrap_or("—").to_string(),
+                match variant.or_later {
+                    Some(true) => t!("family-or-later"),
+                    Some(false) => t!("family-only"),
+                    None => "—".to_string(),
+                },
+                license.is_osi_approved.map_or("—", yes_no).to_string(),
+                license.is_fsf_libre.map_or("—", yes_no).to_string(),
+                yes_no(license.is_deprecated_license_id).to_string(),
+            ]
+        })
+        .collect();
+    let header = [
+        t!("family-id"),
+        t!("family-version"),
+        t!("family-variant"),
+        t!("family-osi"),
+        t!("family-fsf"),
+        t!("family-deprecated"),
+    ];
+    let mut widths = header.each_ref().map(|cell| cell.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let pad = |row: &[String; 6]| {
+        row.iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    let mut out = BufWriter::new(io::stdout().lock());
+    writeln!(out, "{}", cformat!("<bold>{}</>", pad(&header)))?;
+    for (row, variant) in rows.iter().zip(variants) {
+        if variant.license.is_deprecated_license_id {
+            writeln!(out, "{}", cformat!("<dim>{}</>", pad(row)))?;
+        } else {
+            writeln!(out, "{}", pad(row))?;
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::Variant;
+    use crate::spdx::License;
+
+    #[test]
+    fn test_variant_attributes() {
+        let variant = |id: &str| {
+            let license = License {
+                is_deprecated_license_id: false,
+                details_url: String::new(),
+                name: id.to_string(),
+                id: id.to_string(),
+                is_osi_approved: Some(false),
+                is_fsf_libre: None,
+            };
+            let variant = Variant::new(&license);
+            (variant.version.map(str::to_string), variant.or_later)
+        };
+        assert_eq!(
+            variant("GPL-2.0-or-later"),
+            (Some("2.0".to_string()), Some(true))
+        );
+        assert_eq!(variant("GPL-3.0+"), (Some("3.0".to_string()), Some(true)));
+        assert_eq!(
+            variant("LGPL-2.1-only"),
+            (Some("2.1".to_string()), Some(false))
+        );
+        assert_eq!(variant("CC-BY-SA-4.0"), (Some("4.0".to_string()), None));
+        assert_eq!(variant("BSD-3-Clause"), (Some("3".to_string()), None));
+        assert_eq!(variant("BSD-Protection"), (None, None));
+    }
+}
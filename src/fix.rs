@@ -0,0 +1,246 @@
+use std::fs;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    check::{HeaderConflict, MisnamedLicenseFile, PlaceholderViolation},
+    i18n::t,
+    template::{Field, Template},
+    util::defaults::FieldDefaults,
+};
+
+/// The formats findings of `check` are printed in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub(crate) enum CheckFormat {
+    /// Colored findings for reading in a terminal.
+    #[default]
+    Text,
+    /// A JSON array of findings with the text edits fixing them, for
+    /// editor plugins and codemod tools.
+    FixJson,
+}
+
+/// A position in a file, with the line and the column in characters
+/// both starting at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub(crate) struct Location {
+    pub(crate) line: usize,
+    pub(crate) column: usize,
+}
+
+/// The text between two locations, the end being exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub(crate) struct TextRange {
+    pub(crate) start: Location,
+    pub(crate) end: Location,
+}
+
+/// Replaces a range of a file with new text.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct TextEdit {
+    pub(crate) file: String,
+    pub(crate) range: TextRange,
+    pub(crate) replacement: String,
+}
+
+/// A finding of `check` with the edits fixing it, which are empty
+/// when lictool cannot tell the fix, such as a placeholder without a
+/// default value.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Fix {
+    /// The check that found the problem: `unfilled-placeholder`,
+    /// `header-conflict` or `license-drift`.
+    pub(crate) check: &'static str,
+    pub(crate) file: String,
+    /// The line of the finding, starting at 1.
+    pub(crate) line: usize,
+    pub(crate) message: String,
+    pub(crate) edits: Vec<TextEdit>,
+}
+
+/// Describes how to fill each unfilled placeholder with the default
+/// value of its field.
+pub(crate) fn placeholder_fixes(
+    violations: &[PlaceholderViolation],
+    defaults: &FieldDefaults,
+) -> Vec<Fix> {
+    violations
+        .iter()
+        .map(|violation| {
+            let file = violation.path.display().to_string();
+            let value = Field::ALL
+                .into_iter()
+                .find(|field| field.placeholders().contains(&violation.placeholder))
+                .and_then(|field| defaults.field(field));
+            let line = fs::read_to_string(&violation.path)
+                .ok()
+                .and_then(|content| content.lines().nth(violation.line - 1).map(str::to_string))
+                .unwrap_or_default();
+            let edits = value
+                .map(|value| {
+                    line.match_indices(violation.placeholder)
+                        .map(|(offset, placeholder)| {
+                            let column = line[..offset].chars().count() + 1;
+                            TextEdit {
+                                file: file.clone(),
+                                range: TextRange {
+                                    start: Location {
+                                        line: violation.line,
+                                        column,
+                                    },
+                                    end: Location {
+                                        line: violation.line,
+                                        column: column + placeholder.chars().count(),
+                                    },
+                                },
+                                replacement: value.clone(),
+                            }
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Fix {
+                check: "unfilled-placeholder",
+                line: violation.line,
+                message: t!(
+                    "check-unfilled-placeholder",
+                    placeholder = violation.placeholder
+                ),
+                file,
+                edits,
+            }
+        })
+        .collect()
+}
+
+/// Lists header conflicts as findings without edits, since either the
+/// header or the `[paths]` mapping may be the one to change.
+pub(crate) fn header_conflict_fixes(conflicts: &[HeaderConflict]) -> Vec<Fix> {
+    conflicts
+        .iter()
+        .map(|conflict| {
+            Fix {
+                check: "header-conflict",
+                file: conflict.path.display().to_string(),
+                line: conflict.line,
+                message: t!(
+                    "check-header-conflict",
+                    declared = conflict.declared.as_str(),
+                    expected = conflict.expected.as_str()
+                ),
+                edits: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// Lists misnamed license files as findings without edits, since
+/// renaming a file is no text edit; `check --fix` renames them.
+pub(crate) fn misnamed_license_fixes(files: &[MisnamedLicenseFile]) -> Vec<Fix> {
+    files
+        .iter()
+        .map(|file| {
+            Fix {
+                check: "license-filename",
+                file: file.path.display().to_string(),
+                line: 1,
+                message: file.message(),
+                edits: Vec::new(),
+            }
+        })
+        .collect()
+}
+
+/// Describes how to replace a drifted license file with the canonical
+/// text, its placeholders filled with the default values.
+pub(crate) fn drift_fix(
+    file: &str,
+    actual: &str,
+    canonical: &str,
+    license_id: &str,
+    defaults: &FieldDefaults,
+) -> Fix {
+    let template = Template {
+        license_text: canonical.to_string(),
+        year: defaults.year.clone(),
+        owner: defaults.owner.clone(),
+        repo: defaults.repo.clone(),
+        email: defaults.email.clone(),
+        ..Default::default()
+    };
+    Fix {
+        check: "license-drift",
+        file: file.to_string(),
+        line: 1,
+        message: t!("error-license-drift", file = file, license = license_id),
+        edits: vec![TextEdit {
+            file: file.to_string(),
+            range: TextRange {
+                start: Location {
+                    line: 1,
+                    column: 1,
+                },
+                end: end_of(actual),
+            },
+            replacement: template.render(),
+        }],
+    }
+}
+
+/// Returns the location just past the end of a text.
+fn end_of(text: &str) -> Location {
+    let line = text.matches('\n').count() + 1;
+    let last = text.rsplit('\n').next().unwrap_or_default();
+    Location {
+        line,
+        column: last.chars().count() + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::{end_of, placeholder_fixes, Location};
+    use crate::{check::find_unfilled_placeholders, util::defaults::FieldDefaults};
+
+    #[test]
+    fn test_placeholder_fixes() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("LICENSE");
+        fs::write(&path, "MIT License\n\nCopyright (c) [yyyy] [fullname]\n").unwrap();
+        let violations = find_unfilled_placeholders(&[path]).violations;
+        let defaults = FieldDefaults {
+            year: Some("2026".to_string()),
+            ..Default::default()
+        };
+        let fixes = placeholder_fixes(&violations, &defaults);
+        let edits: Vec<_> = fixes
+            .iter()
+            .map(|fix| {
+                fix.edits
+                    .iter()
+                    .map(|edit| {
+                        (
+                            edit.range.start.column,
+                            edit.range.end.column,
+                            edit.replacement.as_str(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(edits, [vec![], vec![(15, 21, "2026")]]);
+        assert_eq!(
+            end_of("one\ntwo\n"),
+            Location {
+                line: 3,
+                column: 1
+            }
+        );
+    }
+}
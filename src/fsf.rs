@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    i18n::t,
+    util::{errors::LictoolResult, http},
+};
+
+/// The FSF license list API, keyed by SPDX ID.
+const FSF_API_BASE_URL: &str = "https://spdx.github.io/fsf-api";
+
+/// A license as categorized by the FSF license list.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FsfLicense {
+    /// The FSF's own identifier of the license.
+    pub id: String,
+    /// The name of the license on the FSF list.
+    pub name: Option<String>,
+    /// The FSF categories of the license, such as `libre`,
+    /// `non-free` or `gpl-3-compatible`.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl FsfLicense {
+    /// Fetches the FSF entry of a license.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the FSF list does not cover the license.
+    pub async fn fetch(spdx_id: &str) -> LictoolResult<Option<Self>> {
+        fetch_fsf_license(FSF_API_BASE_URL, spdx_id).await
+    }
+
+    /// Returns the translated FSF categories of the license, skipping
+    /// tags that only describe the list itself.
+    pub fn categories(&self) -> Vec<String> {
+        self.tags
+            .iter()
+            .filter_map(|tag| {
+                match tag.as_str() {
+                    "libre" => Some(t!("fsf-free")),
+                    "non-free" => Some(t!("fsf-nonfree")),
+                    "gpl-2-compatible" => Some(t!("fsf-gpl-2-compatible")),
+                    "gpl-3-compatible" => Some(t!("fsf-gpl-3-compatible")),
+                    "fdl-compatible" => Some(t!("fsf-fdl-compatible")),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Fetches the FSF entry of a license from a given base URL.
+async fn fetch_fsf_license(
+    base_url: &str,
+    spdx_id: &str,
+) -> LictoolResult<Option<FsfLicense>> {
+    http::get_optional_json(&format!("{}/spdx/{}.json", base_url, spdx_id)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{fetch_fsf_license, FsfLicense};
+
+    #[tokio::test]
+    async fn test_fetch_fsf_license() {
+        let mut server = mockito::Server::new_async().await;
+        let found = server
+            .mock("GET", "/spdx/Apache-2.0.json")
+            .with_body(
+                r#"{"id": "apache2", "name": "Apache License, Version 2.0",
+                    "identifiers": {"spdx": ["Apache-2.0"]},
+                    "tags": ["gpl-3-compatible", "libre"]}"#,
+            )
+            .create_async()
+            .await;
+        let missing = server
+            .mock("GET", "/spdx/BSD-4.3TAHOE.json")
+            .with_status(404)
+            .create_async()
+            .await;
+
+        let license = fetch_fsf_license(&server.url(), "Apache-2.0")
+            .await
+            .unwrap();
+        assert_eq!(
+            license,
+            Some(FsfLicense {
+                id: "apache2".to_string(),
+                name: Some("Apache License, Version 2.0".to_string()),
+                tags: vec!["gpl-3-compatible".to_string(), "libre".to_string()],
+            })
+        );
+        assert_eq!(
+            fetch_fsf_license(&server.url(), "BSD-4.3TAHOE")
+                .await
+                .unwrap(),
+            None
+        );
+        found.assert_async().await;
+        missing.assert_async().await;
+    }
+}
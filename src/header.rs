@@ -0,0 +1,811 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use color_print::cprintln;
+use serde::Deserialize;
+
+use crate::{
+    i18n::t,
+    util::{
+        errors::{Error, LictoolResult},
+        line_ending::LineEnding,
+        output::success,
+    },
+};
+
+/// The byte order mark some UTF-8 files start with.
+const BOM: &str = "\u{feff}";
+
+/// The opening, line prefix and closing delimiters of a block
+/// comment.
+type BlockDelimiters = [&'static str; 3];
+
+/// Line comments, and block comments as opening, line prefix and
+/// closing delimiters, for the file extensions sharing them.
+///
+/// Names such as `Makefile` are matched when a file has no
+/// extension.
+const DEFAULT_COMMENT_STYLES: [(&[&str], Option<&str>, Option<BlockDelimiters>); 12] = [
+    (&["c", "h"], Some("//"), Some(["/*", " *", " */"])),
+    (
+        &[
+            "rs", "cpp", "cc", "cxx", "hpp", "java", "js", "jsx", "mjs", "ts", "tsx", "go", "kt",
+            "kts", "swift", "scala", "cs", "dart", "php", "groovy", "zig",
+        ],
+        Some("//"),
+        Some(["/*", " *", " */"]),
+    ),
+    (
+        &[
+            "py",
+            "sh",
+            "bash",
+            "zsh",
+            "fish",
+            "rb",
+            "pl",
+            "r",
+            "toml",
+            "yaml",
+            "yml",
+            "ps1",
+            "nix",
+            "cmake",
+            "Makefile",
+            "Dockerfile",
+        ],
+        Some("#"),
+        None,
+    ),
+    (&["sql", "lua", "hs", "elm"], Some("--"), None),
+    (&["el", "lisp", "clj", "scm"], Some(";;"), None),
+    (&["tex", "erl", "m"], Some("%"), None),
+    (&["vim"], Some("\""), None),
+    (&["bat", "cmd"], Some("::"), None),
+    (&["css", "scss", "less"], None, Some(["/*", " *", " */"])),
+    (
+        &["html", "htm", "xml", "svg", "md", "vue"],
+        None,
+        Some(["<!--", "", "-->"]),
+    ),
+    (&["ml", "mli"], None, Some(["(*", " *", " *)"])),
+    (&["f90", "f95"], Some("!"), None),
+];
+
+/// The extensions whose headers default to block comments even
+/// though line comments exist.
+const PREFER_BLOCK: [&str; 2] = ["c", "h"];
+
+/// How many lines from the top of a file are searched for a header.
+const HEADER_SEARCH_LINES: usize = 20;
+
+/// The marker opening an SPDX snippet.
+const SNIPPET_BEGIN: &str = "SPDX-SnippetBegin";
+/// The marker closing an SPDX snippet.
+const SNIPPET_END: &str = "SPDX-SnippetEnd";
+
+/// Settings of the `[header]` configuration section.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct HeaderConfig {
+    /// Comment styles keyed by file extension, such as
+    /// `[header.languages.py]`, overriding or extending the built-in
+    /// ones field by field.
+    pub(crate) languages: BTreeMap<String, CommentStyle>,
+    /// The wording of headers, replacing the SPDX tags. See
+    /// `HeaderVars` for the variables it may use.
+    pub(crate) template: Option<String>,
+}
+
+/// How a language writes comments.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct CommentStyle {
+    /// Starts a line comment, such as `//` or `#`.
+    pub(crate) line: Option<String>,
+    /// Opens, prefixes each line of and closes a block comment, such
+    /// as `["/*", " *", " */"]`.
+    pub(crate) block: Option<[String; 3]>,
+    /// Whether headers use a block comment even when the language
+    /// has line comments.
+    pub(crate) prefer_block: Option<bool>,
+    /// The wording of headers in this language, taking precedence
+    /// over the global template.
+    pub(crate) template: Option<String>,
+}
+
+/// The values of the `{{year}}`, `{{owner}}`, `{{spdx_id}}` and
+/// `{{filename}}` variables of a header template.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct HeaderVars<'a> {
+    pub(crate) spdx_id: &'a str,
+    pub(crate) year: &'a str,
+    pub(crate) owner: Option<&'a str>,
+    /// The name of the file, without its directory.
+    pub(crate) filename: &'a str,
+}
+
+impl CommentStyle {
+    /// Wraps the lines of a header in a comment, followed by a blank
+    /// line.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the style defines no comment syntax.
+    pub(crate) fn comment(
+        &self,
+        lines: &[String],
+    ) -> Option<String> {
+        let prefixed = |prefix: &str| {
+            lines
+                .iter()
+                .map(|line| {
+                    if line.is_empty() || prefix.is_empty() {
+                        format!("{}{}\n", prefix, line)
+                    } else {
+                        format!("{} {}\n", prefix, line)
+                    }
+                })
+                .collect::<String>()
+        };
+        let block = || {
+            self.block
+                .as_ref()
+                .map(|[open, prefix, close]| format!("{}\n{}{}\n\n", open, prefixed(prefix), close))
+        };
+        if self.prefer_block.unwrap_or(false) {
+            block().or_else(|| self.line.as_deref().map(|line| prefixed(line) + "\n"))
+        } else {
+            self.line
+                .as_deref()
+                .map(|line| prefixed(line) + "\n")
+                .or_else(block)
+        }
+    }
+}
+
+impl HeaderConfig {
+    /// Returns the comment style of a file, from its extension or,
+    /// without one, its name.
+    ///
+    /// # Returns
+    ///
+    /// `None` if neither the configuration nor the built-in styles
+    /// cover the file.
+    pub(crate) fn comment_style(
+        &self,
+        path: &Path,
+    ) -> Option<CommentStyle> {
+        let key = language_key(path)?;
+        let builtin = DEFAULT_COMMENT_STYLES
+            .iter()
+            .find(|(keys, ..)| keys.contains(&key.as_ref()))
+            .map(|(_, line, block)| {
+                CommentStyle {
+                    line: line.map(str::to_string),
+                    block: block.map(|delimiters| delimiters.map(str::to_string)),
+                    prefer_block: Some(PREFER_BLOCK.contains(&key.as_ref())),
+                    template: None,
+                }
+            });
+        match (self.languages.get(key.as_ref()), builtin) {
+            (Some(configured), Some(builtin)) => {
+                Some(CommentStyle {
+                    line: configured.line.clone().or(builtin.line),
+                    block: configured.block.clone().or(builtin.block),
+                    prefer_block: configured.prefer_block.or(builtin.prefer_block),
+                    template: configured.template.clone(),
+                })
+            }
+            (configured, builtin) => configured.cloned().or(builtin),
+        }
+    }
+
+    /// Returns the lines of the header of a file, from the template
+    /// of its language, the global template, or the SPDX tags.
+    pub(crate) fn header_lines(
+        &self,
+        path: &Path,
+        vars: &HeaderVars,
+    ) -> Vec<String> {
+        let template = language_key(path)
+            .and_then(|key| self.languages.get(key.as_ref()))
+            .and_then(|style| style.template.as_deref())
+            .or(self.template.as_deref());
+        match template {
+            Some(template) => render_header(template, vars),
+            None => header_lines(vars.spdx_id, vars.year, vars.owner),
+        }
+    }
+
+    /// Checks whether a file that can hold a header has an SPDX one.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no comment style covers the file or it cannot be
+    /// read, such as after it was deleted.
+    pub(crate) fn has_header(
+        &self,
+        path: &Path,
+    ) -> Option<bool> {
+        self.comment_style(path)?;
+        let content = fs::read_to_string(path).ok()?;
+        Some(has_spdx_header(&content))
+    }
+}
+
+/// Checks whether a file starts with an SPDX header, within its first
+/// `HEADER_SEARCH_LINES` lines.
+pub(crate) fn has_spdx_header(content: &str) -> bool {
+    spdx_header_license(content).is_some()
+}
+
+/// Returns the license expression of the SPDX header of a file, such
+/// as `MIT` for `// SPDX-License-Identifier: MIT`.
+pub(crate) fn spdx_header_license(content: &str) -> Option<&str> {
+    spdx_header_line(content).map(|(_, expression)| expression)
+}
+
+/// Returns the first `SPDX-License-Identifier` expression anywhere in
+/// a file, such as the one closing a license stub.
+pub(crate) fn declared_expression(content: &str) -> Option<&str> {
+    content.lines().find_map(identifier_expression)
+}
+
+/// Returns the line of the SPDX header of a file, starting at 1, with
+/// its license expression.
+///
+/// Identifiers inside SPDX snippets belong to the snippet rather than
+/// the file, and are skipped.
+pub(crate) fn spdx_header_line(content: &str) -> Option<(usize, &str)> {
+    let mut in_snippet = false;
+    content
+        .lines()
+        .take(HEADER_SEARCH_LINES)
+        .enumerate()
+        .find_map(|(idx, line)| {
+            if comment_tag(line, SNIPPET_BEGIN).is_some() {
+                in_snippet = true;
+            } else if comment_tag(line, SNIPPET_END).is_some() {
+                in_snippet = false;
+            }
+            if in_snippet {
+                return None;
+            }
+            Some((idx + 1, identifier_expression(line)?))
+        })
+}
+
+/// Replaces the license ID `from` with the expression `to` in the
+/// SPDX header of a file, leaving the rest of the line, such as its
+/// comment delimiters, untouched.
+///
+/// A compound `to` replacing one term of a larger expression is put
+/// in parentheses, keeping the meaning of the rest.
+///
+/// # Errors
+/// - `Error::CompoundBeforeWith` if `to` is compound and `from` is
+///   followed by `WITH`, which only takes a single license.
+///
+/// # Returns
+///
+/// `None` if the file has no SPDX header or its header does not name
+/// `from`.
+pub(crate) fn replace_header_license(
+    content: &str,
+    from: &str,
+    to: &str,
+) -> LictoolResult<Option<String>> {
+    let Some((line, expression)) = spdx_header_line(content) else {
+        return Ok(None);
+    };
+    let compound = to.split_whitespace().nth(1).is_some();
+    let mut replaced = String::with_capacity(expression.len());
+    let mut found = false;
+    let mut rest = expression;
+    while let Some(first) = rest.chars().next() {
+        let end = match rest.find(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+            Some(0) => first.len_utf8(),
+            Some(end) => end,
+            None => rest.len(),
+        };
+        let (token, tail) = rest.split_at(end);
+        if token.eq_ignore_ascii_case(from) {
+            let before_with = tail
+                .split_whitespace()
+                .next()
+                .is_some_and(|next| next.eq_ignore_ascii_case("WITH"));
+            if compound && before_with {
+                Err(Error::CompoundBeforeWith {
+                    expression: to.to_string(),
+                })?
+            }
+            found = true;
+            if compound && token != expression {
+                replaced.push_str(&format!("({})", to));
+            } else {
+                replaced.push_str(to);
+            }
+        } else {
+            replaced.push_str(token);
+        }
+        rest = tail;
+    }
+    if !found {
+        return Ok(None);
+    }
+    let mut output = String::with_capacity(content.len());
+    for (idx, text) in content.split_inclusive('\n').enumerate() {
+        match text.split_once("SPDX-License-Identifier:") {
+            Some((head, tail)) if idx + 1 == line => {
+                output.push_str(head);
+                output.push_str("SPDX-License-Identifier:");
+                output.push_str(&tail.replacen(expression, &replaced, 1));
+            }
+            _ => output.push_str(text),
+        }
+    }
+    Ok(Some(output))
+}
+
+/// Returns the license expression of an `SPDX-License-Identifier`
+/// line, without the closing delimiter of its comment.
+///
+/// Expressions holding quotes are taken to be string literals, such
+/// as `"SPDX-License-Identifier: MIT",`, rather than comments.
+fn identifier_expression(line: &str) -> Option<&str> {
+    let expression = comment_tag(line, "SPDX-License-Identifier:")?
+        .trim()
+        .trim_end_matches("*/")
+        .trim_end_matches("-->")
+        .trim_end();
+    (!expression.contains(['"', '\''])).then_some(expression)
+}
+
+/// Returns the text following `tag` on a line when the tag is in
+/// comment position: at the start of the line or after comment
+/// delimiters only, such as `//` or ` * `, so that tags within code
+/// or string literals are not taken for SPDX tags.
+fn comment_tag<'a>(
+    line: &'a str,
+    tag: &str,
+) -> Option<&'a str> {
+    let (prefix, rest) = line.split_once(tag)?;
+    let mut prefix = prefix.trim();
+    while !prefix.is_empty() {
+        let leader = comment_leaders().find(|leader| prefix.starts_with(leader))?;
+        prefix = prefix[leader.len()..].trim_start();
+    }
+    Some(rest)
+}
+
+/// Returns the delimiters starting comments or their lines in the
+/// built-in comment styles, such as `//`, `/*` and `*`.
+fn comment_leaders() -> impl Iterator<Item = &'static str> {
+    DEFAULT_COMMENT_STYLES
+        .iter()
+        .flat_map(|(_, line, block)| {
+            line.iter().copied().chain(
+                block
+                    .iter()
+                    .flat_map(|[open, prefix, _]| [*open, prefix.trim()]),
+            )
+        })
+        .filter(|leader| !leader.is_empty())
+}
+
+/// A block of a file under a license of its own, such as vendored
+/// code, marked by `SPDX-SnippetBegin` and `SPDX-SnippetEnd` comments
+/// with an `SPDX-License-Identifier` in between.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Snippet {
+    /// The line of `SPDX-SnippetBegin`, starting at 1.
+    pub(crate) begin: usize,
+    /// The line of `SPDX-SnippetEnd`, or `None` if the snippet is
+    /// never closed.
+    pub(crate) end: Option<usize>,
+    /// The license expression of the snippet, if it has one.
+    pub(crate) license: Option<String>,
+}
+
+impl Snippet {
+    /// Describes what is wrong with the markers of the snippet in
+    /// `file`, if anything.
+    pub fn problem(
+        &self,
+        file: &str,
+    ) -> Option<String> {
+        if self.end.is_none() {
+            Some(t!(
+                "header-snippet-unterminated",
+                file = file,
+                line = self.begin
+            ))
+        } else if self.license.is_none() {
+            Some(t!(
+                "header-snippet-no-license",
+                file = file,
+                line = self.begin
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Finds the SPDX snippets of a file.
+///
+/// A snippet begun before the previous one ended is taken to close
+/// it, leaving the previous one unterminated.
+pub(crate) fn spdx_snippets(content: &str) -> Vec<Snippet> {
+    let mut snippets: Vec<Snippet> = Vec::new();
+    let mut open = false;
+    for (idx, line) in content.lines().enumerate() {
+        if comment_tag(line, SNIPPET_BEGIN).is_some() {
+            snippets.push(Snippet {
+                begin: idx + 1,
+                end: None,
+                license: None,
+            });
+            open = true;
+        } else if !open {
+            continue;
+        } else if comment_tag(line, SNIPPET_END).is_some() {
+            if let Some(snippet) = snippets.last_mut() {
+                snippet.end = Some(idx + 1);
+            }
+            open = false;
+        } else if let Some(expression) = identifier_expression(line) {
+            if let Some(snippet) = snippets.last_mut() {
+                snippet
+                    .license
+                    .get_or_insert_with(|| expression.to_string());
+            }
+        }
+    }
+    snippets
+}
+
+/// Finds the snippets whose markers are incomplete, by file.
+pub(crate) fn malformed_snippets(files: &[PathBuf]) -> Vec<(PathBuf, Snippet)> {
+    files
+        .iter()
+        .filter_map(|path| Some((path, fs::read_to_string(path).ok()?)))
+        .flat_map(|(path, content)| {
+            spdx_snippets(&content)
+                .into_iter()
+                .filter(|snippet| snippet.end.is_none() || snippet.license.is_none())
+                .map(|snippet| (path.clone(), snippet))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Finds the files that can hold a header but have no SPDX one.
+pub(crate) fn missing_headers(
+    files: &[PathBuf],
+    header: &HeaderConfig,
+) -> BTreeSet<PathBuf> {
+    files
+        .iter()
+        .filter(|path| header.has_header(path) == Some(false))
+        .cloned()
+        .collect()
+}
+
+/// Prints the files without an SPDX header, or that every file has
+/// one.
+pub(crate) fn display_missing_headers(missing: &BTreeSet<PathBuf>) {
+    if missing.is_empty() {
+        success!("<green>✔</> <bold>{}</>", t!("header-all-present"));
+    }
+    for path in missing {
+        cprintln!(
+            "<red>✘</> {}",
+            t!("header-missing", file = path.display().to_string())
+        );
+    }
+}
+
+/// Inserts a commented header at the top of a file, after a leading
+/// shebang or XML declaration, which must stay on the first line, and
+/// after the `<?php` opening tag, outside of which PHP comments are
+/// output as text.
+///
+/// A byte order mark stays at the very start, and the header takes
+/// the line endings of the file's first line.
+pub(crate) fn insert_header(
+    content: &str,
+    header: &str,
+) -> String {
+    let (bom, content) = match content.strip_prefix(BOM) {
+        Some(rest) => (BOM, rest),
+        None => ("", content),
+    };
+    let line_ending = match content.find('\n') {
+        Some(end) if content[..end].ends_with('\r') => LineEnding::Crlf,
+        _ => LineEnding::Lf,
+    };
+    let header = line_ending.apply(header);
+    let line_end = |start: usize| {
+        content[start..]
+            .find('\n')
+            .map_or(content.len(), |end| start + end + 1)
+    };
+    let mut preamble_len = 0;
+    if content.starts_with("#!") || content.starts_with("<?xml") {
+        preamble_len = line_end(0);
+    }
+    // The opening tag of a PHP script may follow its shebang.
+    if content[preamble_len..].starts_with("<?php") {
+        preamble_len = line_end(preamble_len);
+    }
+    let (preamble, rest) = content.split_at(preamble_len);
+    let separator = if !preamble.is_empty() && !preamble.ends_with('\n') {
+        line_ending.apply("\n")
+    } else {
+        String::new()
+    };
+    format!("{}{}{}{}{}", bom, preamble, separator, header, rest)
+}
+
+/// Returns the key of a file in the comment style map: its extension
+/// or, without one, its name.
+fn language_key(path: &Path) -> Option<Cow<'_, str>> {
+    Some(
+        path.extension()
+            .or_else(|| path.file_name())?
+            .to_string_lossy(),
+    )
+}
+
+/// Returns the lines of an SPDX header, with the copyright line
+/// omitted when there is no owner.
+pub(crate) fn header_lines(
+    spdx_id: &str,
+    year: &str,
+    owner: Option<&str>,
+) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(owner) = owner {
+        lines.push(format!("SPDX-FileCopyrightText: {} {}", year, owner));
+    }
+    lines.push(format!("SPDX-License-Identifier: {}", spdx_id));
+    lines
+}
+
+/// Fills the variables of a header template and splits it into lines,
+/// dropping trailing blank ones. An unknown owner is left empty.
+fn render_header(
+    template: &str,
+    vars: &HeaderVars,
+) -> Vec<String> {
+    let text = template
+        .replace("{{year}}", vars.year)
+        .replace("{{owner}}", vars.owner.unwrap_or_default())
+        .replace("{{spdx_id}}", vars.spdx_id)
+        .replace("{{filename}}", vars.filename);
+    text.trim_end()
+        .lines()
+        .map(|line| line.trim_end().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, path::Path};
+
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        header_lines, insert_header, replace_header_license, spdx_header_license, spdx_header_line,
+        spdx_snippets, CommentStyle, HeaderConfig, HeaderVars, Snippet,
+    };
+
+    #[test]
+    fn test_comment_styles() {
+        let config = HeaderConfig {
+            languages: BTreeMap::from([
+                (
+                    "rs".to_string(),
+                    CommentStyle {
+                        prefer_block: Some(true),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "jl".to_string(),
+                    CommentStyle {
+                        line: Some("#".to_string()),
+                        ..Default::default()
+                    },
+                ),
+            ]),
+            ..Default::default()
+        };
+        let lines = header_lines("MIT", "2024", Some("Jane Doe"));
+        let header = |path: &str| {
+            config
+                .comment_style(Path::new(path))
+                .and_then(|style| style.comment(&lines))
+        };
+        assert_eq!(
+            header("src/main.c").as_deref(),
+            Some(
+                "/*\n * SPDX-FileCopyrightText: 2024 Jane Doe\n * SPDX-License-Identifier: MIT\n \
+                 */\n\n"
+            )
+        );
+        assert_eq!(
+            header("build.py").as_deref(),
+            Some("# SPDX-FileCopyrightText: 2024 Jane Doe\n# SPDX-License-Identifier: MIT\n\n")
+        );
+        assert_eq!(
+            header("index.html").as_deref(),
+            Some(
+                "<!--\nSPDX-FileCopyrightText: 2024 Jane Doe\nSPDX-License-Identifier: \
+                 MIT\n-->\n\n"
+            )
+        );
+        assert!(header("lib.rs").unwrap().starts_with("/*\n"));
+        assert!(header("main.jl").unwrap().starts_with("# "));
+        assert_eq!(header("data.bin"), None);
+        let html = header("index.html").unwrap();
+        assert_eq!(spdx_header_license(&html), Some("MIT"));
+        assert_eq!(
+            spdx_header_license("/* SPDX-License-Identifier: MIT OR Apache-2.0 */"),
+            Some("MIT OR Apache-2.0")
+        );
+    }
+
+    #[test]
+    fn test_spdx_snippets() {
+        let content = [
+            "// SPDX-SnippetBegin",
+            "// SPDX-License-Identifier: BSD-3-Clause",
+            "fn vendored() {}",
+            "// SPDX-SnippetEnd",
+            "// SPDX-License-Identifier: MIT",
+            "// SPDX-SnippetBegin",
+        ]
+        .join("\n");
+        let content = content.as_str();
+        assert_eq!(spdx_header_line(content), Some((5, "MIT")));
+        // Tags in code or string literals are not comments.
+        let code = [
+            "const BEGIN: &str = \"// SPDX-SnippetBegin\";",
+            "let tag = \"SPDX-License-Identifier: MIT\";",
+            "    \"SPDX-License-Identifier: ISC\",",
+            "# SPDX-License-Identifier: Apache-2.0",
+        ]
+        .join("\n");
+        assert_eq!(spdx_header_line(&code), Some((4, "Apache-2.0")));
+        assert_eq!(spdx_snippets(&code), []);
+        assert_eq!(
+            spdx_snippets(content),
+            vec![
+                Snippet {
+                    begin: 1,
+                    end: Some(4),
+                    license: Some("BSD-3-Clause".to_string()),
+                },
+                Snippet {
+                    begin: 6,
+                    end: None,
+                    license: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_header_templates() {
+        let config = HeaderConfig {
+            languages: BTreeMap::from([(
+                "py".to_string(),
+                CommentStyle {
+                    template: Some("{{filename}}: {{spdx_id}}".to_string()),
+                    ..Default::default()
+                },
+            )]),
+            template: Some(
+                "Copyright {{year}} {{owner}}\n\nLicensed under {{spdx_id}}.\n".to_string(),
+            ),
+        };
+        let vars = HeaderVars {
+            spdx_id: "Apache-2.0",
+            year: "2024",
+            owner: Some("Acme Corp"),
+            filename: "main.rs",
+        };
+        assert_eq!(
+            config.header_lines(Path::new("src/main.rs"), &vars),
+            vec!["Copyright 2024 Acme Corp", "", "Licensed under Apache-2.0."]
+        );
+        assert_eq!(
+            config.header_lines(Path::new("build.py"), &vars),
+            vec!["main.rs: Apache-2.0"]
+        );
+        assert_eq!(
+            HeaderConfig::default().header_lines(Path::new("src/main.rs"), &vars),
+            header_lines("Apache-2.0", "2024", Some("Acme Corp"))
+        );
+    }
+
+    #[test]
+    fn test_insert_header() {
+        let header = "# SPDX-License-Identifier: MIT\n\n";
+        assert_eq!(
+            insert_header("print(1)\n", header),
+            "# SPDX-License-Identifier: MIT\n\nprint(1)\n"
+        );
+        assert_eq!(
+            insert_header("#!/bin/sh\necho hi\n", header),
+            "#!/bin/sh\n# SPDX-License-Identifier: MIT\n\necho hi\n"
+        );
+        assert_eq!(
+            insert_header("#!/bin/sh", header),
+            "#!/bin/sh\n# SPDX-License-Identifier: MIT\n\n"
+        );
+        assert_eq!(
+            insert_header("\u{feff}#!/bin/sh\necho hi\n", header),
+            "\u{feff}#!/bin/sh\n# SPDX-License-Identifier: MIT\n\necho hi\n"
+        );
+        assert_eq!(
+            insert_header("<?php\necho 1;\n", "// SPDX-License-Identifier: MIT\n"),
+            "<?php\n// SPDX-License-Identifier: MIT\necho 1;\n"
+        );
+        assert_eq!(
+            insert_header("#!/usr/bin/env php\n<?php\necho 1;\n", header),
+            "#!/usr/bin/env php\n<?php\n# SPDX-License-Identifier: MIT\n\necho 1;\n"
+        );
+        assert_eq!(
+            insert_header("print(1)\r\nprint(2)\r\n", header),
+            "# SPDX-License-Identifier: MIT\r\n\r\nprint(1)\r\nprint(2)\r\n"
+        );
+    }
+
+    #[test]
+    fn test_replace_header_license() {
+        assert_eq!(
+            replace_header_license(
+                "// SPDX-FileCopyrightText: 2024 Jane\n// SPDX-License-Identifier: mit\nfn main() \
+                 {}\n",
+                "MIT",
+                "Apache-2.0"
+            )
+            .unwrap()
+            .unwrap(),
+            "// SPDX-FileCopyrightText: 2024 Jane\n// SPDX-License-Identifier: Apache-2.0\nfn \
+             main() {}\n"
+        );
+        assert_eq!(
+            replace_header_license(
+                "/* SPDX-License-Identifier: (MIT OR GPL-2.0-only) */\r\n",
+                "MIT",
+                "Apache-2.0 AND CC0-1.0"
+            )
+            .unwrap()
+            .unwrap(),
+            "/* SPDX-License-Identifier: ((Apache-2.0 AND CC0-1.0) OR GPL-2.0-only) */\r\n"
+        );
+        assert!(replace_header_license(
+            "// SPDX-License-Identifier: GPL-2.0-only WITH Classpath-exception-2.0\n",
+            "GPL-2.0-only",
+            "GPL-2.0-only OR MIT"
+        )
+        .is_err());
+        assert_eq!(
+            replace_header_license("# SPDX-License-Identifier: MIT-0\n", "MIT", "ISC").unwrap(),
+            None
+        );
+        assert_eq!(
+            replace_header_license("fn main() {}\n", "MIT", "ISC").unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use std::{env, sync::OnceLock};
+
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+/// The message catalogs bundled with lictool, keyed by language.
+const CATALOGS: [(&str, &str); 2] = [
+    ("en", include_str!("../locales/en.ftl")),
+    ("de", include_str!("../locales/de.ftl")),
+];
+
+/// The language of the reference catalog, used for messages missing
+/// from the selected one.
+const FALLBACK_LANG: &str = "en";
+
+/// Environment variables inspected, in order, when no language is
+/// given explicitly.
+const LOCALE_VARS: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// The loaded message bundles, the selected language first.
+struct Catalog {
+    /// The selected language.
+    lang: &'static str,
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Catalog {
+    /// Loads the catalog of `lang` followed by the fallback catalog.
+    ///
+    /// # Panics
+    /// - If a bundled catalog is not valid Fluent.
+    fn new(lang: &'static str) -> Self {
+        let bundles = CATALOGS
+            .iter()
+            .filter(|(id, _)| *id == lang)
+            .chain(
+                CATALOGS
+                    .iter()
+                    .filter(|(id, _)| *id == FALLBACK_LANG && *id != lang),
+            )
+            .map(|(id, source)| {
+                let langid: LanguageIdentifier = id.parse().expect("valid language identifier");
+                let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+                // Unicode isolation marks show up as garbage in most
+                // terminals.
+                bundle.set_use_isolating(false);
+                bundle
+                    .add_resource(
+                        FluentResource::try_new(source.to_string())
+                            .expect("bundled catalogs are valid Fluent"),
+                    )
+                    .expect("bundled catalogs have no duplicate messages");
+                bundle
+            })
+            .collect();
+        Self {
+            lang,
+            bundles,
+        }
+    }
+
+    /// Formats the message `key`, or returns `None` if no bundle
+    /// defines it.
+    fn format(
+        &self,
+        key: &str,
+        args: &[(&str, String)],
+    ) -> Option<String> {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            // Integers are passed as numbers so that messages can
+            // select plural forms.
+            let value = match value.parse::<i64>() {
+                Ok(number) if number.to_string() == *value => FluentValue::from(number),
+                _ => FluentValue::from(value.as_str()),
+            };
+            fluent_args.set(*name, value);
+        }
+        self.bundles.iter().find_map(|bundle| {
+            let pattern = bundle.get_message(key)?.value()?;
+            let mut errors = Vec::new();
+            Some(
+                bundle
+                    .format_pattern(pattern, Some(&fluent_args), &mut errors)
+                    .into_owned(),
+            )
+        })
+    }
+}
+
+/// Selects the language of all further messages.
+///
+/// The language is taken from `lang` (`--lang`), or else from the
+/// `LC_ALL`, `LC_MESSAGES` and `LANG` environment variables, falling
+/// back to English when no catalog matches.
+pub(crate) fn init(lang: Option<&str>) {
+    let _ = CATALOG.set(Catalog::new(resolve_lang(lang)));
+}
+
+/// Returns the language of messages, such as `en` or `de`.
+pub(crate) fn lang() -> &'static str {
+    CATALOG.get().map_or(FALLBACK_LANG, |catalog| catalog.lang)
+}
+
+/// Resolves a language tag such as `de`, `de-AT` or `de_DE.UTF-8` to
+/// one of the bundled catalogs.
+fn resolve_lang(lang: Option<&str>) -> &'static str {
+    lang.map(str::to_string)
+        .or_else(|| {
+            LOCALE_VARS
+                .iter()
+                .find_map(|var| env::var(var).ok().filter(|value| !value.is_empty()))
+        })
+        .and_then(|tag| {
+            let tag = tag.split(['.', '@']).next()?.replace('_', "-");
+            let langid: LanguageIdentifier = tag.parse().ok()?;
+            CATALOGS
+                .iter()
+                .map(|(id, _)| *id)
+                .find(|id| *id == langid.language.as_str())
+        })
+        .unwrap_or(FALLBACK_LANG)
+}
+
+/// Formats the message `key` with the given arguments.
+///
+/// Prefer the `t!` macro over calling this function directly.
+///
+/// # Returns
+///
+/// The message from the selected catalog, the fallback catalog, or
+/// the key itself if neither defines it.
+pub(crate) fn message(
+    key: &str,
+    args: &[(&str, String)],
+) -> String {
+    CATALOG
+        .get_or_init(|| Catalog::new(resolve_lang(None)))
+        .format(key, args)
+        .unwrap_or_else(|| key.to_string())
+}
+
+/// Looks up a translated message, passing named arguments by their
+/// `Display` representation.
+///
+/// ```ignore
+/// t!("file-created", file = path.display())
+/// ```
+macro_rules! t {
+    ($key:literal) => {
+        $crate::i18n::message($key, &[])
+    };
+    ($key:literal, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::message($key, &[$((stringify!($name), $value.to_string())),+])
+    };
+}
+pub(crate) use t;
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{Catalog, CATALOGS, FALLBACK_LANG};
+
+    #[test]
+    fn test_catalogs_define_all_messages() {
+        let (_, reference) = CATALOGS
+            .iter()
+            .find(|(id, _)| *id == FALLBACK_LANG)
+            .unwrap();
+        let keys: Vec<&str> = reference
+            .lines()
+            .filter_map(|line| line.split_once(" = ").map(|(key, _)| key))
+            .collect();
+        for (id, _) in CATALOGS {
+            let catalog = Catalog::new(id);
+            for key in &keys {
+                assert!(
+                    catalog.bundles[0].has_message(key),
+                    "the {} catalog is missing {}",
+                    id,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_format_plural_and_string_arguments() {
+        let catalog = Catalog::new("en");
+        let count = |count: &str| {
+            catalog.format("fetching-licenses-details", &[("count", count.to_string())])
+        };
+        assert_eq!(count("1").as_deref(), Some("Fetching one license…"));
+        assert_eq!(count("3").as_deref(), Some("Fetching 3 licenses…"));
+        assert_eq!(
+            catalog
+                .format("invalid-mode", &[("mode", "0644".to_string())])
+                .as_deref(),
+            Some("`0644` is not a valid octal file mode")
+        );
+    }
+}
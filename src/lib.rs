@@ -0,0 +1,245 @@
+//! Lictool fetches licenses from the SPDX license list and renders
+//! them for a project.
+//!
+//! Besides the `lictool` binary, the crate exposes the license list
+//! and templates so that other tools can embed them:
+//!
+//! - [`spdx::Licenses`] fetches the SPDX license list, and
+//!   [`spdx::License::details`] the text and metadata of a license as
+//!   [`spdx::LicenseDetails`].
+//! - [`template::Template`] fills the placeholders of a license text,
+//!   such as the year and the copyright owner.
+//! - [`fsf::FsfLicense`] is the entry of a license on the FSF list.
+//!
+//! Failures are returned as a [`LictoolResult`], whose errors can be
+//! downcast to [`Error`].
+//!
+//! ```no_run
+//! use lictool::{spdx::Licenses, template::Template};
+//!
+//! # async fn example() -> lictool::LictoolResult<()> {
+//! let licenses = Licenses::new().await?;
+//! let mit = licenses.find("MIT").expect("MIT is on the SPDX list");
+//! let details = mit.details().await?;
+//! let mut template = Template::new(details.license_text);
+//! template.year = Some("2024".to_string());
+//! template.owner = Some("Jane Doe".to_string());
+//! println!("{}", template.render());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Responses are cached on disk like those of the binary, and the
+//! types print nothing themselves.
+
+use std::io;
+
+use anyhow::Context;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
+use cli::Cli;
+use i18n::t;
+pub use util::errors::{Error, LictoolResult};
+use util::{
+    errors::{display_error, exit_code},
+    journal,
+};
+
+/// A module to audit the licenses of the dependencies of a project.
+mod audit;
+
+/// A module to generate AUTHORS files from the git history.
+mod authors;
+
+/// A module to generate license badges and README sections.
+mod badge;
+
+/// A module to tag licenses intended for fonts, data and
+/// documentation.
+mod category;
+
+/// A module to browse licenses in a full-screen terminal view.
+mod browser;
+
+/// A module to choose Creative Commons licenses for non-code assets.
+mod cc;
+
+/// A module to check license files against their canonical texts.
+mod check;
+
+/// A module to recommend licenses from the needs of a project.
+mod choose;
+
+/// A module to handle the command-line interface (CLI)
+/// functionalities.
+mod cli;
+
+/// A module to compare the texts and metadata of two licenses.
+mod compare;
+
+/// A module to load and edit the user and project configuration.
+mod config;
+
+/// A module to store constants used throughout the application.
+mod consts;
+
+/// A module providing the DCO and CLA documents for contributors.
+mod contribution;
+
+/// A module to load the custom license templates of the user.
+mod custom;
+
+/// A module to export and import the license dataset for offline use.
+mod db;
+
+/// A module to generate Debian machine-readable copyright files.
+mod debian;
+
+/// A module to suggest the successors of deprecated license IDs.
+mod deprecation;
+
+/// A module to identify licenses by the similarity of their texts.
+mod detect;
+
+/// A module to present licenses and exceptions on the terminal.
+mod display;
+
+/// A module to diagnose problems with the environment of lictool.
+mod doctor;
+
+/// A module to map SPDX IDs to the license names of Linux
+/// distributions.
+mod distro;
+
+/// A module to parse SPDX license expressions.
+mod expression;
+
+/// A module to compare the variants of a license family.
+mod family;
+
+/// A module to describe fixes of check findings as text edits.
+mod fix;
+
+/// A module to look up licenses on the FSF license list.
+pub mod fsf;
+
+/// A module to render the SPDX headers of source files.
+mod header;
+
+/// A module to translate user-facing messages.
+mod i18n;
+
+/// A module to write the licenses of an expression to files of their
+/// own.
+mod license_set;
+
+/// A module to check that license links still resolve.
+mod links;
+
+/// A module providing the language server for license identifiers.
+mod lsp;
+
+/// A module to generate NOTICE files attributing the project and its
+/// dependencies.
+mod notice;
+
+/// A module to label container images with their license.
+mod oci;
+
+/// A module to bundle a standard lictool setup for distribution.
+mod pack;
+
+/// A module providing the interactive license picker.
+mod picker;
+
+/// A module to report the placeholders of license texts.
+mod placeholders;
+
+/// A module to dispatch to `lictool-<name>` plugins.
+mod plugin;
+
+/// A module to evaluate license expressions against a policy.
+mod policy;
+
+/// A module to rank licenses by how widely they are used.
+mod popularity;
+
+/// A module to find and edit the license files and manifest license
+/// fields of a project.
+mod project;
+
+/// A module to check that a crate is ready to be published.
+mod publish;
+
+/// A module to render licensing reports of a project.
+mod report;
+
+/// A module to summarize what licenses permit, require and rule
+/// out.
+mod rules;
+
+/// A module to verify SPDX documents against the project.
+mod sbom;
+
+/// A module to find the licensing of tar and zip archives.
+mod scan;
+
+/// A module to compute placeholder values with user scripts.
+mod script;
+
+/// A module to search the texts of all licenses.
+mod search;
+
+/// A module to summarize the licensing of a project.
+mod status;
+
+/// A module to manage SPDX-related operations and data.
+pub mod spdx;
+
+/// A module to handle template management.
+pub mod template;
+
+/// A module to index license texts for full-text search.
+mod text_index;
+
+/// A module to update lictool from its GitHub releases.
+mod update;
+
+/// A module providing utility functions for various tasks.
+mod util;
+
+/// A module to extend the copyright years of license files.
+mod years;
+
+/// Runs the command-line interface with the arguments of the process,
+/// exiting with the code of the error a command fails with.
+#[doc(hidden)]
+pub async fn run() {
+    // Answers the completion requests of the shell scripts generated
+    // by `lictool completions`, exiting once done.
+    CompleteEnv::with_factory(Cli::command)
+        .var(cli::COMPLETE_VAR)
+        .complete();
+    let args = Cli::parse();
+    let result = args.exec_command().await;
+    // The files written are recorded once the command is done, even if
+    // it failed after writing some.
+    let flushed = journal::flush().context(t!("error-journal"));
+    if let Err(e) = result.and(flushed) {
+        display_error(&e);
+        std::process::exit(exit_code(&e));
+    }
+}
+
+/// Reports that `cargo lictool` could not run the `lictool` binary,
+/// in the language of the user.
+///
+/// # Returns
+///
+/// The code to exit with.
+#[doc(hidden)]
+pub fn report_unrunnable_lictool(error: io::Error) -> i32 {
+    let error = anyhow::Error::new(error).context(Error::LictoolNotRunnable);
+    display_error(&error);
+    exit_code(&error)
+}
@@ -0,0 +1,314 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    config::Config,
+    custom::CustomTemplate,
+    expression::Expression,
+    i18n::t,
+    spdx::{Exceptions, LicenseDetails, Licenses, DETAILS_CONCURRENCY},
+    template::{license_specific_target, license_stub, write_templates, Template, WriteOptions},
+    util::{
+        errors::{Error, LictoolResult},
+        progress::{progress_bar, with_spinner},
+    },
+};
+
+/// The licenses written at once by `add` and `init`, each to its own
+/// file when there are several, with a stub explaining the expression
+/// they were given as.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LicenseSet {
+    /// The expression the licenses were given as, if any.
+    pub(crate) expression: Option<Expression>,
+    /// The licenses, with the exception each is used with.
+    pub(crate) terms: Vec<(String, Option<String>)>,
+}
+
+impl LicenseSet {
+    /// Returns the licenses of `arguments` if they form an
+    /// expression, such as `MIT OR Apache-2.0`, with their IDs
+    /// canonicalized against `licenses`.
+    ///
+    /// # Returns
+    ///
+    /// `None` for plain license IDs.
+    ///
+    /// # Errors
+    /// - `Error::InvalidExpression` if the expression is malformed.
+    /// - If a license of the expression is not on the list.
+    pub(crate) fn from_expression(
+        arguments: &[String],
+        licenses: &Licenses,
+    ) -> LictoolResult<Option<Self>> {
+        let Some(expression) = Expression::from_arguments(arguments)? else {
+            return Ok(None);
+        };
+        let expression = expression.canonicalize(&licenses.body)?;
+        let mut terms: Vec<(String, Option<String>)> = Vec::new();
+        for term in expression.terms() {
+            if !terms.iter().any(|(id, _)| *id == term.id) {
+                terms.push((term.id.clone(), term.exception.clone()));
+            }
+        }
+        Ok(Some(LicenseSet {
+            expression: Some(expression),
+            terms,
+        }))
+    }
+
+    /// Returns the set of licenses given as plain IDs, without the
+    /// duplicates.
+    pub(crate) fn from_terms(terms: Vec<(String, Option<String>)>) -> Self {
+        let mut unique: Vec<(String, Option<String>)> = Vec::with_capacity(terms.len());
+        for term in terms {
+            if !unique.iter().any(|(id, _)| *id == term.0) {
+                unique.push(term);
+            }
+        }
+        LicenseSet {
+            expression: None,
+            terms: unique,
+        }
+    }
+
+    /// Returns the IDs of the licenses.
+    pub(crate) fn ids(&self) -> Vec<String> {
+        self.terms.iter().map(|(id, _)| id.clone()).collect()
+    }
+
+    /// Returns the name the licenses are committed with, the
+    /// expression or the IDs.
+    pub(crate) fn name(&self) -> String {
+        match &self.expression {
+            Some(expression) => expression.to_string(),
+            None => self.ids().join(", "),
+        }
+    }
+
+    /// Returns whether a stub explaining the expression is written in
+    /// place of the license file.
+    pub(crate) fn has_stub(&self) -> bool {
+        self.expression.is_some() && self.terms.len() > 1
+    }
+
+    /// Returns the files each license is written to. A single license
+    /// is written to `paths`; several cannot share a file, so each
+    /// gets its own, such as `LICENSE-MIT` and `LICENSE-APACHE`.
+    pub(crate) fn targets(
+        &self,
+        paths: &[String],
+        default_filename: &str,
+    ) -> Vec<Vec<String>> {
+        if self.terms.len() == 1 {
+            return vec![paths.to_vec()];
+        }
+        file_suffixes(&self.ids())
+            .iter()
+            .map(|suffix| {
+                paths
+                    .iter()
+                    .map(|path| {
+                        license_specific_target(Path::new(path), default_filename, suffix)
+                            .to_string_lossy()
+                            .into_owned()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns all files written for `targets`, including the stub at
+    /// `paths`.
+    pub(crate) fn all_targets(
+        &self,
+        targets: &[Vec<String>],
+        paths: &[String],
+    ) -> Vec<String> {
+        let mut all = targets.concat();
+        if self.has_stub() {
+            all.extend(paths.iter().cloned());
+        }
+        all
+    }
+
+    /// Fetches the details of the licenses, reading custom templates
+    /// instead, with the text of each exception following that of its
+    /// license.
+    ///
+    /// # Returns
+    ///
+    /// The details in the order of the licenses.
+    ///
+    /// # Errors
+    /// - If a license or exception cannot be fetched or read, or an
+    ///   override cannot be read.
+    pub(crate) async fn details(
+        &self,
+        licenses: &Licenses,
+        custom: &[CustomTemplate],
+        config: &Config,
+    ) -> LictoolResult<Vec<LicenseDetails>> {
+        let ids = self.ids();
+        let find_custom = |id: &String| custom.iter().find(|template| template.id == *id);
+        let spdx_ids: Vec<&String> = ids.iter().filter(|id| find_custom(id).is_none()).collect();
+        let progress = progress_bar(
+            t!("fetching-licenses-details", count = spdx_ids.len()),
+            spdx_ids.len(),
+        );
+        let fetched = licenses
+            .details_for(&spdx_ids, DETAILS_CONCURRENCY, || progress.inc(1))
+            .await;
+        progress.finish_and_clear();
+        let mut fetched = fetched?.into_iter();
+        let mut details = Vec::with_capacity(ids.len());
+        for id in &ids {
+            match find_custom(id) {
+                Some(template) => details.push(template.details()?),
+                None => {
+                    let mut fetched = fetched.next().ok_or(Error::NotFound)?;
+                    config.apply_override(&mut fetched)?;
+                    details.push(fetched);
+                }
+            }
+        }
+        if self.terms.iter().any(|(_, exception)| exception.is_some()) {
+            let exceptions = with_spinner(t!("fetching-exceptions"), Exceptions::new()).await?;
+            for (details, (_, exception)) in details.iter_mut().zip(&self.terms) {
+                if let Some(exception) = exception {
+                    let exception = exceptions
+                        .find(exception)
+                        .ok_or(Error::NotFound)?
+                        .details()
+                        .await?;
+                    details.license_text = format!(
+                        "{}\n\n{}",
+                        details.license_text.trim_end(),
+                        exception.license_exception_text
+                    );
+                }
+            }
+        }
+        Ok(details)
+    }
+
+    /// Writes the stub explaining the expression to `paths`, listing
+    /// the files of `targets`, if the set has one.
+    ///
+    /// # Returns
+    ///
+    /// The files written.
+    ///
+    /// # Errors
+    /// - If the stub cannot be written.
+    pub(crate) fn write_stub(
+        &self,
+        paths: &[String],
+        targets: &[Vec<String>],
+        options: &WriteOptions,
+    ) -> LictoolResult<Vec<PathBuf>> {
+        let Some(expression) = self.expression.as_ref().filter(|_| self.has_stub()) else {
+            return Ok(Vec::new());
+        };
+        let files: Vec<(String, String)> = self
+            .terms
+            .iter()
+            .zip(targets)
+            .map(|((id, exception), paths)| {
+                let license = match exception {
+                    Some(exception) => format!("{} WITH {}", id, exception),
+                    None => id.clone(),
+                };
+                let file = Path::new(&paths[0])
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .into_owned();
+                (license, file)
+            })
+            .collect();
+        write_templates(
+            paths,
+            &mut Template {
+                license_text: license_stub(expression, &files),
+                ..Default::default()
+            },
+            options,
+        )
+    }
+}
+
+/// Returns the suffix of the file of each license, its ID up to the
+/// version such as `APACHE` for `Apache-2.0`, making the conventional
+/// `LICENSE-MIT` and `LICENSE-APACHE`. The full IDs are used when two
+/// licenses would share a suffix, as `GPL-2.0-only` and
+/// `GPL-3.0-only` do.
+fn file_suffixes(ids: &[String]) -> Vec<String> {
+    let ids: Vec<&str> = ids
+        .iter()
+        .map(|id| id.trim_start_matches("LicenseRef-"))
+        .collect();
+    let short: Vec<String> = ids
+        .iter()
+        .map(|id| {
+            let family: Vec<&str> = id
+                .split('-')
+                .take_while(|segment| !segment.starts_with(|c: char| c.is_ascii_digit()))
+                .collect();
+            if family.is_empty() {
+                id.to_uppercase()
+            } else {
+                family.join("-").to_uppercase()
+            }
+        })
+        .collect();
+    let unique = short
+        .iter()
+        .enumerate()
+        .all(|(index, suffix)| !short[..index].contains(suffix));
+    if unique {
+        short
+    } else {
+        ids.iter().map(|id| id.to_uppercase()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_file_suffixes() {
+        assert_eq!(
+            file_suffixes(&ids(&["MIT", "Apache-2.0"])),
+            ids(&["MIT", "APACHE"])
+        );
+        assert_eq!(
+            file_suffixes(&ids(&["BSD-3-Clause", "0BSD", "LicenseRef-Acme"])),
+            ids(&["BSD", "0BSD", "ACME"])
+        );
+        assert_eq!(
+            file_suffixes(&ids(&["GPL-2.0-only", "GPL-3.0-only"])),
+            ids(&["GPL-2.0-ONLY", "GPL-3.0-ONLY"])
+        );
+    }
+
+    #[test]
+    fn test_targets() {
+        let set = LicenseSet::from_terms(vec![
+            ("MIT".to_string(), None),
+            ("Apache-2.0".to_string(), None),
+            ("MIT".to_string(), None),
+        ]);
+        assert_eq!(
+            set.targets(&ids(&["docs/LICENSE.md"]), "LICENSE"),
+            vec![ids(&["docs/LICENSE-MIT"]), ids(&["docs/LICENSE-APACHE"])]
+        );
+        assert!(!set.has_stub());
+    }
+}
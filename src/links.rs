@@ -0,0 +1,114 @@
+use color_print::cformat;
+use futures::future::join_all;
+use reqwest::StatusCode;
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use crate::{i18n::t, util::http};
+
+/// The outcome of checking a link.
+#[derive(Debug)]
+pub(crate) struct LinkCheck {
+    pub(crate) url: String,
+    /// The status of the response, or the error if the request
+    /// failed.
+    pub(crate) status: Result<StatusCode, String>,
+}
+
+impl LinkCheck {
+    /// Checks whether the link is dead.
+    ///
+    /// Servers refusing HEAD requests with 405 Method Not Allowed are
+    /// still considered alive.
+    pub(crate) fn is_dead(&self) -> bool {
+        match self.status {
+            Ok(status) => {
+                status != StatusCode::METHOD_NOT_ALLOWED
+                    && (status.is_client_error() || status.is_server_error())
+            }
+            Err(_) => true,
+        }
+    }
+}
+
+impl Serialize for LinkCheck {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut check = serializer.serialize_struct("LinkCheck", 4)?;
+        check.serialize_field("url", &self.url)?;
+        check.serialize_field("status", &self.status.as_ref().ok().map(StatusCode::as_u16))?;
+        check.serialize_field("error", &self.status.as_ref().err())?;
+        check.serialize_field("dead", &self.is_dead())?;
+        check.end()
+    }
+}
+
+/// Sends a HEAD request to each URL concurrently.
+///
+/// # Returns
+///
+/// The checks in the order of `urls`.
+pub(crate) async fn verify_links(urls: &[String]) -> Vec<LinkCheck> {
+    join_all(urls.iter().map(|url| {
+        async move {
+            LinkCheck {
+                url: url.clone(),
+                status: http::head(url).await.map_err(|err| format!("{:#}", err)),
+            }
+        }
+    }))
+    .await
+}
+
+/// Formats link checks for display, flagging dead links.
+pub(crate) fn display_link_checks(checks: &[LinkCheck]) -> String {
+    let dead = checks.iter().filter(|check| check.is_dead()).count();
+    let mut output = cformat!("<s>{}</>", t!("info-link-check", dead = dead));
+    for check in checks {
+        let status = match &check.status {
+            Ok(status) => status.to_string(),
+            Err(err) => err.clone(),
+        };
+        output.push_str(
+            &if check.is_dead() {
+                cformat!("\n  <red>✘</> <u>{}</> <red>({})</>", check.url, status)
+            } else {
+                cformat!("\n  <green>✔</> <u>{}</> ({})", check.url, status)
+            },
+        );
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::verify_links;
+
+    #[tokio::test]
+    async fn test_verify_links() {
+        let mut server = mockito::Server::new_async().await;
+        let alive = server.mock("HEAD", "/alive").create_async().await;
+        let dead = server
+            .mock("HEAD", "/dead")
+            .with_status(404)
+            .create_async()
+            .await;
+        let no_head = server
+            .mock("HEAD", "/no-head")
+            .with_status(405)
+            .create_async()
+            .await;
+
+        let urls: Vec<String> = ["/alive", "/dead", "/no-head"]
+            .iter()
+            .map(|path| format!("{}{}", server.url(), path))
+            .collect();
+        let checks = verify_links(&urls).await;
+        let dead_links: Vec<bool> = checks.iter().map(|check| check.is_dead()).collect();
+        assert_eq!(dead_links, [false, true, false]);
+        alive.assert_async().await;
+        dead.assert_async().await;
+        no_head.assert_async().await;
+    }
+}
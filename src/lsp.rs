@@ -0,0 +1,497 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use anyhow::anyhow;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+        PublishDiagnostics,
+    },
+    request::{Completion, Request as _},
+    CompletionItem, CompletionItemKind, CompletionItemTag, CompletionOptions, CompletionParams,
+    CompletionResponse, Diagnostic, DiagnosticSeverity, DiagnosticTag, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, Position, PublishDiagnosticsParams,
+    Range, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Uri,
+};
+use regex::Regex;
+
+use crate::{
+    expression::Expression,
+    i18n::t,
+    spdx::{Exception, License},
+    util::errors::LictoolResult,
+};
+
+/// An SPDX header in a comment of any file, with the expression up to
+/// the end of the line or of a block comment.
+static SPDX_TAG: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"SPDX-License-Identifier:[ \t]*(.*?)[ \t]*(?:\*/|-->)?[ \t]*$").unwrap()
+});
+
+/// The `license` field of the `[package]` table of a Cargo manifest.
+static CARGO_LICENSE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"^\s*license\s*=\s*"([^"]*)"#).unwrap());
+
+/// The `license` field of an npm `package.json`.
+static NPM_LICENSE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""license"\s*:\s*"([^"]*)"#).unwrap());
+
+/// The characters that end a license ID in an expression.
+const ID_DELIMITERS: [char; 6] = [' ', '\t', '(', ')', '"', ':'];
+
+/// A license expression within a document.
+#[derive(Debug, Clone, PartialEq)]
+struct ExpressionSpan {
+    /// The zero-based line of the expression.
+    line: u32,
+    /// The byte offset of the expression within the line.
+    start: usize,
+    /// The expression as written.
+    text: String,
+}
+
+/// Finds the license expressions of a document: SPDX headers in any
+/// file, and the license fields of Cargo manifests and
+/// `package.json`.
+fn expression_spans(
+    uri: &str,
+    text: &str,
+) -> Vec<ExpressionSpan> {
+    let field = if uri.ends_with("/Cargo.toml") {
+        Some(&*CARGO_LICENSE)
+    } else if uri.ends_with("/package.json") {
+        Some(&*NPM_LICENSE)
+    } else {
+        None
+    };
+    text.lines()
+        .enumerate()
+        .filter_map(|(line, content)| {
+            let found = SPDX_TAG
+                .captures(content)
+                .or_else(|| field.and_then(|field| field.captures(content)))?;
+            let expression = found.get(1)?;
+            Some(ExpressionSpan {
+                line: line as u32,
+                start: expression.start(),
+                text: expression.as_str().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Converts a byte offset within a line to the UTF-16 column LSP
+/// positions use.
+fn utf16_column(
+    line: &str,
+    offset: usize,
+) -> u32 {
+    line[..offset].encode_utf16().count() as u32
+}
+
+/// Converts a UTF-16 column to a byte offset within a line, clamped
+/// to its end.
+fn byte_offset(
+    line: &str,
+    column: u32,
+) -> usize {
+    let mut units = 0;
+    for (offset, char) in line.char_indices() {
+        if units >= column as usize {
+            return offset;
+        }
+        units += char.len_utf16();
+    }
+    line.len()
+}
+
+/// Reports the license IDs of a document that are unknown or
+/// deprecated, and expressions that do not parse.
+fn diagnostics(
+    uri: &str,
+    text: &str,
+    licenses: &[License],
+    exceptions: &[Exception],
+) -> Vec<Diagnostic> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut diagnostics = Vec::new();
+    for span in expression_spans(uri, text) {
+        let line = lines[span.line as usize];
+        let range = |start: usize, end: usize| {
+            Range::new(
+                Position::new(span.line, utf16_column(line, span.start + start)),
+                Position::new(span.line, utf16_column(line, span.start + end)),
+            )
+        };
+        if let Err(error) = span.text.parse::<Expression>() {
+            diagnostics.push(diagnostic(
+                range(0, span.text.len()),
+                DiagnosticSeverity::ERROR,
+                error.to_string(),
+            ));
+            continue;
+        }
+        let mut after_with = false;
+        for (start, word) in words(&span.text) {
+            let range = range(start, start + word.len());
+            if ["AND", "OR"].contains(&word) {
+                after_with = false;
+                continue;
+            }
+            if word == "WITH" {
+                after_with = true;
+                continue;
+            }
+            if word.starts_with("LicenseRef-") || word.starts_with("DocumentRef-") {
+                continue;
+            }
+            let found = if after_with {
+                exceptions
+                    .iter()
+                    .find(|exception| exception.id.eq_ignore_ascii_case(word))
+                    .map(|exception| exception.is_deprecated_license_id)
+            } else {
+                let id = word.strip_suffix('+').unwrap_or(word);
+                licenses
+                    .iter()
+                    .find(|license| license.id.eq_ignore_ascii_case(id))
+                    .map(|license| license.is_deprecated_license_id)
+            };
+            match found {
+                None => {
+                    let message = if after_with {
+                        t!("lsp-unknown-exception", id = word)
+                    } else {
+                        t!("lsp-unknown-license", id = word)
+                    };
+                    diagnostics.push(diagnostic(range, DiagnosticSeverity::ERROR, message))
+                }
+                Some(true) => {
+                    let mut deprecated = diagnostic(
+                        range,
+                        DiagnosticSeverity::WARNING,
+                        t!("lsp-deprecated-id", id = word),
+                    );
+                    deprecated.tags = Some(vec![DiagnosticTag::DEPRECATED]);
+                    diagnostics.push(deprecated)
+                }
+                Some(false) => {}
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Creates a diagnostic attributed to lictool.
+fn diagnostic(
+    range: Range,
+    severity: DiagnosticSeverity,
+    message: String,
+) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        source: Some("lictool".to_string()),
+        message,
+        ..Default::default()
+    }
+}
+
+/// Splits an expression into its IDs and operators, with their byte
+/// offsets.
+fn words(expression: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (offset, char) in expression.char_indices().chain([(expression.len(), ' ')]) {
+        match (start, ID_DELIMITERS.contains(&char)) {
+            (None, false) => start = Some(offset),
+            (Some(word_start), true) => {
+                words.push((word_start, &expression[word_start..offset]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// Completes the license or exception ID before the cursor, when it
+/// is inside a license expression.
+fn completions(
+    uri: &str,
+    text: &str,
+    position: Position,
+    licenses: &[License],
+    exceptions: &[Exception],
+) -> Vec<CompletionItem> {
+    let Some(line) = text.lines().nth(position.line as usize) else {
+        return Vec::new();
+    };
+    let cursor = byte_offset(line, position.character);
+    let in_expression = expression_spans(uri, text).iter().any(|span| {
+        span.line == position.line && span.start <= cursor && cursor <= span.start + span.text.len()
+    });
+    if !in_expression {
+        return Vec::new();
+    }
+    let before = &line[..cursor];
+    let prefix_start = before
+        .rfind(ID_DELIMITERS)
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let prefix = before[prefix_start..].to_lowercase();
+    let after_with = words(&before[..prefix_start])
+        .last()
+        .is_some_and(|(_, word)| *word == "WITH");
+    let item = |id: &str, name: &str, deprecated: bool, kind: CompletionItemKind| {
+        CompletionItem {
+            label: id.to_string(),
+            kind: Some(kind),
+            detail: Some(name.to_string()),
+            tags: deprecated.then(|| vec![CompletionItemTag::DEPRECATED]),
+            // Deprecated IDs sort after the current ones.
+            sort_text: Some(format!("{}{}", u8::from(deprecated), id)),
+            ..Default::default()
+        }
+    };
+    let matches = |id: &str| id.to_lowercase().starts_with(&prefix);
+    if after_with {
+        exceptions
+            .iter()
+            .filter(|exception| matches(&exception.id))
+            .map(|exception| {
+                item(
+                    &exception.id,
+                    &exception.name,
+                    exception.is_deprecated_license_id,
+                    CompletionItemKind::ENUM_MEMBER,
+                )
+            })
+            .collect()
+    } else {
+        licenses
+            .iter()
+            .filter(|license| matches(&license.id))
+            .map(|license| {
+                item(
+                    &license.id,
+                    &license.name,
+                    license.is_deprecated_license_id,
+                    CompletionItemKind::VALUE,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Runs the language server over stdin and stdout until the client
+/// shuts it down.
+///
+/// # Errors
+/// - If the connection to the client fails.
+pub(crate) fn serve(
+    licenses: &[License],
+    exceptions: &[Exception],
+) -> LictoolResult<()> {
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![":".to_string(), " ".to_string(), "\"".to_string()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    connection
+        .initialize(serde_json::to_value(capabilities)?)
+        .map_err(|error| anyhow!(error.to_string()))?;
+    let mut documents: HashMap<String, String> = HashMap::new();
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection
+                    .handle_shutdown(&request)
+                    .map_err(|error| anyhow!(error.to_string()))?
+                {
+                    break;
+                }
+                handle_request(&connection, request, &documents, licenses, exceptions)?;
+            }
+            Message::Notification(notification) => {
+                let Some(uri) = update_documents(notification, &mut documents) else {
+                    continue;
+                };
+                let diagnostics = documents
+                    .get(uri.as_str())
+                    .map(|text| diagnostics(uri.as_str(), text, licenses, exceptions))
+                    .unwrap_or_default();
+                let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+                connection
+                    .sender
+                    .send(Message::Notification(Notification::new(
+                        PublishDiagnostics::METHOD.to_string(),
+                        params,
+                    )))?;
+            }
+            Message::Response(_) => {}
+        }
+    }
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Answers a request, with an empty result for those not supported
+/// and an `InvalidParams` error for malformed parameters.
+fn handle_request(
+    connection: &Connection,
+    request: Request,
+    documents: &HashMap<String, String>,
+    licenses: &[License],
+    exceptions: &[Exception],
+) -> LictoolResult<()> {
+    let response = if request.method == Completion::METHOD {
+        let params: CompletionParams = match serde_json::from_value(request.params) {
+            Ok(params) => params,
+            Err(error) => {
+                let response = Response::new_err(
+                    request.id,
+                    ErrorCode::InvalidParams as i32,
+                    error.to_string(),
+                );
+                connection.sender.send(Message::Response(response))?;
+                return Ok(());
+            }
+        };
+        let document = params.text_document_position;
+        let items = documents
+            .get(document.text_document.uri.as_str())
+            .map(|text| {
+                completions(
+                    document.text_document.uri.as_str(),
+                    text,
+                    document.position,
+                    licenses,
+                    exceptions,
+                )
+            })
+            .unwrap_or_default();
+        Response::new_ok(request.id, CompletionResponse::Array(items))
+    } else {
+        Response::new_ok(request.id, serde_json::Value::Null)
+    };
+    connection.sender.send(Message::Response(response))?;
+    Ok(())
+}
+
+/// Tracks the documents the client opens, changes and closes.
+///
+/// Notifications cannot be answered, so those with malformed
+/// parameters are ignored.
+///
+/// # Returns
+///
+/// The document whose diagnostics need publishing, if any.
+fn update_documents(
+    notification: Notification,
+    documents: &mut HashMap<String, String>,
+) -> Option<Uri> {
+    let uri = match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params: DidOpenTextDocumentParams =
+                serde_json::from_value(notification.params).ok()?;
+            let document = params.text_document;
+            documents.insert(document.uri.to_string(), document.text);
+            document.uri
+        }
+        DidChangeTextDocument::METHOD => {
+            let params: DidChangeTextDocumentParams =
+                serde_json::from_value(notification.params).ok()?;
+            // Full synchronization sends the whole text as the last
+            // change.
+            if let Some(change) = params.content_changes.into_iter().last() {
+                documents.insert(params.text_document.uri.to_string(), change.text);
+            }
+            params.text_document.uri
+        }
+        DidCloseTextDocument::METHOD => {
+            let params: DidCloseTextDocumentParams =
+                serde_json::from_value(notification.params).ok()?;
+            documents.remove(params.text_document.uri.as_str());
+            params.text_document.uri
+        }
+        _ => return None,
+    };
+    Some(uri)
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::{DiagnosticSeverity, Position};
+    use pretty_assertions::assert_eq;
+
+    use super::{completions, diagnostics};
+    use crate::spdx::{Exception, License};
+
+    #[test]
+    fn test_license_diagnostics_and_completion() {
+        let license = |id: &str, deprecated: bool| {
+            License {
+                is_deprecated_license_id: deprecated,
+                details_url: String::new(),
+                name: id.to_string(),
+                id: id.to_string(),
+                is_osi_approved: Some(false),
+                is_fsf_libre: None,
+            }
+        };
+        let licenses = [
+            license("MIT", false),
+            license("GPL-2.0", true),
+            license("GPL-2.0-only", false),
+        ];
+        let exceptions = [Exception {
+            is_deprecated_license_id: false,
+            details_url: String::new(),
+            name: "Classpath exception 2.0".to_string(),
+            id: "Classpath-exception-2.0".to_string(),
+        }];
+        let text = "/* SPDX-License-Identifier: GPL-2.0 WITH Classpath-exception-2.0 OR Foo \
+                    */\n[package]\nlicense = \"MIT OR\"\n";
+        let found = diagnostics("file:///crate/Cargo.toml", text, &licenses, &exceptions);
+        let found: Vec<_> = found
+            .iter()
+            .map(|diagnostic| {
+                (
+                    diagnostic.range.start,
+                    diagnostic.range.end.character,
+                    diagnostic.severity.unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (Position::new(0, 28), 35, DiagnosticSeverity::WARNING),
+                (Position::new(0, 68), 71, DiagnosticSeverity::ERROR),
+                (Position::new(2, 11), 17, DiagnosticSeverity::ERROR),
+            ]
+        );
+        let labels = |line: u32, character: u32| {
+            completions(
+                "file:///crate/Cargo.toml",
+                text,
+                Position::new(line, character),
+                &licenses,
+                &exceptions,
+            )
+            .into_iter()
+            .map(|item| item.label)
+            .collect::<Vec<_>>()
+        };
+        assert_eq!(labels(0, 31), ["GPL-2.0", "GPL-2.0-only"]);
+        assert_eq!(labels(0, 42), ["Classpath-exception-2.0"]);
+        assert_eq!(labels(2, 12), ["MIT"]);
+        assert!(labels(1, 3).is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::audit::AuditedPackage;
+
+/// The file name NOTICE files are written to by default.
+pub(crate) const NOTICE_FILENAME: &str = "NOTICE";
+
+/// The text of a NOTICE file, with the same placeholders as license
+/// templates for the project, owner and year.
+pub(crate) const NOTICE_TEMPLATE: &str = include_str!("../templates/NOTICE");
+
+/// Returns the attributions of the dependencies for a NOTICE file,
+/// one line each, or `None` if no dependency declares a license.
+pub(crate) fn third_party_notices(packages: &[AuditedPackage]) -> Option<String> {
+    let lines: Vec<String> = packages
+        .iter()
+        .filter_map(|package| {
+            let license = package.license.as_ref()?;
+            Some(format!(
+                "- {} {}, licensed under {}\n",
+                package.name, package.version, license
+            ))
+        })
+        .collect();
+    (!lines.is_empty()).then(|| {
+        format!(
+            "This product includes third-party software:\n\n{}",
+            lines.concat()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::third_party_notices;
+    use crate::audit::AuditedPackage;
+
+    #[test]
+    fn test_third_party_notices() {
+        let package = |name: &str, license: Option<&str>| {
+            AuditedPackage {
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                license: license.map(str::to_string),
+                flags: Vec::new(),
+            }
+        };
+        assert_eq!(
+            third_party_notices(&[
+                package("serde", Some("MIT OR Apache-2.0")),
+                package("custom", None),
+            ])
+            .as_deref(),
+            Some(
+                "This product includes third-party software:\n\n- serde 1.0.0, licensed under MIT \
+                 OR Apache-2.0\n"
+            )
+        );
+        assert_eq!(third_party_notices(&[package("custom", None)]), None);
+    }
+}
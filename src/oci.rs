@@ -0,0 +1,105 @@
+use crate::util::line_ending::LineEnding;
+
+/// The OCI image annotation holding the license expression of an
+/// image.
+pub(crate) const LICENSES_LABEL: &str = "org.opencontainers.image.licenses";
+
+/// Returns the Dockerfile instruction labelling an image with a
+/// license expression.
+pub(crate) fn label_instruction(expression: &str) -> String {
+    format!("LABEL {}={:?}", LICENSES_LABEL, expression)
+}
+
+/// Sets the license label of a Dockerfile to `expression`.
+///
+/// Only the final stage, from the last `FROM` on, makes up the image.
+/// An existing label there is updated in place, keeping the other
+/// labels of its instruction. Otherwise a `LABEL` instruction is
+/// added after the last `FROM`. The line endings of the file are
+/// kept.
+///
+/// # Returns
+///
+/// `None` if the Dockerfile already has the label.
+pub(crate) fn patch_dockerfile(
+    content: &str,
+    expression: &str,
+) -> Option<String> {
+    let value = format!("{:?}", expression);
+    let key = format!("{}=", LICENSES_LABEL);
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let last_from = lines.iter().rposition(|line| {
+        line.trim_start()
+            .get(..5)
+            .is_some_and(|instruction| instruction.eq_ignore_ascii_case("FROM "))
+    });
+    let final_stage = last_from.unwrap_or(0);
+    if let Some((idx, start)) = lines
+        .iter()
+        .enumerate()
+        .skip(final_stage)
+        .find_map(|(idx, line)| Some((idx, line.find(&key)? + key.len())))
+    {
+        let line = &lines[idx];
+        let rest = &line[start..];
+        let len = if let Some(quoted) = rest.strip_prefix('"') {
+            quoted.find('"').map_or(rest.len(), |end| end + 2)
+        } else {
+            rest.find(char::is_whitespace).unwrap_or(rest.len())
+        };
+        if rest[..len] == value {
+            return None;
+        }
+        lines[idx] = format!("{}{}{}", &line[..start], value, &rest[len..]);
+    } else {
+        let position = last_from.map_or(lines.len(), |idx| idx + 1);
+        lines.insert(position, label_instruction(expression));
+    }
+    let line_ending = match content.find('\n') {
+        Some(end) if content[..end].ends_with('\r') => LineEnding::Crlf,
+        _ => LineEnding::Lf,
+    };
+    let mut patched = lines.join("\n");
+    if content.ends_with('\n') || content.is_empty() {
+        patched.push('\n');
+    }
+    Some(line_ending.apply(&patched))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::patch_dockerfile;
+
+    #[test]
+    fn test_patch_dockerfile() {
+        let dockerfile = "FROM rust AS build\nRUN cargo build\nFROM debian\nCMD [\"app\"]\n";
+        assert_eq!(
+            patch_dockerfile(dockerfile, "MIT").unwrap(),
+            "FROM rust AS build\nRUN cargo build\nFROM debian\nLABEL \
+             org.opencontainers.image.licenses=\"MIT\"\nCMD [\"app\"]\n"
+        );
+        let labelled = "FROM debian\nLABEL a=b org.opencontainers.image.licenses=\"GPL-2.0\" c=d\n";
+        assert_eq!(
+            patch_dockerfile(labelled, "MIT OR Apache-2.0").unwrap(),
+            "FROM debian\nLABEL a=b org.opencontainers.image.licenses=\"MIT OR Apache-2.0\" c=d\n"
+        );
+        assert_eq!(
+            patch_dockerfile("LABEL org.opencontainers.image.licenses=MIT\n", "ISC").unwrap(),
+            "LABEL org.opencontainers.image.licenses=\"ISC\"\n"
+        );
+        assert_eq!(
+            patch_dockerfile("LABEL org.opencontainers.image.licenses=\"MIT\"", "MIT"),
+            None
+        );
+        // Labels of earlier build stages are not part of the image.
+        let staged = "FROM rust AS build\r\nLABEL \
+                      org.opencontainers.image.licenses=\"MIT\"\r\nFROM debian\r\n";
+        assert_eq!(
+            patch_dockerfile(staged, "MIT").unwrap(),
+            "FROM rust AS build\r\nLABEL org.opencontainers.image.licenses=\"MIT\"\r\nFROM \
+             debian\r\nLABEL org.opencontainers.image.licenses=\"MIT\"\r\n"
+        );
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::Context;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use crate::{
+    config::{referenced_paths, resolve_paths, Config, Scope},
+    i18n::t,
+    util::{
+        errors::{Error, LictoolResult},
+        journal,
+    },
+};
+
+/// The file of a pack holding its settings.
+const PACK_SETTINGS_FILE: &str = "lictool.toml";
+/// The directory of a pack holding the files its settings reference.
+const PACK_FILES_DIR: &str = "files";
+
+/// The settings distributed by a pack: the custom license texts, the
+/// placeholder script, the policy, the profiles and the header
+/// comment styles. Identity settings such as `owner` stay on each
+/// machine.
+pub(crate) const PACK_KEYS: [&str; 5] = [
+    "overrides",
+    "placeholder-script",
+    "policy",
+    "profile",
+    "header",
+];
+
+/// Returns the directory the files of packs imported into the
+/// configuration of `scope` are written to.
+pub(crate) fn pack_dir(scope: Scope) -> PathBuf {
+    match scope {
+        Scope::User => {
+            Scope::User
+                .path()
+                .parent()
+                .map_or_else(|| PathBuf::from("pack"), |dir| dir.join("pack"))
+        }
+        Scope::Project => PathBuf::from(".lictool-pack"),
+    }
+}
+
+/// A standard lictool setup bundled into a single `.tar.gz` file, for
+/// organizations to distribute.
+///
+/// The paths of the settings point into `files`, relative to the
+/// pack.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct Pack {
+    pub(crate) settings: toml::Table,
+    pub(crate) files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl Pack {
+    /// Bundles the pack settings of a configuration file along with
+    /// the files they reference.
+    ///
+    /// # Errors
+    /// - If the configuration file or a referenced file cannot be
+    ///   read.
+    pub fn from_config_file(path: &Path) -> LictoolResult<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| t!("error-read-file", path = path.display().to_string()))?;
+        let mut table: toml::Table = toml::from_str(&content)
+            .with_context(|| t!("error-parse-file", path = path.display().to_string()))?;
+        resolve_paths(&mut table, path.parent().unwrap_or(Path::new("")));
+        let mut pack = Self {
+            settings: pack_settings(table),
+            files: BTreeMap::new(),
+        };
+        for value in referenced_paths(&mut pack.settings) {
+            let file = PathBuf::from(value.as_str().unwrap_or_default());
+            let contents = fs::read(&file)
+                .with_context(|| t!("error-read-file", path = file.display().to_string()))?;
+            let name = file.file_name().unwrap_or_default().to_string_lossy();
+            let mut relative = Path::new(PACK_FILES_DIR).join(name.as_ref());
+            // Files of the same name from different directories.
+            let mut suffix = 1;
+            while pack.files.contains_key(&relative) {
+                suffix += 1;
+                relative = Path::new(PACK_FILES_DIR).join(format!("{}-{}", suffix, name));
+            }
+            *value = toml::Value::String(relative.to_string_lossy().replace('\\', "/"));
+            pack.files.insert(relative, contents);
+        }
+        Ok(pack)
+    }
+
+    /// Reads a pack from a gzipped tarball, checking its settings.
+    ///
+    /// # Errors
+    /// - `Error::InvalidPack` if the tarball cannot be read, lacks
+    ///   the settings or references a file it does not hold.
+    /// - If a setting has the wrong type.
+    pub fn from_archive(
+        archive: &[u8],
+        source: &str,
+    ) -> LictoolResult<Self> {
+        let invalid = || {
+            Error::InvalidPack {
+                path: source.to_string(),
+            }
+        };
+        let mut files = BTreeMap::new();
+        let mut archive = tar::Archive::new(GzDecoder::new(archive));
+        for entry in archive.entries().map_err(|_| invalid())? {
+            let mut entry = entry.map_err(|_| invalid())?;
+            if !entry.header().entry_type().is_file() {
+                continue;
+            }
+            let relative = entry.path().map_err(|_| invalid())?.into_owned();
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents).map_err(|_| invalid())?;
+            // Paths escaping the pack are left out.
+            if relative
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+            {
+                files.insert(relative, contents);
+            }
+        }
+        let settings = files
+            .remove(Path::new(PACK_SETTINGS_FILE))
+            .ok_or_else(invalid)?;
+        let settings: toml::Table = toml::from_str(&String::from_utf8_lossy(&settings))
+            .with_context(|| t!("error-parse-file", path = source))?;
+        let mut pack = Self {
+            settings: pack_settings(settings),
+            files,
+        };
+        let _: Config = pack
+            .settings
+            .clone()
+            .try_into()
+            .with_context(|| t!("error-parse-file", path = source))?;
+        let missing = referenced_paths(&mut pack.settings)
+            .into_iter()
+            .any(|value| {
+                !pack
+                    .files
+                    .contains_key(Path::new(value.as_str().unwrap_or_default()))
+            });
+        if missing {
+            Err(invalid())?
+        }
+        Ok(pack)
+    }
+
+    /// Packs the settings and files into a gzipped tarball.
+    ///
+    /// # Errors
+    /// - If the tarball cannot be built.
+    pub fn to_archive(&self) -> LictoolResult<Vec<u8>> {
+        let settings = toml::to_string(&self.settings)?.into_bytes();
+        let mut archive = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::default()));
+        let files = self
+            .files
+            .iter()
+            .map(|(relative, contents)| (relative.as_path(), contents.as_slice()))
+            .chain([(Path::new(PACK_SETTINGS_FILE), settings.as_slice())]);
+        for (relative, contents) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            archive.append_data(&mut header, relative, contents)?;
+        }
+        Ok(archive.into_inner()?.finish()?)
+    }
+
+    /// Writes the files of the pack below `dir`.
+    ///
+    /// # Returns
+    ///
+    /// The settings of the pack, with their paths pointing into
+    /// `dir`.
+    ///
+    /// # Errors
+    /// - If a file cannot be written.
+    pub fn install(
+        &self,
+        dir: &Path,
+    ) -> LictoolResult<toml::Table> {
+        for (relative, contents) in &self.files {
+            let path = dir.join(relative);
+            let write_error = || t!("error-write-file", path = path.display().to_string());
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent).with_context(write_error)?;
+            }
+            journal::write(&path, contents).with_context(write_error)?;
+        }
+        let mut settings = self.settings.clone();
+        for value in referenced_paths(&mut settings) {
+            let path = dir.join(value.as_str().unwrap_or_default());
+            *value = toml::Value::String(path.to_string_lossy().replace('\\', "/"));
+        }
+        Ok(settings)
+    }
+}
+
+/// Keeps the settings of a configuration distributed by packs.
+fn pack_settings(mut table: toml::Table) -> toml::Table {
+    table.retain(|key, _| PACK_KEYS.contains(&key));
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::Pack;
+    use crate::util::errors::Error;
+
+    #[test]
+    fn test_pack_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let text = dir.path().join("acme.txt");
+        fs::write(&text, "Acme license").unwrap();
+        let config = dir.path().join("config.toml");
+        fs::write(
+            &config,
+            format!(
+                "owner = \"Jane\"\n[policy]\ndeny = \
+                 [\"GPL-3.0-only\"]\n[overrides.LicenseRef-Acme]\ntext = {:?}\n",
+                text.display().to_string()
+            ),
+        )
+        .unwrap();
+        let pack = Pack::from_config_file(&config).unwrap();
+        assert!(!pack.settings.contains_key("owner"));
+        assert_eq!(
+            pack.settings["overrides"]["LicenseRef-Acme"]["text"].as_str(),
+            Some("files/acme.txt")
+        );
+        let read = Pack::from_archive(&pack.to_archive().unwrap(), "pack.tar.gz").unwrap();
+        assert_eq!(read, pack);
+        let settings = read.install(&dir.path().join("pack")).unwrap();
+        let installed = settings["overrides"]["LicenseRef-Acme"]["text"]
+            .as_str()
+            .unwrap();
+        assert_eq!(fs::read_to_string(installed).unwrap(), "Acme license");
+        let error = Pack::from_archive(b"not a pack", "pack.tar.gz").unwrap_err();
+        assert!(matches!(
+            error.downcast_ref(),
+            Some(Error::InvalidPack { .. })
+        ));
+    }
+}
@@ -0,0 +1,309 @@
+use std::{cmp::Reverse, fmt, io};
+
+use console::{style, Key, Term};
+use dialoguer::theme::Theme;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+
+use crate::{consts::POPULAR, i18n::t, spdx::License};
+
+/// Key toggling the "OSI only" filter (Ctrl-O).
+pub(crate) const TOGGLE_OSI: char = '\u{f}';
+/// Key toggling the "hide deprecated" filter (Ctrl-D).
+pub(crate) const TOGGLE_DEPRECATED: char = '\u{4}';
+/// Key toggling the "popular only" filter (Ctrl-P).
+pub(crate) const TOGGLE_POPULAR: char = '\u{10}';
+
+/// A set of filters that can be toggled while the picker is open.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct PickerFilters {
+    /// Only show licenses approved by the OSI.
+    pub(crate) osi_only: bool,
+    /// Hide licenses with a deprecated ID.
+    pub(crate) hide_deprecated: bool,
+    /// Only show widely used licenses.
+    pub(crate) popular_only: bool,
+}
+
+impl PickerFilters {
+    /// Checks whether a license passes all enabled filters.
+    pub(crate) fn matches(
+        &self,
+        license: &License,
+    ) -> bool {
+        (!self.osi_only || license.is_osi_approved == Some(true))
+            && (!self.hide_deprecated || !license.is_deprecated_license_id)
+            && (!self.popular_only || POPULAR.contains(&license.id.as_str()))
+    }
+
+    /// Renders the keybinding hint line with the state of each
+    /// filter.
+    pub(crate) fn hint(&self) -> String {
+        let toggle = |label: &str, key: &str, enabled: bool| {
+            if enabled {
+                format!("{} {}", style(key).bold(), style(label).green().bold())
+            } else {
+                format!("{} {}", style(key).bold(), style(label).dim())
+            }
+        };
+        format!(
+            "  {}  {}  {}",
+            toggle(&t!("picker-filter-osi"), "^O", self.osi_only),
+            toggle(&t!("picker-filter-deprecated"), "^D", self.hide_deprecated),
+            toggle(&t!("picker-filter-popular"), "^P", self.popular_only),
+        )
+    }
+}
+
+/// An interactive fuzzy picker over SPDX licenses.
+///
+/// Unlike `dialoguer::FuzzySelect`, this picker lets users narrow the
+/// candidates with keybindings while it is open, instead of having to
+/// abort and re-run `list` with filter flags.
+pub(crate) struct LicensePicker<'t, 'a> {
+    theme: &'t dyn Theme,
+    prompt: String,
+    licenses: &'a [License],
+    labels: Vec<String>,
+    filters: PickerFilters,
+    groups: Vec<(String, Vec<usize>)>,
+    /// The rank of each license among equally good matches, lower
+    /// first.
+    ranks: Vec<Option<usize>>,
+    max_length: usize,
+}
+
+impl<'t, 'a> LicensePicker<'t, 'a> {
+    /// Creates a picker over the given licenses using the provided
+    /// theme.
+    pub(crate) fn new(
+        theme: &'t dyn Theme,
+        licenses: &'a [License],
+    ) -> Self {
+        Self {
+            theme,
+            prompt: String::new(),
+            licenses,
+            labels: licenses.iter().map(License::picker_label).collect(),
+            filters: PickerFilters::default(),
+            groups: Vec::new(),
+            ranks: vec![None; licenses.len()],
+            max_length: 7,
+        }
+    }
+
+    /// Sets the prompt displayed above the search field.
+    pub(crate) fn with_prompt<S: Into<String>>(
+        mut self,
+        prompt: S,
+    ) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Pins the given license IDs at the top of the picker under a
+    /// group heading, in the given order.
+    ///
+    /// Groups are displayed in the order they are added, and a
+    /// license only appears in the first group that contains it.
+    pub(crate) fn group<S: Into<String>>(
+        mut self,
+        heading: S,
+        ids: &[String],
+    ) -> Self {
+        let members = ids
+            .iter()
+            .filter_map(|id| self.licenses.iter().position(|license| license.id == *id))
+            .filter(|idx| self.group_of(*idx).is_none())
+            .collect();
+        self.groups.push((heading.into(), members));
+        self
+    }
+
+    /// Orders equally good matches by the given rank, lower first,
+    /// and licenses without a rank last.
+    pub(crate) fn rank_by(
+        mut self,
+        rank: impl Fn(&License) -> Option<usize>,
+    ) -> Self {
+        self.ranks = self.licenses.iter().map(rank).collect();
+        self
+    }
+
+    /// Sets the maximum number of candidates displayed at once.
+    pub(crate) fn max_length(
+        mut self,
+        max_length: usize,
+    ) -> Self {
+        self.max_length = max_length.max(1);
+        self
+    }
+
+    /// Formats a group heading.
+    fn heading(
+        &self,
+        text: &str,
+    ) -> String {
+        style(format!("  {}", text)).dim().italic().to_string()
+    }
+
+    /// Returns the position of the group containing the license, if
+    /// it is pinned.
+    fn group_of(
+        &self,
+        idx: usize,
+    ) -> Option<usize> {
+        self.groups
+            .iter()
+            .position(|(_, members)| members.contains(&idx))
+    }
+
+    /// Returns the indices of the licenses matching the query and the
+    /// enabled filters, pinned groups first and then the best
+    /// matches.
+    fn candidates(
+        &self,
+        matcher: &SkimMatcherV2,
+        query: &str,
+    ) -> Vec<usize> {
+        let is_match = |idx: usize| {
+            self.filters.matches(&self.licenses[idx])
+                && matcher.fuzzy_match(&self.labels[idx], query).is_some()
+        };
+        let mut scored: Vec<(usize, i64)> = self
+            .licenses
+            .iter()
+            .enumerate()
+            .filter(|(idx, license)| self.filters.matches(license) && self.group_of(*idx).is_none())
+            .filter_map(|(idx, _)| {
+                matcher
+                    .fuzzy_match(&self.labels[idx], query)
+                    .map(|score| (idx, score))
+            })
+            .collect();
+        scored.sort_by_key(|&(idx, score)| (Reverse(score), self.ranks[idx].unwrap_or(usize::MAX)));
+        self.groups
+            .iter()
+            .flat_map(|(_, members)| members.iter().copied())
+            .filter(|&idx| is_match(idx))
+            .chain(scored.into_iter().map(|(idx, _)| idx))
+            .collect()
+    }
+
+    /// Runs the picker on stderr and returns the selected license.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(None)` if the user cancelled the selection with `Esc`.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the terminal cannot be
+    /// read from or written to.
+    pub(crate) fn interact_opt(mut self) -> io::Result<Option<&'a License>> {
+        let term = Term::stderr();
+        let matcher = SkimMatcherV2::default();
+        let width = term.size().1 as usize;
+        let mut query = String::new();
+        let mut sel = 0;
+        let mut offset = 0;
+        let mut drawn = 0;
+
+        term.hide_cursor()?;
+        let picked = loop {
+            let candidates = self.candidates(&matcher, &query);
+            sel = sel.min(candidates.len().saturating_sub(1));
+            if sel < offset {
+                offset = sel;
+            } else if sel >= offset + self.max_length {
+                offset = sel + 1 - self.max_length;
+            }
+
+            let mut lines = Vec::with_capacity(self.max_length + 4);
+            let mut prompt = String::new();
+            self.theme
+                .format_fuzzy_select_prompt(&mut prompt, &self.prompt, &query, query.len())
+                .map_err(fmt_error)?;
+            lines.push(prompt);
+            lines.push(self.filters.hint());
+            for (pos, &idx) in candidates
+                .iter()
+                .enumerate()
+                .skip(offset)
+                .take(self.max_length)
+            {
+                // Pinned groups always come first, so a heading is
+                // needed whenever the group changes.
+                let group = self.group_of(idx);
+                let starts_group = pos == offset || self.group_of(candidates[pos - 1]) != group;
+                if starts_group {
+                    match group {
+                        Some(group) => lines.push(self.heading(&self.groups[group].0)),
+                        None if self.group_of(candidates[0]).is_some() => {
+                            lines.push(self.heading(&t!("picker-group-all")))
+                        }
+                        None => {}
+                    }
+                }
+                let mut item = String::new();
+                self.theme
+                    .format_fuzzy_select_prompt_item(
+                        &mut item,
+                        &self.labels[idx],
+                        pos == sel,
+                        true,
+                        &matcher,
+                        &query,
+                    )
+                    .map_err(fmt_error)?;
+                lines.push(item);
+            }
+
+            term.clear_last_lines(drawn)?;
+            for line in &lines {
+                term.write_line(&console::truncate_str(line, width, "…"))?;
+            }
+            drawn = lines.len();
+
+            match term.read_key()? {
+                Key::Escape => break None,
+                Key::Enter if !candidates.is_empty() => break Some(candidates[sel]),
+                Key::ArrowUp | Key::BackTab if !candidates.is_empty() => {
+                    sel = sel.checked_sub(1).unwrap_or(candidates.len() - 1);
+                }
+                Key::ArrowDown | Key::Tab if !candidates.is_empty() => {
+                    sel = (sel + 1) % candidates.len();
+                }
+                Key::Backspace => {
+                    query.pop();
+                    sel = 0;
+                }
+                Key::Char(TOGGLE_OSI) => self.filters.osi_only = !self.filters.osi_only,
+                Key::Char(TOGGLE_DEPRECATED) => {
+                    self.filters.hide_deprecated = !self.filters.hide_deprecated
+                }
+                Key::Char(TOGGLE_POPULAR) => self.filters.popular_only = !self.filters.popular_only,
+                Key::Char(c) if !c.is_control() => {
+                    query.push(c);
+                    sel = 0;
+                }
+                _ => {}
+            }
+        };
+        term.clear_last_lines(drawn)?;
+        term.show_cursor()?;
+
+        let picked = picked.map(|idx| &self.licenses[idx]);
+        if let Some(license) = picked {
+            let mut report = String::new();
+            self.theme
+                .format_input_prompt_selection(&mut report, &self.prompt, &license.id)
+                .map_err(fmt_error)?;
+            term.write_line(&report)?;
+        }
+        Ok(picked)
+    }
+}
+
+fn fmt_error(_: fmt::Error) -> io::Error {
+    io::Error::other("failed to format picker output")
+}
@@ -0,0 +1,216 @@
+use color_print::cprintln;
+
+use crate::{i18n::t, spdx::LicenseDetails, template::Field};
+
+/// The longest text between brackets considered a placeholder.
+const MAX_PLACEHOLDER_LENGTH: usize = 80;
+
+/// The placeholders found in a license text.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PlaceholderReport {
+    /// Each field with the placeholders of it found in the text.
+    pub(crate) fields: Vec<(Field, Vec<&'static str>)>,
+    /// Bracketed text that looks like a placeholder but is not
+    /// filled by any field.
+    pub(crate) others: Vec<String>,
+}
+
+/// Lists the placeholders of a license text, by field.
+///
+/// Fields are detected like `LicenseDetails::has_*` does, and any
+/// other short `<...>` or `[...]` text is reported separately, since
+/// `add` cannot fill it.
+pub(crate) fn find_placeholders(details: &LicenseDetails) -> PlaceholderReport {
+    let text = &details.license_text;
+    let present = Field::present_in(details);
+    let fields = Field::ALL
+        .into_iter()
+        .map(|field| {
+            let found = if present.contains(&field) {
+                field
+                    .placeholders()
+                    .iter()
+                    .copied()
+                    .filter(|placeholder| text.contains(placeholder))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            (field, found)
+        })
+        .collect();
+    PlaceholderReport {
+        fields,
+        others: other_placeholders(text),
+    }
+}
+
+/// Returns the known placeholders still present in a text, in the
+/// order of the fields.
+pub(crate) fn unfilled_placeholders(text: &str) -> Vec<&'static str> {
+    Field::ALL
+        .iter()
+        .flat_map(Field::placeholders)
+        .copied()
+        .filter(|placeholder| text.contains(placeholder))
+        .collect()
+}
+
+/// Finds the next `{{key}}` placeholder of a text, returning its
+/// byte range and its key.
+///
+/// Keys consist of letters, digits, `_` and `-`, and may be
+/// surrounded by spaces, as in `{{ year }}`.
+fn next_key(text: &str) -> Option<(usize, usize, &str)> {
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find("{{").map(|idx| offset + idx) {
+        let len = text[start + 2..].find("}}")?;
+        let key = text[start + 2..start + 2 + len].trim();
+        if !key.is_empty()
+            && key
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            return Some((start, start + len + 4, key));
+        }
+        offset = start + 2;
+    }
+    None
+}
+
+/// Lists the keys of the `{{key}}` placeholders of a text, in the
+/// order they first appear.
+pub(crate) fn template_keys(text: &str) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    let mut rest = text;
+    while let Some((_, end, key)) = next_key(rest) {
+        if !keys.iter().any(|known| known == key) {
+            keys.push(key.to_string());
+        }
+        rest = &rest[end..];
+    }
+    keys
+}
+
+/// Replaces the `{{key}}` placeholders of a text with the values
+/// `lookup` returns, leaving those without a value in place.
+pub(crate) fn render_keys(
+    text: &str,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> String {
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((start, end, key)) = next_key(rest) {
+        rendered.push_str(&rest[..start]);
+        match lookup(key) {
+            Some(value) => rendered.push_str(&value),
+            None => rendered.push_str(&rest[start..end]),
+        }
+        rest = &rest[end..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Finds short bracketed texts that are not known placeholders,
+/// skipping links and email addresses.
+fn other_placeholders(text: &str) -> Vec<String> {
+    let known = unfilled_placeholders(text);
+    let mut others: Vec<String> = Vec::new();
+    for (open, close) in [('<', '>'), ('[', ']')] {
+        let mut rest = text;
+        while let Some(start) = rest.find(open) {
+            rest = &rest[start..];
+            let Some(end) = rest[1..].find([close, open, '\n']).map(|idx| idx + 1) else {
+                break;
+            };
+            let candidate = &rest[..=end];
+            rest = &rest[end..];
+            if !candidate.ends_with(close) {
+                continue;
+            }
+            let inner = &candidate[1..candidate.len() - 1];
+            if inner.len() <= MAX_PLACEHOLDER_LENGTH
+                && inner.chars().any(char::is_alphabetic)
+                && !inner.contains("://")
+                && !inner.contains('@')
+                && !known.contains(&candidate)
+                && !others.iter().any(|other| other == candidate)
+            {
+                others.push(candidate.to_string());
+            }
+        }
+    }
+    others
+}
+
+/// Prints which fields a license needs, with the `add` flags filling
+/// them.
+pub(crate) fn display_placeholders(
+    license_id: &str,
+    report: &PlaceholderReport,
+) {
+    cprintln!(
+        "<bold>{}</>",
+        t!("placeholders-heading", license = license_id)
+    );
+    let width = report
+        .fields
+        .iter()
+        .map(|(field, _)| field.label().chars().count())
+        .max()
+        .unwrap_or(0);
+    for (field, found) in &report.fields {
+        let label = format!("{:width$}", field.label(), width = width);
+        if found.is_empty() {
+            cprintln!("  <dim>✘ {}  {:7}</>", label, field.flag());
+        } else {
+            cprintln!(
+                "  <green>✔</> {}  <cyan>{:7}</>  {}",
+                label,
+                field.flag(),
+                found.join(", ")
+            );
+        }
+    }
+    if !report.others.is_empty() {
+        cprintln!(
+            "<yellow>{}</> {}",
+            t!("placeholders-others"),
+            report.others.join(", ")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{other_placeholders, render_keys, template_keys, unfilled_placeholders};
+
+    #[test]
+    fn test_find_placeholders() {
+        let text = "Copyright (C) <year> <name of author>\n\
+                    <program>  Copyright (C) <year>  <name of author>\n\
+                    See <https://www.gnu.org/licenses/> or write to <gnu@gnu.org>.\n\
+                    Licensed to [COMPANY] under <license\nterms>.";
+        assert_eq!(
+            unfilled_placeholders(text),
+            vec!["<name of author>", "<year>", "<program>"]
+        );
+        assert_eq!(other_placeholders(text), vec!["[COMPANY]"]);
+    }
+
+    #[test]
+    fn test_render_keys() {
+        let text = "Copyright {{ year }} {{owner}} for {{department}}.\n{{owner}} keeps {{ not a \
+                    key }} and {{}} as-is.";
+        assert_eq!(template_keys(text), ["year", "owner", "department"]);
+        assert_eq!(
+            render_keys(text, |key| {
+                (key != "department").then(|| key.to_uppercase())
+            }),
+            "Copyright YEAR OWNER for {{department}}.\nOWNER keeps {{ not a key }} and {{}} as-is."
+        );
+    }
+}
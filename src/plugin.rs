@@ -0,0 +1,236 @@
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use anyhow::Context;
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    i18n::{self, t},
+    util::{
+        errors::{Error, LictoolResult},
+        schema::closest,
+    },
+};
+
+/// The prefix of plugin executables, such as `lictool-jira` for
+/// `lictool jira`.
+const PLUGIN_PREFIX: &str = "lictool-";
+
+/// The environment variable holding the `PluginContext` as JSON.
+const CONTEXT_VAR: &str = "LICTOOL_PLUGIN_CONTEXT";
+
+/// What a plugin is told about the invocation, so that it can behave
+/// like a built-in command.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct PluginContext {
+    /// The version of lictool running the plugin.
+    pub(crate) version: &'static str,
+    /// The lictool executable, for plugins calling back into it.
+    pub(crate) executable: Option<PathBuf>,
+    /// The language of messages, such as `en` or `de`.
+    pub(crate) lang: &'static str,
+    /// The profile selected with `--profile`.
+    pub(crate) profile: Option<String>,
+    /// The configuration file given with `--config`.
+    pub(crate) config_file: Option<PathBuf>,
+    pub(crate) default_license: Option<String>,
+    pub(crate) default_filename: String,
+    pub(crate) dry_run: bool,
+    pub(crate) quiet: bool,
+    pub(crate) verify_integrity: bool,
+    pub(crate) log_json: bool,
+}
+
+impl PluginContext {
+    /// Describes the current invocation, with the settings of
+    /// `config` if it could be loaded.
+    pub fn new(
+        config: Option<&Config>,
+        profile: Option<&str>,
+        config_file: Option<&Path>,
+        dry_run: bool,
+        quiet: bool,
+        verify_integrity: bool,
+        log_json: bool,
+    ) -> Self {
+        let default = Config::default();
+        let config = config.unwrap_or(&default);
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            executable: env::current_exe().ok(),
+            lang: i18n::lang(),
+            profile: profile.map(str::to_string),
+            config_file: config_file.map(Path::to_path_buf),
+            default_license: config.default_license().map(str::to_string),
+            default_filename: config.default_filename().to_string(),
+            dry_run,
+            quiet,
+            verify_integrity,
+            log_json,
+        }
+    }
+}
+
+/// Finds the executable of a plugin in the directories of
+/// `search_path`, a `PATH`-like list.
+fn find_plugin(
+    name: &str,
+    search_path: &OsStr,
+) -> Option<PathBuf> {
+    let file_name = format!("{}{}{}", PLUGIN_PREFIX, name, env::consts::EXE_SUFFIX);
+    env::split_paths(search_path)
+        .map(|dir| dir.join(&file_name))
+        .find(|path| path.is_file())
+}
+
+/// Returns the names of the plugins in the directories of
+/// `search_path`, such as `jira` for `lictool-jira`.
+fn plugin_names(search_path: &OsStr) -> Vec<String> {
+    env::split_paths(search_path)
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.ok()?.file_name().into_string().ok()?;
+            let name = file_name
+                .strip_prefix(PLUGIN_PREFIX)?
+                .trim_end_matches(env::consts::EXE_SUFFIX);
+            Some(name.to_string()).filter(|name| !name.is_empty())
+        })
+        .collect()
+}
+
+/// Runs the plugin named by the first of `args` with the remaining
+/// arguments, passing `context` as JSON in `LICTOOL_PLUGIN_CONTEXT`.
+///
+/// # Arguments
+///
+/// * `commands` - The built-in commands, suggested with the plugins
+///   when no plugin has the name.
+///
+/// # Errors
+/// - `Error::UnknownCommand` if no such plugin is on the `PATH`.
+/// - If the plugin cannot be started.
+/// - `Error::PluginFailed` with the exit code of a failing plugin.
+pub(crate) fn run_plugin(
+    args: &[OsString],
+    context: &PluginContext,
+    commands: &[String],
+) -> LictoolResult<()> {
+    let search_path = env::var_os("PATH").unwrap_or_default();
+    run_plugin_in(args, context, commands, &search_path)
+}
+
+/// Runs a plugin like `run_plugin`, looking for it in the directories
+/// of `search_path`.
+fn run_plugin_in(
+    args: &[OsString],
+    context: &PluginContext,
+    commands: &[String],
+    search_path: &OsStr,
+) -> LictoolResult<()> {
+    let name = args
+        .first()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let Some(executable) = find_plugin(&name, search_path) else {
+        let plugins = plugin_names(search_path);
+        let candidates = commands.iter().chain(&plugins).map(String::as_str);
+        Err(Error::UnknownCommand {
+            suggestion: closest(&name, candidates).map(str::to_string),
+            name,
+        })?
+    };
+    let status = Command::new(&executable)
+        .args(&args[1..])
+        .env(CONTEXT_VAR, serde_json::to_string(context)?)
+        .status()
+        .with_context(|| {
+            t!(
+                "error-run-plugin",
+                plugin = executable.display().to_string()
+            )
+        })?;
+    if !status.success() {
+        Err(Error::PluginFailed {
+            name,
+            code: status.code().unwrap_or(1),
+        })?
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::OsString, fs};
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::{find_plugin, plugin_names, run_plugin_in, PluginContext};
+    use crate::util::errors::{exit_code, Error};
+
+    /// Writes a plugin script exiting with `code` to `dir`.
+    #[cfg(unix)]
+    fn write_plugin(
+        dir: &TempDir,
+        name: &str,
+        code: i32,
+    ) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.path().join(format!("lictool-{}", name));
+        fs::write(&path, format!("#!/bin/sh\nexit {}\n", code)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plugin_discovery() {
+        let dir = TempDir::new().unwrap();
+        write_plugin(&dir, "jira", 0);
+        let search_path = dir.path().as_os_str();
+        assert_eq!(
+            find_plugin("jira", search_path),
+            Some(dir.path().join("lictool-jira"))
+        );
+        assert_eq!(find_plugin("nope", search_path), None);
+        assert_eq!(plugin_names(search_path), ["jira"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_plugin() {
+        let dir = TempDir::new().unwrap();
+        write_plugin(&dir, "ok", 0);
+        write_plugin(&dir, "fail", 3);
+        let context = PluginContext::new(None, None, None, false, false, false, false);
+        let commands = vec!["check".to_string()];
+        let run = |name: &str| {
+            run_plugin_in(
+                &[OsString::from(name)],
+                &context,
+                &commands,
+                dir.path().as_os_str(),
+            )
+        };
+        assert!(run("ok").is_ok());
+        let failed = run("fail").unwrap_err();
+        assert_eq!(exit_code(&failed), 3);
+        let unknown = run("chek").unwrap_err();
+        assert!(matches!(
+            unknown.downcast_ref::<Error>(),
+            Some(Error::UnknownCommand { suggestion: Some(suggestion), .. }) if suggestion == "check"
+        ));
+        let unknown = run("fial").unwrap_err();
+        assert!(matches!(
+            unknown.downcast_ref::<Error>(),
+            Some(Error::UnknownCommand { suggestion: Some(suggestion), .. }) if suggestion == "fail"
+        ));
+    }
+}
@@ -0,0 +1,152 @@
+use color_print::cprintln;
+use serde::Deserialize;
+
+use crate::{
+    expression::{Expression, LicenseTerm},
+    i18n::t,
+};
+
+/// The licenses a project accepts, read from the `[policy]` section
+/// of the configuration.
+///
+/// Entries are license IDs, or full terms such as
+/// `GPL-2.0-only WITH Classpath-exception-2.0`.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct PolicyConfig {
+    /// Licenses that are accepted; when empty, every license that is
+    /// not denied is accepted.
+    pub(crate) allow: Vec<String>,
+    /// Licenses that are never accepted.
+    pub(crate) deny: Vec<String>,
+}
+
+/// The outcome of evaluating part of an expression against a policy.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Verdict {
+    /// The evaluated part of the expression.
+    pub(crate) subject: String,
+    /// Whether this part can be satisfied.
+    pub(crate) satisfied: bool,
+    /// Why a license was accepted or rejected, or how the operands
+    /// combine.
+    pub(crate) reason: String,
+    /// The verdicts of the operands.
+    pub(crate) operands: Vec<Verdict>,
+}
+
+impl PolicyConfig {
+    /// Evaluates an expression, which is satisfiable if every operand
+    /// of an `AND` and at least one alternative of an `OR` is
+    /// accepted.
+    pub fn evaluate(
+        &self,
+        expression: &Expression,
+    ) -> Verdict {
+        match expression {
+            Expression::License(term) => self.evaluate_term(term),
+            Expression::And(operands) => {
+                let operands: Vec<Verdict> = operands
+                    .iter()
+                    .map(|operand| self.evaluate(operand))
+                    .collect();
+                Verdict {
+                    subject: expression.to_string(),
+                    satisfied: operands.iter().all(|verdict| verdict.satisfied),
+                    reason: t!("policy-all-of"),
+                    operands,
+                }
+            }
+            Expression::Or(operands) => {
+                let operands: Vec<Verdict> = operands
+                    .iter()
+                    .map(|operand| self.evaluate(operand))
+                    .collect();
+                Verdict {
+                    subject: expression.to_string(),
+                    satisfied: operands.iter().any(|verdict| verdict.satisfied),
+                    reason: t!("policy-any-of"),
+                    operands,
+                }
+            }
+        }
+    }
+
+    /// Evaluates a single license against the allow and deny lists.
+    fn evaluate_term(
+        &self,
+        term: &LicenseTerm,
+    ) -> Verdict {
+        let subject = term.to_string();
+        let listed = |list: &[String]| {
+            list.iter()
+                .any(|entry| *entry == term.id || *entry == subject)
+        };
+        let (satisfied, reason) = if listed(&self.deny) {
+            (false, t!("policy-denied"))
+        } else if listed(&self.allow) {
+            (true, t!("policy-allowed"))
+        } else if self.allow.is_empty() {
+            (true, t!("policy-no-allow-list"))
+        } else {
+            (false, t!("policy-not-allowed"))
+        };
+        Verdict {
+            subject,
+            satisfied,
+            reason,
+            operands: Vec::new(),
+        }
+    }
+}
+
+/// Prints a verdict as an indented trace, one line per operand.
+pub(crate) fn display_verdict(
+    verdict: &Verdict,
+    depth: usize,
+) {
+    let indent = "  ".repeat(depth);
+    if verdict.satisfied {
+        cprintln!(
+            "{}<green>✔</> <bold>{}</> <dim>{}</>",
+            indent,
+            verdict.subject,
+            verdict.reason
+        );
+    } else {
+        cprintln!(
+            "{}<red>✘</> <bold>{}</> <dim>{}</>",
+            indent,
+            verdict.subject,
+            verdict.reason
+        );
+    }
+    for operand in &verdict.operands {
+        display_verdict(operand, depth + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PolicyConfig;
+
+    #[test]
+    fn test_evaluate_policy() {
+        let policy = PolicyConfig {
+            allow: vec!["MIT".to_string(), "Apache-2.0".to_string()],
+            deny: vec!["GPL-3.0-only".to_string()],
+        };
+        let evaluate = |expression: &str| policy.evaluate(&expression.parse().unwrap());
+        assert!(evaluate("MIT OR GPL-3.0-only").satisfied);
+        assert!(!evaluate("MIT AND GPL-3.0-only").satisfied);
+        assert!(!evaluate("ISC").satisfied);
+        let verdict = evaluate("GPL-3.0-only OR (MIT AND Apache-2.0)");
+        assert!(verdict.satisfied);
+        assert!(!verdict.operands[0].satisfied);
+        assert!(
+            PolicyConfig::default()
+                .evaluate(&"ISC".parse().unwrap())
+                .satisfied
+        );
+    }
+}
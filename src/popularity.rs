@@ -0,0 +1,142 @@
+use std::io::{self, BufWriter, Write};
+
+use serde::Serialize;
+
+use crate::{consts::POPULAR, display::color_id, spdx::License, util::errors::LictoolResult};
+
+/// The share of licensed GitHub repositories using each license, in
+/// percent, most popular first.
+///
+/// Taken from GitHub's published license usage statistics, which
+/// only cover the most common licenses.
+const POPULARITY: [(&str, f64); 9] = [
+    ("MIT", 44.69),
+    ("GPL-2.0-only", 12.96),
+    ("Apache-2.0", 11.19),
+    ("GPL-3.0-only", 8.88),
+    ("BSD-3-Clause", 4.53),
+    ("Unlicense", 1.87),
+    ("BSD-2-Clause", 1.70),
+    ("LGPL-3.0-only", 1.30),
+    ("AGPL-3.0-only", 1.05),
+];
+
+/// Returns the popularity rank of a license, `0` being the most
+/// popular, or `None` if it is not known to be popular.
+///
+/// Licenses with usage data come first, followed by the other
+/// `POPULAR` licenses. Deprecated GNU IDs such as `GPL-2.0` share the
+/// rank of their `-only` form.
+pub(crate) fn rank(license_id: &str) -> Option<usize> {
+    POPULARITY
+        .iter()
+        .position(|(id, _)| *id == license_id || id.strip_suffix("-only") == Some(license_id))
+        .or_else(|| {
+            POPULAR
+                .iter()
+                .position(|id| *id == license_id)
+                .map(|idx| POPULARITY.len() + idx)
+        })
+}
+
+/// Returns the share of repositories using a license, in percent.
+pub(crate) fn share(license_id: &str) -> Option<f64> {
+    rank(license_id)
+        .and_then(|rank| POPULARITY.get(rank))
+        .map(|(_, share)| *share)
+}
+
+/// Keeps the popular licenses, most popular first.
+pub(crate) fn most_popular<'a>(licenses: &[&'a License]) -> Vec<&'a License> {
+    let mut popular: Vec<&License> = licenses
+        .iter()
+        .copied()
+        .filter(|license| rank(&license.id).is_some())
+        .collect();
+    popular.sort_by_key(|license| rank(&license.id));
+    popular
+}
+
+/// A license along with its share of repositories, as listed by
+/// `list --popular --format json`.
+#[derive(Debug, Serialize)]
+pub(crate) struct PopularLicense<'a> {
+    #[serde(flatten)]
+    pub(crate) license: &'a License,
+    /// The share of repositories using the license, in percent.
+    pub(crate) share: Option<f64>,
+}
+
+/// Pairs licenses with their share of repositories.
+pub(crate) fn with_shares<'a>(licenses: &[&'a License]) -> Vec<PopularLicense<'a>> {
+    licenses
+        .iter()
+        .map(|license| {
+            PopularLicense {
+                license,
+                share: share(&license.id),
+            }
+        })
+        .collect()
+}
+
+/// Prints licenses with their share of repositories, or bare IDs in
+/// plain mode.
+pub(crate) fn display_popular(
+    licenses: &[&License],
+    plain: bool,
+) -> LictoolResult<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let width = licenses
+        .iter()
+        .map(|license| license.id.len())
+        .max()
+        .unwrap_or(0);
+    for license in licenses {
+        if plain {
+            writeln!(out, "{}", license.id)?;
+        } else {
+            let padding = " ".repeat(width - license.id.len());
+            match share(&license.id) {
+                Some(share) => writeln!(out, "{}{}  {:>5.2}%", color_id(license), padding, share)?,
+                None => writeln!(out, "{}", color_id(license))?,
+            }
+        }
+    }
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{rank, share, with_shares, POPULARITY};
+    use crate::spdx::License;
+
+    #[test]
+    fn test_rank() {
+        assert_eq!(rank("MIT"), Some(0));
+        assert_eq!(rank("GPL-2.0"), Some(1));
+        assert_eq!(rank("GPL-2.0-only"), Some(1));
+        assert_eq!(rank("GPL-2.0-or-later"), Some(POPULARITY.len() + 5));
+        assert_eq!(share("GPL-2.0-or-later"), None);
+        assert_eq!(rank("WTFPL"), None);
+    }
+
+    #[test]
+    fn test_with_shares_json() {
+        let license = License {
+            is_deprecated_license_id: false,
+            details_url: "https://spdx.org/licenses/MIT.json".to_string(),
+            name: "MIT License".to_string(),
+            id: "MIT".to_string(),
+            is_osi_approved: Some(true),
+            is_fsf_libre: Some(true),
+        };
+        let json = serde_json::to_value(with_shares(&[&license])).unwrap();
+        assert_eq!(json[0]["licenseId"], "MIT");
+        assert_eq!(json[0]["isOsiApproved"], true);
+        assert_eq!(json[0]["share"], 44.69);
+    }
+}
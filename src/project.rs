@@ -0,0 +1,747 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use toml_edit::{value, DocumentMut};
+
+/// The names license files start with, in uppercase.
+const LICENSE_FILE_PREFIXES: [&str; 4] = ["LICENSE", "LICENCE", "COPYING", "UNLICENSE"];
+
+/// The extensions of plain text license files, which follow the
+/// extension of the configured license file name.
+const TEXT_EXTENSIONS: [&str; 3] = ["md", "markdown", "txt"];
+
+/// The manifest keys naming the license of a package.
+const CARGO_LICENSE_KEYS: [&str; 2] = ["license", "license-file"];
+
+/// Replaces the license fields in the content of a manifest, or
+/// removes them without a license, returning `None` if it has none.
+/// With `insert`, a license is added to a manifest declaring none.
+type LicenseEditor = fn(&str, Option<&str>, bool) -> Option<String>;
+
+/// The manifests declaring the license of a project, with the editor
+/// of their license fields.
+const MANIFESTS: [(&str, LicenseEditor); 3] = [
+    ("Cargo.toml", cargo_license),
+    ("package.json", package_json_license),
+    ("pyproject.toml", pyproject_license),
+];
+
+/// A manifest whose license fields change along with the license.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ManifestEdit {
+    pub(crate) path: PathBuf,
+    /// The manifest with its license fields replaced or removed.
+    pub(crate) content: String,
+}
+
+/// Lists the license files in `dir`, such as `LICENSE.md`,
+/// `LICENSE-MIT.md` or `COPYING`, along with the configured license
+/// file name.
+pub(crate) fn license_files(
+    dir: &Path,
+    default_filename: &str,
+) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path.file_name().is_some_and(|name| {
+                    let name = name.to_string_lossy();
+                    name.eq_ignore_ascii_case(default_filename) || is_license_file_name(&name)
+                })
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Checks whether a file name is one license files commonly have,
+/// such as `LICENSE`, `LICENSE-MIT.md` or `COPYING`.
+pub(crate) fn is_license_file_name(name: &str) -> bool {
+    let name = name.to_uppercase();
+    LICENSE_FILE_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// Returns the main license file in `dir`: the configured license
+/// file name if it exists, or else the first license file found.
+pub(crate) fn main_license_file(
+    dir: &Path,
+    default_filename: &str,
+) -> Option<PathBuf> {
+    let default_path = dir.join(default_filename);
+    if default_path.is_file() {
+        Some(default_path)
+    } else {
+        license_files(dir, default_filename).into_iter().next()
+    }
+}
+
+/// Returns the name a license file should have by convention: its
+/// prefix in uppercase, spelled `LICENSE` rather than `LICENCE`, and
+/// a plain text extension matching that of `default_filename`. Other
+/// suffixes, such as that of `COPYING.LESSER`, keep their case.
+///
+/// # Returns
+///
+/// `None` if `name` is not the name of a license file.
+pub(crate) fn conventional_license_file_name(
+    name: &str,
+    default_filename: &str,
+) -> Option<String> {
+    if name.eq_ignore_ascii_case(default_filename) {
+        return Some(default_filename.to_string());
+    }
+    let (stem, extension) = split_extension(name);
+    let upper = stem.to_uppercase();
+    let prefix = LICENSE_FILE_PREFIXES
+        .iter()
+        .find(|prefix| upper.starts_with(*prefix))?;
+    let rest = &stem[prefix.len()..];
+    let prefix = if *prefix == "LICENCE" {
+        "LICENSE"
+    } else {
+        prefix
+    };
+    let default_extension = split_extension(default_filename)
+        .1
+        .map(str::to_lowercase)
+        .filter(|extension| TEXT_EXTENSIONS.contains(&extension.as_str()));
+    let extension = extension.map(|extension| {
+        let lower = extension.to_lowercase();
+        match &default_extension {
+            _ if !TEXT_EXTENSIONS.contains(&lower.as_str()) => extension.to_string(),
+            Some(default) => default.clone(),
+            None => lower,
+        }
+    });
+    Some(match extension {
+        Some(extension) => format!("{}{}.{}", prefix, rest, extension),
+        None => format!("{}{}", prefix, rest),
+    })
+}
+
+/// Splits a file name at its last dot, if any.
+fn split_extension(name: &str) -> (&str, Option<&str>) {
+    match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (name, None),
+    }
+}
+
+/// Finds the manifests in `dir` that reference the license file
+/// `old`, returning their content referencing `new` instead: the
+/// `license-file` of a Cargo manifest, or `SEE LICENSE IN <file>` in
+/// a `package.json`.
+pub(crate) fn license_file_reference_edits(
+    dir: &Path,
+    old: &str,
+    new: &str,
+) -> Vec<ManifestEdit> {
+    let cargo = dir.join("Cargo.toml");
+    let cargo_edit = fs::read_to_string(&cargo)
+        .ok()
+        .and_then(|content| cargo_license_file(&content, old, new))
+        .map(|content| {
+            ManifestEdit {
+                path: cargo,
+                content,
+            }
+        });
+    let package_json = dir.join("package.json");
+    let package_json_edit = fs::read_to_string(&package_json)
+        .ok()
+        .and_then(|content| {
+            let reference = |file: &str| format!("\"SEE LICENSE IN {}\"", file);
+            content
+                .contains(&reference(old))
+                .then(|| content.replacen(&reference(old), &reference(new), 1))
+        })
+        .map(|content| {
+            ManifestEdit {
+                path: package_json,
+                content,
+            }
+        });
+    cargo_edit.into_iter().chain(package_json_edit).collect()
+}
+
+/// Replaces a `license-file` of `old` in the `[package]` and
+/// `[workspace.package]` tables of a Cargo manifest with `new`,
+/// keeping its formatting.
+///
+/// # Returns
+///
+/// `None` if the manifest is invalid or references no such file.
+fn cargo_license_file(
+    content: &str,
+    old: &str,
+    new: &str,
+) -> Option<String> {
+    let mut document = content.parse::<DocumentMut>().ok()?;
+    let mut changed = false;
+    for section in [&["package"][..], &["workspace", "package"]] {
+        let table = section
+            .iter()
+            .try_fold(document.as_table_mut(), |table, key| {
+                table.get_mut(key)?.as_table_mut()
+            });
+        let Some(license_file) = table.and_then(|table| table.get_mut("license-file")) else {
+            continue;
+        };
+        if license_file
+            .as_str()
+            .is_some_and(|file| file.trim_start_matches("./") == old)
+        {
+            *license_file = value(new);
+            changed = true;
+        }
+    }
+    changed.then(|| document.to_string())
+}
+
+/// Finds the manifests in `dir` that declare a license, returning
+/// their content with `license` instead, or without a license.
+pub(crate) fn manifest_edits(
+    dir: &Path,
+    license: Option<&str>,
+) -> Vec<ManifestEdit> {
+    edit_manifests(dir, license, false)
+}
+
+/// Returns the content of every manifest in `dir` declaring
+/// `license`, whether it declared another license or none.
+///
+/// Manifests already declaring `license` are left out.
+pub(crate) fn manifest_declarations(
+    dir: &Path,
+    license: &str,
+) -> Vec<ManifestEdit> {
+    edit_manifests(dir, Some(license), true)
+        .into_iter()
+        .filter(|edit| fs::read_to_string(&edit.path).ok().as_ref() != Some(&edit.content))
+        .collect()
+}
+
+/// Returns the names of the manifests in `dir`, whether they declare
+/// a license or not.
+pub(crate) fn manifest_names(dir: &Path) -> Vec<&'static str> {
+    MANIFESTS
+        .iter()
+        .map(|(name, _)| *name)
+        .filter(|name| dir.join(name).is_file())
+        .collect()
+}
+
+/// Edits the license fields of the manifests in `dir`.
+fn edit_manifests(
+    dir: &Path,
+    license: Option<&str>,
+    insert: bool,
+) -> Vec<ManifestEdit> {
+    MANIFESTS
+        .into_iter()
+        .filter_map(|(name, edit)| {
+            let path = dir.join(name);
+            let content = edit(&fs::read_to_string(&path).ok()?, license, insert)?;
+            Some(ManifestEdit {
+                path,
+                content,
+            })
+        })
+        .collect()
+}
+
+/// Reads the license IDs declared by the manifests in `dir`, from the
+/// `license` of a Cargo manifest, a `package.json` or the `[project]`
+/// of a `pyproject.toml`.
+///
+/// A Cargo package inheriting its license with `license.workspace =
+/// true` declares that of its workspace. A `license-file` names no
+/// license, so it is left out.
+pub(crate) fn manifest_licenses(dir: &Path) -> Vec<(PathBuf, String)> {
+    let mut licenses = Vec::new();
+    let cargo = dir.join("Cargo.toml");
+    if let Some(manifest) = read_toml(&cargo) {
+        let license = match manifest
+            .get("package")
+            .and_then(|package| package.get("license"))
+        {
+            Some(license) if is_inherited(license) => workspace_license(dir),
+            Some(license) => license.as_str().map(str::to_string),
+            None => own_workspace_license(&manifest),
+        };
+        if let Some(license) = license {
+            licenses.push((cargo, license));
+        }
+    }
+    let package_json = dir.join("package.json");
+    if let Some(license) = fs::read_to_string(&package_json)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|manifest| manifest.get("license")?.as_str().map(str::to_string))
+    {
+        licenses.push((package_json, license));
+    }
+    let pyproject = dir.join("pyproject.toml");
+    if let Some(license) = fs::read_to_string(&pyproject)
+        .ok()
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .and_then(|manifest| {
+            // The license was a table with its text before PEP 639.
+            let license = manifest.get("project")?.get("license")?;
+            license
+                .as_str()
+                .or_else(|| license.get("text")?.as_str())
+                .map(str::to_string)
+        })
+    {
+        licenses.push((pyproject, license));
+    }
+    licenses
+}
+
+/// Returns the license of the Cargo workspace `dir` belongs to, from
+/// the `[workspace.package]` table of its root manifest.
+pub(crate) fn workspace_license(dir: &Path) -> Option<String> {
+    own_workspace_license(&workspace_root(dir)?.1)
+}
+
+/// Finds the root of the Cargo workspace `dir` belongs to: the
+/// nearest directory, `dir` itself or above it, whose manifest has a
+/// `[workspace]`.
+///
+/// # Returns
+///
+/// The directory and its manifest.
+pub(crate) fn workspace_root(dir: &Path) -> Option<(PathBuf, toml::Table)> {
+    dir.canonicalize()
+        .ok()?
+        .ancestors()
+        .filter_map(|ancestor| {
+            let manifest = read_toml(&ancestor.join("Cargo.toml"))?;
+            Some((ancestor.to_path_buf(), manifest))
+        })
+        .find(|(_, manifest)| manifest.contains_key("workspace"))
+}
+
+/// Returns the license a Cargo manifest declares for the members of
+/// its workspace.
+fn own_workspace_license(manifest: &toml::Table) -> Option<String> {
+    manifest
+        .get("workspace")?
+        .get("package")?
+        .get("license")?
+        .as_str()
+        .map(str::to_string)
+}
+
+/// Checks whether a field of a Cargo manifest is inherited from the
+/// workspace, as with `license.workspace = true`.
+pub(crate) fn is_inherited(field: &toml::Value) -> bool {
+    field
+        .get("workspace")
+        .and_then(toml::Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// Reads a TOML manifest.
+///
+/// # Returns
+///
+/// `None` if it cannot be read or is invalid.
+fn read_toml(path: &Path) -> Option<toml::Table> {
+    fs::read_to_string(path).ok()?.parse().ok()
+}
+
+/// Reads the name and version of the package a manifest of `dir`
+/// describes, trying `Cargo.toml`, `package.json` and then
+/// `pyproject.toml`.
+///
+/// A version inherited from a workspace is not resolved.
+pub(crate) fn manifest_package(dir: &Path) -> Option<(String, Option<String>)> {
+    let field = |value: Option<&toml::Value>, key: &str| {
+        value
+            .and_then(|table| table.get(key))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    };
+    let read_toml = |name: &str| {
+        fs::read_to_string(dir.join(name))
+            .ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+    };
+    if let Some(manifest) = read_toml("Cargo.toml") {
+        let package = manifest.get("package");
+        if let Some(name) = field(package, "name") {
+            return Some((name, field(package, "version")));
+        }
+    }
+    if let Some(manifest) = fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    {
+        if let Some(name) = manifest.get("name").and_then(|name| name.as_str()) {
+            let version = manifest
+                .get("version")
+                .and_then(|version| version.as_str())
+                .map(str::to_string);
+            return Some((name.to_string(), version));
+        }
+    }
+    let manifest = read_toml("pyproject.toml")?;
+    let project = manifest.get("project");
+    Some((field(project, "name")?, field(project, "version")))
+}
+
+/// Replaces the `license` or `license-file` of the `[package]` and
+/// `[workspace.package]` tables of a Cargo manifest with `license`,
+/// or removes them, keeping its formatting.
+///
+/// With `insert`, a manifest declaring no license gets `license` in
+/// its `[package]` table, or else in its `[workspace.package]`. A
+/// package inheriting its license from the workspace is left alone,
+/// as the license is edited where the workspace declares it.
+///
+/// # Returns
+///
+/// `None` if the manifest is invalid or declares no license of its
+/// own.
+fn cargo_license(
+    content: &str,
+    license: Option<&str>,
+    insert: bool,
+) -> Option<String> {
+    let mut document = content.parse::<DocumentMut>().ok()?;
+    let (mut changed, mut inherited) = (false, false);
+    for section in [&["package"][..], &["workspace", "package"]] {
+        let table = section
+            .iter()
+            .try_fold(document.as_table_mut(), |table, key| {
+                table.get_mut(key)?.as_table_mut()
+            });
+        let Some(table) =
+            table.filter(|table| CARGO_LICENSE_KEYS.iter().any(|key| table.contains_key(key)))
+        else {
+            continue;
+        };
+        let inherits = table
+            .get("license")
+            .and_then(|license| license.as_table_like())
+            .and_then(|license| license.get("workspace"))
+            .and_then(|workspace| workspace.as_bool())
+            .unwrap_or(false);
+        if inherits {
+            inherited = true;
+            continue;
+        }
+        changed = true;
+        match license {
+            Some(license) if table.contains_key("license") => {
+                table.remove("license-file");
+                table["license"] = value(license);
+            }
+            Some(license) => {
+                table.remove("license-file");
+                table.insert("license", value(license));
+            }
+            None => {
+                for key in CARGO_LICENSE_KEYS {
+                    table.remove(key);
+                }
+            }
+        }
+    }
+    if let Some(license) = license.filter(|_| insert && !changed && !inherited) {
+        let section: &[&str] = if document.contains_table("package") {
+            &["package"]
+        } else {
+            &["workspace", "package"]
+        };
+        let table = section
+            .iter()
+            .try_fold(document.as_table_mut(), |table, key| {
+                table.get_mut(key)?.as_table_mut()
+            })?;
+        table.insert("license", value(license));
+        changed = true;
+    }
+    changed.then(|| document.to_string())
+}
+
+/// Replaces the `license` of the `[project]` table of a
+/// `pyproject.toml` with `license`, or removes it, keeping its
+/// formatting.
+///
+/// A license given as a table, such as `{ text = "MIT" }`, becomes an
+/// SPDX expression as in PEP 639. With `insert`, a project declaring
+/// no license gets `license`.
+///
+/// # Returns
+///
+/// `None` if the manifest is invalid, has no `[project]` table or
+/// declares no license.
+fn pyproject_license(
+    content: &str,
+    license: Option<&str>,
+    insert: bool,
+) -> Option<String> {
+    let mut document = content.parse::<DocumentMut>().ok()?;
+    let project = document.get_mut("project")?.as_table_like_mut()?;
+    match license {
+        Some(license) if insert || project.contains_key("license") => {
+            project.insert("license", value(license));
+        }
+        None if project.contains_key("license") => {
+            project.remove("license");
+        }
+        _ => return None,
+    }
+    Some(document.to_string())
+}
+
+/// Replaces the value of the top-level `"license"` line of a
+/// `package.json` with `license`, or removes the line.
+///
+/// The manifest is edited line by line rather than re-serialized, so
+/// its key order and formatting are kept. When the line is removed, a
+/// trailing comma left on the previous line is removed with it. With
+/// `insert`, a manifest without a `"license"` line gets one after its
+/// `"version"`, or else its `"name"`.
+///
+/// # Returns
+///
+/// `None` if the manifest has no `"license"` line.
+fn package_json_license(
+    content: &str,
+    license: Option<&str>,
+    insert: bool,
+) -> Option<String> {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let top_level_line = |key: &str| {
+        let mut depth = 0;
+        lines.iter().position(|line| {
+            let found = depth == 1 && line.trim_start().starts_with(&format!("\"{}\"", key));
+            depth += line.matches(['{', '[']).count();
+            depth -= line.matches(['}', ']']).count().min(depth);
+            found
+        })
+    };
+    let Some(idx) = top_level_line("license") else {
+        let license = license.filter(|_| insert)?;
+        let Some(idx) = top_level_line("version").or_else(|| top_level_line("name")) else {
+            return insert_inline_license(content, license);
+        };
+        let previous = lines[idx].trim_end().to_string();
+        let indent = &previous[..previous.len() - previous.trim_start().len()];
+        let line = match previous.strip_suffix(',') {
+            Some(_) => format!("{}\"license\": \"{}\",", indent, license),
+            None => {
+                lines[idx] = format!("{},", previous);
+                format!("{}\"license\": \"{}\"", indent, license)
+            }
+        };
+        lines.insert(idx + 1, line);
+        return Some(join_lines(&lines, content));
+    };
+    if let Some(license) = license {
+        let line = &lines[idx];
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let comma = if line.trim_end().ends_with(',') {
+            ","
+        } else {
+            ""
+        };
+        lines[idx] = format!("{}\"license\": \"{}\"{}", indent, license, comma);
+    } else {
+        let removed = lines.remove(idx);
+        if !removed.trim_end().ends_with(',') && idx > 0 {
+            if let Some(previous) = lines[..idx]
+                .iter_mut()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+            {
+                if let Some(stripped) = previous.trim_end().strip_suffix(',') {
+                    *previous = stripped.to_string();
+                }
+            }
+        }
+    }
+    Some(join_lines(&lines, content))
+}
+
+/// Inserts `"license"` as the first key of a `package.json` holding
+/// its whole object on one line, such as `{"name":"demo"}`.
+///
+/// # Returns
+///
+/// `None` if the manifest spans several lines, is invalid or already
+/// has a license.
+fn insert_inline_license(
+    content: &str,
+    license: &str,
+) -> Option<String> {
+    let manifest: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(content).ok()?;
+    if manifest.contains_key("license") || content.trim().lines().count() != 1 {
+        return None;
+    }
+    let (head, rest) = content.split_once('{')?;
+    let separator = if manifest.is_empty() { "" } else { ", " };
+    Some(format!(
+        "{}{{\"license\": \"{}\"{}{}",
+        head, license, separator, rest
+    ))
+}
+
+/// Joins edited lines, ending them with a newline if `content` did.
+fn join_lines(
+    lines: &[String],
+    content: &str,
+) -> String {
+    let mut result = lines.join("\n");
+    if content.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::{
+        cargo_license, cargo_license_file, conventional_license_file_name, manifest_licenses,
+        package_json_license, pyproject_license,
+    };
+
+    #[test]
+    fn test_manifest_licenses() {
+        let cargo = "[package]\nname = \"demo\" # the crate\nlicense = \"MIT\"\n\n[dependencies]\n";
+        assert_eq!(
+            cargo_license(cargo, None, false).as_deref(),
+            Some("[package]\nname = \"demo\" # the crate\n\n[dependencies]\n")
+        );
+        assert_eq!(
+            cargo_license(cargo, Some("Apache-2.0"), false).as_deref(),
+            Some(
+                "[package]\nname = \"demo\" # the crate\nlicense = \
+                 \"Apache-2.0\"\n\n[dependencies]\n"
+            )
+        );
+        assert_eq!(
+            cargo_license("[package]\nname = \"demo\"\n", None, false),
+            None
+        );
+        assert_eq!(
+            cargo_license("[package]\nname = \"demo\"\n", Some("MIT"), true).as_deref(),
+            Some("[package]\nname = \"demo\"\nlicense = \"MIT\"\n")
+        );
+        let member = "[package]\nname = \"demo\"\nlicense.workspace = true\n";
+        assert_eq!(cargo_license(member, Some("MIT"), true), None);
+        assert_eq!(cargo_license(member, None, false), None);
+        let json = "{\n  \"name\": \"demo\",\n  \"nested\": {\n    \"license\": \"x\"\n  },\n  \
+                    \"license\": \"MIT\"\n}\n";
+        assert_eq!(
+            package_json_license(json, None, false).as_deref(),
+            Some("{\n  \"name\": \"demo\",\n  \"nested\": {\n    \"license\": \"x\"\n  }\n}\n")
+        );
+        assert_eq!(
+            package_json_license(json, Some("ISC"), false).as_deref(),
+            Some(
+                "{\n  \"name\": \"demo\",\n  \"nested\": {\n    \"license\": \"x\"\n  },\n  \
+                 \"license\": \"ISC\"\n}\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_insert_manifest_licenses() {
+        let json = "{\n  \"name\": \"demo\",\n  \"version\": \"1.0.0\"\n}\n";
+        assert_eq!(package_json_license(json, Some("MIT"), false), None);
+        assert_eq!(
+            package_json_license(json, Some("MIT"), true).as_deref(),
+            Some(
+                "{\n  \"name\": \"demo\",\n  \"version\": \"1.0.0\",\n  \"license\": \"MIT\"\n}\n"
+            )
+        );
+        assert_eq!(
+            package_json_license("{\"name\":\"demo\"}\n", Some("MIT"), true).as_deref(),
+            Some("{\"license\": \"MIT\", \"name\":\"demo\"}\n")
+        );
+        let pyproject = "[project]\nname = \"demo\"\nlicense = { text = \"MIT\" }\n";
+        assert_eq!(
+            pyproject_license(pyproject, Some("Apache-2.0"), false).as_deref(),
+            Some("[project]\nname = \"demo\"\nlicense = \"Apache-2.0\"\n")
+        );
+        assert_eq!(
+            pyproject_license("[project]\nname = \"demo\"\n", Some("MIT"), true).as_deref(),
+            Some("[project]\nname = \"demo\"\nlicense = \"MIT\"\n")
+        );
+        assert_eq!(pyproject_license("[tool.black]\n", Some("MIT"), true), None);
+    }
+
+    #[test]
+    fn test_workspace_licenses() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"demo\"]\n\n[workspace.package]\nlicense = \"MIT\"\n",
+        )
+        .unwrap();
+        let member = dir.path().join("demo");
+        fs::create_dir(&member).unwrap();
+        fs::write(
+            member.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nlicense = { workspace = true }\n",
+        )
+        .unwrap();
+        assert_eq!(
+            manifest_licenses(dir.path()),
+            [(dir.path().join("Cargo.toml"), "MIT".to_string())]
+        );
+        assert_eq!(
+            manifest_licenses(&member),
+            [(member.join("Cargo.toml"), "MIT".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_license_file_names() {
+        let conventional = |name| conventional_license_file_name(name, "LICENSE.md");
+        assert_eq!(conventional("license.TXT").as_deref(), Some("LICENSE.md"));
+        assert_eq!(conventional("Licence.md").as_deref(), Some("LICENSE.md"));
+        assert_eq!(conventional("license-MIT").as_deref(), Some("LICENSE-MIT"));
+        assert_eq!(
+            conventional("copying.html").as_deref(),
+            Some("COPYING.html")
+        );
+        assert_eq!(
+            conventional("COPYING.LESSER").as_deref(),
+            Some("COPYING.LESSER")
+        );
+        assert_eq!(conventional("README.md"), None);
+        assert_eq!(
+            conventional_license_file_name("licence.MD", "LICENSE").as_deref(),
+            Some("LICENSE.md")
+        );
+        assert_eq!(
+            cargo_license_file(
+                "[package]\nlicense-file = \"./license.TXT\"\n",
+                "license.TXT",
+                "LICENSE.md"
+            )
+            .as_deref(),
+            Some("[package]\nlicense-file = \"LICENSE.md\"\n")
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context};
+use color_print::cprintln;
+use tokio::process::Command;
+
+use crate::{
+    check::{display_placeholder_violations, find_unfilled_placeholders, Baseline},
+    config::Config,
+    expression::Expression,
+    i18n::t,
+    project::{is_inherited, license_files, workspace_root},
+    spdx::{License, Licenses},
+    util::{
+        errors::{Error, LictoolResult},
+        events::emit,
+        output::success,
+        progress::with_spinner,
+    },
+};
+
+/// The license fields of the `[package]` table of a Cargo manifest.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct PackageLicense {
+    pub(crate) license: Option<String>,
+    pub(crate) license_file: Option<String>,
+}
+
+impl PackageLicense {
+    /// Reads the license fields of the manifest in `dir`, resolving
+    /// fields inherited from the `[workspace.package]` of the
+    /// workspace root, in `dir` or above it.
+    ///
+    /// An inherited `license-file` is relative to the workspace root,
+    /// so it is returned as a path from there.
+    ///
+    /// # Errors
+    /// - If the manifest cannot be read or parsed.
+    pub fn load(dir: &Path) -> LictoolResult<Self> {
+        let path = dir.join("Cargo.toml");
+        let manifest = fs::read_to_string(&path)
+            .with_context(|| t!("error-read-file", path = path.display().to_string()))?
+            .parse::<toml::Table>()
+            .with_context(|| t!("error-parse-file", path = path.display().to_string()))?;
+        let package = manifest.get("package");
+        let field = |key: &str| -> Option<String> {
+            let value = package?.get(key)?;
+            if !is_inherited(value) {
+                return value.as_str().map(str::to_string);
+            }
+            let (root, workspace) = workspace_root(dir)?;
+            let value = workspace
+                .get("workspace")?
+                .get("package")?
+                .get(key)?
+                .as_str()?;
+            Some(match key {
+                "license-file" => root.join(value).display().to_string(),
+                _ => value.to_string(),
+            })
+        };
+        Ok(Self {
+            license: field("license"),
+            license_file: field("license-file"),
+        })
+    }
+}
+
+/// A problem that would make crates.io or downstream packagers reject
+/// or misreport a crate.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum PublishIssue {
+    /// Neither `license` nor `license-file` is set.
+    MissingLicense,
+    InvalidLicense {
+        license: String,
+        reason: String,
+    },
+    /// The `license-file` of the manifest does not exist.
+    MissingLicenseFile {
+        path: String,
+    },
+    /// A license file is left out of the package.
+    ExcludedLicenseFile {
+        path: String,
+    },
+}
+
+impl PublishIssue {
+    /// Returns the localized description of the issue.
+    pub fn message(&self) -> String {
+        match self {
+            PublishIssue::MissingLicense => t!("publish-missing-license"),
+            PublishIssue::InvalidLicense {
+                license,
+                reason,
+            } => {
+                t!(
+                    "publish-invalid-license",
+                    license = license.as_str(),
+                    reason = reason.as_str()
+                )
+            }
+            PublishIssue::MissingLicenseFile {
+                path,
+            } => t!("publish-missing-license-file", path = path.as_str()),
+            PublishIssue::ExcludedLicenseFile {
+                path,
+            } => t!("publish-excluded-license-file", path = path.as_str()),
+        }
+    }
+}
+
+/// Lists the files `cargo package` would include for the crate in
+/// `dir`, relative to it.
+///
+/// # Errors
+/// - If cargo cannot be run or fails, such as outside of a crate.
+pub(crate) async fn package_files(dir: &Path) -> LictoolResult<Vec<String>> {
+    let output = Command::new("cargo")
+        .args(["package", "--list", "--allow-dirty", "--quiet"])
+        .current_dir(dir)
+        .output()
+        .await
+        .with_context(|| t!("error-cargo-package"))?;
+    if !output.status.success() {
+        return Err(
+            anyhow!(String::from_utf8_lossy(&output.stderr).trim().to_string())
+                .context(t!("error-cargo-package")),
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.replace('\\', "/"))
+        .collect())
+}
+
+/// Checks the license fields of a crate and that its license files,
+/// given relative to its directory, are part of the package.
+pub(crate) fn publish_issues(
+    package: &PackageLicense,
+    license_files: &[String],
+    packaged: &[String],
+    licenses: &[License],
+    dir: &Path,
+) -> Vec<PublishIssue> {
+    let mut issues = Vec::new();
+    if package.license.is_none() && package.license_file.is_none() {
+        issues.push(PublishIssue::MissingLicense);
+    }
+    if let Some(license) = &package.license {
+        let valid = license
+            .parse::<Expression>()
+            .and_then(|expression| expression.canonicalize(licenses));
+        if let Err(error) = valid {
+            issues.push(PublishIssue::InvalidLicense {
+                license: license.clone(),
+                reason: error.to_string(),
+            });
+        }
+    }
+    let mut expected: Vec<&String> = license_files.iter().collect();
+    if let Some(license_file) = &package.license_file {
+        // Cargo copies a license file from outside the package into
+        // it, so only one within the package can be left out.
+        let within = Path::new(license_file)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if !dir.join(license_file).is_file() {
+            issues.push(PublishIssue::MissingLicenseFile {
+                path: license_file.clone(),
+            });
+        } else if within {
+            expected.push(license_file);
+        }
+    }
+    expected.sort();
+    expected.dedup();
+    for path in expected {
+        let normalized = path.trim_start_matches("./").replace('\\', "/");
+        if !packaged.contains(&normalized) {
+            issues.push(PublishIssue::ExcludedLicenseFile {
+                path: path.clone(),
+            });
+        }
+    }
+    issues
+}
+
+/// Returns the packaged files that exist in `dir`, leaving out those
+/// cargo generates, such as `Cargo.toml.orig`.
+pub(crate) fn existing_package_files(
+    dir: &Path,
+    packaged: &[String],
+) -> Vec<PathBuf> {
+    packaged
+        .iter()
+        .map(|path| dir.join(path))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// Prints the issues found, or that the license fields and files are
+/// ready for publishing.
+pub(crate) fn display_publish_issues(issues: &[PublishIssue]) {
+    if issues.is_empty() {
+        success!("<green>✔</> <bold>{}</>", t!("publish-license-ready"));
+    }
+    for issue in issues {
+        cprintln!("<red>✘</> {}", issue.message());
+    }
+}
+
+/// Checks that the crate in `root` is ready to be published, for
+/// `check --publish`: that its manifest declares a valid license,
+/// that its license files are packaged, and that no packaged file
+/// has placeholders not accepted by the baseline at `baseline`.
+///
+/// # Errors
+/// - `Error::NotReadyToPublish` with the number of problems found.
+/// - If the manifest, the package files or the baseline cannot be
+///   read, or the license list cannot be fetched.
+pub(crate) async fn check_publish(
+    root: &Path,
+    config: &Config,
+    baseline: &Path,
+) -> LictoolResult<()> {
+    let package = PackageLicense::load(root)?;
+    let packaged = with_spinner(t!("listing-package-files"), package_files(root)).await?;
+    let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+    let license_files: Vec<String> = license_files(root, config.default_filename())
+        .iter()
+        .map(|path| {
+            path.strip_prefix(root)
+                .unwrap_or(path)
+                .display()
+                .to_string()
+        })
+        .collect();
+    let issues = publish_issues(&package, &license_files, &packaged, &licenses.body, root);
+    display_publish_issues(&issues);
+    let lint = find_unfilled_placeholders(&existing_package_files(root, &packaged));
+    let (violations, _) = Baseline::load(baseline)?.filter(lint.violations);
+    violations
+        .iter()
+        .for_each(|violation| emit(violation.event()));
+    if violations.is_empty() {
+        success!("<green>✔</> <bold>{}</>", t!("check-no-placeholders"));
+    } else {
+        display_placeholder_violations(&violations);
+    }
+    let count = issues.len() + violations.len();
+    if count > 0 {
+        Err(Error::NotReadyToPublish {
+            count,
+        })?
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::{publish_issues, PackageLicense, PublishIssue};
+    use crate::spdx::License;
+
+    #[test]
+    fn test_publish_issues() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace.package]\nlicense = \"MIT/Apache-2.0\"\n\n[package]\nname = \
+             \"demo\"\nlicense.workspace = true\nlicense-file = \"LICENSE.txt\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("LICENSE-MIT"), "").unwrap();
+        let package = PackageLicense::load(dir.path()).unwrap();
+        assert_eq!(package.license.as_deref(), Some("MIT/Apache-2.0"));
+        let issues = publish_issues(
+            &package,
+            &["LICENSE-MIT".to_string()],
+            &["Cargo.toml".to_string(), "src/lib.rs".to_string()],
+            &[],
+            dir.path(),
+        );
+        assert!(matches!(issues[0], PublishIssue::InvalidLicense { .. }));
+        assert_eq!(
+            issues[1..],
+            [
+                PublishIssue::MissingLicenseFile {
+                    path: "LICENSE.txt".to_string()
+                },
+                PublishIssue::ExcludedLicenseFile {
+                    path: "LICENSE-MIT".to_string()
+                }
+            ]
+        );
+        assert_eq!(
+            publish_issues(&PackageLicense::default(), &[], &[], &[], dir.path()),
+            vec![PublishIssue::MissingLicense]
+        );
+    }
+
+    #[test]
+    fn test_workspace_member_publish_issues() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"demo\"]\n\n[workspace.package]\nlicense = \"MIT\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("LICENSE"), "").unwrap();
+        let member = dir.path().join("demo");
+        fs::create_dir(&member).unwrap();
+        fs::write(
+            member.join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nlicense.workspace = true\nlicense-file = \"../LICENSE\"\n",
+        )
+        .unwrap();
+        let package = PackageLicense::load(&member).unwrap();
+        assert_eq!(package.license.as_deref(), Some("MIT"));
+        let mit = License {
+            is_deprecated_license_id: false,
+            details_url: String::new(),
+            name: "MIT License".to_string(),
+            id: "MIT".to_string(),
+            is_osi_approved: Some(true),
+            is_fsf_libre: Some(true),
+        };
+        let packaged = ["Cargo.toml".to_string(), "LICENSE".to_string()];
+        assert_eq!(
+            publish_issues(&package, &[], &packaged, &[mit], &member),
+            []
+        );
+    }
+}
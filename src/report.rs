@@ -0,0 +1,414 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+
+use crate::{
+    expression::Expression,
+    header::spdx_header_license,
+    i18n::t,
+    policy::PolicyConfig,
+    status::{DeclaredLicense, Status},
+};
+
+/// The style sheet embedded in HTML reports.
+const HTML_STYLE: &str =
+    "body { font-family: sans-serif; max-width: 60em; margin: 2em auto; color: #222; } table { \
+     border-collapse: collapse; } th, td { text-align: left; padding: 0.3em 1em 0.3em 0; } .chart \
+     .row { display: flex; align-items: center; gap: 0.5em; margin: 0.2em 0; } .chart .label { \
+     width: 12em; } .chart .bar { display: inline-block; height: 1em; background: #4a7bd0; } \
+     details { margin: 0.5em 0; } summary { cursor: pointer; } .violation { color: #b00020; }";
+
+/// The width of the longest bar of the chart of HTML reports, in em.
+const CHART_WIDTH_EM: usize = 30;
+
+/// The formats reports are rendered in.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub(crate) enum ReportFormat {
+    Markdown,
+    /// A standalone page with charts and collapsible sections.
+    Html,
+}
+
+impl ReportFormat {
+    /// Returns the format for a report written to `output`: HTML for
+    /// an `.html` or `.htm` file, and Markdown otherwise.
+    pub fn for_output(output: Option<&str>) -> Self {
+        let extension = output
+            .and_then(|output| Path::new(output).extension())
+            .map(|extension| extension.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("html" | "htm") => ReportFormat::Html,
+            _ => ReportFormat::Markdown,
+        }
+    }
+}
+
+/// The licensing of a project, rendered as Markdown or HTML.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Report {
+    /// The name of the project, used as the title.
+    pub(crate) project: String,
+    pub(crate) status: Status,
+    /// The files with an SPDX header, by the license expression of
+    /// their header.
+    pub(crate) files_by_license: BTreeMap<String, Vec<PathBuf>>,
+    /// The files whose header, or declaration, the policy rejects.
+    pub(crate) violations: Vec<Violation>,
+}
+
+/// A license the policy rejects, and where it is used.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Violation {
+    pub(crate) license: String,
+    /// The manifests, settings or files using the license.
+    pub(crate) sources: Vec<String>,
+}
+
+/// Groups the files with an SPDX header by the license expression of
+/// their header, leaving out license files and unreadable ones.
+pub(crate) fn files_by_license(
+    files: &[PathBuf],
+    license_files: &[PathBuf],
+) -> BTreeMap<String, Vec<PathBuf>> {
+    let mut grouped: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for path in files {
+        if license_files.contains(path) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(path) else {
+            continue;
+        };
+        if let Some(license) = spdx_header_license(&content) {
+            grouped
+                .entry(license.to_string())
+                .or_default()
+                .push(path.strip_prefix(".").unwrap_or(path).to_path_buf());
+        }
+    }
+    grouped
+}
+
+/// Finds the declared licenses and file headers the policy rejects.
+///
+/// Expressions that are not valid are left to the `validate` command.
+pub(crate) fn find_violations(
+    declared: &[DeclaredLicense],
+    files_by_license: &BTreeMap<String, Vec<PathBuf>>,
+    policy: &PolicyConfig,
+) -> Vec<Violation> {
+    let mut sources: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for declared in declared {
+        sources
+            .entry(&declared.license)
+            .or_default()
+            .push(declared.source.clone());
+    }
+    for (license, files) in files_by_license {
+        sources
+            .entry(license)
+            .or_default()
+            .extend(files.iter().map(|path| path.display().to_string()));
+    }
+    sources
+        .into_iter()
+        .filter(|(license, _)| {
+            license
+                .parse::<Expression>()
+                .is_ok_and(|expression| !policy.evaluate(&expression).satisfied)
+        })
+        .map(|(license, sources)| {
+            Violation {
+                license: license.to_string(),
+                sources,
+            }
+        })
+        .collect()
+}
+
+impl Report {
+    /// Returns the labels and values of the summary table, with file
+    /// names and licenses quoted in backticks.
+    fn summary_rows(&self) -> [(String, String); 4] {
+        let status = &self.status;
+        let license_file = match (&status.license_file, &status.detected_license) {
+            (None, _) => t!("status-no-license-file"),
+            (Some(file), Some(license)) => {
+                format!(
+                    "`{}` ({})",
+                    file.strip_prefix(".").unwrap_or(file).display(),
+                    license
+                )
+            }
+            (Some(file), None) => {
+                format!(
+                    "`{}` ({})",
+                    file.strip_prefix(".").unwrap_or(file).display(),
+                    t!("status-undetected")
+                )
+            }
+        };
+        let declared = if status.declared.is_empty() {
+            t!("status-no-manifests")
+        } else {
+            status
+                .declared
+                .iter()
+                .map(|declared| format!("{}: `{}`", declared.source, declared.license))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let coverage = status.header_coverage;
+        let headers = match coverage.percent {
+            None => t!("status-no-source-files"),
+            Some(percent) => {
+                t!(
+                    "status-header-coverage",
+                    percent = percent,
+                    covered = coverage.covered,
+                    total = coverage.total
+                )
+            }
+        };
+        let violations = if self.violations.is_empty() {
+            t!("status-no-violations")
+        } else {
+            self.violations
+                .iter()
+                .map(|violation| format!("`{}`", violation.license))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        [
+            (t!("status-license"), license_file),
+            (t!("status-manifests"), declared),
+            (t!("status-headers"), headers),
+            (t!("status-policy"), violations),
+        ]
+    }
+
+    /// Renders the report as Markdown: a summary table, the files of
+    /// each license and the policy violations.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        let _ = writeln!(
+            markdown,
+            "# {}\n",
+            t!("report-title", project = self.project.as_str())
+        );
+
+        let _ = writeln!(markdown, "## {}\n", t!("report-summary"));
+        let _ = writeln!(markdown, "| | |\n|---|---|");
+        for (label, value) in self.summary_rows() {
+            let _ = writeln!(markdown, "| **{}** | {} |", label, escape_cell(&value));
+        }
+
+        let _ = writeln!(markdown, "\n## {}\n", t!("report-files-by-license"));
+        if self.files_by_license.is_empty() {
+            let _ = writeln!(markdown, "{}", t!("report-no-headers"));
+        }
+        for (license, files) in &self.files_by_license {
+            let _ = writeln!(
+                markdown,
+                "### {}\n\n{}\n",
+                license,
+                t!("report-file-count", count = files.len())
+            );
+            for file in files {
+                let _ = writeln!(markdown, "- `{}`", file.display());
+            }
+            markdown.push('\n');
+        }
+
+        let _ = writeln!(markdown, "## {}\n", t!("report-violations"));
+        if self.violations.is_empty() {
+            let _ = writeln!(markdown, "{}", t!("status-no-violations"));
+        }
+        for violation in &self.violations {
+            let _ = writeln!(
+                markdown,
+                "- `{}`: {}",
+                violation.license,
+                violation.sources.join(", ")
+            );
+        }
+        markdown
+    }
+
+    /// Renders the report as a standalone HTML page, with a bar chart
+    /// of the files per license and collapsible file lists.
+    pub fn to_html(&self) -> String {
+        let title = t!("report-title", project = self.project.as_str());
+        let mut html = String::new();
+        let _ = writeln!(
+            html,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta \
+             charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<h1>{}</\
+             h1>",
+            escape_html(&title),
+            HTML_STYLE,
+            escape_html(&title)
+        );
+
+        let _ = writeln!(html, "<h2>{}</h2>\n<table>", t!("report-summary"));
+        for (label, value) in self.summary_rows() {
+            let _ = writeln!(
+                html,
+                "<tr><th>{}</th><td>{}</td></tr>",
+                escape_html(&label),
+                code_spans(&escape_html(&value))
+            );
+        }
+        let _ = writeln!(html, "</table>");
+
+        let _ = writeln!(html, "<h2>{}</h2>", t!("report-files-by-license"));
+        if self.files_by_license.is_empty() {
+            let _ = writeln!(html, "<p>{}</p>", t!("report-no-headers"));
+        } else {
+            let most = self
+                .files_by_license
+                .values()
+                .map(Vec::len)
+                .max()
+                .unwrap_or(1);
+            let _ = writeln!(html, "<div class=\"chart\">");
+            for (license, files) in &self.files_by_license {
+                let _ = writeln!(
+                    html,
+                    "<div class=\"row\"><span class=\"label\">{}</span><span class=\"bar\" \
+                     style=\"width: {}em\"></span><span>{}</span></div>",
+                    escape_html(license),
+                    (files.len() * CHART_WIDTH_EM).div_ceil(most),
+                    files.len()
+                );
+            }
+            let _ = writeln!(html, "</div>");
+        }
+        for (license, files) in &self.files_by_license {
+            let _ = writeln!(
+                html,
+                "<details>\n<summary>{} ({})</summary>\n<ul>",
+                escape_html(license),
+                t!("report-file-count", count = files.len())
+            );
+            for file in files {
+                let _ = writeln!(
+                    html,
+                    "<li><code>{}</code></li>",
+                    escape_html(&file.display().to_string())
+                );
+            }
+            let _ = writeln!(html, "</ul>\n</details>");
+        }
+
+        let _ = writeln!(html, "<h2>{}</h2>", t!("report-violations"));
+        if self.violations.is_empty() {
+            let _ = writeln!(html, "<p>{}</p>", t!("status-no-violations"));
+        }
+        for violation in &self.violations {
+            let _ = writeln!(
+                html,
+                "<details open>\n<summary class=\"violation\">{}</summary>\n<ul>",
+                escape_html(&violation.license)
+            );
+            for source in &violation.sources {
+                let _ = writeln!(html, "<li><code>{}</code></li>", escape_html(source));
+            }
+            let _ = writeln!(html, "</ul>\n</details>");
+        }
+        let _ = writeln!(html, "</body>\n</html>");
+        html
+    }
+}
+
+/// Escapes the characters of HTML markup.
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Turns the spans of text quoted in backticks into `<code>`
+/// elements.
+fn code_spans(value: &str) -> String {
+    value
+        .split('`')
+        .enumerate()
+        .map(|(idx, part)| {
+            if idx % 2 == 1 {
+                format!("<code>{}</code>", part)
+            } else {
+                part.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Escapes the pipes of a table cell.
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+/// Returns the name of the project in `root`: the name of its
+/// directory, or `project` if it has none.
+pub(crate) fn project_name(root: &Path) -> String {
+    fs::canonicalize(root)
+        .ok()
+        .and_then(|path| Some(path.file_name()?.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "project".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    use pretty_assertions::assert_eq;
+
+    use super::{code_spans, escape_html, find_violations, ReportFormat, Violation};
+    use crate::{policy::PolicyConfig, status::DeclaredLicense};
+
+    #[test]
+    fn test_find_violations() {
+        let declared = [DeclaredLicense {
+            source: "Cargo.toml".to_string(),
+            license: "MIT".to_string(),
+        }];
+        let files = BTreeMap::from([
+            ("MIT".to_string(), vec![PathBuf::from("src/lib.rs")]),
+            (
+                "GPL-3.0-only".to_string(),
+                vec![PathBuf::from("vendor/a.c"), PathBuf::from("vendor/b.c")],
+            ),
+        ]);
+        let policy = PolicyConfig {
+            allow: Vec::new(),
+            deny: vec!["GPL-3.0-only".to_string()],
+        };
+        assert_eq!(
+            find_violations(&declared, &files, &policy),
+            vec![Violation {
+                license: "GPL-3.0-only".to_string(),
+                sources: vec!["vendor/a.c".to_string(), "vendor/b.c".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_html_report() {
+        assert_eq!(
+            ReportFormat::for_output(Some("docs/licensing.HTML")),
+            ReportFormat::Html
+        );
+        assert_eq!(ReportFormat::for_output(None), ReportFormat::Markdown);
+        assert_eq!(
+            code_spans(&escape_html("`<stdin>` & `MIT`")),
+            "<code>&lt;stdin&gt;</code> &amp; <code>MIT</code>"
+        );
+    }
+}
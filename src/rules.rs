@@ -0,0 +1,327 @@
+use color_print::cformat;
+use serde::Serialize;
+
+use crate::i18n::t;
+
+/// Something a license permits, requires or rules out, after the
+/// rules of choosealicense.com.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Rule {
+    CommercialUse,
+    Modifications,
+    Distribution,
+    PatentUse,
+    PrivateUse,
+    IncludeCopyright,
+    DocumentChanges,
+    DiscloseSource,
+    NetworkUseDisclose,
+    SameLicense,
+    /// Modifications of existing files must keep the license.
+    SameLicenseFile,
+    /// Modifications of the library must keep the license, but not
+    /// the programs using it.
+    SameLicenseLibrary,
+    Liability,
+    Warranty,
+    TrademarkUse,
+}
+
+impl Rule {
+    /// Returns the translated name of the rule.
+    pub fn name(self) -> String {
+        match self {
+            Rule::CommercialUse => t!("rule-commercial-use"),
+            Rule::Modifications => t!("rule-modifications"),
+            Rule::Distribution => t!("rule-distribution"),
+            Rule::PatentUse => t!("rule-patent-use"),
+            Rule::PrivateUse => t!("rule-private-use"),
+            Rule::IncludeCopyright => t!("rule-include-copyright"),
+            Rule::DocumentChanges => t!("rule-document-changes"),
+            Rule::DiscloseSource => t!("rule-disclose-source"),
+            Rule::NetworkUseDisclose => t!("rule-network-use-disclose"),
+            Rule::SameLicense => t!("rule-same-license"),
+            Rule::SameLicenseFile => t!("rule-same-license-file"),
+            Rule::SameLicenseLibrary => t!("rule-same-license-library"),
+            Rule::Liability => t!("rule-liability"),
+            Rule::Warranty => t!("rule-warranty"),
+            Rule::TrademarkUse => t!("rule-trademark-use"),
+        }
+    }
+}
+
+/// What a license allows, on which conditions, and what it does not
+/// cover.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub(crate) struct LicenseRules {
+    /// The SPDX license ID, without the `-only` or `-or-later` of
+    /// GNU licenses, which share their rules.
+    #[serde(skip)]
+    pub(crate) spdx: &'static str,
+    pub(crate) permissions: &'static [Rule],
+    pub(crate) conditions: &'static [Rule],
+    pub(crate) limitations: &'static [Rule],
+}
+
+use Rule::*;
+
+/// The permissions of permissive licenses.
+const PERMISSIVE: &[Rule] = &[CommercialUse, Modifications, Distribution, PrivateUse];
+/// The permissions of licenses granting patent rights.
+const WITH_PATENTS: &[Rule] = &[
+    CommercialUse,
+    Modifications,
+    Distribution,
+    PatentUse,
+    PrivateUse,
+];
+const NO_LIABILITY: &[Rule] = &[Liability, Warranty];
+
+/// The rules of common licenses, sorted by SPDX ID.
+const LICENSE_RULES: [LicenseRules; 29] = [
+    rules("0BSD", PERMISSIVE, &[], NO_LIABILITY),
+    rules(
+        "AGPL-3.0",
+        WITH_PATENTS,
+        &[
+            IncludeCopyright,
+            DocumentChanges,
+            DiscloseSource,
+            NetworkUseDisclose,
+            SameLicense,
+        ],
+        NO_LIABILITY,
+    ),
+    rules(
+        "Apache-2.0",
+        WITH_PATENTS,
+        &[IncludeCopyright, DocumentChanges],
+        &[TrademarkUse, Liability, Warranty],
+    ),
+    rules(
+        "Artistic-2.0",
+        WITH_PATENTS,
+        &[IncludeCopyright, DocumentChanges],
+        &[TrademarkUse, Liability, Warranty],
+    ),
+    rules(
+        "BSD-2-Clause",
+        PERMISSIVE,
+        &[IncludeCopyright],
+        NO_LIABILITY,
+    ),
+    rules(
+        "BSD-3-Clause",
+        PERMISSIVE,
+        &[IncludeCopyright],
+        NO_LIABILITY,
+    ),
+    rules(
+        "BSD-3-Clause-Clear",
+        PERMISSIVE,
+        &[IncludeCopyright],
+        &[Liability, PatentUse, Warranty],
+    ),
+    rules("BSL-1.0", PERMISSIVE, &[IncludeCopyright], NO_LIABILITY),
+    rules(
+        "CC-BY-4.0",
+        PERMISSIVE,
+        &[IncludeCopyright, DocumentChanges],
+        &[Liability, TrademarkUse, PatentUse, Warranty],
+    ),
+    rules(
+        "CC-BY-SA-4.0",
+        PERMISSIVE,
+        &[IncludeCopyright, DocumentChanges, SameLicense],
+        &[Liability, TrademarkUse, PatentUse, Warranty],
+    ),
+    rules(
+        "CC0-1.0",
+        PERMISSIVE,
+        &[],
+        &[Liability, TrademarkUse, PatentUse, Warranty],
+    ),
+    rules(
+        "EPL-1.0",
+        WITH_PATENTS,
+        &[DiscloseSource, IncludeCopyright, SameLicense],
+        NO_LIABILITY,
+    ),
+    rules(
+        "EPL-2.0",
+        WITH_PATENTS,
+        &[DiscloseSource, IncludeCopyright, SameLicense],
+        NO_LIABILITY,
+    ),
+    rules(
+        "EUPL-1.2",
+        WITH_PATENTS,
+        &[
+            DiscloseSource,
+            IncludeCopyright,
+            DocumentChanges,
+            NetworkUseDisclose,
+            SameLicense,
+        ],
+        &[Liability, TrademarkUse, Warranty],
+    ),
+    rules(
+        "GPL-2.0",
+        PERMISSIVE,
+        &[
+            IncludeCopyright,
+            DocumentChanges,
+            DiscloseSource,
+            SameLicense,
+        ],
+        NO_LIABILITY,
+    ),
+    rules(
+        "GPL-3.0",
+        WITH_PATENTS,
+        &[
+            IncludeCopyright,
+            DocumentChanges,
+            DiscloseSource,
+            SameLicense,
+        ],
+        NO_LIABILITY,
+    ),
+    rules("ISC", PERMISSIVE, &[IncludeCopyright], NO_LIABILITY),
+    rules(
+        "LGPL-2.1",
+        PERMISSIVE,
+        &[
+            IncludeCopyright,
+            DiscloseSource,
+            DocumentChanges,
+            SameLicenseLibrary,
+        ],
+        NO_LIABILITY,
+    ),
+    rules(
+        "LGPL-3.0",
+        WITH_PATENTS,
+        &[
+            IncludeCopyright,
+            DiscloseSource,
+            DocumentChanges,
+            SameLicenseLibrary,
+        ],
+        NO_LIABILITY,
+    ),
+    rules("MIT", PERMISSIVE, &[IncludeCopyright], NO_LIABILITY),
+    rules("MIT-0", PERMISSIVE, &[], NO_LIABILITY),
+    rules(
+        "MPL-2.0",
+        WITH_PATENTS,
+        &[DiscloseSource, IncludeCopyright, SameLicenseFile],
+        &[Liability, TrademarkUse, Warranty],
+    ),
+    rules(
+        "MS-PL",
+        WITH_PATENTS,
+        &[IncludeCopyright],
+        &[TrademarkUse, Warranty],
+    ),
+    rules(
+        "OFL-1.1",
+        PERMISSIVE,
+        &[IncludeCopyright, SameLicense],
+        NO_LIABILITY,
+    ),
+    rules(
+        "OSL-3.0",
+        WITH_PATENTS,
+        &[
+            IncludeCopyright,
+            DiscloseSource,
+            DocumentChanges,
+            NetworkUseDisclose,
+            SameLicense,
+        ],
+        &[TrademarkUse, Liability, Warranty],
+    ),
+    rules("Unlicense", PERMISSIVE, &[], NO_LIABILITY),
+    rules("UPL-1.0", WITH_PATENTS, &[IncludeCopyright], NO_LIABILITY),
+    rules("WTFPL", PERMISSIVE, &[], &[]),
+    rules(
+        "Zlib",
+        PERMISSIVE,
+        &[IncludeCopyright, DocumentChanges],
+        NO_LIABILITY,
+    ),
+];
+
+const fn rules(
+    spdx: &'static str,
+    permissions: &'static [Rule],
+    conditions: &'static [Rule],
+    limitations: &'static [Rule],
+) -> LicenseRules {
+    LicenseRules {
+        spdx,
+        permissions,
+        conditions,
+        limitations,
+    }
+}
+
+/// Looks up the rules of a license, ignoring case and the `-only`,
+/// `-or-later` or `+` suffix of GNU licenses.
+pub(crate) fn license_rules(spdx_id: &str) -> Option<LicenseRules> {
+    let base = spdx_id.trim_end_matches('+');
+    let base = base
+        .strip_suffix("-only")
+        .or_else(|| base.strip_suffix("-or-later"))
+        .unwrap_or(base);
+    LICENSE_RULES
+        .iter()
+        .find(|rules| rules.spdx.eq_ignore_ascii_case(base))
+        .copied()
+}
+
+/// Renders the rules of a license as a permissions, a conditions and
+/// a limitations line.
+pub(crate) fn display_license_rules(rules: &LicenseRules) -> String {
+    let names = |rules: &[Rule]| {
+        if rules.is_empty() {
+            "-".to_string()
+        } else {
+            rules
+                .iter()
+                .map(|rule| rule.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+    };
+    cformat!(
+        "<s>{}</> <green>{}</>\n<s>{}</> <blue>{}</>\n<s>{}</> <red>{}</>",
+        t!("info-permissions"),
+        names(rules.permissions),
+        t!("info-conditions"),
+        names(rules.conditions),
+        t!("info-limitations"),
+        names(rules.limitations)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{license_rules, Rule, LICENSE_RULES};
+
+    #[test]
+    fn test_license_rules() {
+        let mit = license_rules("mit").unwrap();
+        assert!(mit.permissions.contains(&Rule::CommercialUse));
+        assert_eq!(mit.conditions, [Rule::IncludeCopyright]);
+        let gpl = license_rules("GPL-3.0-or-later").unwrap();
+        assert!(gpl.conditions.contains(&Rule::DiscloseSource));
+        assert_eq!(license_rules("GPL-2.0+").unwrap().spdx, "GPL-2.0");
+        assert!(license_rules("NCSA").is_none());
+        assert!(LICENSE_RULES
+            .windows(2)
+            .all(|pair| pair[0].spdx.to_lowercase() < pair[1].spdx.to_lowercase()));
+    }
+}
@@ -0,0 +1,751 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use chrono::Utc;
+use color_print::cprintln;
+use serde::Deserialize;
+use serde_json::json;
+use sha1::{Digest, Sha1};
+
+use crate::{
+    config::Config,
+    expression::Expression,
+    i18n::t,
+    project::{main_license_file, manifest_package},
+    report::project_name,
+    scan::{scan_dir, ArchiveScan},
+    status::declared_licenses,
+    util::{
+        errors::{Error, LictoolResult},
+        events::Event,
+        input::read_file,
+        integrity::sha256_hex,
+        output::success,
+    },
+    years::notice_lines,
+};
+
+/// The values of SPDX license fields that name no license.
+const NO_LICENSE: [&str; 2] = ["NOASSERTION", "NONE"];
+
+/// The SPDX version of the documents `lictool sbom` writes.
+const SPDX_VERSION: &str = "SPDX-2.3";
+
+/// The license of the documents themselves, which the SPDX
+/// specification requires to be CC0-1.0.
+const DATA_LICENSE: &str = "CC0-1.0";
+
+/// The value of fields lictool has no answer for.
+const NO_ASSERTION: &str = "NOASSERTION";
+
+/// The SPDX ID of the document itself.
+const DOCUMENT_ID: &str = "SPDXRef-DOCUMENT";
+
+/// An SPDX 2 document in its JSON form, such as `project.spdx.json`,
+/// reduced to its licensing.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct SpdxDocument {
+    pub(crate) packages: Vec<SpdxPackage>,
+    pub(crate) files: Vec<SpdxFile>,
+    /// The SPDX IDs of the packages the document is about.
+    pub(crate) document_describes: Vec<String>,
+    pub(crate) relationships: Vec<SpdxRelationship>,
+}
+
+/// A relationship between two elements of an SPDX document.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct SpdxRelationship {
+    pub(crate) spdx_element_id: String,
+    pub(crate) relationship_type: String,
+    pub(crate) related_spdx_element: String,
+}
+
+/// A package of an SPDX document.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct SpdxPackage {
+    pub(crate) name: String,
+    #[serde(rename = "SPDXID")]
+    pub(crate) spdx_id: String,
+    pub(crate) license_declared: Option<String>,
+    pub(crate) license_concluded: Option<String>,
+}
+
+/// A file of an SPDX document.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub(crate) struct SpdxFile {
+    /// The path of the file, such as `./src/main.rs`.
+    pub(crate) file_name: String,
+    /// The licenses found in the file when the document was made.
+    pub(crate) license_info_in_files: Vec<String>,
+}
+
+/// A difference between an SPDX document and the project on disk.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Discrepancy {
+    /// A license a package declares that no license file or header
+    /// of the project has.
+    Undetected { license: String, package: String },
+    /// A license of a license file or header that the document does
+    /// not mention.
+    Undeclared { license: String, file: String },
+    /// A file of the document missing from the project.
+    MissingFile { file: String },
+    /// A file whose header names other licenses than the document
+    /// lists for it.
+    FileMismatch {
+        file: String,
+        declared: String,
+        found: Option<String>,
+    },
+}
+
+impl Discrepancy {
+    /// Returns a one-line description of the discrepancy.
+    pub fn message(&self) -> String {
+        match self {
+            Discrepancy::Undetected {
+                license,
+                package,
+            } => {
+                t!(
+                    "verify-undetected",
+                    license = license.as_str(),
+                    package = package.as_str()
+                )
+            }
+            Discrepancy::Undeclared {
+                license,
+                file,
+            } => {
+                t!(
+                    "verify-undeclared",
+                    license = license.as_str(),
+                    file = file.as_str()
+                )
+            }
+            Discrepancy::MissingFile {
+                file,
+            } => t!("verify-missing-file", file = file.as_str()),
+            Discrepancy::FileMismatch {
+                file,
+                declared,
+                found: Some(found),
+            } => {
+                t!(
+                    "verify-file-mismatch",
+                    file = file.as_str(),
+                    declared = declared.as_str(),
+                    found = found.as_str()
+                )
+            }
+            Discrepancy::FileMismatch {
+                file,
+                declared,
+                found: None,
+            } => {
+                t!(
+                    "verify-file-no-header",
+                    file = file.as_str(),
+                    declared = declared.as_str()
+                )
+            }
+        }
+    }
+
+    /// Returns the event reporting this discrepancy with
+    /// `--log-json`.
+    pub fn event(&self) -> Event {
+        let file = match self {
+            Discrepancy::Undetected {
+                ..
+            } => String::new(),
+            Discrepancy::Undeclared {
+                file, ..
+            }
+            | Discrepancy::MissingFile {
+                file,
+            }
+            | Discrepancy::FileMismatch {
+                file, ..
+            } => file.clone(),
+        };
+        Event::ViolationFound {
+            check: "sbom",
+            file,
+            line: None,
+            message: self.message(),
+        }
+    }
+}
+
+impl SpdxDocument {
+    /// Reads an SPDX document in JSON form.
+    ///
+    /// # Errors
+    /// - If the file cannot be read.
+    /// - `Error::InvalidSbom` if the file is no SPDX JSON document.
+    pub fn read(path: &Path) -> LictoolResult<Self> {
+        let content = read_file(path)?;
+        let value: serde_json::Value = serde_json::from_str(&content).map_err(|_| {
+            Error::InvalidSbom {
+                path: path.display().to_string(),
+            }
+        })?;
+        if value.get("spdxVersion").is_none() {
+            Err(Error::InvalidSbom {
+                path: path.display().to_string(),
+            })?
+        }
+        Ok(serde_json::from_value(value).map_err(|_| {
+            Error::InvalidSbom {
+                path: path.display().to_string(),
+            }
+        })?)
+    }
+
+    /// Returns the packages the document is about, or all of them if
+    /// it does not say.
+    ///
+    /// Both `documentDescribes` and `DESCRIBES` or `DESCRIBED_BY`
+    /// relationships of the document count.
+    fn described_packages(&self) -> Vec<&SpdxPackage> {
+        let mut describes: BTreeSet<&str> =
+            self.document_describes.iter().map(String::as_str).collect();
+        for relationship in &self.relationships {
+            match relationship.relationship_type.as_str() {
+                "DESCRIBES" if relationship.spdx_element_id == DOCUMENT_ID => {
+                    describes.insert(&relationship.related_spdx_element);
+                }
+                "DESCRIBED_BY" if relationship.related_spdx_element == DOCUMENT_ID => {
+                    describes.insert(&relationship.spdx_element_id);
+                }
+                _ => {}
+            }
+        }
+        let described: Vec<_> = self
+            .packages
+            .iter()
+            .filter(|package| describes.contains(package.spdx_id.as_str()))
+            .collect();
+        if described.is_empty() {
+            self.packages.iter().collect()
+        } else {
+            described
+        }
+    }
+
+    /// Compares the licenses of the document with those found in the
+    /// project in `root`.
+    pub fn verify(
+        &self,
+        scan: &ArchiveScan,
+        root: &Path,
+    ) -> Vec<Discrepancy> {
+        let mut discrepancies = Vec::new();
+        // The licenses found on disk, with the first file using each.
+        let mut found: BTreeMap<String, String> = BTreeMap::new();
+        // The licenses detected in files, by their path.
+        let mut detected: BTreeMap<&str, &str> = BTreeMap::new();
+        for license_file in &scan.license_files {
+            if let Some(license) = &license_file.license {
+                detected.insert(&license_file.path, license);
+                for id in license_ids(license) {
+                    found.entry(id).or_insert_with(|| license_file.path.clone());
+                }
+            }
+        }
+        for (license, files) in &scan.headers {
+            for file in files {
+                detected.insert(file, license);
+                for id in license_ids(license) {
+                    found.entry(id).or_insert_with(|| file.clone());
+                }
+            }
+        }
+        // Licenses of snippets count for their file, as in the
+        // licenseInfoInFiles of SPDX documents.
+        let mut snippets: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+        for (license, found_snippets) in &scan.snippets {
+            for snippet in found_snippets {
+                for id in license_ids(license) {
+                    found
+                        .entry(id.clone())
+                        .or_insert_with(|| snippet.path.clone());
+                    snippets.entry(&snippet.path).or_default().insert(id);
+                }
+            }
+        }
+        let mut declared = BTreeSet::new();
+        for package in self.described_packages() {
+            let license = [&package.license_declared, &package.license_concluded]
+                .into_iter()
+                .flatten()
+                .map(String::as_str)
+                .find(|license| !NO_LICENSE.contains(license));
+            for id in license.map(license_ids).unwrap_or_default() {
+                if !found.contains_key(&id) {
+                    discrepancies.push(Discrepancy::Undetected {
+                        license: id.clone(),
+                        package: package.name.clone(),
+                    });
+                }
+                declared.insert(id);
+            }
+        }
+        for file in &self.files {
+            let path = file.file_name.strip_prefix("./").unwrap_or(&file.file_name);
+            let listed: Vec<&str> = file
+                .license_info_in_files
+                .iter()
+                .map(String::as_str)
+                .filter(|license| !NO_LICENSE.contains(license))
+                .collect();
+            let listed_ids: BTreeSet<String> = listed
+                .iter()
+                .flat_map(|license| license_ids(license))
+                .collect();
+            declared.extend(listed_ids.iter().cloned());
+            if !root.join(path).is_file() {
+                discrepancies.push(Discrepancy::MissingFile {
+                    file: path.to_string(),
+                });
+                continue;
+            }
+            if listed.is_empty() {
+                continue;
+            }
+            let license = detected.get(path).copied();
+            let mut found_ids: BTreeSet<String> = license
+                .map(license_ids)
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            found_ids.extend(snippets.get(path).into_iter().flatten().cloned());
+            if found_ids != listed_ids {
+                discrepancies.push(Discrepancy::FileMismatch {
+                    file: path.to_string(),
+                    declared: listed.join(", "),
+                    found: license.map(str::to_string),
+                });
+            }
+        }
+        for (license, file) in found {
+            if !declared.contains(&license) {
+                discrepancies.push(Discrepancy::Undeclared {
+                    license,
+                    file,
+                });
+            }
+        }
+        discrepancies
+    }
+}
+
+/// Returns the license IDs of an expression, or the expression itself
+/// if it does not parse.
+fn license_ids(expression: &str) -> Vec<String> {
+    match expression.parse::<Expression>() {
+        Ok(parsed) => {
+            parsed
+                .terms()
+                .into_iter()
+                .map(|term| term.id.clone())
+                .collect()
+        }
+        Err(_) => vec![expression.to_string()],
+    }
+}
+
+/// Prints the discrepancies between an SPDX document and the project.
+pub(crate) fn display_discrepancies(
+    sbom: &str,
+    discrepancies: &[Discrepancy],
+) {
+    if discrepancies.is_empty() {
+        success!(
+            "<green>✔</> <bold>{}</>",
+            t!("verify-consistent", sbom = sbom)
+        );
+        return;
+    }
+    for discrepancy in discrepancies {
+        cprintln!("<red>✘</> {}", discrepancy.message());
+    }
+}
+
+/// A file of the project with an SPDX header, as listed by
+/// `lictool sbom --files`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DescribedFile {
+    /// The path of the file relative to the project, with `/`
+    /// separators.
+    pub(crate) path: String,
+    /// The license IDs of its header.
+    pub(crate) licenses: Vec<String>,
+    /// The SHA-1 digest of its content, in hex.
+    pub(crate) sha1: String,
+}
+
+/// A minimal SPDX document describing the project in a directory as
+/// a single package.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ProjectSbom {
+    pub(crate) name: String,
+    pub(crate) version: Option<String>,
+    /// The license expression the project declares.
+    pub(crate) license: Option<String>,
+    /// The copyright lines of the main license file.
+    pub(crate) copyright: Option<String>,
+    pub(crate) files: Vec<DescribedFile>,
+    /// When the document was made, in UTC.
+    pub(crate) created: String,
+    /// The unique URI of the document.
+    pub(crate) namespace: String,
+}
+
+impl ProjectSbom {
+    /// Describes the project in `root`, with the files having an SPDX
+    /// header if `with_files` is set.
+    ///
+    /// The name and version come from the first manifest naming the
+    /// package, or else the name of the directory.
+    pub fn collect(
+        root: &Path,
+        config: &Config,
+        with_files: bool,
+    ) -> Self {
+        let (name, version) = manifest_package(root).unwrap_or_else(|| (project_name(root), None));
+        let license = declared_licenses(root, config)
+            .into_iter()
+            .next()
+            .map(|declared| declared.license);
+        let copyright = main_license_file(root, config.default_filename())
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|text| notice_lines(&text).join("\n"))
+            .filter(|copyright| !copyright.is_empty());
+        let files = if with_files {
+            described_files(root)
+        } else {
+            Vec::new()
+        };
+        let created = Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+        let digest = sha256_hex(format!("{}\n{:?}\n{}", name, version, created).as_bytes());
+        let namespace = format!("https://spdx.org/spdxdocs/{}-{}", name, &digest[..16]);
+        ProjectSbom {
+            name,
+            version,
+            license,
+            copyright,
+            files,
+            created,
+            namespace,
+        }
+    }
+
+    /// Returns the name of the document, the package name followed
+    /// by its version.
+    fn document_name(&self) -> String {
+        match &self.version {
+            Some(version) => format!("{}-{}", self.name, version),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Returns the package verification code over the described
+    /// files: the SHA-1 of their SHA-1 digests, sorted and joined.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no files are described, when the package's files
+    /// are not analyzed.
+    fn verification_code(&self) -> Option<String> {
+        if self.files.is_empty() {
+            return None;
+        }
+        let mut digests: Vec<&str> = self.files.iter().map(|file| file.sha1.as_str()).collect();
+        digests.sort_unstable();
+        Some(format!("{:x}", Sha1::digest(digests.concat())))
+    }
+
+    /// Returns the SPDX ID of the `index`th file, from zero.
+    fn file_id(index: usize) -> String {
+        format!("SPDXRef-File-{}", index + 1)
+    }
+
+    /// Renders the document in the SPDX tag-value format.
+    pub fn to_tag_value(&self) -> String {
+        // Values spanning lines must be wrapped in text tags.
+        let text = |value: &str| {
+            if value.contains('\n') {
+                format!("<text>{}</text>", value)
+            } else {
+                value.to_string()
+            }
+        };
+        let mut lines = vec![
+            format!("SPDXVersion: {}", SPDX_VERSION),
+            format!("DataLicense: {}", DATA_LICENSE),
+            "SPDXID: SPDXRef-DOCUMENT".to_string(),
+            format!("DocumentName: {}", self.document_name()),
+            format!("DocumentNamespace: {}", self.namespace),
+            format!("Creator: Tool: lictool-{}", env!("CARGO_PKG_VERSION")),
+            format!("Created: {}", self.created),
+            String::new(),
+            format!("PackageName: {}", self.name),
+            "SPDXID: SPDXRef-Package".to_string(),
+        ];
+        if let Some(version) = &self.version {
+            lines.push(format!("PackageVersion: {}", version));
+        }
+        lines.push(format!("PackageDownloadLocation: {}", NO_ASSERTION));
+        match self.verification_code() {
+            Some(code) => {
+                lines.extend([
+                    "FilesAnalyzed: true".to_string(),
+                    format!("PackageVerificationCode: {}", code),
+                ])
+            }
+            None => lines.push("FilesAnalyzed: false".to_string()),
+        }
+        lines.extend([
+            format!("PackageLicenseConcluded: {}", NO_ASSERTION),
+            format!(
+                "PackageLicenseDeclared: {}",
+                self.license.as_deref().unwrap_or(NO_ASSERTION)
+            ),
+            format!(
+                "PackageCopyrightText: {}",
+                text(self.copyright.as_deref().unwrap_or(NO_ASSERTION))
+            ),
+            String::new(),
+            "Relationship: SPDXRef-DOCUMENT DESCRIBES SPDXRef-Package".to_string(),
+        ]);
+        for (index, file) in self.files.iter().enumerate() {
+            lines.extend([
+                String::new(),
+                format!("FileName: ./{}", file.path),
+                format!("SPDXID: {}", Self::file_id(index)),
+                format!("FileChecksum: SHA1: {}", file.sha1),
+                format!("LicenseConcluded: {}", NO_ASSERTION),
+            ]);
+            lines.extend(
+                file.licenses
+                    .iter()
+                    .map(|license| format!("LicenseInfoInFile: {}", license)),
+            );
+            lines.extend([
+                format!("FileCopyrightText: {}", NO_ASSERTION),
+                format!(
+                    "Relationship: SPDXRef-Package CONTAINS {}",
+                    Self::file_id(index)
+                ),
+            ]);
+        }
+        lines.join("\n") + "\n"
+    }
+
+    /// Renders the document in the SPDX 2.3 JSON format.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut package = json!({
+            "name": self.name,
+            "SPDXID": "SPDXRef-Package",
+            "downloadLocation": NO_ASSERTION,
+            "filesAnalyzed": false,
+            "licenseConcluded": NO_ASSERTION,
+            "licenseDeclared": self.license.as_deref().unwrap_or(NO_ASSERTION),
+            "copyrightText": self.copyright.as_deref().unwrap_or(NO_ASSERTION),
+        });
+        if let Some(version) = &self.version {
+            package["versionInfo"] = json!(version);
+        }
+        if let Some(code) = self.verification_code() {
+            package["filesAnalyzed"] = json!(true);
+            package["packageVerificationCode"] = json!({"packageVerificationCodeValue": code});
+        }
+        let mut relationships = vec![json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": "SPDXRef-Package",
+        })];
+        let files: Vec<serde_json::Value> = self
+            .files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| {
+                relationships.push(json!({
+                    "spdxElementId": "SPDXRef-Package",
+                    "relationshipType": "CONTAINS",
+                    "relatedSpdxElement": Self::file_id(index),
+                }));
+                json!({
+                    "fileName": format!("./{}", file.path),
+                    "SPDXID": Self::file_id(index),
+                    "checksums": [{"algorithm": "SHA1", "checksumValue": file.sha1}],
+                    "licenseConcluded": NO_ASSERTION,
+                    "licenseInfoInFiles": file.licenses,
+                    "copyrightText": NO_ASSERTION,
+                })
+            })
+            .collect();
+        let mut document = json!({
+            "spdxVersion": SPDX_VERSION,
+            "dataLicense": DATA_LICENSE,
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": self.document_name(),
+            "documentNamespace": self.namespace,
+            "creationInfo": {
+                "created": self.created,
+                "creators": [format!("Tool: lictool-{}", env!("CARGO_PKG_VERSION"))],
+            },
+            "documentDescribes": ["SPDXRef-Package"],
+            "packages": [package],
+            "relationships": relationships,
+        });
+        if !files.is_empty() {
+            document["files"] = json!(files);
+        }
+        document
+    }
+}
+
+/// Lists the files of the project in `root` with an SPDX header,
+/// sorted by path.
+fn described_files(root: &Path) -> Vec<DescribedFile> {
+    let mut files: Vec<DescribedFile> = scan_dir(root)
+        .headers
+        .into_iter()
+        .flat_map(|(license, paths)| paths.into_iter().map(move |path| (path, license.clone())))
+        .filter_map(|(path, license)| {
+            let content = fs::read(root.join(&path)).ok()?;
+            Some(DescribedFile {
+                sha1: format!("{:x}", Sha1::digest(&content)),
+                licenses: license_ids(&license),
+                path,
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::{Discrepancy, ProjectSbom, SpdxDocument};
+    use crate::{config::Config, scan::scan_dir};
+
+    #[test]
+    fn test_verify_sbom() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("LICENSE"), "SPDX-License-Identifier: MIT\n").unwrap();
+        fs::write(
+            dir.path().join("main.rs"),
+            "// SPDX-License-Identifier: Apache-2.0\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("lib.rs"),
+            "// SPDX-License-Identifier: MIT\n",
+        )
+        .unwrap();
+        let sbom = dir.path().join("project.spdx.json");
+        fs::write(
+            &sbom,
+            r#"{
+                "spdxVersion": "SPDX-2.3",
+                "relationships": [
+                    {
+                        "spdxElementId": "SPDXRef-DOCUMENT",
+                        "relationshipType": "DESCRIBES",
+                        "relatedSpdxElement": "SPDXRef-Package"
+                    }
+                ],
+                "packages": [
+                    {"name": "demo", "SPDXID": "SPDXRef-Package", "licenseDeclared": "MIT OR ISC"},
+                    {"name": "dep", "SPDXID": "SPDXRef-Dep", "licenseDeclared": "Zlib"}
+                ],
+                "files": [
+                    {"fileName": "./LICENSE", "licenseInfoInFiles": ["MIT"]},
+                    {"fileName": "./lib.rs", "licenseInfoInFiles": ["MIT"]},
+                    {"fileName": "./main.rs", "licenseInfoInFiles": ["MIT"]},
+                    {"fileName": "./gone.rs", "licenseInfoInFiles": ["NOASSERTION"]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let document = SpdxDocument::read(&sbom).unwrap();
+        let discrepancies = document.verify(&scan_dir(dir.path()), dir.path());
+        assert_eq!(
+            discrepancies,
+            [
+                Discrepancy::Undetected {
+                    license: "ISC".to_string(),
+                    package: "demo".to_string(),
+                },
+                Discrepancy::FileMismatch {
+                    file: "main.rs".to_string(),
+                    declared: "MIT".to_string(),
+                    found: Some("Apache-2.0".to_string()),
+                },
+                Discrepancy::MissingFile {
+                    file: "gone.rs".to_string(),
+                },
+                Discrepancy::Undeclared {
+                    license: "Apache-2.0".to_string(),
+                    file: "main.rs".to_string(),
+                },
+            ]
+        );
+        fs::write(&sbom, "{}").unwrap();
+        assert!(SpdxDocument::read(&sbom).is_err());
+    }
+
+    #[test]
+    fn test_project_sbom() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"1.2.0\"\nlicense = \"MIT\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("LICENSE"),
+            "MIT License\n\nCopyright (c) 2024 Jane Doe\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("main.rs"),
+            "// SPDX-License-Identifier: MIT OR Apache-2.0\n",
+        )
+        .unwrap();
+        let sbom = ProjectSbom::collect(dir.path(), &Config::default(), true);
+        assert_eq!(sbom.name, "demo");
+        assert_eq!(sbom.version.as_deref(), Some("1.2.0"));
+        assert_eq!(sbom.license.as_deref(), Some("MIT"));
+        assert_eq!(
+            sbom.copyright.as_deref(),
+            Some("Copyright (c) 2024 Jane Doe")
+        );
+        assert_eq!(sbom.files.len(), 1);
+        assert_eq!(sbom.files[0].licenses, ["MIT", "Apache-2.0"]);
+        let tag_value = sbom.to_tag_value();
+        assert!(tag_value.contains("FilesAnalyzed: true\nPackageVerificationCode: "));
+        assert!(tag_value.contains("PackageLicenseDeclared: MIT\n"));
+        assert!(tag_value.contains("LicenseInfoInFile: Apache-2.0\n"));
+        // The generated document passes verification against the
+        // project it describes.
+        let document: SpdxDocument = serde_json::from_value(sbom.to_json()).unwrap();
+        assert_eq!(document.verify(&scan_dir(dir.path()), dir.path()), []);
+    }
+}
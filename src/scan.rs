@@ -0,0 +1,370 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{self, Read, Seek},
+    path::Path,
+};
+
+use anyhow::Context;
+use color_print::cprintln;
+use flate2::read::GzDecoder;
+use indicatif::ProgressBar;
+use serde::Serialize;
+use zip::ZipArchive;
+
+use crate::{
+    check::find_drift,
+    header::{spdx_header_license, spdx_snippets},
+    i18n::t,
+    project::is_license_file_name,
+    spdx::LicenseDetails,
+    util::{
+        errors::{Error, LictoolResult},
+        matching::MatchNormalization,
+        output::note,
+        walk::project_files,
+    },
+};
+
+/// Files in an archive larger than this are counted but not read.
+const MAX_SCANNED_FILE_SIZE: u64 = 1024 * 1024;
+
+/// The kinds of archives that can be scanned.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArchiveFormat {
+    Tar,
+    /// A gzip-compressed tarball, such as a `.tar.gz` or a `.crate`.
+    TarGz,
+    /// A zip file, such as a `.zip`, `.jar` or `.whl`.
+    Zip,
+}
+
+impl ArchiveFormat {
+    /// Tells the format of an archive from its extension.
+    fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".tar.gz") || name.ends_with(".tgz") || name.ends_with(".crate") {
+            Some(Self::TarGz)
+        } else if name.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if [".zip", ".jar", ".whl"]
+            .iter()
+            .any(|extension| name.ends_with(extension))
+        {
+            Some(Self::Zip)
+        } else {
+            None
+        }
+    }
+}
+
+/// The licensing found in an archive.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub(crate) struct ArchiveScan {
+    /// How many files the archive contains.
+    pub(crate) files: usize,
+    pub(crate) license_files: Vec<ArchivedLicense>,
+    /// The files with an SPDX header, by the license expression of
+    /// their header.
+    pub(crate) headers: BTreeMap<String, Vec<String>>,
+    /// The SPDX snippets with a license, by their license expression.
+    pub(crate) snippets: BTreeMap<String, Vec<ArchivedSnippet>>,
+}
+
+/// An SPDX snippet found in a file of an archive.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ArchivedSnippet {
+    pub(crate) path: String,
+    /// The lines of the snippet markers, starting at 1.
+    pub(crate) begin: usize,
+    pub(crate) end: Option<usize>,
+}
+
+/// A license file found in an archive.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct ArchivedLicense {
+    /// The path of the file within the archive.
+    pub(crate) path: String,
+    /// The license whose canonical text the file matches, if any.
+    pub(crate) license: Option<String>,
+    #[serde(skip)]
+    pub(crate) text: String,
+}
+
+impl ArchiveScan {
+    /// Records a file of the archive, reading it if it is a license
+    /// file or may have an SPDX header.
+    fn visit(
+        &mut self,
+        path: String,
+        size: u64,
+        reader: &mut dyn Read,
+    ) -> io::Result<()> {
+        self.files += 1;
+        if size > MAX_SCANNED_FILE_SIZE {
+            return Ok(());
+        }
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        // Binary files hold neither license texts nor headers.
+        let Ok(content) = String::from_utf8(bytes) else {
+            return Ok(());
+        };
+        let name = path.rsplit('/').next().unwrap_or(&path);
+        if is_license_file_name(name) {
+            self.license_files.push(ArchivedLicense {
+                license: spdx_header_license(&content).map(str::to_string),
+                path,
+                text: content,
+            });
+            return Ok(());
+        }
+        for snippet in spdx_snippets(&content) {
+            if let Some(license) = snippet.license {
+                self.snippets
+                    .entry(license)
+                    .or_default()
+                    .push(ArchivedSnippet {
+                        path: path.clone(),
+                        begin: snippet.begin,
+                        end: snippet.end,
+                    });
+            }
+        }
+        if let Some(license) = spdx_header_license(&content) {
+            self.headers
+                .entry(license.to_string())
+                .or_default()
+                .push(path);
+        }
+        Ok(())
+    }
+
+    /// Checks whether some license file is still to be identified
+    /// against the license texts.
+    pub fn needs_identification(&self) -> bool {
+        self.license_files
+            .iter()
+            .any(|license_file| license_file.license.is_none())
+    }
+
+    /// Identifies the license files without an SPDX identifier of
+    /// their own by their text.
+    pub fn identify(
+        &mut self,
+        details: &[LicenseDetails],
+    ) {
+        for license_file in &mut self.license_files {
+            if license_file.license.is_none() {
+                license_file.license = identify_license(&license_file.text, details);
+            }
+        }
+    }
+}
+
+/// Reads the license files and SPDX headers of a tar or zip archive,
+/// advancing `progress` by the bytes of the archive read.
+///
+/// # Errors
+/// - `Error::UnsupportedArchive` if the archive format is not known.
+/// - If the archive cannot be read or is corrupt.
+pub(crate) fn scan_archive(
+    path: &Path,
+    progress: &ProgressBar,
+) -> LictoolResult<ArchiveScan> {
+    let format = ArchiveFormat::from_path(path).ok_or_else(|| {
+        Error::UnsupportedArchive {
+            path: path.display().to_string(),
+        }
+    })?;
+    let read_error = || t!("error-read-file", path = path.display().to_string());
+    let file = File::open(path).with_context(read_error)?;
+    progress.set_length(file.metadata().map_or(0, |metadata| metadata.len()));
+    let file = progress.wrap_read(file);
+    let mut scan = ArchiveScan::default();
+    match format {
+        ArchiveFormat::Tar => scan_tar(tar::Archive::new(file), &mut scan),
+        ArchiveFormat::TarGz => scan_tar(tar::Archive::new(GzDecoder::new(file)), &mut scan),
+        ArchiveFormat::Zip => scan_zip(file, &mut scan),
+    }
+    .with_context(read_error)?;
+    scan.license_files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(scan)
+}
+
+/// Reads the license files and SPDX headers of the project in `root`
+/// like those of an archive, with paths relative to `root`.
+pub(crate) fn scan_dir(root: &Path) -> ArchiveScan {
+    let mut scan = ArchiveScan::default();
+    for path in project_files(root) {
+        let Ok(mut file) = File::open(&path) else {
+            continue;
+        };
+        let size = file.metadata().map_or(0, |metadata| metadata.len());
+        let relative = path.strip_prefix(root).unwrap_or(&path);
+        let name = relative.to_string_lossy().replace('\\', "/");
+        // Unreadable files are counted without their licensing.
+        let _ = scan.visit(name, size, &mut file);
+    }
+    scan.license_files.sort_by(|a, b| a.path.cmp(&b.path));
+    scan
+}
+
+/// Visits the regular files of a tarball.
+fn scan_tar<R: Read>(
+    mut archive: tar::Archive<R>,
+    scan: &mut ArchiveScan,
+) -> LictoolResult<()> {
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if entry.header().entry_type().is_file() {
+            let path = entry.path()?.to_string_lossy().into_owned();
+            let size = entry.size();
+            scan.visit(path, size, &mut entry)?;
+        }
+    }
+    Ok(())
+}
+
+/// Visits the regular files of a zip file.
+fn scan_zip<R: Read + Seek>(
+    file: R,
+    scan: &mut ArchiveScan,
+) -> LictoolResult<()> {
+    let mut archive = ZipArchive::new(file)?;
+    for idx in 0..archive.len() {
+        let mut entry = archive.by_index(idx)?;
+        if entry.is_file() {
+            let name = entry.name().to_string();
+            let size = entry.size();
+            scan.visit(name, size, &mut entry)?;
+        }
+    }
+    Ok(())
+}
+
+/// Finds the license whose canonical text a license file matches,
+/// following the SPDX matching guidelines.
+pub(crate) fn identify_license(
+    text: &str,
+    details: &[LicenseDetails],
+) -> Option<String> {
+    let normalization = MatchNormalization {
+        spdx_matching: true,
+        ..Default::default()
+    };
+    details
+        .iter()
+        .find(|details| find_drift(&details.license_text, text, &normalization).is_empty())
+        .map(|details| details.license_id.clone())
+}
+
+/// Prints the license files of an archive, followed by its SPDX
+/// headers grouped by license.
+pub(crate) fn display_scan(
+    archive: &str,
+    scan: &ArchiveScan,
+) {
+    cprintln!(
+        "<bold>{}</>",
+        t!("scan-summary", archive = archive, files = scan.files)
+    );
+    if scan.license_files.is_empty() {
+        note!(t!("scan-no-license-files"));
+    }
+    for license_file in &scan.license_files {
+        match &license_file.license {
+            Some(license) => {
+                cprintln!("  <green>✔</> {} <dim>{}</>", license, license_file.path)
+            }
+            None => {
+                cprintln!(
+                    "  <y>?</> {} <dim>{}</>",
+                    t!("scan-unidentified"),
+                    license_file.path
+                )
+            }
+        }
+    }
+    if scan.headers.is_empty() {
+        note!(t!("scan-no-headers"));
+    } else {
+        cprintln!("<bold>{}</>", t!("scan-headers"));
+    }
+    for (license, files) in &scan.headers {
+        cprintln!(
+            "  {} <dim>{}</>",
+            license,
+            t!("scan-header-files", count = files.len())
+        );
+    }
+    if !scan.snippets.is_empty() {
+        cprintln!("<bold>{}</>", t!("scan-snippets"));
+    }
+    for (license, snippets) in &scan.snippets {
+        cprintln!(
+            "  {} <dim>{}</>",
+            license,
+            t!("scan-snippet-count", count = snippets.len())
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use indicatif::ProgressBar;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::scan_archive;
+
+    #[test]
+    fn test_scan_archive() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("dist.zip");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        let files = [
+            (
+                "pkg/LICENSE",
+                "SPDX-License-Identifier: MIT\n\nMIT License\n",
+            ),
+            ("pkg/vendor/COPYING.txt", "Some license\n"),
+            (
+                "pkg/src/lib.rs",
+                "// SPDX-License-Identifier: MIT\nfn main() {}\n",
+            ),
+            (
+                "pkg/src/sort.rs",
+                "// SPDX-SnippetBegin\n// SPDX-License-Identifier: Zlib\n// SPDX-SnippetEnd\n",
+            ),
+            ("pkg/src/io.rs", "// SPDX-License-Identifier: MIT\n"),
+            ("pkg/README.md", "# pkg\n"),
+        ];
+        for (name, content) in files {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let scan = scan_archive(&path, &ProgressBar::hidden()).unwrap();
+        assert_eq!(scan.files, 6);
+        assert_eq!(
+            scan.license_files
+                .iter()
+                .map(|file| (file.path.as_str(), file.license.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("pkg/LICENSE", Some("MIT")),
+                ("pkg/vendor/COPYING.txt", None)
+            ]
+        );
+        assert!(scan.needs_identification());
+        assert_eq!(scan.headers["MIT"], vec!["pkg/src/lib.rs", "pkg/src/io.rs"]);
+        assert_eq!(scan.snippets["Zlib"][0].path, "pkg/src/sort.rs");
+        assert!(!scan.headers.contains_key("Zlib"));
+        assert!(scan_archive(&dir.path().join("dist.rar"), &ProgressBar::hidden()).is_err());
+    }
+}
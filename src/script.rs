@@ -0,0 +1,189 @@
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, Local};
+use color_print::cformat;
+use rhai::{Dynamic, Engine, Map, Scope, AST};
+
+use crate::{
+    template::Field,
+    util::{
+        defaults::FieldDefaults,
+        errors::{Error, LictoolResult},
+        git::remote_url,
+        input::read_file,
+    },
+};
+
+/// The number of operations after which a script is stopped, so that
+/// an endless loop cannot hang lictool.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// A Rhai script computing placeholder values, referenced by
+/// `placeholder-script` in the configuration.
+///
+/// The script may define the functions `owner`, `year`, `repo` and
+/// `email`, each taking a map with the current values along with the
+/// `remote` URL of the repository and the `current_year`. A function
+/// returns the value of its placeholder, or `()` to keep the current
+/// one.
+#[derive(Debug, Clone)]
+pub(crate) struct PlaceholderScript {
+    pub(crate) path: PathBuf,
+    ast: AST,
+}
+
+impl PlaceholderScript {
+    /// Reads and compiles the script at `path`.
+    ///
+    /// # Errors
+    /// - If the script cannot be read.
+    /// - `Error::InvalidScript` if the script does not compile.
+    pub fn load(path: &Path) -> LictoolResult<Self> {
+        let source = read_file(path)?;
+        let ast = engine().compile(source).map_err(|err| {
+            Error::InvalidScript {
+                path: path.display().to_string(),
+                message: err.to_string(),
+            }
+        })?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            ast,
+        })
+    }
+
+    /// Returns the values of the placeholders as computed by the
+    /// script from `values`.
+    ///
+    /// # Errors
+    /// - `Error::InvalidScript` if a function of the script fails.
+    pub fn apply(
+        &self,
+        values: &FieldDefaults,
+    ) -> LictoolResult<FieldDefaults> {
+        let engine = engine();
+        let context = self.context(values);
+        let mut computed = values.clone();
+        for field in Field::ALL {
+            let name = function_name(field);
+            let defined = self
+                .ast
+                .iter_functions()
+                .any(|function| function.name == name && function.params.len() == 1);
+            if !defined {
+                continue;
+            }
+            let value = engine
+                .call_fn::<Dynamic>(&mut Scope::new(), &self.ast, name, (context.clone(),))
+                .map_err(|err| {
+                    Error::InvalidScript {
+                        path: self.path.display().to_string(),
+                        message: err.to_string(),
+                    }
+                })?;
+            if value.is_unit() {
+                continue;
+            }
+            let value = Some(value.to_string());
+            match field {
+                Field::Owner => computed.owner = value,
+                Field::Year => computed.year = value,
+                Field::Repo => computed.repo = value,
+                Field::Email => computed.email = value,
+            }
+        }
+        Ok(computed)
+    }
+
+    /// Builds the map passed to the functions of the script.
+    fn context(
+        &self,
+        values: &FieldDefaults,
+    ) -> Map {
+        let optional = |value: Option<&String>| value.cloned().map_or(Dynamic::UNIT, Dynamic::from);
+        let mut context = Map::new();
+        for field in Field::ALL {
+            context.insert(function_name(field).into(), optional(values.field(field)));
+        }
+        context.insert(
+            "remote".into(),
+            optional(remote_url(Path::new(".")).as_ref()),
+        );
+        context.insert(
+            "current_year".into(),
+            Dynamic::from(i64::from(Local::now().year())),
+        );
+        context
+    }
+
+    /// Like `apply`, but keeps `values` when the script fails,
+    /// warning about the failure.
+    pub fn apply_or_warn(
+        &self,
+        values: &FieldDefaults,
+    ) -> FieldDefaults {
+        self.apply(values).unwrap_or_else(|err| {
+            // Printed to stderr, keeping the output of the command
+            // itself intact.
+            eprintln!("{}", cformat!("<yellow>{}</>", err));
+            values.clone()
+        })
+    }
+}
+
+/// Returns the script function computing a field.
+fn function_name(field: Field) -> &'static str {
+    match field {
+        Field::Owner => "owner",
+        Field::Year => "year",
+        Field::Repo => "repo",
+        Field::Email => "email",
+    }
+}
+
+/// Returns the engine running placeholder scripts.
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::PlaceholderScript;
+    use crate::util::defaults::FieldDefaults;
+
+    #[test]
+    fn test_placeholder_script() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("placeholders.rhai");
+        fs::write(
+            &path,
+            r#"
+            fn owner(ctx) { if ctx.owner == () { "Example Org" } }
+            fn year(ctx) { `${ctx.year}-${ctx.current_year}` }
+            "#,
+        )
+        .unwrap();
+        let script = PlaceholderScript::load(&path).unwrap();
+        let values = FieldDefaults {
+            year: Some("2019".to_string()),
+            repo: Some("lictool".to_string()),
+            ..Default::default()
+        };
+        let computed = script.apply(&values).unwrap();
+        assert_eq!(computed.owner.as_deref(), Some("Example Org"));
+        assert!(computed.year.unwrap().starts_with("2019-20"));
+        assert_eq!(computed.repo.as_deref(), Some("lictool"));
+        fs::write(&path, "fn owner(ctx) { ctx.missing.len() }").unwrap();
+        let script = PlaceholderScript::load(&path).unwrap();
+        assert!(script.apply(&values).is_err());
+        fs::write(&path, "fn owner(ctx) {").unwrap();
+        assert!(PlaceholderScript::load(&path).is_err());
+    }
+}
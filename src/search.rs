@@ -0,0 +1,236 @@
+use std::cmp::Reverse;
+
+use color_print::cprintln;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+
+use crate::{
+    display::color_id,
+    i18n::t,
+    spdx::License,
+    util::{errors::Error, output::note},
+};
+
+/// How many characters around a match are shown for context.
+const CONTEXT_CHARS: usize = 30;
+
+/// A passage of a license text matching the search pattern.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct SearchMatch {
+    /// The line of the license text where the match starts.
+    pub(crate) line: usize,
+    /// Up to `CONTEXT_CHARS` characters preceding the match.
+    pub(crate) before: String,
+    pub(crate) matched: String,
+    /// Up to `CONTEXT_CHARS` characters following the match.
+    pub(crate) after: String,
+}
+
+/// Finds the licenses whose ID or name matches `query`, best first.
+///
+/// Licenses whose ID or name contains the query, ignoring case, are
+/// returned, those whose ID starts with it first. Only when there are
+/// none are the licenses matching it fuzzily returned, such as
+/// `GPL-3.0-only` for `gplv3`.
+pub(crate) fn search_names<'a>(
+    licenses: &'a [License],
+    query: &str,
+) -> Vec<&'a License> {
+    let matcher = SkimMatcherV2::default().ignore_case();
+    let needle = query.to_lowercase();
+    let mut scored: Vec<(&License, bool, bool, i64)> = licenses
+        .iter()
+        .filter_map(|license| {
+            let score = matcher.fuzzy_match(&format!("{} {}", license.id, license.name), query)?;
+            let id = license.id.to_lowercase();
+            let contains = id.contains(&needle) || license.name.to_lowercase().contains(&needle);
+            Some((license, contains, id.starts_with(&needle), score))
+        })
+        .collect();
+    if scored.iter().any(|(_, contains, ..)| *contains) {
+        scored.retain(|(_, contains, ..)| *contains);
+    }
+    scored
+        .sort_by_key(|&(license, _, prefix, score)| (Reverse(prefix), Reverse(score), &license.id));
+    scored.into_iter().map(|(license, ..)| license).collect()
+}
+
+/// Prints the licenses found by `search_names` with their names.
+pub(crate) fn display_name_matches(
+    licenses: &[&License],
+    query: &str,
+) {
+    if licenses.is_empty() {
+        note!(t!("search-no-licenses", query = query));
+        return;
+    }
+    let width = licenses
+        .iter()
+        .map(|license| license.id.len())
+        .max()
+        .unwrap_or(0);
+    for license in licenses {
+        let padding = " ".repeat(width - license.id.len());
+        cprintln!("{}{}  <dim>{}</>", color_id(license), padding, license.name);
+    }
+}
+
+/// Builds the pattern searched for, escaping it unless `regex` is
+/// set.
+///
+/// # Errors
+/// - `Error::InvalidPattern` if the regular expression is invalid.
+pub(crate) fn build_pattern(
+    pattern: &str,
+    regex: bool,
+    ignore_case: bool,
+) -> Result<Regex, Error> {
+    let source = if regex {
+        pattern.to_string()
+    } else {
+        regex::escape(pattern)
+    };
+    RegexBuilder::new(&source)
+        .case_insensitive(ignore_case)
+        .build()
+        .map_err(|error| {
+            Error::InvalidPattern {
+                pattern: pattern.to_string(),
+                reason: error.to_string(),
+            }
+        })
+}
+
+/// Finds the passages of a license text matching `pattern`.
+///
+/// Runs of whitespace, line breaks included, are collapsed into a
+/// single space first, so clauses wrapped across lines still match.
+pub(crate) fn search_text(
+    pattern: &Regex,
+    text: &str,
+) -> Vec<SearchMatch> {
+    let mut collapsed = String::new();
+    // The offset in the collapsed text at which each line starts.
+    let mut line_starts: Vec<(usize, usize)> = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        let words = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if words.is_empty() {
+            continue;
+        }
+        if !collapsed.is_empty() {
+            collapsed.push(' ');
+        }
+        line_starts.push((collapsed.len(), idx + 1));
+        collapsed.push_str(&words);
+    }
+    pattern
+        .find_iter(&collapsed)
+        .filter(|found| !found.is_empty())
+        .map(|found| {
+            let line = line_starts
+                .iter()
+                .take_while(|(offset, _)| *offset <= found.start())
+                .last()
+                .map_or(1, |(_, line)| *line);
+            let before = &collapsed[..found.start()];
+            let after = &collapsed[found.end()..];
+            SearchMatch {
+                line,
+                before: before
+                    .chars()
+                    .rev()
+                    .take(CONTEXT_CHARS)
+                    .collect::<Vec<_>>()
+                    .into_iter()
+                    .rev()
+                    .collect(),
+                matched: found.as_str().to_string(),
+                after: after.chars().take(CONTEXT_CHARS).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Prints the matches of each license, followed by their count.
+pub(crate) fn display_matches(
+    results: &[(String, Vec<SearchMatch>)],
+    pattern: &str,
+) {
+    let licenses = results.len();
+    let matches: usize = results.iter().map(|(_, matches)| matches.len()).sum();
+    if matches == 0 {
+        note!(t!("search-no-matches", pattern = pattern));
+        return;
+    }
+    for (license_id, matches) in results {
+        cprintln!("<bold>{}</>", license_id);
+        for found in matches {
+            cprintln!(
+                "  <cyan>{:>4}</>: …{}<red, bold>{}</>{}…",
+                found.line,
+                found.before,
+                found.matched,
+                found.after
+            );
+        }
+    }
+    note!(t!("search-summary", matches = matches, licenses = licenses));
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{build_pattern, search_names, search_text};
+    use crate::spdx::License;
+
+    #[test]
+    fn test_search_text() {
+        let text = "10. Automatic Licensing.\n\nIf you institute patent litigation, then\nany \
+                    patent licenses granted to you\n   shall terminate.\n";
+        let pattern = build_pattern("patent.{0,40}terminat", true, false).unwrap();
+        let matches = search_text(&pattern, text);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line, 4);
+        assert_eq!(
+            matches[0].matched,
+            "patent licenses granted to you shall terminat"
+        );
+        assert_eq!(matches[0].after, "e.");
+        let literal = build_pattern("PATENT.", false, true).unwrap();
+        assert!(search_text(&literal, text).is_empty());
+        assert!(build_pattern("(", true, false).is_err());
+    }
+
+    #[test]
+    fn test_search_names() {
+        let license = |id: &str, name: &str| {
+            License {
+                is_deprecated_license_id: false,
+                details_url: String::new(),
+                name: name.to_string(),
+                id: id.to_string(),
+                is_osi_approved: Some(true),
+                is_fsf_libre: None,
+            }
+        };
+        let licenses = [
+            license("MIT", "MIT License"),
+            license("Apache-1.1", "Apache License 1.1"),
+            license("ECL-2.0", "Educational Community License v2.0"),
+            license("Apache-2.0", "Apache License 2.0"),
+            license("GPL-3.0-only", "GNU General Public License v3.0 only"),
+        ];
+        let ids = |query| {
+            search_names(&licenses, query)
+                .into_iter()
+                .map(|license| license.id.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("apache"), ["Apache-1.1", "Apache-2.0"]);
+        assert_eq!(ids("educational"), ["ECL-2.0"]);
+        assert_eq!(ids("gplv3"), ["GPL-3.0-only"]);
+        assert!(ids("zzz").is_empty());
+    }
+}
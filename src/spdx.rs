@@ -1,19 +1,68 @@
-use http_cache_reqwest::{CACacheManager, Cache, CacheMode, HttpCache, HttpCacheOptions};
-use reqwest::Client;
-use reqwest_middleware::ClientBuilder;
-use serde::Deserialize;
+use async_trait::async_trait;
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
 extern crate reqwest;
-use std::fmt::Display;
-
-use color_print::{cformat, cstr};
+use std::{borrow::Cow, fmt::Display, str::FromStr, sync::OnceLock};
 
 use crate::{
     consts::{EMAIL, OWNER, REPO, YEAR},
-    util::{cache::http_cache_dir, errors::LictoolResult},
+    fsf::FsfLicense,
+    i18n::t,
+    placeholders::template_keys,
+    template::Field,
+    util::{
+        errors::{Error, LictoolResult},
+        http,
+        mirror::read_mirrored,
+    },
 };
 
 const SPDX_BASE_URL: &str = "https://spdx.org";
 
+/// The GitHub REST API, whose Licenses API serves the licenses
+/// GitHub offers for repositories.
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+static SOURCE: OnceLock<Source> = OnceLock::new();
+
+/// How many license details are fetched at once by bulk operations.
+pub const DETAILS_CONCURRENCY: usize = 16;
+
+/// Common short names of licenses with the IDs they stand for, keyed
+/// by their normalized form (see `normalize_id`).
+const LICENSE_ALIASES: [(&str, &str); 24] = [
+    ("gpl", "GPL-3.0-only"),
+    ("gpl2", "GPL-2.0-only"),
+    ("gplv2", "GPL-2.0-only"),
+    ("gpl3", "GPL-3.0-only"),
+    ("gplv3", "GPL-3.0-only"),
+    ("lgpl", "LGPL-3.0-only"),
+    ("lgpl2", "LGPL-2.1-only"),
+    ("lgplv2", "LGPL-2.1-only"),
+    ("lgpl3", "LGPL-3.0-only"),
+    ("lgplv3", "LGPL-3.0-only"),
+    ("agpl", "AGPL-3.0-only"),
+    ("agpl3", "AGPL-3.0-only"),
+    ("agplv3", "AGPL-3.0-only"),
+    ("apache", "Apache-2.0"),
+    ("apache2", "Apache-2.0"),
+    ("bsd", "BSD-3-Clause"),
+    ("bsd2", "BSD-2-Clause"),
+    ("bsd3", "BSD-3-Clause"),
+    ("mpl", "MPL-2.0"),
+    ("mpl2", "MPL-2.0"),
+    ("epl", "EPL-2.0"),
+    ("eupl", "EUPL-1.2"),
+    ("boost", "BSL-1.0"),
+    ("publicdomain", "CC0-1.0"),
+];
+
+/// How similar an ID must be to a query to be suggested for it.
+const MIN_SUGGESTION_SIMILARITY: f64 = 0.85;
+
+/// How many IDs are suggested for a query at most.
+const MAX_SUGGESTIONS: usize = 5;
+
 // const SPDX_LICENSES_URL: Url = "https://spdx.org/licenses/licenses.json";
 
 /// A struct representing a collection of software licenses.
@@ -25,73 +74,444 @@ pub struct Licenses {
     /// A vector of `License` structs representing the licenses.
     #[serde(rename = "licenses")]
     pub body: Vec<License>,
+    /// The release of the SPDX license list, such as `3.24.0`.
+    #[serde(rename = "licenseListVersion", default)]
+    pub version: String,
 }
 
 impl Licenses {
+    /// Fetches the SPDX license list from the configured source, from
+    /// the cache if it is fresh.
+    ///
+    /// # Errors
+    /// - If the list cannot be fetched or decoded.
     pub async fn new() -> LictoolResult<Self> {
-        fetch_licenses(SPDX_BASE_URL).await
+        source().backend().licenses().await
     }
 
-    /// Filters the licenses based on specified criteria.
+    /// Fetches the SPDX license list, revalidating a cached copy with
+    /// spdx.org even while it is fresh.
+    ///
+    /// Other sources are fetched as by `Licenses::new`.
+    ///
+    /// # Errors
+    /// - If the list cannot be fetched or decoded.
+    pub async fn revalidate() -> LictoolResult<Self> {
+        match source() {
+            Source::Spdx => http::revalidate_json(&spdx_licenses_url()).await,
+            _ => Self::new().await,
+        }
+    }
+
+    /// Reads the license list of the configured source from the
+    /// imported mirror or the cache only, for shell completion, which
+    /// must not wait on the network.
+    ///
+    /// # Errors
+    /// - If the list was never fetched from the source.
+    pub async fn cached() -> LictoolResult<Self> {
+        match source() {
+            Source::Github => {
+                let licenses = http::get_cached_json(&github_licenses_url(GITHUB_API_URL)).await?;
+                Ok(github_licenses(licenses))
+            }
+            Source::Spdx | Source::Mirror(_) => http::get_cached_json(&spdx_licenses_url()).await,
+        }
+    }
+
+    /// Finds the license with the given ID.
     ///
-    /// This function returns a vector of references to `License`
-    /// objects that match the given criteria for deprecation
-    /// status, support status, OSI approval, and FSF Libre approval.
+    /// IDs are compared as-is, without formatting each license, since
+    /// lookups run over the whole list.
+    pub fn find(
+        &self,
+        license_id: &str,
+    ) -> Option<&License> {
+        self.body.iter().find(|license| license.id == license_id)
+    }
+
+    /// Finds the license a user most likely means by `query`, such as
+    /// `MIT` for `mit` or `GPL-3.0-only` for `gpl3`.
+    ///
+    /// An exact ID is preferred, then one differing only in case,
+    /// then one differing only in punctuation, such as `Apache 2.0`,
+    /// and finally a common alias.
+    ///
+    /// # Errors
+    /// - `Error::LicenseNotFound` with the closest IDs if none match.
+    /// - `Error::NotFound` if no ID is even close.
+    pub fn resolve(
+        &self,
+        query: &str,
+    ) -> LictoolResult<&License> {
+        let normalized = normalize_id(query);
+        let alias = LICENSE_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == normalized)
+            .map(|(_, id)| *id);
+        let resolved = self.find(query).or_else(|| {
+            self.body
+                .iter()
+                .find(|license| license.id.eq_ignore_ascii_case(query))
+                .or_else(|| {
+                    self.body
+                        .iter()
+                        .find(|license| normalize_id(&license.id) == normalized)
+                })
+                .or_else(|| alias.and_then(|id| self.find(id)))
+        });
+        if let Some(license) = resolved {
+            return Ok(license);
+        }
+        let mut candidates: Vec<(f64, &str)> = self
+            .body
+            .iter()
+            .filter(|license| !license.is_deprecated_license_id)
+            .filter_map(|license| {
+                let id = normalize_id(&license.id);
+                let similarity = if !normalized.is_empty() && id.starts_with(&normalized) {
+                    1.0
+                } else {
+                    strsim::jaro_winkler(&normalized, &id)
+                };
+                (similarity >= MIN_SUGGESTION_SIMILARITY)
+                    .then_some((similarity, license.id.as_str()))
+            })
+            .collect();
+        if candidates.is_empty() {
+            Err(Error::NotFound)?
+        }
+        candidates.sort_by(|(a, a_id), (b, b_id)| b.total_cmp(a).then(a_id.cmp(b_id)));
+        let suggestions: Vec<&str> = candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, id)| id)
+            .collect();
+        Err(Error::LicenseNotFound {
+            id: query.to_string(),
+            suggestions: suggestions.join(", "),
+        })?
+    }
+
+    /// Fetches the details of the licenses with the given IDs, at
+    /// most `max_concurrency` at a time, calling `on_fetched` once
+    /// each is fetched, such as to count them on a progress bar.
     ///
-    /// # Arguments
+    /// # Returns
+    ///
+    /// The details in the order of `ids`.
+    ///
+    /// # Errors
+    /// - `Error::UnknownLicenses` if an ID is not on the list.
+    /// - If the details of a license cannot be fetched or decoded.
+    pub async fn details_for<S: AsRef<str>>(
+        &self,
+        ids: &[S],
+        max_concurrency: usize,
+        mut on_fetched: impl FnMut(),
+    ) -> LictoolResult<Vec<LicenseDetails>> {
+        let unknown: Vec<&str> = ids
+            .iter()
+            .map(AsRef::as_ref)
+            .filter(|id| self.find(id).is_none())
+            .collect();
+        if !unknown.is_empty() {
+            Err(Error::UnknownLicenses {
+                ids: unknown.join(", "),
+            })?
+        }
+        let licenses: Vec<&License> = ids.iter().filter_map(|id| self.find(id.as_ref())).collect();
+        let fetched: LictoolResult<Vec<(usize, LicenseDetails)>> =
+            stream::iter(licenses.into_iter().enumerate())
+                .map(|(index, license)| async move { Ok((index, license.details().await?)) })
+                .buffer_unordered(max_concurrency.max(1))
+                .inspect(|_| on_fetched())
+                .try_collect()
+                .await;
+        let mut fetched = fetched?;
+        fetched.sort_by_key(|(index, _)| *index);
+        Ok(fetched.into_iter().map(|(_, details)| details).collect())
+    }
+
+    /// Fetches the details of every license on the list, like
+    /// `details_for` without a callback.
     ///
-    /// * `deprecated` - A boolean indicating whether to include
-    ///   deprecated licenses.
-    /// * `supported` - A boolean indicating whether to include
-    ///   supported licenses.
-    /// * `osi_approved` - A boolean indicating whether to include OSI
-    ///   approved licenses.
-    /// * `fsf_libre` - A boolean indicating whether to include FSF
-    ///   Libre approved licenses.
+    /// # Errors
+    /// - If the details of a license cannot be fetched or decoded.
+    pub async fn all_details(&self) -> LictoolResult<Vec<LicenseDetails>> {
+        let ids: Vec<&str> = self
+            .body
+            .iter()
+            .map(|license| license.id.as_str())
+            .collect();
+        self.details_for(&ids, DETAILS_CONCURRENCY, || {}).await
+    }
+
+    /// Filters the licenses based on specified criteria.
     ///
     /// # Returns
     ///
-    /// A vector of references to `License` structs that meet the
-    /// specified criteria.
+    /// A vector of references to the `License` structs that meet
+    /// every criterion of `options`.
     ///
     /// # Example
     ///
-    /// ```
+    /// ```ignore
     /// let licenses = Licenses { /* initialize fields */ };
-    /// let filtered = licenses.filter_by(true, true, false, false);
+    /// // OSI approved, but not deprecated.
+    /// let filtered = licenses.filter_by(&FilterOptions {
+    ///     deprecated: Some(false),
+    ///     osi_approved: Some(true),
+    ///     ..Default::default()
+    /// });
     /// println!("{:?}", filtered);
     /// ```
     pub fn filter_by(
         &self,
-        deprecated: bool,
-        supported: bool,
-        osi_approved: bool,
-        fsf_libre: bool,
+        options: &FilterOptions,
     ) -> Vec<&License> {
         self.body
             .iter()
-            .filter(|license| {
-                let mut result = true;
-                if deprecated {
-                    result = result && license.is_deprecated_license_id == deprecated;
-                }
-                if supported {
-                    result = result && license.is_deprecated_license_id != supported;
-                }
-                if osi_approved {
-                    result = result && license.is_osi_approved == osi_approved;
-                }
-                if fsf_libre {
-                    result = result && license.is_fsf_libre == Some(fsf_libre);
-                }
-                result
-            })
+            .filter(|license| options.matches(license))
             .collect()
     }
 }
 
+/// The criteria `Licenses::filter_by` selects licenses by.
+///
+/// Each criterion requires a property with `Some(true)`, excludes it
+/// with `Some(false)` and is ignored with `None`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct FilterOptions {
+    /// Whether the license ID is deprecated, the opposite of being
+    /// supported.
+    pub deprecated: Option<bool>,
+    /// Whether the OSI approved the license; licenses of unknown
+    /// status count as not approved.
+    pub osi_approved: Option<bool>,
+    /// Whether the FSF considers the license free; licenses it has
+    /// not rated count as not free.
+    pub fsf_libre: Option<bool>,
+}
+
+impl FilterOptions {
+    /// Checks whether a license meets every criterion.
+    pub fn matches(
+        &self,
+        license: &License,
+    ) -> bool {
+        let meets = |criterion: Option<bool>, value: bool| criterion.is_none_or(|c| c == value);
+        meets(self.deprecated, license.is_deprecated_license_id)
+            && meets(self.osi_approved, license.is_osi_approved == Some(true))
+            && meets(self.fsf_libre, license.is_fsf_libre == Some(true))
+    }
+}
+
+/// Where license metadata is fetched from, for networks blocking
+/// spdx.org.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Source {
+    /// The SPDX license list on spdx.org.
+    #[default]
+    Spdx,
+    /// The GitHub Licenses API, which only serves the licenses GitHub
+    /// offers for repositories, without OSI or FSF approval.
+    Github,
+    /// A self-hosted copy of spdx.org at this base URL, serving
+    /// `/licenses/licenses.json` and the license details next to it.
+    Mirror(String),
+}
+
+impl FromStr for Source {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "spdx" => Ok(Source::Spdx),
+            "github" => Ok(Source::Github),
+            url if url.starts_with("https://") || url.starts_with("http://") => {
+                Ok(Source::Mirror(url.trim_end_matches('/').to_string()))
+            }
+            _ => Err(t!("invalid-source", source = value)),
+        }
+    }
+}
+
+impl TryFrom<String> for Source {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Source {
+    /// Returns the implementation fetching from this source.
+    pub(crate) fn backend(&self) -> Box<dyn LicenseSource> {
+        match self {
+            Source::Spdx => {
+                Box::new(SpdxSource {
+                    base_url: SPDX_BASE_URL.to_string(),
+                })
+            }
+            Source::Github => {
+                Box::new(GithubSource {
+                    api_url: GITHUB_API_URL.to_string(),
+                })
+            }
+            Source::Mirror(base_url) => {
+                Box::new(SpdxSource {
+                    base_url: base_url.clone(),
+                })
+            }
+        }
+    }
+
+    /// Returns the base URL serving data in the format of spdx.org,
+    /// such as the exception list; spdx.org itself for GitHub, which
+    /// has no exceptions.
+    fn spdx_base_url(&self) -> &str {
+        match self {
+            Source::Mirror(base_url) => base_url,
+            Source::Spdx | Source::Github => SPDX_BASE_URL,
+        }
+    }
+}
+
+/// Sets the source license metadata is fetched from.
+///
+/// Only takes effect if called before the first fetch.
+pub(crate) fn set_source(source: Source) {
+    let _ = SOURCE.set(source);
+}
+
+/// Returns the configured source, spdx.org by default.
+fn source() -> &'static Source {
+    SOURCE.get_or_init(Source::default)
+}
+
+/// A service serving license metadata, decoded from its own format
+/// into the types of the SPDX list.
+#[async_trait]
+pub(crate) trait LicenseSource: Send + Sync {
+    /// Fetches the list of licenses.
+    async fn licenses(&self) -> LictoolResult<Licenses>;
+
+    /// Fetches the details of a license of the list.
+    async fn details(
+        &self,
+        license: &License,
+    ) -> LictoolResult<LicenseDetails>;
+}
+
+/// The SPDX license list, on spdx.org or a mirror of it.
+struct SpdxSource {
+    base_url: String,
+}
+
+#[async_trait]
+impl LicenseSource for SpdxSource {
+    async fn licenses(&self) -> LictoolResult<Licenses> {
+        fetch_licenses(self.base_url.as_str()).await
+    }
+
+    async fn details(
+        &self,
+        license: &License,
+    ) -> LictoolResult<LicenseDetails> {
+        fetch_license_details(&license.details_url).await
+    }
+}
+
+/// The GitHub Licenses API.
+struct GithubSource {
+    api_url: String,
+}
+
+/// A license of the GitHub Licenses API list.
+#[derive(Debug, Deserialize)]
+struct GithubLicense {
+    name: String,
+    /// The SPDX ID, or `NOASSERTION` for licenses SPDX does not
+    /// list.
+    spdx_id: String,
+    /// The URL of the license details.
+    url: Option<String>,
+}
+
+/// The details of a license of the GitHub Licenses API.
+#[derive(Debug, Deserialize)]
+struct GithubLicenseDetails {
+    name: String,
+    spdx_id: String,
+    html_url: Option<String>,
+    description: Option<String>,
+    body: String,
+}
+
+#[async_trait]
+impl LicenseSource for GithubSource {
+    async fn licenses(&self) -> LictoolResult<Licenses> {
+        let licenses = http::get_json(&github_licenses_url(&self.api_url)).await?;
+        Ok(github_licenses(licenses))
+    }
+
+    async fn details(
+        &self,
+        license: &License,
+    ) -> LictoolResult<LicenseDetails> {
+        let details: GithubLicenseDetails = http::get_json(&license.details_url).await?;
+        Ok(LicenseDetails {
+            is_deprecated_license_id: false,
+            license_text: details.body,
+            name: details.name,
+            license_comments: details.description,
+            license_id: details.spdx_id,
+            see_also: details.html_url.into_iter().collect(),
+            is_osi_approved: None,
+            is_fsf_libre: None,
+            deprecated_version: None,
+            fsf: None,
+        })
+    }
+}
+
+/// Returns the URL of the license list of the GitHub Licenses API at
+/// `api_url`.
+fn github_licenses_url(api_url: &str) -> String {
+    format!("{}/licenses?per_page=100", api_url)
+}
+
+/// Converts the license list of the GitHub Licenses API, leaving out
+/// the licenses SPDX does not list.
+fn github_licenses(licenses: Vec<GithubLicense>) -> Licenses {
+    let body = licenses
+        .into_iter()
+        .filter(|license| license.spdx_id != "NOASSERTION")
+        .filter_map(|license| {
+            Some(License {
+                is_deprecated_license_id: false,
+                details_url: license.url?,
+                name: license.name,
+                id: license.spdx_id,
+                is_osi_approved: None,
+                is_fsf_libre: None,
+            })
+        })
+        .collect();
+    Licenses {
+        body,
+        version: String::new(),
+    }
+}
+
 /// Asynchronously fetches licenses from a given base URL.
 ///
+/// The list is decoded through `ListedLicenses`, borrowing from the
+/// response, and each license links its details next to the list on
+/// `base_url`.
+///
 /// # Parameters
 /// - `base_url`: The base URL as a string-like type.
 ///
@@ -99,57 +519,130 @@ impl Licenses {
 /// - `LictoolResult<Licenses>`: The result containing the licenses or
 ///   an error.
 async fn fetch_licenses<S: Into<String>>(base_url: S) -> LictoolResult<Licenses> {
-    let client = ClientBuilder::new(Client::new())
-        .with(Cache(HttpCache {
-            mode: CacheMode::Default,
-            manager: CACacheManager {
-                path: http_cache_dir(),
-            },
-            options: HttpCacheOptions::default(),
-        }))
-        .build();
-    let res = client
-        .get(format!("{}{}", base_url.into(), "/licenses/licenses.json"))
-        .send()
-        .await?
-        .json::<Licenses>()
-        .await?;
-    Ok(res)
-}
-
-/// Displays the IDs of licenses.
-///
-/// This function sorts the given slice of licenses by their
-/// deprecation status and then prints the ID of each license.
-///
-/// # Arguments
-///
-/// * `licenses` - A mutable slice of references to `License` objects.
+    let base_url = base_url.into();
+    http::get_json_with(&licenses_url(&base_url), |body| {
+        serde_json::from_slice::<ListedLicenses>(body).map(|list| list.into_licenses(&base_url))
+    })
+    .await
+}
+
+/// The SPDX license list as served, decoding only the fields lictool
+/// uses. Strings are borrowed from the response unless they hold
+/// escapes, and `detailsUrl` is skipped since it follows from the ID.
+#[derive(Debug, Deserialize)]
+struct ListedLicenses<'a> {
+    /// The licenses of the list.
+    #[serde(borrow)]
+    licenses: Vec<ListedLicense<'a>>,
+    /// The release of the list.
+    #[serde(rename = "licenseListVersion", default, borrow)]
+    version: Cow<'a, str>,
+}
+
+/// A license of `ListedLicenses`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListedLicense<'a> {
+    is_deprecated_license_id: bool,
+    #[serde(borrow)]
+    name: Cow<'a, str>,
+    #[serde(rename = "licenseId", borrow)]
+    id: Cow<'a, str>,
+    is_osi_approved: bool,
+    is_fsf_libre: Option<bool>,
+}
+
+impl ListedLicenses<'_> {
+    /// Returns the licenses, with their details served from
+    /// `base_url`.
+    fn into_licenses(
+        self,
+        base_url: &str,
+    ) -> Licenses {
+        let body = self
+            .licenses
+            .into_iter()
+            .map(|license| {
+                License {
+                    is_deprecated_license_id: license.is_deprecated_license_id,
+                    details_url: format!("{}/licenses/{}.json", base_url, license.id),
+                    name: license.name.into_owned(),
+                    id: license.id.into_owned(),
+                    is_osi_approved: Some(license.is_osi_approved),
+                    is_fsf_libre: license.is_fsf_libre,
+                }
+            })
+            .collect();
+        Licenses {
+            body,
+            version: self.version.into_owned(),
+        }
+    }
+}
+
+/// Returns the URL of the license list served from `base_url`.
+fn licenses_url(base_url: &str) -> String {
+    format!("{}{}", base_url, "/licenses/licenses.json")
+}
+
+/// Reduces a license ID to its lowercase letters and digits, so that
+/// `Apache 2.0` and `apache-2.0` compare equal.
+fn normalize_id(id: &str) -> String {
+    id.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Fetches the release of the SPDX license list from the configured
+/// source, bypassing the cache so that it can be compared with the
+/// cached list, or reads it from the imported mirror.
 ///
 /// # Returns
 ///
-/// A `LictoolResult` indicating the success or failure of the
-/// operation.
+/// `None` for GitHub, whose list has no releases.
 ///
-/// # Example
-///
-/// ```
-/// let mut licenses = vec![&license1, &license2];
-/// display_license_ids(&mut licenses)?;
-/// ```
-pub(crate) fn display_license_ids(licenses: &mut [&License]) -> LictoolResult<()> {
-    licenses.sort_by_key(|license| license.is_deprecated_license_id);
-    licenses
-        .iter()
-        .for_each(|license| println!("{}", license.color_id()));
-    Ok(())
+/// # Errors
+/// - If the request fails or the list cannot be decoded.
+pub(crate) async fn fetch_upstream_list_version() -> LictoolResult<Option<String>> {
+    if *source() == Source::Github {
+        return Ok(None);
+    }
+    let url = spdx_licenses_url();
+    // An imported dataset stands in for spdx.org here as everywhere.
+    let body = match read_mirrored(&url)? {
+        Some(body) => body,
+        None => http::download(&url).await?,
+    };
+    Ok(Some(serde_json::from_slice::<Licenses>(&body)?.version))
+}
+
+/// Returns the URL of the SPDX license list of the configured source,
+/// on spdx.org or a self-hosted mirror of it.
+pub(crate) fn spdx_licenses_url() -> String {
+    licenses_url(source().spdx_base_url())
+}
+
+/// Returns the URL of the SPDX exception list of the configured
+/// source.
+pub(crate) fn spdx_exceptions_url() -> String {
+    exceptions_url(source().spdx_base_url())
+}
+
+/// Returns the URL of the license list of the configured source, in
+/// its own format.
+pub(crate) fn source_licenses_url() -> String {
+    match source() {
+        Source::Github => github_licenses_url(GITHUB_API_URL),
+        Source::Spdx | Source::Mirror(_) => spdx_licenses_url(),
+    }
 }
 
 /// A struct representing the details of a software license.
 ///
 /// This struct is used to hold various information about a license,
 /// including its text, ID, name, and other related metadata.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct LicenseDetails {
     /// A boolean indicating if the license ID is deprecated.
@@ -164,112 +657,54 @@ pub struct LicenseDetails {
     pub license_id: String,
     /// A list of URLs for additional information about the license.
     pub see_also: Vec<String>,
-    /// A boolean indicating if the license is approved by the OSI.
-    pub is_osi_approved: bool,
+    /// An optional boolean indicating if the license is approved by
+    /// the OSI; `None` when the source does not say, as for GitHub.
+    pub is_osi_approved: Option<bool>,
     /// An optional boolean indicating if the license is approved by
     /// the FSF.
     pub is_fsf_libre: Option<bool>,
     /// An optional string indicating the version in which the license
     /// was deprecated.
     pub deprecated_version: Option<String>,
-}
-
-impl Display for LicenseDetails {
-    fn fmt(
-        &self,
-        f: &mut std::fmt::Formatter<'_>,
-    ) -> std::fmt::Result {
-        let mut output = String::new();
-        let term_width = termion::terminal_size().unwrap().0 as usize;
-        let padding = (term_width - self.name.len()) / 2;
-        write!(f, "{:width$}", "", width = padding)?;
-        writeln!(f, "{}", cformat!("«<s>{}</>»", self.name))?;
-        output.push_str(&cformat!(
-            "<s>Reference:</> <u>https://spdx.org/licenses/{}.html</>\n",
-            self.license_id
-        ));
-        output.push_str(&cformat!("<s>License ID:</> {}\n", self.license_id));
-        if let Some(ref comments) = self.license_comments {
-            output.push_str(&cformat!("<s>License Comments:</> {}\n", comments));
-        }
-        output.push_str(&cformat!("<s>See Also:</>\n"));
-        for link in &self.see_also {
-            output.push_str(&cformat!("  - <u>{}</>\n", link));
-        }
-        output.push_str(&cformat!(
-            "<s>Is Supported License ID:</> {}\n",
-            (!self.is_deprecated_license_id).as_checkbox()
-        ));
-        output.push_str(&cformat!(
-            "<s>Is OSI Approved:</> {}",
-            self.is_osi_approved.as_checkbox()
-        ));
-        if let Some(is_fsf_libre) = self.is_fsf_libre {
-            output.push_str(&cformat!(
-                "\n<s>Is FSF Free/Libre:</> {}",
-                is_fsf_libre.as_checkbox()
-            ));
-        }
-        if let Some(ref deprecated_version) = self.deprecated_version {
-            output.push_str(&cformat!(
-                "\n<s>Deprecated Version:</> {}",
-                deprecated_version
-            ));
-        }
-        write!(f, "{}", output)
-    }
+    /// The entry of the license on the FSF license list, if fetched
+    /// and listed there.
+    #[serde(skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub fsf: Option<FsfLicense>,
 }
 
 impl LicenseDetails {
     /// Checks if the license text contains any year-related keywords.
     pub fn has_year(&self) -> bool {
-        YEAR.iter().any(|&word| self.license_text.contains(word))
+        YEAR.iter().any(|&word| self.license_text.contains(word)) || self.has_key(Field::Year)
     }
 
     /// Checks if the license text contains any owner-related
     /// keywords.
     pub fn has_owner(&self) -> bool {
-        OWNER.iter().any(|&word| self.license_text.contains(word))
+        OWNER.iter().any(|&word| self.license_text.contains(word)) || self.has_key(Field::Owner)
     }
 
     /// Checks if the license text contains any repository-related
     /// keywords.
     pub fn has_repo(&self) -> bool {
-        REPO.iter().any(|&word| self.license_text.contains(word))
+        REPO.iter().any(|&word| self.license_text.contains(word)) || self.has_key(Field::Repo)
     }
 
     /// Checks if the license text contains any email-related
     /// keywords.
     pub fn has_email(&self) -> bool {
-        EMAIL.iter().any(|&word| self.license_text.contains(word))
+        EMAIL.iter().any(|&word| self.license_text.contains(word)) || self.has_key(Field::Email)
     }
-}
-
-/// An interface for representing a boolean value as a checkbox.
-///
-/// This trait provides a method to convert a boolean value into
-/// a string representation of a checkbox, typically for display
-/// purposes.
-///
-/// # Example
-///
-/// ```
-/// let checked = true;
-/// println!("{}", checked.as_checkbox()); // Output: <green, bold>󰄲</>
-///
-/// let unchecked = false;
-/// println!("{}", unchecked.as_checkbox()); // Output: <red, bold>󰅗</>
-/// ```
-trait Checkbox {
-    fn as_checkbox(&self) -> &str;
-}
 
-impl Checkbox for bool {
-    fn as_checkbox(&self) -> &str {
-        match self {
-            true => cstr!("<green, bold>󰄲</>"),
-            false => cstr!("<red, bold>󰅗</>"),
-        }
+    /// Checks if the text, that of a custom template, has a `{{key}}`
+    /// placeholder of the field.
+    fn has_key(
+        &self,
+        field: Field,
+    ) -> bool {
+        template_keys(&self.license_text)
+            .iter()
+            .any(|key| Field::from_key(key) == Some(field))
     }
 }
 
@@ -289,35 +724,20 @@ impl Checkbox for bool {
 ///
 /// # Example
 ///
-/// ```
+/// ```ignore
 /// let url = "https://example.com/license";
 /// let details = fetch_license_details(url).await?;
 /// println!("{:?}", details);
 /// ```
 pub(crate) async fn fetch_license_details(details_url: &str) -> LictoolResult<LicenseDetails> {
-    let client = ClientBuilder::new(Client::new())
-        .with(Cache(HttpCache {
-            mode: CacheMode::Default,
-            manager: CACacheManager {
-                path: http_cache_dir(),
-            },
-            options: HttpCacheOptions::default(),
-        }))
-        .build();
-    let res = client
-        .get(details_url)
-        .send()
-        .await?
-        .json::<LicenseDetails>()
-        .await?;
-    Ok(res)
+    http::get_json(details_url).await
 }
 
 /// A struct representing a software license.
 ///
 /// This struct holds essential information about a license, such as
 /// its ID, approval status, and URL for more details.
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct License {
     // pub reference: String,
@@ -327,13 +747,15 @@ pub struct License {
     /// license.
     pub details_url: String,
     // pub reference_number: i64,
-    // pub name: String,
+    /// The human-readable name of the license.
+    pub name: String,
     /// The unique identifier of the license.
     #[serde(rename = "licenseId")]
     pub id: String,
     // pub see_also: Vec<String>,
-    /// A boolean indicating if the license is approved by the OSI.
-    pub is_osi_approved: bool,
+    /// An optional boolean indicating if the license is approved by
+    /// the OSI; `None` when the source does not say, as for GitHub.
+    pub is_osi_approved: Option<bool>,
     /// An optional boolean indicating if the license is approved by
     /// the FSF.
     pub is_fsf_libre: Option<bool>,
@@ -344,7 +766,7 @@ impl License {
     ///
     /// This asynchronous function retrieves the license details from
     /// the URL specified in the `details_url` field of the
-    /// `License` struct.
+    /// `License` struct, in the format of the configured source.
     ///
     /// # Returns
     ///
@@ -353,38 +775,37 @@ impl License {
     ///
     /// # Example
     ///
-    /// ```
+    /// ```ignore
     /// let license = License { /* initialize fields */ };
     /// let details = license.details().await?;
     /// println!("{:?}", details);
     /// ```
     pub async fn details(&self) -> LictoolResult<LicenseDetails> {
-        fetch_license_details(&self.details_url).await
+        source().backend().details(self).await
     }
 
-    /// Returns the license ID as a colored string.
+    /// Returns a label describing the license for interactive
+    /// pickers.
     ///
-    /// This function formats the license ID as a colored string based
-    /// on its deprecation status. Deprecated IDs are displayed in
-    /// red, while non-deprecated IDs are displayed in green.
-    ///
-    /// # Returns
-    ///
-    /// A `String` containing the colored license ID.
+    /// The label combines the license ID with its human-readable name
+    /// and short markers for OSI approval and deprecation, so fuzzy
+    /// matching covers names as well as IDs.
     ///
     /// # Example
     ///
-    /// ```
+    /// ```ignore
     /// let license = License { /* initialize fields */ };
-    /// let colored_id = license.color_id();
-    /// println!("{}", colored_id);
+    /// assert_eq!(license.picker_label(), "MIT — MIT License [OSI]");
     /// ```
-    pub fn color_id(&self) -> String {
+    pub fn picker_label(&self) -> String {
+        let mut label = format!("{} — {}", self.id, self.name);
+        if self.is_osi_approved == Some(true) {
+            label.push_str(" [OSI]");
+        }
         if self.is_deprecated_license_id {
-            cformat!("<bold, red>{}</>", self.id)
-        } else {
-            cformat!("<bold, green>{}</>", self.id)
+            label.push_str(" [deprecated]");
         }
+        label
     }
 }
 
@@ -397,13 +818,266 @@ impl Display for License {
     }
 }
 
+/// The license exceptions of the SPDX list, added to a license with
+/// `WITH`.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Exceptions {
+    /// The exceptions of the list.
+    #[serde(rename = "exceptions")]
+    pub body: Vec<Exception>,
+}
+
+impl Exceptions {
+    /// Fetches the SPDX exception list, from the cache if it is
+    /// fresh.
+    ///
+    /// # Errors
+    /// - If the list cannot be fetched or decoded.
+    pub async fn new() -> LictoolResult<Self> {
+        let base_url = source().spdx_base_url();
+        let mut exceptions = fetch_exceptions(base_url).await?;
+        if base_url != SPDX_BASE_URL {
+            for exception in &mut exceptions.body {
+                exception.details_url = format!("{}/licenses/{}.json", base_url, exception.id);
+            }
+        }
+        Ok(exceptions)
+    }
+
+    /// Finds the exception with the given ID.
+    pub fn find(
+        &self,
+        exception_id: &str,
+    ) -> Option<&Exception> {
+        self.body
+            .iter()
+            .find(|exception| exception.id == exception_id)
+    }
+
+    /// Filters the exceptions by their deprecation status, like
+    /// `Licenses::filter_by`.
+    pub fn filter_by(
+        &self,
+        deprecated: Option<bool>,
+    ) -> Vec<&Exception> {
+        self.body
+            .iter()
+            .filter(|exception| deprecated.is_none_or(|d| d == exception.is_deprecated_license_id))
+            .collect()
+    }
+}
+
+/// Fetches the exception list from a given base URL.
+async fn fetch_exceptions(base_url: &str) -> LictoolResult<Exceptions> {
+    http::get_json(&exceptions_url(base_url)).await
+}
+
+/// Returns the URL of the exception list served from `base_url`.
+fn exceptions_url(base_url: &str) -> String {
+    format!("{}{}", base_url, "/licenses/exceptions.json")
+}
+
+/// A license exception of the SPDX list.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Exception {
+    /// Whether the exception ID is deprecated.
+    pub is_deprecated_license_id: bool,
+    /// The URL of the exception details.
+    pub details_url: String,
+    /// The human-readable name of the exception.
+    pub name: String,
+    /// The unique identifier of the exception.
+    #[serde(rename = "licenseExceptionId")]
+    pub id: String,
+}
+
+impl Exception {
+    /// Fetches the details of the exception, including its text.
+    pub async fn details(&self) -> LictoolResult<ExceptionDetails> {
+        http::get_json(&self.details_url).await
+    }
+}
+
+/// The details of a license exception.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExceptionDetails {
+    /// Whether the exception ID is deprecated.
+    pub is_deprecated_license_id: bool,
+    /// The full text of the exception.
+    pub license_exception_text: String,
+    /// The name of the exception.
+    pub name: String,
+    /// Optional comments about the exception, which usually name the
+    /// licenses it is used with.
+    pub license_comments: Option<String>,
+    /// The unique identifier of the exception.
+    pub license_exception_id: String,
+    /// A list of URLs for additional information about the
+    /// exception.
+    pub see_also: Vec<String>,
+    /// The version of the SPDX list that deprecated the exception.
+    pub deprecated_version: Option<String>,
+    /// The licenses the exception is commonly combined with, as
+    /// mentioned in its comments.
+    #[serde(skip_deserializing)]
+    pub combined_with: Vec<String>,
+}
+
+impl ExceptionDetails {
+    /// Fills `combined_with` with the IDs of the licenses mentioned
+    /// in the comments of the exception.
+    pub fn resolve_combined_with(
+        &mut self,
+        licenses: &Licenses,
+    ) {
+        let Some(comments) = &self.license_comments else {
+            return;
+        };
+        let words = comments
+            .split(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '+')))
+            .map(|word| word.trim_end_matches('.'));
+        for word in words {
+            if let Some(license) = licenses.find(word) {
+                if !self.combined_with.contains(&license.id) {
+                    self.combined_with.push(license.id.clone());
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
 
-    use super::{fetch_licenses, License, Licenses};
+    use super::{
+        fetch_exceptions, fetch_licenses, Exception, FilterOptions, GithubSource, License,
+        LicenseSource, Licenses,
+    };
     use crate::spdx::{fetch_license_details, LicenseDetails};
 
+    #[test]
+    fn test_filter_by() {
+        let license = |id: &str, deprecated: bool, osi: bool, fsf: Option<bool>| {
+            License {
+                is_deprecated_license_id: deprecated,
+                details_url: String::new(),
+                name: id.to_string(),
+                id: id.to_string(),
+                is_osi_approved: Some(osi),
+                is_fsf_libre: fsf,
+            }
+        };
+        let licenses = Licenses {
+            body: vec![
+                license("MIT", false, true, Some(true)),
+                license("GPL-2.0", true, true, Some(true)),
+                license("JSON", false, false, None),
+                license("NASA-1.3", false, true, Some(false)),
+            ],
+            version: "3.24.0".to_string(),
+        };
+        let ids = |options: FilterOptions| {
+            licenses
+                .filter_by(&options)
+                .iter()
+                .map(|license| license.id.as_str())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(FilterOptions::default()).len(), 4);
+        assert_eq!(
+            ids(FilterOptions {
+                deprecated: Some(false),
+                osi_approved: Some(true),
+                ..Default::default()
+            }),
+            ["MIT", "NASA-1.3"]
+        );
+        assert_eq!(
+            ids(FilterOptions {
+                fsf_libre: Some(false),
+                ..Default::default()
+            }),
+            ["JSON", "NASA-1.3"]
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let license = |id: &str| {
+            License {
+                is_deprecated_license_id: false,
+                details_url: String::new(),
+                name: id.to_string(),
+                id: id.to_string(),
+                is_osi_approved: Some(true),
+                is_fsf_libre: None,
+            }
+        };
+        let licenses = Licenses {
+            body: [
+                "MIT",
+                "Apache-1.1",
+                "Apache-2.0",
+                "GPL-3.0-only",
+                "BSD-3-Clause",
+            ]
+            .map(license)
+            .to_vec(),
+            version: "3.24.0".to_string(),
+        };
+        let resolve = |query: &str| licenses.resolve(query).map(|license| license.id.as_str());
+        assert_eq!(resolve("mit").unwrap(), "MIT");
+        assert_eq!(resolve("Apache 2.0").unwrap(), "Apache-2.0");
+        assert_eq!(resolve("gpl3").unwrap(), "GPL-3.0-only");
+        assert_eq!(resolve("bsd").unwrap(), "BSD-3-Clause");
+        assert_eq!(
+            resolve("apach").unwrap_err().to_string(),
+            "No license found matching apach. Did you mean: Apache-1.1, Apache-2.0?"
+        );
+        assert!(resolve("zzz").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_github_source() {
+        let mut server = mockito::Server::new_async().await;
+        let list = server
+            .mock("GET", "/licenses?per_page=100")
+            .with_body(format!(
+                r#"[
+                    {{"key": "mit", "name": "MIT License", "spdx_id": "MIT", "url": "{0}/licenses/mit"}},
+                    {{"key": "other", "name": "Other", "spdx_id": "NOASSERTION", "url": null}}
+                ]"#,
+                server.url()
+            ))
+            .create_async()
+            .await;
+        let details_mock = server
+            .mock("GET", "/licenses/mit")
+            .with_body(
+                r#"{"key": "mit", "name": "MIT License", "spdx_id": "MIT",
+                    "html_url": "https://choosealicense.com/licenses/mit/",
+                    "description": "A short and simple permissive license.",
+                    "body": "Copyright (c) [year] [fullname]"}"#,
+            )
+            .create_async()
+            .await;
+        let source = GithubSource {
+            api_url: server.url(),
+        };
+        let licenses = source.licenses().await.unwrap();
+        assert_eq!(licenses.body.len(), 1);
+        let license = &licenses.body[0];
+        assert_eq!(license.id, "MIT");
+        let details = source.details(license).await.unwrap();
+        assert_eq!(details.license_id, "MIT");
+        assert!(details.has_year() && details.has_owner());
+        list.assert_async().await;
+        details_mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn test_fetch_licenses() {
         let mut server = mockito::Server::new_async().await;
@@ -420,21 +1094,24 @@ mod tests {
         let list = vec![
             License {
                 is_deprecated_license_id: false,
-                details_url: "https://spdx.org/licenses/BSD-4.3TAHOE.json".to_string(),
+                details_url: format!("{}/licenses/BSD-4.3TAHOE.json", server.url()),
+                name: "BSD 4.3 TAHOE License".to_string(),
                 id: "BSD-4.3TAHOE".to_string(),
-                is_osi_approved: false,
+                is_osi_approved: Some(false),
                 is_fsf_libre: None,
             },
             License {
                 is_deprecated_license_id: false,
-                details_url: "https://spdx.org/licenses/AML-glslang.json".to_string(),
+                details_url: format!("{}/licenses/AML-glslang.json", server.url()),
+                name: "AML glslang variant License".to_string(),
                 id: "AML-glslang".to_string(),
-                is_osi_approved: false,
+                is_osi_approved: Some(false),
                 is_fsf_libre: None,
             },
         ];
         let expected = Licenses {
             body: list,
+            version: "3.24.0".to_string(),
         };
         assert_eq!(licenses, expected);
         mock.assert_async().await;
@@ -502,11 +1179,78 @@ mod tests {
                 "https://docs.omniverse.nvidia.com/install-guide/latest/common/licenses.html"
                     .to_string(),
             ],
-            is_osi_approved: false,
+            is_osi_approved: Some(false),
             is_fsf_libre: None,
             deprecated_version: None,
+            fsf: None,
         };
         assert_eq!(details, expected);
         mock.assert_async().await;
     }
+
+    #[tokio::test]
+    async fn test_details_for() {
+        let mut server = mockito::Server::new_async().await;
+        let mut licenses = Licenses {
+            body: Vec::new(),
+            version: "3.24.0".to_string(),
+        };
+        for id in ["MIT", "ISC", "Zlib"] {
+            server
+                .mock("GET", format!("/{}.json", id).as_str())
+                .with_body(format!(
+                    r#"{{"isDeprecatedLicenseId": false, "licenseText": "{id} text", "name": "{id}",
+                        "licenseId": "{id}", "seeAlso": [], "isOsiApproved": true}}"#
+                ))
+                .create_async()
+                .await;
+            licenses.body.push(License {
+                is_deprecated_license_id: false,
+                details_url: format!("{}/{}.json", server.url(), id),
+                name: id.to_string(),
+                id: id.to_string(),
+                is_osi_approved: Some(true),
+                is_fsf_libre: None,
+            });
+        }
+        let details = licenses
+            .details_for(&["Zlib", "MIT", "ISC"], 2, || {})
+            .await
+            .unwrap();
+        let ids: Vec<&str> = details
+            .iter()
+            .map(|details| details.license_id.as_str())
+            .collect();
+        assert_eq!(ids, ["Zlib", "MIT", "ISC"]);
+        assert!(licenses
+            .details_for(&["MIT", "Nope"], 2, || {})
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_exceptions() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/licenses/exceptions.json")
+            .with_body(include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/files/exceptions.json"
+            )))
+            .create_async()
+            .await;
+
+        let exceptions = fetch_exceptions(&server.url()).await.unwrap();
+        assert_eq!(
+            exceptions.find("Classpath-exception-2.0"),
+            Some(&Exception {
+                is_deprecated_license_id: false,
+                details_url: "https://spdx.org/licenses/Classpath-exception-2.0.json".to_string(),
+                name: "Classpath exception 2.0".to_string(),
+                id: "Classpath-exception-2.0".to_string(),
+            })
+        );
+        assert_eq!(exceptions.body.len(), 2);
+        mock.assert_async().await;
+    }
 }
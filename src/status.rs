@@ -0,0 +1,413 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use color_print::cprintln;
+use serde::Serialize;
+
+use crate::{
+    check::find_drift,
+    config::Config,
+    expression::Expression,
+    header::{declared_expression, has_spdx_header, HeaderConfig},
+    i18n::t,
+    policy::PolicyConfig,
+    project::{license_files, main_license_file, manifest_licenses},
+    spdx::Licenses,
+    util::{
+        errors::LictoolResult, matching::MatchNormalization, progress::with_spinner,
+        walk::project_files,
+    },
+};
+
+/// A snapshot of the licensing of a project.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Status {
+    pub(crate) license_file: Option<PathBuf>,
+    /// The declared license whose canonical text the license file
+    /// matches.
+    pub(crate) detected_license: Option<String>,
+    pub(crate) declared: Vec<DeclaredLicense>,
+    /// Whether the declared licenses and the detected one are all the
+    /// same, or `None` if no license is declared.
+    pub(crate) manifests_agree: Option<bool>,
+    pub(crate) header_coverage: HeaderCoverage,
+    /// The declared licenses the configured policy rejects.
+    pub(crate) policy_violations: Vec<String>,
+}
+
+/// A license declared by a manifest or the configuration.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct DeclaredLicense {
+    /// The manifest declaring the license, or `default-license`.
+    pub(crate) source: String,
+    pub(crate) license: String,
+}
+
+/// How many of the files that can hold a header have an SPDX one.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub(crate) struct HeaderCoverage {
+    pub(crate) covered: usize,
+    pub(crate) total: usize,
+    /// The covered share, rounded down, or `None` without files.
+    pub(crate) percent: Option<usize>,
+}
+
+impl Status {
+    /// Collects the status of the project in `root`.
+    ///
+    /// # Errors
+    /// - If the license file cannot be read, or the license texts
+    ///   cannot be fetched.
+    pub async fn collect(
+        root: &Path,
+        config: &Config,
+    ) -> LictoolResult<Self> {
+        let license_file = main_license_file(root, config.default_filename());
+        let declared = declared_licenses(root, config);
+        let detected_license = match license_file.as_ref() {
+            // Only the declared licenses are compared, so nothing is
+            // fetched without them.
+            Some(file) if !declared.is_empty() => {
+                let text = fs::read_to_string(file)
+                    .with_context(|| t!("error-read-file", path = file.display().to_string()))?;
+                let licenses = with_spinner(t!("fetching-licenses"), Licenses::new()).await?;
+                detect_license(&text, &declared, &licenses, config).await?
+            }
+            _ => None,
+        };
+        Ok(Status {
+            manifests_agree: licenses_agree(&declared, detected_license.as_deref()),
+            header_coverage: header_coverage(
+                &project_files(root),
+                &license_files(root, config.default_filename()),
+                &config.header,
+            ),
+            policy_violations: policy_violations(&declared, &config.policy),
+            license_file,
+            detected_license,
+            declared,
+        })
+    }
+}
+
+/// Lists the licenses declared by the manifests in `root` and by the
+/// `default-license` setting.
+pub(crate) fn declared_licenses(
+    root: &Path,
+    config: &Config,
+) -> Vec<DeclaredLicense> {
+    let mut declared: Vec<DeclaredLicense> = manifest_licenses(root)
+        .into_iter()
+        .map(|(path, license)| {
+            DeclaredLicense {
+                source: path
+                    .file_name()
+                    .unwrap_or(path.as_os_str())
+                    .to_string_lossy()
+                    .into_owned(),
+                license,
+            }
+        })
+        .collect();
+    if let Some(license) = config.default_license() {
+        declared.push(DeclaredLicense {
+            source: "default-license".to_string(),
+            license: license.to_string(),
+        });
+    }
+    declared
+}
+
+/// Returns the license expression of the project: the one its license
+/// file declares with an `SPDX-License-Identifier`, or else the first
+/// declared license.
+pub(crate) fn project_license(
+    root: &Path,
+    config: &Config,
+) -> Option<String> {
+    main_license_file(root, config.default_filename())
+        .and_then(|file| fs::read_to_string(file).ok())
+        .and_then(|content| declared_expression(&content).map(str::to_string))
+        .or_else(|| {
+            declared_licenses(root, config)
+                .into_iter()
+                .next()
+                .map(|declared| declared.license)
+        })
+}
+
+/// Counts the files with an SPDX header among those the header
+/// configuration has a comment style for, leaving out license files
+/// and unreadable ones.
+pub(crate) fn header_coverage(
+    files: &[PathBuf],
+    license_files: &[PathBuf],
+    header: &HeaderConfig,
+) -> HeaderCoverage {
+    let contents: Vec<String> = files
+        .iter()
+        .filter(|path| !license_files.iter().any(|license| same_file(path, license)))
+        .filter(|path| header.comment_style(path).is_some())
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect();
+    let covered = contents
+        .iter()
+        .filter(|content| has_spdx_header(content))
+        .count();
+    HeaderCoverage {
+        covered,
+        total: contents.len(),
+        percent: (!contents.is_empty()).then(|| covered * 100 / contents.len()),
+    }
+}
+
+/// Finds which declared license the license file contains, comparing
+/// it against their canonical texts as the SPDX matching guidelines
+/// do. Each license of a declared expression, such as `MIT OR
+/// Apache-2.0`, is a candidate.
+///
+/// # Errors
+/// - If the license texts cannot be fetched.
+pub(crate) async fn detect_license(
+    text: &str,
+    declared: &[DeclaredLicense],
+    licenses: &Licenses,
+    config: &Config,
+) -> LictoolResult<Option<String>> {
+    let normalization = MatchNormalization {
+        spdx_matching: true,
+        ..Default::default()
+    };
+    let mut candidates: Vec<String> = Vec::new();
+    for id in declared
+        .iter()
+        .flat_map(|declared| license_ids(&declared.license))
+    {
+        if !candidates.contains(&id) {
+            candidates.push(id);
+        }
+    }
+    for candidate in candidates {
+        let Some(license) = licenses.find(&candidate) else {
+            continue;
+        };
+        let mut details = license.details().await?;
+        config.apply_override(&mut details)?;
+        if find_drift(&details.license_text, text, &normalization).is_empty() {
+            return Ok(Some(license.id.clone()));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the license IDs of an expression, or the expression itself
+/// if it does not parse.
+fn license_ids(expression: &str) -> Vec<String> {
+    match expression.parse::<Expression>() {
+        Ok(parsed) => {
+            parsed
+                .terms()
+                .into_iter()
+                .map(|term| term.id.clone())
+                .collect()
+        }
+        Err(_) => vec![expression.to_string()],
+    }
+}
+
+/// Checks whether the declared licenses are all the same, and the
+/// detected one is among their licenses.
+pub(crate) fn licenses_agree(
+    declared: &[DeclaredLicense],
+    detected: Option<&str>,
+) -> Option<bool> {
+    let first = &declared.first()?.license;
+    Some(
+        declared.iter().all(|declared| declared.license == *first)
+            && detected.is_none_or(|detected| license_ids(first).iter().any(|id| id == detected)),
+    )
+}
+
+/// Returns the declared licenses the policy rejects, each once.
+///
+/// Declarations that are not valid expressions are left to the
+/// `validate` command.
+pub(crate) fn policy_violations(
+    declared: &[DeclaredLicense],
+    policy: &PolicyConfig,
+) -> Vec<String> {
+    let mut violations: Vec<String> = declared
+        .iter()
+        .filter(|declared| {
+            declared
+                .license
+                .parse::<Expression>()
+                .is_ok_and(|expression| !policy.evaluate(&expression).satisfied)
+        })
+        .map(|declared| declared.license.clone())
+        .collect();
+    violations.sort();
+    violations.dedup();
+    violations
+}
+
+/// Prints the status as one labelled line per aspect.
+pub(crate) fn display_status(status: &Status) {
+    let labels = [
+        t!("status-license"),
+        t!("status-manifests"),
+        t!("status-headers"),
+        t!("status-policy"),
+    ];
+    let width = labels
+        .iter()
+        .map(|label| label.chars().count())
+        .max()
+        .unwrap_or_default();
+    let label = |idx: usize| format!("{:<width$}", labels[idx], width = width);
+
+    match (&status.license_file, &status.detected_license) {
+        (None, _) => {
+            cprintln!(
+                "<bold>{}</>  <red>✘</> {}",
+                label(0),
+                t!("status-no-license-file")
+            )
+        }
+        (Some(file), Some(license)) => {
+            cprintln!(
+                "<bold>{}</>  <green>✔</> {} <dim>({})</>",
+                label(0),
+                license,
+                file.display()
+            )
+        }
+        (Some(file), None) => {
+            cprintln!(
+                "<bold>{}</>  <y>?</> {} <dim>({})</>",
+                label(0),
+                t!("status-undetected"),
+                file.display()
+            )
+        }
+    }
+
+    let declared = status
+        .declared
+        .iter()
+        .map(|declared| format!("{}: {}", declared.source, declared.license))
+        .collect::<Vec<_>>()
+        .join(", ");
+    match status.manifests_agree {
+        None => {
+            cprintln!(
+                "<bold>{}</>  <dim>{}</>",
+                label(1),
+                t!("status-no-manifests")
+            )
+        }
+        Some(true) => cprintln!("<bold>{}</>  <green>✔</> {}", label(1), declared),
+        Some(false) => cprintln!("<bold>{}</>  <red>✘</> {}", label(1), declared),
+    }
+
+    let coverage = status.header_coverage;
+    match coverage.percent {
+        None => {
+            cprintln!(
+                "<bold>{}</>  <dim>{}</>",
+                label(2),
+                t!("status-no-source-files")
+            )
+        }
+        Some(percent) => {
+            let text = t!(
+                "status-header-coverage",
+                percent = percent,
+                covered = coverage.covered,
+                total = coverage.total
+            );
+            if percent == 100 {
+                cprintln!("<bold>{}</>  <green>✔</> {}", label(2), text)
+            } else {
+                cprintln!("<bold>{}</>  <y>~</> {}", label(2), text)
+            }
+        }
+    }
+
+    if status.policy_violations.is_empty() {
+        cprintln!(
+            "<bold>{}</>  <green>✔</> {}",
+            label(3),
+            t!("status-no-violations")
+        );
+    } else {
+        cprintln!(
+            "<bold>{}</>  <red>✘</> {}",
+            label(3),
+            status.policy_violations.join(", ")
+        );
+    }
+}
+
+/// Checks whether two paths name the same file, ignoring a leading
+/// `./`.
+pub(crate) fn same_file(
+    a: &Path,
+    b: &Path,
+) -> bool {
+    a.strip_prefix(".").unwrap_or(a) == b.strip_prefix(".").unwrap_or(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{licenses_agree, policy_violations, DeclaredLicense};
+    use crate::policy::PolicyConfig;
+
+    #[test]
+    fn test_declared_licenses() {
+        let declared = |licenses: &[&str]| -> Vec<DeclaredLicense> {
+            licenses
+                .iter()
+                .map(|license| {
+                    DeclaredLicense {
+                        source: "Cargo.toml".to_string(),
+                        license: license.to_string(),
+                    }
+                })
+                .collect()
+        };
+        assert_eq!(licenses_agree(&[], Some("MIT")), None);
+        assert_eq!(
+            licenses_agree(&declared(&["MIT", "MIT"]), Some("MIT")),
+            Some(true)
+        );
+        assert_eq!(
+            licenses_agree(&declared(&["MIT", "ISC"]), None),
+            Some(false)
+        );
+        assert_eq!(
+            licenses_agree(&declared(&["MIT"]), Some("ISC")),
+            Some(false)
+        );
+        assert_eq!(
+            licenses_agree(&declared(&["MIT OR Apache-2.0"]), Some("Apache-2.0")),
+            Some(true)
+        );
+        let policy = PolicyConfig {
+            allow: Vec::new(),
+            deny: vec!["GPL-3.0-only".to_string()],
+        };
+        assert_eq!(
+            policy_violations(
+                &declared(&["GPL-3.0-only", "MIT OR GPL-3.0-only", "GPL-3.0-only"]),
+                &policy
+            ),
+            vec!["GPL-3.0-only"]
+        );
+    }
+}
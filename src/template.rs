@@ -1,20 +1,51 @@
-use std::{fmt::Display, fs, mem::take, path::Path};
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs::{self, Permissions},
+    io::{self, IsTerminal, Write},
+    mem::take,
+    path::{Path, PathBuf},
+};
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use chrono::{Datelike, Local};
-use color_print::cprintln;
-use dialoguer::{
-    theme::{ColorfulTheme, Theme},
-    Input,
-};
+use clap::ValueEnum;
+use color_print::{cformat, cprintln};
+use dialoguer::{theme::Theme, Confirm, Input, Select};
 
 use super::util::errors::Error;
 use crate::{
-    consts::{EMAIL, OWNER, REPO, YEAR},
+    consts::{DEFAULT_LICENSE_FILE, EMAIL, OWNER, REPO, YEAR},
+    expression::Expression,
+    i18n::t,
+    placeholders::{render_keys, template_keys, unfilled_placeholders},
+    script::PlaceholderScript,
     spdx::LicenseDetails,
-    util::{errors::LictoolResult, git::GitConfig},
+    util::{
+        defaults::{years_since_first_commit, FieldDefaults},
+        dry_run::{is_dry_run, print_planned_write},
+        encoding::Encoding,
+        errors::LictoolResult,
+        hooks::run_post_write_hooks,
+        journal,
+        line_ending::LineEnding,
+        normalize::Normalization,
+        output::{note, success},
+        pager::page,
+        progress::is_quiet,
+        theme::ThemeConfig,
+        validate::{validate_email, validate_year},
+    },
 };
 
+/// The path writing a license to stdout instead of a file.
+pub(crate) const STDOUT_PATH: &str = "-";
+
+/// Checks whether `path` stands for stdout rather than a file.
+pub(crate) fn is_stdout(path: &Path) -> bool {
+    path == Path::new(STDOUT_PATH)
+}
+
 /// A struct representing a template for a license.
 ///
 /// This struct holds fields for various components of a license
@@ -24,23 +55,43 @@ use crate::{
 pub struct Template {
     /// A string containing the text of the license.
     pub license_text: String,
-    /// An optional integer representing the year of the license.
-    pub year: Option<i32>,
+    /// An optional year or year range (e.g. `2019-2024`) of the
+    /// license.
+    pub year: Option<String>,
     /// An optional string containing the owner's name.
     pub owner: Option<String>,
     /// An optional string containing the repository name.
     pub repo: Option<String>,
     /// An optional string containing the owner's email address.
     pub email: Option<String>,
+    /// The values of user-defined `{{key}}` placeholders, such as
+    /// `department`, by key.
+    pub variables: BTreeMap<String, String>,
+    /// The script computing placeholder values, if one is configured.
+    pub(crate) script: Option<PlaceholderScript>,
 }
 
 impl Template {
+    /// Creates a template of a license text, with no placeholder
+    /// values yet.
+    pub fn new(license_text: impl Into<String>) -> Self {
+        Template {
+            license_text: license_text.into(),
+            ..Default::default()
+        }
+    }
+
     /// Renders the license template as a string.
     ///
     /// This function processes the fields of the `Template` struct,
     /// replacing placeholders with the actual values of `year`,
     /// `owner`, `repo`, and `email`, and returns the resulting
-    /// string.
+    /// string. A configured `script` computes the values first.
+    ///
+    /// Besides the placeholders of SPDX texts, `{{key}}` placeholders
+    /// of custom templates are filled: `{{year}}`, `{{owner}}`,
+    /// `{{project}}` and `{{email}}` from the fields, and any other
+    /// key from `variables`.
     ///
     /// # Returns
     ///
@@ -49,37 +100,238 @@ impl Template {
     /// # Example
     ///
     /// ```
-    /// let mut template = Template {
-    ///     license_text: "This software is licensed under the terms of the LICENSE file.".to_string(),
-    ///     year: Some(2024),
-    ///     owner: Some("Alice".to_string()),
-    ///     repo: Some("example_repo".to_string()),
-    ///     email: Some("alice@example.com".to_string()),
-    /// };
-    /// let rendered = template.render();
-    /// println!("{}", rendered);
+    /// use lictool::template::Template;
+    ///
+    /// let mut template = Template::new("Copyright (c) <year> <copyright holders>");
+    /// template.year = Some("2024".to_string());
+    /// template.owner = Some("Alice".to_string());
+    /// assert_eq!(template.render(), "Copyright (c) 2024 Alice");
+    ///
+    /// template.license_text = "{{owner}}, {{ team }}".to_string();
+    /// template
+    ///     .variables
+    ///     .insert("team".to_string(), "Ops".to_string());
+    /// assert_eq!(template.render(), "Alice, Ops");
     /// ```
-    fn render(&mut self) -> String {
-        let mut res = take(&mut self.license_text);
-        if let Some(year) = self.year {
-            YEAR.iter()
-                .for_each(|&word| res = res.replace(word, &year.to_string()));
+    pub fn render(&self) -> String {
+        self.render_with(self.script.as_ref())
+    }
+
+    /// Renders the license template like `render`, with `script`
+    /// computing the values first.
+    pub(crate) fn render_with(
+        &self,
+        script: Option<&PlaceholderScript>,
+    ) -> String {
+        let mut values = FieldDefaults {
+            owner: self.owner.clone(),
+            email: self.email.clone(),
+            year: self.year.clone(),
+            repo: self.repo.clone(),
+        };
+        if let Some(script) = script {
+            values = script.apply_or_warn(&values);
         }
-        if let Some(owner) = &self.owner {
+        let mut res = self.license_text.clone();
+        if let Some(year) = &values.year {
+            YEAR.iter().for_each(|&word| res = res.replace(word, year));
+        }
+        if let Some(owner) = &values.owner {
             OWNER
                 .iter()
                 .for_each(|&word| res = res.replace(word, owner));
         }
-        if let Some(repo) = &self.repo {
+        if let Some(repo) = &values.repo {
             REPO.iter().for_each(|&word| res = res.replace(word, repo));
         }
 
-        if let Some(email) = &self.email {
+        if let Some(email) = &values.email {
             EMAIL
                 .iter()
                 .for_each(|&word| res = res.replace(word, email));
         }
-        res
+        render_keys(&res, |key| {
+            match Field::from_key(key) {
+                Some(field) => values.field(field).cloned(),
+                None => self.variables.get(key).cloned(),
+            }
+        })
+    }
+}
+
+/// A placeholder field of a license template that can be filled in
+/// by the user.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Field {
+    /// The name of the copyright owner.
+    Owner,
+    /// The year of creation.
+    Year,
+    /// The name of the program.
+    Repo,
+    /// The email address of the owner.
+    Email,
+}
+
+impl Field {
+    /// All fields, in the order they are prompted for.
+    pub(crate) const ALL: [Field; 4] = [Field::Owner, Field::Year, Field::Repo, Field::Email];
+
+    /// Returns the field a `{{key}}` placeholder stands for, if any.
+    pub(crate) fn from_key(key: &str) -> Option<Field> {
+        Field::ALL
+            .into_iter()
+            .find(|field| field.keys().contains(&key))
+    }
+
+    /// Returns the keys of the `{{key}}` placeholders of the field in
+    /// custom templates.
+    pub(crate) fn keys(&self) -> &'static [&'static str] {
+        match self {
+            Field::Owner => &["owner"],
+            Field::Year => &["year"],
+            Field::Repo => &["project", "repo"],
+            Field::Email => &["email"],
+        }
+    }
+
+    /// Returns the fields whose placeholders appear in the license
+    /// text.
+    pub(crate) fn present_in(details: &LicenseDetails) -> Vec<Field> {
+        Field::ALL
+            .into_iter()
+            .filter(|field| {
+                match field {
+                    Field::Owner => details.has_owner(),
+                    Field::Year => details.has_year(),
+                    Field::Repo => details.has_repo(),
+                    Field::Email => details.has_email(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the placeholders of the field recognized in license
+    /// texts.
+    pub(crate) fn placeholders(&self) -> &'static [&'static str] {
+        match self {
+            Field::Owner => &OWNER,
+            Field::Year => &YEAR,
+            Field::Repo => &REPO,
+            Field::Email => &EMAIL,
+        }
+    }
+
+    /// Returns the `add` flag filling the field.
+    pub(crate) fn flag(&self) -> &'static str {
+        match self {
+            Field::Owner => "--owner",
+            Field::Year => "--year",
+            Field::Repo => "--repo",
+            Field::Email => "--email",
+        }
+    }
+
+    /// Returns a short human-readable description of the field.
+    pub(crate) fn label(&self) -> String {
+        match self {
+            Field::Owner => t!("field-owner"),
+            Field::Year => t!("field-year"),
+            Field::Repo => t!("field-repo"),
+            Field::Email => t!("field-email"),
+        }
+    }
+}
+
+impl Template {
+    /// Returns the value of a field for updating.
+    fn field_mut(
+        &mut self,
+        field: Field,
+    ) -> &mut Option<String> {
+        match field {
+            Field::Owner => &mut self.owner,
+            Field::Year => &mut self.year,
+            Field::Repo => &mut self.repo,
+            Field::Email => &mut self.email,
+        }
+    }
+
+    /// Prompts the user for the value of a single field, using the
+    /// current value (or the field defaults) as the default.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the prompt cannot be
+    /// displayed or read.
+    fn prompt_field(
+        &mut self,
+        field: Field,
+        theme: &dyn Theme,
+        defaults: &FieldDefaults,
+    ) -> LictoolResult<()> {
+        let prompt = t!("prompt-field", field = field.label());
+        match field {
+            Field::Owner => {
+                let mut input = Input::<String>::with_theme(theme)
+                    .with_prompt(prompt)
+                    .show_default(true);
+                if let Some(owner) = self.owner.as_ref().or(defaults.owner.as_ref()) {
+                    input = input.default(owner.clone());
+                }
+                self.owner = Some(input.interact_text()?);
+            }
+            Field::Year => {
+                let year: String = Input::with_theme(theme)
+                    .with_prompt(prompt)
+                    .show_default(true)
+                    .default(
+                        self.year
+                            .clone()
+                            .or(defaults.year.clone())
+                            .or_else(|| years_since_first_commit(Path::new(".")))
+                            .unwrap_or(Local::now().year().to_string()),
+                    )
+                    .allow_empty(true)
+                    .validate_with(|input: &String| {
+                        if input.is_empty() {
+                            Ok(())
+                        } else {
+                            validate_year(input)
+                        }
+                    })
+                    .interact_text()?;
+                self.year = if year.is_empty() { None } else { Some(year) };
+            }
+            Field::Repo => {
+                let mut input = Input::<String>::with_theme(theme)
+                    .with_prompt(prompt)
+                    .allow_empty(true);
+                if let Some(repo) = self.repo.as_ref().or(defaults.repo.as_ref()) {
+                    input = input.default(repo.clone());
+                }
+                let repo = input.interact_text()?;
+                self.repo = if repo.is_empty() { None } else { Some(repo) };
+            }
+            Field::Email => {
+                let mut input = Input::<String>::with_theme(theme)
+                    .with_prompt(prompt)
+                    .allow_empty(true)
+                    .validate_with(|input: &String| {
+                        if input.is_empty() {
+                            Ok(())
+                        } else {
+                            validate_email(input)
+                        }
+                    });
+                if let Some(email) = self.email.as_ref().or(defaults.email.as_ref()) {
+                    input = input.default(email.clone());
+                }
+                let email = input.interact_text()?;
+                self.email = if email.is_empty() { None } else { Some(email) };
+            }
+        }
+        Ok(())
     }
 }
 
@@ -96,6 +348,7 @@ impl Template {
 ///   the license information.
 /// * `theme` - A reference to a `Theme` trait object that customizes
 ///   the template appearance.
+/// * `defaults` - The `FieldDefaults` offered in the prompts.
 ///
 /// # Returns
 ///
@@ -104,57 +357,426 @@ impl Template {
 ///
 /// # Example
 ///
-/// ```
+/// ```ignore
 /// let mut details = LicenseDetails { /* initialize fields */ };
 /// let theme = /* create a theme instance */;
-/// let template = fill_license_forms(&mut details, &theme)?;
+/// let template = fill_license_forms(&mut details, &theme, &FieldDefaults::default())?;
 /// println!("{:?}", template);
 /// ```
 pub(crate) fn fill_license_forms(
     details: &mut LicenseDetails,
     theme: &dyn Theme,
+    defaults: &FieldDefaults,
 ) -> LictoolResult<Template> {
     let mut template = Template::default();
-    let gitconfig = GitConfig::load();
-    if details.has_owner() {
-        let owner: String = Input::with_theme(theme)
-            .with_prompt("Please enter the author's name")
-            .show_default(true)
-            .default(gitconfig.username)
-            .interact_text()
-            .unwrap();
-        template.owner = Some(owner);
-    }
-    if details.has_year() {
-        let year: i32 = Input::with_theme(theme)
-            .with_prompt("Please enter the year of creation")
-            .show_default(true)
-            .default(Local::now().year())
-            .interact_text()
-            .unwrap();
-        template.year = if year == 0 { None } else { Some(year) };
-    }
-    if details.has_repo() {
-        let repo: String = Input::with_theme(theme)
-            .with_prompt("Please enter the program's name")
-            .allow_empty(true)
-            .interact_text()
-            .unwrap();
-        template.repo = if repo.is_empty() { None } else { Some(repo) };
-    }
-    if details.has_email() {
-        let email: String = Input::with_theme(theme)
-            .with_prompt("Please enter the email")
-            .default(gitconfig.email)
-            .allow_empty(true)
-            .interact_text()
-            .unwrap();
-        template.email = if email.is_empty() { None } else { Some(email) };
+    for field in Field::present_in(details) {
+        template.prompt_field(field, theme, defaults)?;
     }
     template.license_text = take(&mut details.license_text);
     Ok(template)
 }
 
+/// Prompts once for the fields any of several licenses has, such as
+/// the owner and year shared by the licenses of a dual-licensed
+/// project.
+///
+/// # Arguments
+///
+/// * `licenses` - The text of each license and the fields it has.
+/// * `theme` - The theme of the prompts.
+/// * `defaults` - The `FieldDefaults` offered in the prompts.
+///
+/// # Returns
+///
+/// A template for each license, with its text and the values of its
+/// fields.
+///
+/// # Errors
+///
+/// This function will return an error if a prompt cannot be
+/// displayed or read.
+pub(crate) fn fill_shared_license_forms(
+    licenses: Vec<(String, Vec<Field>)>,
+    theme: &dyn Theme,
+    defaults: &FieldDefaults,
+) -> LictoolResult<Vec<Template>> {
+    let mut shared = Template::default();
+    for field in Field::ALL {
+        if licenses.iter().any(|(_, fields)| fields.contains(&field)) {
+            shared.prompt_field(field, theme, defaults)?;
+        }
+    }
+    Ok(licenses
+        .into_iter()
+        .map(|(license_text, fields)| {
+            let mut template = Template {
+                license_text,
+                ..Default::default()
+            };
+            for field in fields {
+                *template.field_mut(field) = shared.field_mut(field).clone();
+            }
+            template
+        })
+        .collect())
+}
+
+/// Fills in the placeholders left in an existing license text, such
+/// as one copied from spdx.org by hand.
+///
+/// Fields already set on `template` are kept. The others are
+/// prompted for when `interactive`, or else taken from `defaults`;
+/// fields without a value are left unfilled.
+///
+/// # Returns
+///
+/// The fields whose placeholders were found in the text.
+///
+/// # Errors
+///
+/// This function will return an error if a prompt cannot be
+/// displayed or read.
+pub(crate) fn fill_placeholders(
+    template: &mut Template,
+    theme: &dyn Theme,
+    defaults: &FieldDefaults,
+    interactive: bool,
+) -> LictoolResult<Vec<Field>> {
+    let keys = template_keys(&template.license_text);
+    let present: Vec<Field> = Field::ALL
+        .into_iter()
+        .filter(|field| {
+            field
+                .placeholders()
+                .iter()
+                .any(|placeholder| template.license_text.contains(placeholder))
+                || keys.iter().any(|key| Field::from_key(key) == Some(*field))
+        })
+        .collect();
+    for &field in &present {
+        if template.field_mut(field).is_some() {
+            continue;
+        }
+        if interactive {
+            template.prompt_field(field, theme, defaults)?;
+        } else {
+            *template.field_mut(field) = defaults.field(field).cloned();
+        }
+    }
+    Ok(present)
+}
+
+/// Fills the fields of a template without prompting, from `defaults`
+/// or, for the year, the git history and the current year as the
+/// prompt would offer.
+///
+/// Fields already set on `template` are kept.
+///
+/// # Errors
+///
+/// Returns `Error::MissingFieldValue` naming the first field left
+/// without a value.
+pub(crate) fn fill_fields(
+    template: &mut Template,
+    fields: &[Field],
+    defaults: &FieldDefaults,
+) -> LictoolResult<()> {
+    for &field in fields {
+        if template.field_mut(field).is_some() {
+            continue;
+        }
+        let value = match field {
+            Field::Year => {
+                defaults
+                    .year
+                    .clone()
+                    .or_else(|| years_since_first_commit(Path::new(".")))
+                    .or_else(|| Some(Local::now().year().to_string()))
+            }
+            _ => defaults.field(field).cloned(),
+        };
+        match value {
+            Some(value) => *template.field_mut(field) = Some(value),
+            None => {
+                Err(Error::MissingFieldValue {
+                    field: field.label(),
+                    flag: field.flag().to_string(),
+                })?
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Fills the user-defined `{{key}}` placeholders of a custom
+/// template that `template.variables` has no value for, prompting
+/// for them with `theme` if one is given.
+///
+/// # Errors
+///
+/// Returns `Error::MissingFieldValue` naming the first key left
+/// without a value when there is no `theme`, or an error if a prompt
+/// cannot be displayed or read.
+pub(crate) fn fill_variables(
+    template: &mut Template,
+    theme: Option<&dyn Theme>,
+) -> LictoolResult<()> {
+    for key in template_keys(&template.license_text) {
+        if Field::from_key(&key).is_some() || template.variables.contains_key(&key) {
+            continue;
+        }
+        let Some(theme) = theme else {
+            Err(Error::MissingFieldValue {
+                field: key.clone(),
+                flag: format!("--set {}=...", key),
+            })?
+        };
+        let value = Input::<String>::with_theme(theme)
+            .with_prompt(t!("prompt-field", field = key.as_str()))
+            .interact_text()?;
+        template.variables.insert(key, value);
+    }
+    Ok(())
+}
+
+/// The outcome of reviewing a filled template in the init wizard.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Review {
+    /// The user confirmed the preview and wants it written.
+    Confirm,
+    /// The user wants to go back and pick another license.
+    ChangeLicense,
+    /// The user aborted the wizard.
+    Abort,
+}
+
+/// Shows a paged preview of the rendered template and lets the user
+/// confirm it, edit a specific field, or go back.
+///
+/// # Arguments
+///
+/// * `template` - The filled template to preview and edit.
+/// * `fields` - The fields that can be edited for this license.
+/// * `theme` - A reference to a `Theme` trait object that customizes
+///   the prompt appearance.
+/// * `defaults` - The `FieldDefaults` offered when editing a field.
+/// * `options` - The `WriteOptions` the license will be written with,
+///   so that the preview shows the text as written.
+///
+/// # Returns
+///
+/// A `LictoolResult` wrapping the `Review` chosen by the user.
+pub(crate) fn review_template(
+    template: &mut Template,
+    fields: &[Field],
+    theme: &dyn Theme,
+    defaults: &FieldDefaults,
+    options: &WriteOptions,
+) -> LictoolResult<Review> {
+    loop {
+        page(&formatted_text(
+            template,
+            options,
+            options.markdown,
+            LineEnding::Lf,
+        ))?;
+        let mut items = vec![t!("review-write")];
+        items.extend(
+            fields
+                .iter()
+                .map(|field| t!("review-edit", field = field.label())),
+        );
+        items.push(t!("review-change-license"));
+        items.push(t!("review-abort"));
+        let choice = Select::with_theme(theme)
+            .with_prompt(t!("review-prompt"))
+            .items(&items)
+            .default(0)
+            .interact_opt()?;
+        match choice {
+            Some(0) => return Ok(Review::Confirm),
+            Some(idx) if idx <= fields.len() => {
+                template.prompt_field(fields[idx - 1], theme, defaults)?
+            }
+            Some(idx) if idx == fields.len() + 1 => return Ok(Review::ChangeLicense),
+            _ => return Ok(Review::Abort),
+        }
+    }
+}
+
+/// The name license files are written with when no path is given,
+/// which also sets the format of their text.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub(crate) enum FileNameConvention {
+    /// `LICENSE`, in plain text.
+    #[value(name = "LICENSE")]
+    Plain,
+    /// `LICENSE.md`, with the title as a heading and placeholders
+    /// escaped so that Markdown renders them.
+    #[value(name = "LICENSE.md")]
+    Markdown,
+    /// `LICENSE.txt`, in plain text.
+    #[value(name = "LICENSE.txt")]
+    Text,
+}
+
+impl FileNameConvention {
+    pub(crate) fn file_name(self) -> &'static str {
+        match self {
+            FileNameConvention::Plain => "LICENSE",
+            FileNameConvention::Markdown => "LICENSE.md",
+            FileNameConvention::Text => "LICENSE.txt",
+        }
+    }
+}
+
+/// Options controlling how rendered licenses are written to disk.
+#[derive(Debug, Clone)]
+pub(crate) struct WriteOptions {
+    /// Create missing parent directories without asking.
+    pub(crate) parents: bool,
+    /// The file name used when the target is a directory.
+    pub(crate) default_filename: String,
+    /// The line endings to write, detected from the project when
+    /// unset.
+    pub(crate) line_ending: Option<LineEnding>,
+    /// The character encoding to write.
+    pub(crate) encoding: Encoding,
+    /// Clean-ups applied to the rendered text.
+    pub(crate) normalization: Normalization,
+    /// Format the text printed or previewed as Markdown, as when the
+    /// file name written without a path is `LICENSE.md`. Written
+    /// files are formatted by their own extension.
+    pub(crate) markdown: bool,
+    /// The permissions of the written file; existing permissions are
+    /// preserved when unset.
+    pub(crate) mode: Option<u32>,
+    /// Shell commands run with the written paths once writing is
+    /// done.
+    pub(crate) post_write_hooks: Vec<String>,
+    /// The theme of the confirmation prompts shown while writing.
+    pub(crate) theme: ThemeConfig,
+    /// Refuse to write texts that still contain known placeholders.
+    pub(crate) strict: bool,
+    /// Overwrite existing files instead of failing.
+    pub(crate) force: bool,
+    /// Copy existing files to `<file>.bak` before overwriting them.
+    pub(crate) backup: bool,
+    /// The values of user-defined `{{key}}` placeholders, from the
+    /// configuration and `--set`.
+    pub(crate) variables: BTreeMap<String, String>,
+    /// The configured script computing placeholder values, used for
+    /// templates without a script of their own.
+    pub(crate) script: Option<PlaceholderScript>,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            parents: false,
+            default_filename: DEFAULT_LICENSE_FILE.to_string(),
+            line_ending: None,
+            encoding: Encoding::default(),
+            normalization: Normalization::default(),
+            markdown: false,
+            mode: None,
+            post_write_hooks: Vec::new(),
+            theme: ThemeConfig::default(),
+            strict: false,
+            force: false,
+            backup: false,
+            variables: BTreeMap::new(),
+            script: None,
+        }
+    }
+}
+
+impl WriteOptions {
+    /// Checks whether existing files may be written over.
+    fn overwrites(&self) -> bool {
+        self.force || self.backup
+    }
+
+    /// Renders a template with its own script, or else the
+    /// configured one.
+    fn render(
+        &self,
+        template: &Template,
+    ) -> String {
+        template.render_with(template.script.as_ref().or(self.script.as_ref()))
+    }
+}
+
+/// Ensures the rendered template has no placeholders left when
+/// `options.strict` is set.
+///
+/// # Errors
+///
+/// Returns `Error::UnfilledPlaceholders` listing the placeholders
+/// still present.
+fn ensure_filled(
+    template: &Template,
+    options: &WriteOptions,
+) -> LictoolResult<()> {
+    if !options.strict {
+        return Ok(());
+    }
+    let rendered = options.render(template);
+    let mut unfilled: Vec<String> = unfilled_placeholders(&rendered)
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    unfilled.extend(
+        template_keys(&rendered)
+            .into_iter()
+            .map(|key| format!("{{{{{}}}}}", key)),
+    );
+    if unfilled.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::UnfilledPlaceholders {
+            placeholders: unfilled.join(", "),
+        }
+        .into())
+    }
+}
+
+/// Ensures the parent directory of `path` exists.
+///
+/// Missing directories are created when `options.parents` is set, or
+/// after confirmation when running in a terminal.
+///
+/// # Errors
+///
+/// Returns `Error::MissingDirectory` if the directory is missing and
+/// creating it was not allowed.
+fn ensure_parent_dir(
+    path: &Path,
+    options: &WriteOptions,
+) -> LictoolResult<()> {
+    let Some(parent) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) else {
+        return Ok(());
+    };
+    if parent.is_dir() {
+        return Ok(());
+    }
+    let dir = parent.to_string_lossy().into_owned();
+    let allowed = options.parents
+        || (io::stdin().is_terminal()
+            && Confirm::with_theme(&options.theme.build())
+                .with_prompt(t!("prompt-create-directory", dir = dir))
+                .default(true)
+                .interact()?);
+    if !allowed {
+        return Err(Error::MissingDirectory {
+            dir,
+        }
+        .into());
+    }
+    fs::create_dir_all(parent)?;
+    Ok(())
+}
+
 /// Writes the rendered license template to a file.
 ///
 /// This function takes a file path and a mutable reference to a
@@ -167,62 +789,534 @@ pub(crate) fn fill_license_forms(
 ///   `Path`, representing the file path.
 /// * `template` - A mutable reference to a `Template` struct to be
 ///   rendered and written.
+/// * `options` - The `WriteOptions` to apply.
 ///
 /// # Returns
 ///
-/// A `Result` indicating the success or failure of the file write
-/// operation.
+/// A `Result` wrapping the path of the written file.
 ///
 /// # Example
 ///
-/// ```
+/// ```ignore
 /// let mut template = Template { /* initialize fields */ };
-/// write_template("output.txt", &mut template)?;
+/// write_template("output.txt", &mut template, &WriteOptions::default())?;
 /// ```
 pub(crate) fn write_template<P: AsRef<Path> + Display>(
     path: P,
     template: &mut Template,
-) -> Result<(), anyhow::Error> {
-    let path_ref = path.as_ref();
+    options: &WriteOptions,
+) -> Result<PathBuf, anyhow::Error> {
+    ensure_filled(template, options)?;
+    let written = write_file(path.as_ref(), template, options)?;
+    run_hooks(options, std::slice::from_ref(&written))?;
+    Ok(written)
+}
+
+/// Runs the post-write hooks with the written files, leaving out
+/// stdout.
+fn run_hooks(
+    options: &WriteOptions,
+    written: &[PathBuf],
+) -> LictoolResult<()> {
+    let files: Vec<PathBuf> = written
+        .iter()
+        .filter(|path| !is_stdout(path))
+        .cloned()
+        .collect();
+    if files.is_empty() {
+        return Ok(());
+    }
+    run_post_write_hooks(&options.post_write_hooks, &files)
+}
 
-    if path_ref.exists() && path_ref.is_file() {
+/// Renders the template and writes it to a single file, or to stdout
+/// for `-`, without running post-write hooks.
+///
+/// # Returns
+///
+/// The path of the written file.
+fn write_file(
+    path: &Path,
+    template: &Template,
+    options: &WriteOptions,
+) -> LictoolResult<PathBuf> {
+    if is_stdout(path) {
+        write_stdout(template, options)?;
+        return Ok(path.to_path_buf());
+    }
+    let path_ref = resolve_target(path, &options.default_filename);
+
+    if path_ref.exists() && path_ref.is_file() && !options.overwrites() {
         return Err(Error::AlreadyExists {
             file: path_ref.to_string_lossy().into_owned(),
         }
         .into());
+    }
+    if options.backup && path_ref.is_file() {
+        back_up(&path_ref)?;
+    }
+    if write_rendered(&path_ref, template, options)? {
+        success!(
+            "<green>✔</> <bold>{}</>",
+            t!("file-created", file = path_ref.display())
+        );
+    }
+    Ok(path_ref)
+}
+
+/// Copies a file about to be overwritten to `<file>.bak`, replacing
+/// an earlier backup.
+///
+/// The file itself stays in place, so that its permissions and line
+/// endings carry over to the text written over it.
+///
+/// # Errors
+/// - If the file cannot be read or the backup cannot be written.
+fn back_up(path: &Path) -> LictoolResult<()> {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+    let backup = PathBuf::from(backup);
+    let contents =
+        fs::read(path).with_context(|| t!("error-read-file", path = path.display().to_string()))?;
+    if is_dry_run() {
+        print_planned_write(&backup, &String::from_utf8_lossy(&contents));
+        return Ok(());
+    }
+    journal::write(&backup, contents)
+        .with_context(|| t!("error-write-file", path = backup.display().to_string()))?;
+    note!(t!(
+        "file-backed-up",
+        file = path.display().to_string(),
+        backup = backup.display().to_string()
+    ));
+    Ok(())
+}
+
+/// Renders the template over an existing license file, such as when
+/// switching licenses, then runs the post-write hooks.
+///
+/// # Errors
+/// - If the template has placeholders left in strict mode.
+/// - If the file cannot be written or a hook fails.
+pub(crate) fn replace_template(
+    path: &Path,
+    template: &mut Template,
+    options: &WriteOptions,
+) -> LictoolResult<()> {
+    ensure_filled(template, options)?;
+    write_rendered(path, template, options)?;
+    run_post_write_hooks(&options.post_write_hooks, &[path.to_path_buf()])
+}
+
+/// Renders the template with the line endings, clean-ups, encoding
+/// and permissions of `options`, and writes it to `path`.
+///
+/// # Returns
+///
+/// `false` if this is a dry run and the changes were only printed.
+fn write_rendered(
+    path: &Path,
+    template: &Template,
+    options: &WriteOptions,
+) -> LictoolResult<bool> {
+    let line_ending = options
+        .line_ending
+        .unwrap_or_else(|| LineEnding::detect(path));
+    let text = formatted_text(template, options, is_markdown_file(path), line_ending);
+    if is_dry_run() {
+        print_planned_write(path, &text);
+        return Ok(false);
+    }
+    ensure_parent_dir(path, options)?;
+    let previous = fs::metadata(path)
+        .ok()
+        .map(|metadata| metadata.permissions());
+    journal::write_with_permissions(
+        path,
+        options.encoding.encode(&text)?,
+        permissions(options.mode, previous),
+    )?;
+    Ok(true)
+}
+
+/// Renders the template like `write_rendered` and writes it to
+/// stdout, reporting it on stderr so that the text can be piped.
+fn write_stdout(
+    template: &Template,
+    options: &WriteOptions,
+) -> LictoolResult<()> {
+    let line_ending = options
+        .line_ending
+        .unwrap_or_else(|| LineEnding::detect(Path::new(STDOUT_PATH)));
+    let text = formatted_text(template, options, options.markdown, line_ending);
+    let mut out = io::stdout().lock();
+    out.write_all(&options.encoding.encode(&text)?)?;
+    out.flush()?;
+    if !is_quiet() {
+        eprintln!(
+            "{}",
+            cformat!("<green>✔</> <bold>{}</>", t!("license-written-stdout"))
+        );
+    }
+    Ok(())
+}
+
+/// Renders the template in the format, with the clean-ups and with
+/// the line endings it is written with, as Markdown if `markdown` is
+/// set.
+fn formatted_text(
+    template: &Template,
+    options: &WriteOptions,
+    markdown: bool,
+    line_ending: LineEnding,
+) -> String {
+    let mut text = options.render(template);
+    if markdown {
+        text = markdown_text(&text);
+    }
+    line_ending.apply(&options.normalization.apply(&text))
+}
+
+/// Formats a license text as Markdown: a first line standing on its
+/// own, such as `MIT License`, becomes the heading, and the `<` of
+/// placeholders such as `<copyright holders>` is escaped, leaving
+/// autolinks such as `<jane@example.com>` alone.
+fn markdown_text(text: &str) -> String {
+    let mut lines: Vec<String> = text.split('\n').map(str::to_string).collect();
+    if let Some(first) = lines.iter().position(|line| !line.trim().is_empty()) {
+        let title = lines[first].trim();
+        let stands_alone = lines
+            .get(first + 1)
+            .is_none_or(|next| next.trim().is_empty());
+        if stands_alone && !title.starts_with('#') && !title.ends_with(['.', ',', ';', ':']) {
+            lines[first] = format!("# {}", title);
+        }
+    }
+    let mut markdown = String::with_capacity(text.len());
+    for (idx, line) in lines.iter().enumerate() {
+        if idx > 0 {
+            markdown.push('\n');
+        }
+        let mut rest = line.as_str();
+        while let Some(start) = rest.find('<') {
+            markdown.push_str(&rest[..start]);
+            let is_autolink = rest[start..].find('>').is_some_and(|end| {
+                let inner = &rest[start + 1..start + end];
+                inner.contains('@') || inner.contains("://")
+            });
+            markdown.push_str(if is_autolink { "<" } else { "\\<" });
+            rest = &rest[start + 1..];
+        }
+        markdown.push_str(rest);
+    }
+    markdown
+}
+
+/// Undoes the formatting of `markdown_text`, so that a `LICENSE.md`
+/// compares equal to the plain text it was written from.
+pub(crate) fn markdown_plain_text(text: &str) -> String {
+    let mut lines: Vec<&str> = text.split('\n').collect();
+    if let Some(first) = lines.iter().position(|line| !line.trim().is_empty()) {
+        if let Some(title) = lines[first].trim_start().strip_prefix("# ") {
+            lines[first] = title;
+        }
+    }
+    lines.join("\n").replace("\\<", "<")
+}
+
+/// Checks whether a license file is written as Markdown, by its
+/// extension.
+pub(crate) fn is_markdown_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("md"))
+}
+
+/// Returns the permissions a written file gets: the requested mode,
+/// or else those it had before being overwritten.
+///
+/// Modes are only supported on Unix and are ignored elsewhere.
+fn permissions(
+    mode: Option<u32>,
+    previous: Option<Permissions>,
+) -> Option<Permissions> {
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        return Some(Permissions::from_mode(mode));
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+    previous
+}
+
+/// Resolves the file a license should be written to.
+///
+/// If `path` is an existing directory, the default license file name
+/// inside it is used, so `-p ./subcrate` writes
+/// `./subcrate/LICENSE.md`.
+fn resolve_target(
+    path: &Path,
+    default_filename: &str,
+) -> PathBuf {
+    if path.is_dir() {
+        path.join(default_filename)
     } else {
-        fs::write(&path, template.render())?;
-        cprintln!("<green>✔</> <bold>Successfully created {} file.</>", path);
-        Ok(())
+        path.to_path_buf()
+    }
+}
+
+/// Returns the file a license should be written to when several
+/// licenses are written at once, such as `LICENSE-MIT` for
+/// `LICENSE.md` and the suffix `MIT`. Stdout is kept, so that the
+/// licenses follow each other there.
+pub(crate) fn license_specific_target(
+    path: &Path,
+    default_filename: &str,
+    suffix: &str,
+) -> PathBuf {
+    if is_stdout(path) {
+        return path.to_path_buf();
+    }
+    let target = resolve_target(path, default_filename);
+    let stem = target
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    target.with_file_name(format!("{}-{}", stem, suffix))
+}
+
+/// Returns the text of the license file explaining a license
+/// expression whose licenses are written to their own files.
+///
+/// # Arguments
+///
+/// * `expression` - The expression of the project.
+/// * `files` - The license, with its exception, and the name of the
+///   file of each license of the expression.
+pub(crate) fn license_stub(
+    expression: &Expression,
+    files: &[(String, String)],
+) -> String {
+    let intro = match expression {
+        Expression::Or(_) => {
+            "You may use it under the terms of any of these licenses, at your option:"
+        }
+        Expression::And(_) => "Its use is subject to the terms of all of these licenses:",
+        Expression::License(_) => "The texts of its licenses are in these files:",
+    };
+    let mut stub = format!(
+        "This project is licensed under {}.\n\n{}\n\n",
+        expression, intro
+    );
+    for (license, file) in files {
+        stub.push_str(&format!("- {}: {}\n", license, file));
+    }
+    stub.push_str(&format!("\nSPDX-License-Identifier: {}\n", expression));
+    stub
+}
+
+/// Checks that none of the paths points at an existing file, unless
+/// `options` allow overwriting them.
+///
+/// # Errors
+///
+/// Returns `Error::AlreadyExists` naming the first existing file.
+pub(crate) fn ensure_targets_free<P: AsRef<Path>>(
+    paths: &[P],
+    options: &WriteOptions,
+) -> LictoolResult<()> {
+    if options.overwrites() {
+        return Ok(());
+    }
+    let default_filename = &options.default_filename;
+    match paths
+        .iter()
+        .filter(|path| !is_stdout(path.as_ref()))
+        .map(|path| resolve_target(path.as_ref(), default_filename))
+        .find(|path| path.is_file())
+    {
+        Some(existing) => {
+            Err(Error::AlreadyExists {
+                file: existing.to_string_lossy().into_owned(),
+            }
+            .into())
+        }
+        None => Ok(()),
     }
 }
 
+/// Writes the rendered license template to several files.
+///
+/// All paths are checked before anything is written, so an existing
+/// file at any of them leaves every location untouched.
+///
+/// # Arguments
+///
+/// * `paths` - The file paths to write the license to.
+/// * `template` - A mutable reference to a `Template` struct to be
+///   rendered and written.
+/// * `options` - The `WriteOptions` to apply to every file.
+///
+/// # Returns
+///
+/// A `Result` wrapping the paths of the written files.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut template = Template { /* initialize fields */ };
+/// let paths = ["LICENSE", "docs/LICENSE.txt"];
+/// write_templates(&paths, &mut template, &WriteOptions::default())?;
+/// ```
+pub(crate) fn write_templates<P: AsRef<Path> + Display>(
+    paths: &[P],
+    template: &mut Template,
+    options: &WriteOptions,
+) -> Result<Vec<PathBuf>, anyhow::Error> {
+    ensure_filled(template, options)?;
+    ensure_targets_free(paths, options)?;
+    let written = paths
+        .iter()
+        .map(|path| write_file(path.as_ref(), template, options))
+        .collect::<LictoolResult<Vec<_>>>()?;
+    run_hooks(options, &written)?;
+    Ok(written)
+}
+
 pub(crate) fn interact_write_template<P: AsRef<Path> + Display>(
     path: P,
     template: &mut Template,
-) -> Result<(), anyhow::Error> {
+    options: &WriteOptions,
+    theme: &dyn Theme,
+) -> Result<PathBuf, anyhow::Error> {
     let mut path = path.as_ref().to_string_lossy().into_owned();
     loop {
-        match write_template(&path, template) {
-            Ok(_) => return Ok(()),
+        match write_template(&path, template, options) {
+            Ok(written) => return Ok(written),
             Err(e) => {
                 if matches!(
                     e.downcast_ref::<Error>(),
                     Some(Error::AlreadyExists {
                         file: _
                     })
-                ) {
+                ) && io::stdin().is_terminal()
+                {
                     cprintln!("<y, bold>\u{f421}</> <bold>{}</>", e.to_string());
-                    let new_path: String = Input::with_theme(&ColorfulTheme::default())
-                        .with_prompt("Please specify a new file name to avoid overwriting.")
+                    let new_path: String = Input::with_theme(theme)
+                        .with_prompt(t!("prompt-new-file-name"))
                         .default(path.clone())
                         .interact_text()
                         .unwrap();
                     path = new_path;
+                } else if e.downcast_ref::<Error>().is_some() {
+                    return Err(e);
                 } else {
-                    return Err(anyhow!("An unknown error occurred: {}", e));
+                    return Err(anyhow!(t!("error-unknown", error = e)));
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        fill_fields, formatted_text, license_specific_target, markdown_plain_text, markdown_text,
+        Field, Template, WriteOptions, STDOUT_PATH,
+    };
+    use crate::{
+        check::find_drift,
+        script::PlaceholderScript,
+        util::{defaults::FieldDefaults, line_ending::LineEnding, normalize::Normalization},
+    };
+
+    #[test]
+    fn test_configured_script() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("placeholders.rhai");
+        std::fs::write(
+            &path,
+            r#"fn owner(ctx) { `${ctx.owner} and contributors` }"#,
+        )
+        .unwrap();
+        // Built as `init` builds its templates, without a script.
+        let mut template = Template::new("Copyright (c) <year> <copyright holders>");
+        let defaults = FieldDefaults {
+            owner: Some("Jane".to_string()),
+            year: Some("2024".to_string()),
+            ..Default::default()
+        };
+        fill_fields(&mut template, &[Field::Year, Field::Owner], &defaults).unwrap();
+        let options = WriteOptions {
+            script: Some(PlaceholderScript::load(&path).unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            formatted_text(&template, &options, false, LineEnding::Lf),
+            "Copyright (c) 2024 Jane and contributors"
+        );
+    }
+
+    #[test]
+    fn test_wrapped_markdown_has_no_drift() {
+        let canonical = "MIT License\n\nCopyright (c) <year> <copyright holders>\n\nPermission is \
+                         hereby granted, free of charge, to any person obtaining a copy\nof this \
+                         software and associated documentation files (the \"Software\"), to \
+                         deal\nin the Software without restriction.\n\n  1. The above copyright \
+                         notice shall be included in all\n     copies or substantial portions of \
+                         the Software.\n";
+        let mut template = Template::new(canonical);
+        let defaults = FieldDefaults {
+            owner: Some("Jane".to_string()),
+            year: Some("2024".to_string()),
+            ..Default::default()
+        };
+        fill_fields(&mut template, &[Field::Year, Field::Owner], &defaults).unwrap();
+        let options = WriteOptions {
+            normalization: Normalization {
+                wrap: Some(40),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let written = formatted_text(&template, &options, true, LineEnding::Lf);
+        assert!(written.starts_with("# MIT License\n"));
+        assert_eq!(find_drift(canonical, &written, &Default::default()), vec![]);
+    }
+
+    #[test]
+    fn test_license_specific_target() {
+        assert_eq!(
+            license_specific_target(Path::new("docs/LICENSE.md"), "LICENSE", "MIT"),
+            PathBuf::from("docs/LICENSE-MIT")
+        );
+        assert_eq!(
+            license_specific_target(Path::new("COPYING"), "LICENSE", "GPL"),
+            PathBuf::from("COPYING-GPL")
+        );
+        assert_eq!(
+            license_specific_target(Path::new(STDOUT_PATH), "LICENSE", "MIT"),
+            PathBuf::from(STDOUT_PATH)
+        );
+    }
+
+    #[test]
+    fn test_markdown_text() {
+        assert_eq!(
+            markdown_text(
+                "MIT License\n\nCopyright (c) <year> <copyright holders> <jane@example.com>\n"
+            ),
+            "# MIT License\n\nCopyright (c) \\<year> \\<copyright holders> <jane@example.com>\n"
+        );
+        assert_eq!(
+            markdown_text("Copyright 2024 Jane\nAll rights reserved.\n"),
+            "Copyright 2024 Jane\nAll rights reserved.\n"
+        );
+        let plain = "MIT License\n\nCopyright (c) <year> <copyright holders> <jane@example.com>\n";
+        assert_eq!(markdown_plain_text(&markdown_text(plain)), plain);
+    }
+}
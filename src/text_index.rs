@@ -0,0 +1,198 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::Path,
+};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    i18n::t,
+    search::{build_pattern, search_text, SearchMatch},
+    spdx::LicenseDetails,
+    util::errors::LictoolResult,
+};
+
+/// A full-text index of the license texts of one version of the SPDX
+/// license list, kept in the cache directory so that searches after
+/// the first one fetch nothing but the license list.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct TextIndex {
+    /// The version of the license list the texts belong to.
+    pub(crate) version: String,
+    /// The ID and text of each license, sorted by ID.
+    licenses: Vec<(String, String)>,
+    /// The indices of the licenses holding each lowercased word.
+    words: BTreeMap<String, Vec<usize>>,
+}
+
+impl TextIndex {
+    /// Indexes the texts of licenses of the license list `version`.
+    pub(crate) fn build(
+        version: &str,
+        details: &[LicenseDetails],
+    ) -> Self {
+        let mut licenses: Vec<(String, String)> = details
+            .iter()
+            .map(|details| (details.license_id.clone(), details.license_text.clone()))
+            .collect();
+        licenses.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let mut words: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        for (idx, (_, text)) in licenses.iter().enumerate() {
+            for word in text_words(text).collect::<BTreeSet<_>>() {
+                words.entry(word).or_default().push(idx);
+            }
+        }
+        TextIndex {
+            version: version.to_string(),
+            licenses,
+            words,
+        }
+    }
+
+    /// Reads the index at `path` if it was built for the license list
+    /// `version`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if there is no index, it cannot be read, or it is out
+    /// of date.
+    pub(crate) fn load(
+        path: &Path,
+        version: &str,
+    ) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        serde_json::from_str::<Self>(&content)
+            .ok()
+            .filter(|index| index.version == version)
+    }
+
+    /// Writes the index to `path`, creating its directory.
+    ///
+    /// # Errors
+    /// - If the directory or the file cannot be written.
+    pub(crate) fn save(
+        &self,
+        path: &Path,
+    ) -> LictoolResult<()> {
+        let file = path.display().to_string();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| t!("error-write-file", path = file.as_str()))?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+            .with_context(|| t!("error-write-file", path = file.as_str()))?;
+        Ok(())
+    }
+
+    /// Finds the licenses whose text holds `phrase`, ignoring case
+    /// and how the text is wrapped, with the passages matching
+    /// it.
+    ///
+    /// Only the licenses holding every word of the phrase, or a word
+    /// containing it, are searched.
+    ///
+    /// # Errors
+    /// - `Error::InvalidPattern` if the phrase cannot be searched
+    ///   for.
+    pub(crate) fn search(
+        &self,
+        phrase: &str,
+    ) -> LictoolResult<Vec<(String, Vec<SearchMatch>)>> {
+        let phrase = phrase.split_whitespace().collect::<Vec<_>>().join(" ");
+        let pattern = build_pattern(&phrase, false, true)?;
+        let mut candidates: Option<BTreeSet<usize>> = None;
+        for word in text_words(&phrase) {
+            let holding: BTreeSet<usize> = self
+                .words
+                .iter()
+                .filter(|(indexed, _)| indexed.contains(&word))
+                .flat_map(|(_, licenses)| licenses.iter().copied())
+                .collect();
+            candidates = Some(match candidates {
+                Some(candidates) => candidates.intersection(&holding).copied().collect(),
+                None => holding,
+            });
+        }
+        let candidates =
+            candidates.unwrap_or_else(|| (0..self.licenses.len()).collect::<BTreeSet<_>>());
+        Ok(candidates
+            .into_iter()
+            .map(|idx| {
+                let (id, text) = &self.licenses[idx];
+                (id.clone(), search_text(&pattern, text))
+            })
+            .filter(|(_, matches)| !matches.is_empty())
+            .collect())
+    }
+}
+
+/// Splits a text into its lowercased words, leaving out punctuation.
+fn text_words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::TextIndex;
+    use crate::spdx::LicenseDetails;
+
+    #[test]
+    fn test_text_index() {
+        let details = |id: &str, text: &str| {
+            LicenseDetails {
+                is_deprecated_license_id: false,
+                license_text: text.to_string(),
+                name: id.to_string(),
+                license_comments: None,
+                license_id: id.to_string(),
+                see_also: Vec::new(),
+                is_osi_approved: Some(true),
+                is_fsf_libre: None,
+                deprecated_version: None,
+                fsf: None,
+            }
+        };
+        let index = TextIndex::build(
+            "3.24",
+            &[
+                details("MIT", "Permission is hereby granted, free of charge."),
+                details(
+                    "Apache-2.0",
+                    "If You institute patent\n   litigation against any entity, then any patent \
+                     licenses shall terminate.",
+                ),
+            ],
+        );
+        let ids = |phrase| {
+            index
+                .search(phrase)
+                .unwrap()
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids("Patent  Litigation"), ["Apache-2.0"]);
+        assert_eq!(ids("free of char"), ["MIT"]);
+        assert_eq!(ids("."), ["Apache-2.0", "MIT"]);
+        assert!(ids("litigation free").is_empty());
+        let found = index.search("patent litigation").unwrap();
+        assert_eq!(found[0].1[0].line, 1);
+        assert_eq!(found[0].1[0].matched, "patent litigation");
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("cache").join("text-index.json");
+        index.save(&path).unwrap();
+        assert_eq!(
+            TextIndex::load(&path, "3.24").map(|index| index.licenses.len()),
+            Some(2)
+        );
+        assert!(TextIndex::load(&path, "3.25").is_none());
+    }
+}
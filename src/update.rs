@@ -0,0 +1,194 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::util::{
+    errors::{Error, LictoolResult},
+    http,
+    integrity::sha256_hex,
+};
+
+/// The GitHub API endpoint describing the latest release.
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/fracturesky/lictool/releases/latest";
+
+/// A GitHub release of lictool.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub(crate) struct Release {
+    /// The tag of the release, such as `v0.6.0`.
+    pub(crate) tag_name: String,
+    pub(crate) assets: Vec<ReleaseAsset>,
+}
+
+/// A file attached to a release.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub(crate) struct ReleaseAsset {
+    pub(crate) name: String,
+    pub(crate) browser_download_url: String,
+}
+
+impl Release {
+    /// Fetches the latest release from GitHub, revalidating a cached
+    /// response so that a new release is seen as soon as it is out.
+    ///
+    /// # Errors
+    /// - If the request fails or the response cannot be decoded.
+    pub async fn latest() -> LictoolResult<Self> {
+        http::revalidate_json(LATEST_RELEASE_URL).await
+    }
+
+    /// Returns the version of the release, without the `v` of its
+    /// tag.
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    /// Finds the asset with the given name.
+    ///
+    /// # Errors
+    /// - `Error::MissingReleaseAsset` if the release has no such
+    ///   asset, such as for an unsupported platform.
+    pub fn asset(
+        &self,
+        name: &str,
+    ) -> Result<&ReleaseAsset, Error> {
+        self.assets
+            .iter()
+            .find(|asset| asset.name == name)
+            .ok_or_else(|| {
+                Error::MissingReleaseAsset {
+                    name: name.to_string(),
+                    version: self.version().to_string(),
+                }
+            })
+    }
+}
+
+/// Returns the name of the release binary for this platform, such as
+/// `lictool-x86_64-linux` or `lictool-aarch64-windows.exe`.
+///
+/// Its checksum is published alongside it, with a `.sha256` suffix.
+pub(crate) fn binary_asset_name() -> String {
+    format!(
+        "{}-{}-{}{}",
+        env!("CARGO_PKG_NAME"),
+        env::consts::ARCH,
+        env::consts::OS,
+        env::consts::EXE_SUFFIX
+    )
+}
+
+/// Checks whether `latest` is a higher version than `current`,
+/// comparing their dot-separated numbers.
+///
+/// Pre-release suffixes such as `-rc.1` are ignored.
+pub(crate) fn is_newer(
+    latest: &str,
+    current: &str,
+) -> bool {
+    let numbers = |version: &str| -> Vec<u64> {
+        version
+            .split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|number| number.parse().unwrap_or(0))
+            .collect()
+    };
+    numbers(latest) > numbers(current)
+}
+
+/// Checks a downloaded binary against the contents of its `.sha256`
+/// file, in the `<hex digest>  <file name>` format of `sha256sum`.
+///
+/// # Errors
+/// - `Error::ChecksumMismatch` if the digests differ.
+pub(crate) fn verify_checksum(
+    binary: &[u8],
+    checksum_file: &str,
+) -> Result<(), Error> {
+    let expected = checksum_file
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    let actual = sha256_hex(binary);
+    if expected != actual {
+        return Err(Error::ChecksumMismatch {
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Replaces the running executable with `binary`.
+///
+/// The new binary is written next to the executable first, so the
+/// executable is never left half written, and removed again if it
+/// cannot take its place. Windows cannot overwrite a running
+/// executable, so it is moved aside to `<name>.old` instead.
+///
+/// # Returns
+///
+/// The path of the replaced executable.
+///
+/// # Errors
+/// - If the executable cannot be located or its directory is not
+///   writable.
+pub(crate) fn replace_executable(binary: &[u8]) -> io::Result<PathBuf> {
+    let exe = fs::canonicalize(env::current_exe()?)?;
+    let staged = exe.with_extension("new");
+    let replaced = stage_and_swap(binary, &staged, &exe);
+    if replaced.is_err() {
+        let _ = fs::remove_file(&staged);
+    }
+    replaced.map(|()| exe)
+}
+
+/// Writes `binary` to `staged` and moves it over `exe`, putting a
+/// Windows executable moved aside back if the move fails.
+fn stage_and_swap(
+    binary: &[u8],
+    staged: &Path,
+    exe: &Path,
+) -> io::Result<()> {
+    fs::write(staged, binary)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(staged, fs::Permissions::from_mode(0o755))?;
+    }
+    if cfg!(windows) {
+        let old = exe.with_extension("old");
+        let _ = fs::remove_file(&old);
+        fs::rename(exe, &old)?;
+        if let Err(error) = fs::rename(staged, exe) {
+            let _ = fs::rename(&old, exe);
+            return Err(error);
+        }
+        return Ok(());
+    }
+    fs::rename(staged, exe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_newer, verify_checksum};
+
+    #[test]
+    fn test_release_checks() {
+        assert!(is_newer("0.10.0", "0.9.3"));
+        assert!(is_newer("1.0.0", "0.5.0"));
+        assert!(!is_newer("0.5.0", "0.5.0"));
+        assert!(!is_newer("0.5.0-rc.1", "0.5.0"));
+        assert!(verify_checksum(
+            b"abc",
+            "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD  lictool\n"
+        )
+        .is_ok());
+        assert!(verify_checksum(b"abd", "ba7816bf8f01cfea414140de5dae2223").is_err());
+    }
+}
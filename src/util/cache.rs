@@ -1,4 +1,10 @@
-use std::path::PathBuf;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::i18n::t;
 
 /// Returns the path to the HTTP cache directory.
 ///
@@ -10,3 +16,147 @@ pub(crate) fn http_cache_dir() -> PathBuf {
         .join(env!("CARGO_PKG_NAME"))
         .join("http-cache")
 }
+
+/// Returns the directory holding the files fetched by an interrupted
+/// `db export`, so that the next run can resume it.
+///
+/// # Panics
+/// - If the cache directory cannot be found.
+pub(crate) fn partial_export_dir() -> PathBuf {
+    dirs::cache_dir()
+        .expect("Cache directory not found.")
+        .join(env!("CARGO_PKG_NAME"))
+        .join("partial-export")
+}
+
+/// Returns the path of the full-text index of license texts built by
+/// `grep`.
+///
+/// # Panics
+/// - If the cache directory cannot be found.
+pub(crate) fn text_index_path() -> PathBuf {
+    dirs::cache_dir()
+        .expect("Cache directory not found.")
+        .join(env!("CARGO_PKG_NAME"))
+        .join("text-index.json")
+}
+
+/// Returns the total size in bytes and the number of files under
+/// `dir`, which is empty if it does not exist.
+///
+/// # Errors
+/// - If an entry cannot be read.
+pub(crate) fn dir_size(dir: &Path) -> io::Result<(u64, usize)> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok((0, 0)),
+        Err(error) => return Err(error),
+    };
+    let mut total = (0, 0);
+    for entry in entries {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        let (bytes, files) = if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            (metadata.len(), 1)
+        };
+        total = (total.0 + bytes, total.1 + files);
+    }
+    Ok(total)
+}
+
+/// Returns the modification times of the oldest and the newest file
+/// under `dir`, or `None` if it holds no files.
+///
+/// # Errors
+/// - If an entry cannot be read.
+pub(crate) fn modified_range(dir: &Path) -> io::Result<Option<(SystemTime, SystemTime)>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    let mut range: Option<(SystemTime, SystemTime)> = None;
+    for entry in entries {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        let (oldest, newest) = if metadata.is_dir() {
+            match modified_range(&entry.path())? {
+                Some(times) => times,
+                None => continue,
+            }
+        } else {
+            (metadata.modified()?, metadata.modified()?)
+        };
+        range = Some(match range {
+            Some((first, last)) => (first.min(oldest), last.max(newest)),
+            None => (oldest, newest),
+        });
+    }
+    Ok(range)
+}
+
+/// Formats a size in bytes with a binary unit, such as `1.5 MiB`.
+pub(crate) fn format_size(bytes: u64) -> String {
+    let units = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < units.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, units[0])
+    } else {
+        format!("{:.1} {}", size, units[unit])
+    }
+}
+
+/// Formats how long ago `time` was, such as `3 days ago`.
+pub(crate) fn format_age(time: SystemTime) -> String {
+    let age = SystemTime::now()
+        .duration_since(time)
+        .unwrap_or(Duration::ZERO)
+        .as_secs();
+    match age {
+        0..60 => t!("age-just-now"),
+        60..3600 => t!("age-minutes", count = age / 60),
+        3600..86400 => t!("age-hours", count = age / 3600),
+        _ => t!("age-days", count = age / 86400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        time::{Duration, SystemTime},
+    };
+
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::modified_range;
+
+    #[test]
+    fn test_modified_range() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(modified_range(dir.path()).unwrap(), None);
+        assert_eq!(modified_range(&dir.path().join("missing")).unwrap(), None);
+        let old = dir.path().join("nested").join("old");
+        fs::create_dir(old.parent().unwrap()).unwrap();
+        fs::write(&old, "old").unwrap();
+        fs::write(dir.path().join("new"), "new").unwrap();
+        let day_ago = SystemTime::now() - Duration::from_secs(24 * 60 * 60);
+        fs::File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(day_ago)
+            .unwrap();
+        let (oldest, newest) = modified_range(dir.path()).unwrap().unwrap();
+        assert_eq!(oldest, day_ago);
+        assert!(newest > day_ago);
+    }
+}
@@ -0,0 +1,379 @@
+use std::{env, fs, path::Path};
+
+use chrono::{Datelike, Local};
+
+use super::{
+    git::{
+        file_commit_years, first_commit_year, remote_url, repository_name, Contributor, GitConfig,
+    },
+    remote::{parse_remote, ForgeConfig},
+    validate::parse_year,
+};
+use crate::{config::Config, template::Field, years::notice_lines};
+
+/// Default values offered for the template fields.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct FieldDefaults {
+    pub(crate) owner: Option<String>,
+    pub(crate) email: Option<String>,
+    pub(crate) year: Option<String>,
+    pub(crate) repo: Option<String>,
+}
+
+impl FieldDefaults {
+    /// Loads the defaults, preferring the `LICTOOL_*` environment
+    /// variables, then the active profile and the configuration file,
+    /// then the project metadata, then the git configuration, then
+    /// the owner and name of the repository in the URL of the
+    /// `origin` remote, and finally a repository name from any
+    /// remote URL or the top-level directory of the repository.
+    pub fn load(config: &Config) -> Self {
+        let gitconfig = GitConfig::load();
+        Self::from_env()
+            .or(config.profile_defaults())
+            .or(config.field_defaults())
+            .or(Self::from_cargo_manifest(Path::new("Cargo.toml")))
+            .or(Self::from_package_json(Path::new("package.json")))
+            .or(FieldDefaults {
+                owner: non_empty(gitconfig.username),
+                email: non_empty(gitconfig.email),
+                ..Default::default()
+            })
+            .or(remote_url(Path::new("."))
+                .map(|url| Self::from_remote(&url, &config.forges))
+                .unwrap_or_default())
+            .or(FieldDefaults {
+                repo: repository_name(Path::new(".")),
+                ..Default::default()
+            })
+    }
+
+    /// Reads the owner and repository name from the URL of a GitHub,
+    /// GitLab or Bitbucket repository.
+    pub fn from_remote(
+        url: &str,
+        forges: &ForgeConfig,
+    ) -> Self {
+        let Some(remote) = parse_remote(url, forges) else {
+            return Self::default();
+        };
+        FieldDefaults {
+            owner: Some(remote.owner),
+            repo: Some(remote.name),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the default value of a field.
+    pub fn field(
+        &self,
+        field: Field,
+    ) -> Option<&String> {
+        match field {
+            Field::Owner => self.owner.as_ref(),
+            Field::Year => self.year.as_ref(),
+            Field::Repo => self.repo.as_ref(),
+            Field::Email => self.email.as_ref(),
+        }
+    }
+
+    /// Reads the owner and email from the `authors` field of a
+    /// `Cargo.toml` manifest, if present.
+    ///
+    /// Both `package.authors` and `workspace.package.authors` are
+    /// considered. Multiple authors are joined into a single owner,
+    /// while the email of the first author is used.
+    pub fn from_cargo_manifest(path: &Path) -> Self {
+        let Some(manifest) = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+        else {
+            return Self::default();
+        };
+        let authors = ["package", "workspace.package"]
+            .iter()
+            .filter_map(|section| {
+                section
+                    .split('.')
+                    .try_fold(&manifest, |table, key| table.get(key)?.as_table())
+            })
+            .find_map(|table| table.get("authors")?.as_array())
+            .map(|authors| {
+                authors
+                    .iter()
+                    .filter_map(|author| author.as_str())
+                    .map(parse_author)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        Self::from_authors(&authors)
+    }
+
+    /// Reads the owner and email from the `author` field of a
+    /// `package.json` manifest, falling back to its `contributors`.
+    ///
+    /// People can be given either as `"Name <email> (url)"` strings
+    /// or as `{ "name", "email", "url" }` objects.
+    pub fn from_package_json(path: &Path) -> Self {
+        let Some(manifest) = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        else {
+            return Self::default();
+        };
+        let person = |value: &serde_json::Value| {
+            match value {
+                serde_json::Value::String(author) => Some(parse_author(author)),
+                serde_json::Value::Object(fields) => {
+                    Some((
+                        fields.get("name")?.as_str()?.trim().to_string(),
+                        fields
+                            .get("email")
+                            .and_then(|email| email.as_str())
+                            .map(str::to_string),
+                    ))
+                }
+                _ => None,
+            }
+        };
+        let authors: Vec<_> = match manifest.get("author").and_then(person) {
+            Some(author) => vec![author],
+            None => {
+                manifest
+                    .get("contributors")
+                    .and_then(|contributors| contributors.as_array())
+                    .map(|contributors| contributors.iter().filter_map(person).collect())
+                    .unwrap_or_default()
+            }
+        };
+        Self::from_authors(&authors)
+    }
+
+    /// Builds the defaults from the git authors with at least
+    /// `min_commits` commits, naming each as a copyright holder.
+    pub fn from_contributors(
+        contributors: &[Contributor],
+        min_commits: usize,
+    ) -> Self {
+        let authors: Vec<(String, Option<String>)> = contributors
+            .iter()
+            .filter(|contributor| contributor.commits >= min_commits)
+            .map(|contributor| {
+                (
+                    contributor.name.clone(),
+                    non_empty(contributor.email.clone()),
+                )
+            })
+            .collect();
+        Self::from_authors(&authors)
+    }
+
+    /// Builds the defaults from a list of `(name, email)` authors.
+    fn from_authors(authors: &[(String, Option<String>)]) -> Self {
+        let names: Vec<&str> = authors
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .filter(|name| !name.is_empty())
+            .collect();
+        FieldDefaults {
+            owner: non_empty(names.join(", ")),
+            email: authors.iter().find_map(|(_, email)| email.clone()),
+            ..Default::default()
+        }
+    }
+
+    /// Reads the year, owner and email from the first copyright
+    /// notice of a license text, such as `Copyright (c) 2019-2024
+    /// Jane Doe <jane@example.com>`.
+    ///
+    /// Steward notices, like the FSF's in the GPL, and lines still
+    /// holding placeholders yield nothing.
+    pub fn from_license_text(text: &str) -> Self {
+        let Some(line) = notice_lines(text).into_iter().next() else {
+            return Self::default();
+        };
+        let mut rest = match line.get(.."copyright".len()) {
+            Some(word) if word.eq_ignore_ascii_case("copyright") => &line[word.len()..],
+            _ => line,
+        }
+        .trim_start();
+        for mark in ["(c)", "(C)", "©"] {
+            rest = rest.strip_prefix(mark).unwrap_or(rest).trim_start();
+        }
+        let year_end = rest
+            .find(|ch: char| !(ch.is_ascii_digit() || ch == '-'))
+            .unwrap_or(rest.len());
+        let Ok(year) = parse_year(&rest[..year_end]) else {
+            return Self::default();
+        };
+        let (owner, email) = parse_author(rest[year_end..].trim_start_matches([',', ' ']));
+        if owner.contains(['<', '[', '{']) {
+            return Self::default();
+        }
+        FieldDefaults {
+            owner: non_empty(owner),
+            email,
+            year: Some(year),
+            repo: None,
+        }
+    }
+
+    /// Reads the defaults from the `LICTOOL_OWNER`, `LICTOOL_EMAIL`,
+    /// `LICTOOL_YEAR` and `LICTOOL_REPO` environment variables.
+    pub fn from_env() -> Self {
+        let var = |name: &str| env::var(name).ok().and_then(non_empty);
+        FieldDefaults {
+            owner: var("LICTOOL_OWNER"),
+            email: var("LICTOOL_EMAIL"),
+            year: var("LICTOOL_YEAR").and_then(|year| parse_year(&year).ok()),
+            repo: var("LICTOOL_REPO"),
+        }
+    }
+
+    /// Fills the missing values from `fallback`.
+    pub fn or(
+        self,
+        fallback: FieldDefaults,
+    ) -> Self {
+        FieldDefaults {
+            owner: self.owner.or(fallback.owner),
+            email: self.email.or(fallback.email),
+            year: self.year.or(fallback.year),
+            repo: self.repo.or(fallback.repo),
+        }
+    }
+}
+
+/// Splits an author string such as `Jane Doe <jane@example.com>` into
+/// its name and optional email.
+///
+/// Anything following the email, like an `(url)` suffix, is ignored.
+pub(crate) fn parse_author(author: &str) -> (String, Option<String>) {
+    match author.split_once('<') {
+        Some((name, rest)) => {
+            let email = rest.split_once('>').map_or(rest, |(email, _)| email);
+            (name.trim().to_string(), non_empty(email.trim().to_string()))
+        }
+        None => {
+            let name = author.split_once('(').map_or(author, |(name, _)| name);
+            (name.trim().to_string(), None)
+        }
+    }
+}
+
+/// Returns the copyright years of the repository containing `dir`,
+/// from its first commit to the current year, such as `2019-2024`.
+pub(crate) fn years_since_first_commit(dir: &Path) -> Option<String> {
+    first_commit_year(dir).map(|start| year_range(start, Local::now().year()))
+}
+
+/// Returns the copyright years of a file, from the oldest to the
+/// newest commit still visible in its `git blame`.
+pub(crate) fn file_years(path: &Path) -> Option<String> {
+    file_commit_years(path).map(|(start, end)| year_range(start, end))
+}
+
+/// Formats the years from `start` to `end`, or a single year if they
+/// are the same.
+fn year_range(
+    start: i32,
+    end: i32,
+) -> String {
+    if start >= end {
+        end.to_string()
+    } else {
+        format!("{}-{}", start, end)
+    }
+}
+
+/// Returns `None` for empty strings.
+fn non_empty(value: String) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{parse_author, year_range, FieldDefaults};
+
+    #[test]
+    fn test_parse_author() {
+        assert_eq!(
+            parse_author("Jane Doe <jane@example.com> (https://example.com)"),
+            ("Jane Doe".to_string(), Some("jane@example.com".to_string()))
+        );
+        assert_eq!(parse_author("Jane Doe"), ("Jane Doe".to_string(), None));
+        assert_eq!(year_range(2019, 2024), "2019-2024");
+        assert_eq!(year_range(2024, 2024), "2024");
+    }
+
+    #[test]
+    fn test_defaults_from_cargo_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &path,
+            "[package]\nname = \"demo\"\nauthors = [\"Jane Doe <jane@example.com>\", \"John \
+             Roe\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            FieldDefaults::from_cargo_manifest(&path),
+            FieldDefaults {
+                owner: Some("Jane Doe, John Roe".to_string()),
+                email: Some("jane@example.com".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_defaults_from_package_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("package.json");
+        std::fs::write(
+            &path,
+            r#"{ "contributors": [{ "name": "Acme Corp", "email": "legal@acme.test" }] }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            FieldDefaults::from_package_json(&path),
+            FieldDefaults {
+                owner: Some("Acme Corp".to_string()),
+                email: Some("legal@acme.test".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_defaults_from_license_text() {
+        assert_eq!(
+            FieldDefaults::from_license_text(
+                "MIT License\n\nCopyright (c) 2019-2024 Jane Doe <jane@example.com>\n"
+            ),
+            FieldDefaults {
+                owner: Some("Jane Doe".to_string()),
+                email: Some("jane@example.com".to_string()),
+                year: Some("2019-2024".to_string()),
+                repo: None,
+            }
+        );
+        assert_eq!(
+            FieldDefaults::from_license_text("Copyright (c) <year> <copyright holders>"),
+            FieldDefaults::default()
+        );
+        assert_eq!(
+            FieldDefaults::from_license_text(
+                "GNU GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007\n\n Copyright (C) 2007 Free \
+                 Software Foundation, Inc. <https://fsf.org/>\n"
+            ),
+            FieldDefaults::default()
+        );
+    }
+}
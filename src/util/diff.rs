@@ -0,0 +1,77 @@
+/// A step of a line diff turning an old text into a new one, holding
+/// line indices.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Edit {
+    /// A line present in both texts.
+    Keep(usize, usize),
+    /// A line only present in the old text.
+    Remove(usize),
+    /// A line only present in the new text.
+    Add(usize),
+}
+
+/// Computes a minimal line diff between `old` and `new`, comparing
+/// lines with `eq`.
+///
+/// Uses the longest common subsequence, which is fast enough for
+/// license-sized texts.
+pub(crate) fn diff<T, U>(
+    old: &[T],
+    new: &[U],
+    eq: impl Fn(&T, &U) -> bool,
+) -> Vec<Edit> {
+    let (n, m) = (old.len(), new.len());
+    // lcs[i][j] is the length of the common subsequence of old[i..]
+    // and new[j..].
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if eq(&old[i], &new[j]) {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut edits = Vec::with_capacity(n.max(m));
+    while i < n || j < m {
+        if i < n && j < m && eq(&old[i], &new[j]) {
+            edits.push(Edit::Keep(i, j));
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            edits.push(Edit::Remove(i));
+            i += 1;
+        } else {
+            edits.push(Edit::Add(j));
+            j += 1;
+        }
+    }
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{diff, Edit};
+
+    #[test]
+    fn test_diff_lines() {
+        let old = ["a", "b", "c", "d"];
+        let new = ["a", "x", "c", "d", "e"];
+        let edits = diff(&old, &new, |a, b| a == b);
+        assert_eq!(
+            edits,
+            vec![
+                Edit::Keep(0, 0),
+                Edit::Remove(1),
+                Edit::Add(1),
+                Edit::Keep(2, 2),
+                Edit::Keep(3, 3),
+                Edit::Add(4),
+            ]
+        );
+    }
+}
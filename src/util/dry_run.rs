@@ -0,0 +1,125 @@
+use std::{
+    fs,
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use color_print::cprintln;
+
+use crate::{
+    i18n::t,
+    util::diff::{diff, Edit},
+};
+
+/// Whether files are left untouched with `--dry-run`.
+static DRY_RUN: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables dry runs.
+pub(crate) fn set_dry_run(dry_run: bool) {
+    DRY_RUN.store(dry_run, Ordering::Relaxed);
+}
+
+/// Checks whether changes should only be printed, not made.
+pub(crate) fn is_dry_run() -> bool {
+    DRY_RUN.load(Ordering::Relaxed)
+}
+
+/// The lines writing a file would add and remove.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct PlannedChange {
+    /// Whether the file does not exist yet.
+    pub(crate) created: bool,
+    pub(crate) removed: Vec<String>,
+    pub(crate) added: Vec<String>,
+}
+
+/// Compares the current content of a file, if any, with the content
+/// about to be written.
+pub(crate) fn plan_write(
+    path: &Path,
+    contents: &str,
+) -> PlannedChange {
+    let Ok(bytes) = fs::read(path) else {
+        return PlannedChange {
+            created: true,
+            removed: Vec::new(),
+            added: contents.lines().map(str::to_string).collect(),
+        };
+    };
+    let current = String::from_utf8_lossy(&bytes);
+    let old: Vec<&str> = current.lines().collect();
+    let new: Vec<&str> = contents.lines().collect();
+    let mut change = PlannedChange::default();
+    for edit in diff(&old, &new, |a, b| a == b) {
+        match edit {
+            Edit::Keep(..) => {}
+            Edit::Remove(idx) => change.removed.push(old[idx].to_string()),
+            Edit::Add(idx) => change.added.push(new[idx].to_string()),
+        }
+    }
+    change
+}
+
+/// Prints what writing `contents` to `path` would change, as the
+/// summary line of a diff followed by the changed lines of existing
+/// files.
+pub(crate) fn print_planned_write(
+    path: &Path,
+    contents: &str,
+) {
+    let change = plan_write(path, contents);
+    let file = path.display().to_string();
+    if change.created {
+        cprintln!(
+            "<green>+ {}</> <dim>{}</>",
+            file,
+            t!("dry-run-create", lines = change.added.len())
+        );
+        return;
+    }
+    if change.added.is_empty() && change.removed.is_empty() {
+        cprintln!("<dim>= {} {}</>", file, t!("dry-run-unchanged"));
+        return;
+    }
+    cprintln!(
+        "<yellow>~ {}</> <dim>{}</>",
+        file,
+        t!(
+            "dry-run-modify",
+            added = change.added.len(),
+            removed = change.removed.len()
+        )
+    );
+    for line in &change.removed {
+        cprintln!("  <red>- {}</>", line);
+    }
+    for line in &change.added {
+        cprintln!("  <green>+ {}</>", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    use super::{plan_write, PlannedChange};
+
+    #[test]
+    fn test_plan_write() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("LICENSE");
+        assert!(plan_write(&path, "MIT License\n").created);
+        fs::write(&path, "Copyright <year>\nPermission is granted.\n").unwrap();
+        assert_eq!(
+            plan_write(&path, "Copyright 2024\nPermission is granted.\n"),
+            PlannedChange {
+                created: false,
+                removed: vec!["Copyright <year>".to_string()],
+                added: vec!["Copyright 2024".to_string()],
+            }
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use clap::ValueEnum;
+
+use super::errors::Error;
+
+/// The byte order mark written at the start of UTF-8 files.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// The character encoding used when writing files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, ValueEnum)]
+pub(crate) enum Encoding {
+    /// UTF-8 without a byte order mark.
+    #[default]
+    Utf8,
+    /// UTF-8 with a byte order mark.
+    Utf8Bom,
+    /// ISO-8859-1, for legacy toolchains.
+    Latin1,
+}
+
+impl Encoding {
+    /// Encodes `text` into bytes.
+    ///
+    /// # Errors
+    /// - `Error::Unencodable` if the text contains a character that
+    ///   cannot be represented in this encoding.
+    pub fn encode(
+        &self,
+        text: &str,
+    ) -> Result<Vec<u8>, Error> {
+        match self {
+            Encoding::Utf8 => Ok(text.as_bytes().to_vec()),
+            Encoding::Utf8Bom => Ok([UTF8_BOM, text.as_bytes()].concat()),
+            Encoding::Latin1 => {
+                text.chars()
+                    .map(|ch| {
+                        u8::try_from(u32::from(ch)).map_err(|_| {
+                            Error::Unencodable {
+                                ch,
+                                encoding: "Latin-1",
+                            }
+                        })
+                    })
+                    .collect()
+            }
+        }
+    }
+}
@@ -1,19 +1,221 @@
 use color_print::cformat;
 use snafu::prelude::*;
+
+use crate::i18n::t;
+
+/// The result of lictool operations, whose errors are either an
+/// `Error` or the context of a failed I/O or network call.
 pub type LictoolResult<T> = anyhow::Result<T>;
 
+/// The failures of lictool itself, which the errors of a
+/// `LictoolResult` can be downcast to.
 #[derive(Snafu, Debug)]
-pub(crate) enum Error {
-    #[snafu(display("No license found matching the ID provided."))]
+pub enum Error {
+    #[snafu(display("{}", t!("error-not-found")))]
     NotFound,
-    #[snafu(display("The {file} file already exists."))]
+    #[snafu(display("{}", t!("error-file-not-found", path = path)))]
+    FileNotFound { path: String },
+    #[snafu(display("{}", t!("error-lictool-not-runnable")))]
+    LictoolNotRunnable,
+    #[snafu(display("{}", t!("error-license-not-found", id = id, suggestions = suggestions)))]
+    LicenseNotFound { id: String, suggestions: String },
+    #[snafu(display("{}", t!("error-already-exists", file = file)))]
     AlreadyExists { file: String },
+    #[snafu(display("{}", t!("error-missing-directory", dir = dir)))]
+    MissingDirectory { dir: String },
+    #[snafu(display("{}", t!("error-unencodable", ch = format!("{:?}", ch), encoding = encoding)))]
+    Unencodable { ch: char, encoding: &'static str },
+    #[snafu(display("{}", t!("error-hook-failed", command = command, status = status)))]
+    HookFailed { command: String, status: String },
+    #[snafu(display("{}", t!("error-confirmation-required")))]
+    ConfirmationRequired,
+    #[snafu(display("{}", t!("error-nothing-to-undo")))]
+    NothingToUndo,
+    #[snafu(display("{}", t!("error-no-license-file")))]
+    NoLicenseFile,
+    #[snafu(display("{}", t!("error-changed-since-operation", files = files)))]
+    ChangedSinceOperation { files: String },
+    #[snafu(display("{}", t!("error-config-not-found", path = path)))]
+    ConfigNotFound { path: String },
+    #[snafu(display("{}", t!("error-config-key-not-set", key = key)))]
+    ConfigKeyNotSet { key: String },
+    #[snafu(display("{}", t!("error-unknown-config-keys", file = file, keys = keys)))]
+    UnknownConfigKeys { file: String, keys: String },
+    #[snafu(display("{}", t!("error-unknown-profile", name = name)))]
+    UnknownProfile { name: String },
+    #[snafu(display("{}", t!("error-missing-license-id")))]
+    MissingLicenseId,
+    #[snafu(display("{}", t!("error-http-status", url = url, status = status, origin = origin)))]
+    HttpStatus {
+        url: String,
+        status: String,
+        origin: String,
+    },
+    #[snafu(display("{}", t!("error-license-drift", file = file, license = license)))]
+    LicenseDrift { file: String, license: String },
+    #[snafu(display("{}", t!("error-compound-license", expression = expression)))]
+    CompoundLicense { expression: String },
+    #[snafu(display("{}", t!("error-compound-replace", expression = expression)))]
+    CompoundReplace { expression: String },
+    #[snafu(display("{}", t!("error-compound-before-with", expression = expression)))]
+    CompoundBeforeWith { expression: String },
+    #[snafu(display("{}", t!("error-invalid-expression", expression = expression, reason = reason)))]
+    InvalidExpression { expression: String, reason: String },
+    #[snafu(display("{}", t!("error-policy-violation", expression = expression)))]
+    PolicyViolation { expression: String },
+    #[snafu(display("{}", t!("error-missing-release-asset", name = name, version = version)))]
+    MissingReleaseAsset { name: String, version: String },
+    #[snafu(display("{}", t!("error-checksum-mismatch", expected = expected, actual = actual)))]
+    ChecksumMismatch { expected: String, actual: String },
+    #[snafu(display("{}", t!("error-diagnostics-failed", count = count)))]
+    DiagnosticsFailed { count: usize },
+    #[snafu(display("{}", t!("error-unknown-licenses", ids = ids)))]
+    UnknownLicenses { ids: String },
+    #[snafu(display("{}", t!("error-unfilled-placeholders", placeholders = placeholders)))]
+    UnfilledPlaceholders { placeholders: String },
+    #[snafu(display("{}", t!("error-missing-field-value", field = field, flag = flag)))]
+    MissingFieldValue { field: String, flag: String },
+    #[snafu(display("{}", t!("error-placeholder-violations", count = count)))]
+    PlaceholderViolations { count: usize },
+    #[snafu(display("{}", t!("error-header-conflicts", count = count)))]
+    HeaderConflicts { count: usize },
+    #[snafu(display("{}", t!("error-misnamed-license-files", count = count)))]
+    MisnamedLicenseFiles { count: usize },
+    #[snafu(display("{}", t!("error-missing-asset-dirs", dirs = dirs)))]
+    MissingAssetDirs { dirs: String },
+    #[snafu(display("{}", t!("error-not-ready-to-publish", count = count)))]
+    NotReadyToPublish { count: usize },
+    #[snafu(display("{}", t!("error-missing-license-ref-text", id = id)))]
+    MissingLicenseRefText { id: String },
+    #[snafu(display("{}", t!("error-invalid-license-ref", id = id)))]
+    InvalidLicenseRef { id: String },
+    #[snafu(display("{}", t!("error-unsupported-file-type", file = file)))]
+    UnsupportedFileType { file: String },
+    #[snafu(display("{}", t!("error-empty-family", prefix = prefix)))]
+    EmptyFamily { prefix: String },
+    #[snafu(display("{}", t!("error-invalid-pattern", pattern = pattern, reason = reason)))]
+    InvalidPattern { pattern: String, reason: String },
+    #[snafu(display("{}", t!("error-unsupported-archive", path = path)))]
+    UnsupportedArchive { path: String },
+    #[snafu(display("{}", t!("error-missing-headers", count = count)))]
+    MissingHeaders { count: usize },
+    #[snafu(display("{}", t!("error-malformed-snippets", count = count)))]
+    MalformedSnippets { count: usize },
+    #[snafu(display("{}", t!("error-invalid-dataset", path = path)))]
+    InvalidDataset { path: String },
+    #[snafu(display("{}", t!("error-corrupted-dataset", path = path, file = file)))]
+    CorruptedDataset { path: String, file: String },
+    #[snafu(display("{}", t!("error-corrupted-mirror", file = file)))]
+    CorruptedMirror { file: String },
+    #[snafu(display("{}", match suggestion {
+        Some(suggestion) => t!("error-unknown-command-suggestion", name = name, suggestion = suggestion),
+        None => t!("error-unknown-command", name = name),
+    }))]
+    UnknownCommand {
+        name: String,
+        suggestion: Option<String>,
+    },
+    #[snafu(display("{}", t!("error-plugin-failed", name = name, code = code)))]
+    PluginFailed { name: String, code: i32 },
+    #[snafu(display("{}", t!("error-invalid-script", path = path, message = message)))]
+    InvalidScript { path: String, message: String },
+    #[snafu(display("{}", t!("error-unsupported-format", format = format)))]
+    UnsupportedFormat { format: String },
+    #[snafu(display("{}", t!("error-invalid-sbom", path = path)))]
+    InvalidSbom { path: String },
+    #[snafu(display("{}", t!("error-sbom-discrepancies", count = count)))]
+    SbomDiscrepancies { count: usize },
+    #[snafu(display("{}", t!("error-invalid-pack", path = path)))]
+    InvalidPack { path: String },
+    #[snafu(display("{}", t!("error-aborted")))]
+    Aborted,
+    #[snafu(display("{}", t!("error-no-manifest")))]
+    NoManifest,
+    #[snafu(display("{}", t!("error-undetected-license", file = file)))]
+    UndetectedLicense { file: String },
+    #[snafu(display("{}", t!("error-manifests-out-of-sync", count = count)))]
+    ManifestsOutOfSync { count: usize },
+    #[snafu(display("{}", t!("error-denied-dependencies", count = count)))]
+    DeniedDependencies { count: usize },
+}
+
+/// The exit codes of the failures scripts tell apart. Other failures
+/// exit with 1, and invalid arguments with 2 like every clap program.
+const NOT_FOUND_EXIT_CODE: i32 = 3;
+const ALREADY_EXISTS_EXIT_CODE: i32 = 4;
+const INVALID_EXPRESSION_EXIT_CODE: i32 = 5;
+const POLICY_VIOLATION_EXIT_CODE: i32 = 6;
+const NETWORK_EXIT_CODE: i32 = 7;
+
+/// The exit code used when the user aborts an interactive command,
+/// matching shells' convention for an interrupt.
+const ABORTED_EXIT_CODE: i32 = 130;
+
+/// Returns the process exit code for an error, from the first
+/// `Error` of its chain or, for failed requests, the network error
+/// beneath its context.
+pub(crate) fn exit_code(err: &anyhow::Error) -> i32 {
+    let lictool_error = err.chain().find_map(|cause| cause.downcast_ref::<Error>());
+    match lictool_error {
+        Some(Error::Aborted) => ABORTED_EXIT_CODE,
+        // The plugin decides how lictool exits.
+        Some(Error::PluginFailed {
+            code, ..
+        }) => *code,
+        Some(
+            Error::NotFound
+            | Error::FileNotFound {
+                ..
+            }
+            | Error::LicenseNotFound {
+                ..
+            }
+            | Error::UnknownLicenses {
+                ..
+            },
+        ) => NOT_FOUND_EXIT_CODE,
+        Some(Error::AlreadyExists {
+            ..
+        }) => ALREADY_EXISTS_EXIT_CODE,
+        Some(Error::InvalidExpression {
+            ..
+        }) => INVALID_EXPRESSION_EXIT_CODE,
+        Some(
+            Error::PolicyViolation {
+                ..
+            }
+            | Error::DeniedDependencies {
+                ..
+            },
+        ) => POLICY_VIOLATION_EXIT_CODE,
+        Some(Error::HttpStatus {
+            ..
+        }) => NETWORK_EXIT_CODE,
+        _ if err.chain().any(|cause| {
+            cause.is::<reqwest::Error>() || cause.is::<reqwest_middleware::Error>()
+        }) =>
+        {
+            NETWORK_EXIT_CODE
+        }
+        _ => 1,
+    }
 }
 
 pub(crate) fn display_error(err: &anyhow::Error) {
-    eprintln!("{}", cformat!("<red, bold>Error:</> {}", err));
+    // A failing plugin has reported its errors itself, and its exit
+    // code is passed on.
+    if matches!(
+        err.downcast_ref::<Error>(),
+        Some(Error::PluginFailed { .. })
+    ) {
+        return;
+    }
+    eprintln!(
+        "{}",
+        cformat!("<red, bold>{}</> {}", t!("error-prefix"), err)
+    );
     for cause in err.chain().skip(1) {
-        eprintln!("{}", cformat!("\n<bold>Caused by:</>"));
+        eprintln!("{}", cformat!("\n<bold>{}</>", t!("error-caused-by")));
         for line in cause.to_string().lines() {
             if line.is_empty() {
                 eprintln!();
@@ -23,3 +225,44 @@ pub(crate) fn display_error(err: &anyhow::Error) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use pretty_assertions::assert_eq;
+
+    use super::{exit_code, Error};
+
+    #[test]
+    fn test_exit_code() {
+        let code = |error: Error| exit_code(&anyhow::Error::from(error).context("Failed to add"));
+        assert_eq!(code(Error::NotFound), 3);
+        assert_eq!(
+            code(Error::AlreadyExists {
+                file: "LICENSE".to_string()
+            }),
+            4
+        );
+        assert_eq!(
+            code(Error::InvalidExpression {
+                expression: "MIT OR".to_string(),
+                reason: String::new()
+            }),
+            5
+        );
+        assert_eq!(code(Error::Aborted), 130);
+        assert_eq!(
+            code(Error::FileNotFound {
+                path: "LICENSE".to_string()
+            }),
+            3
+        );
+        // A missing executable or cache directory is no missing input.
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "git");
+        assert_eq!(
+            exit_code(&anyhow::Error::from(missing).context("Failed to run git")),
+            1
+        );
+        assert_eq!(exit_code(&anyhow!("disk full")), 1);
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use chrono::{Local, SecondsFormat};
+use serde::Serialize;
+
+/// Whether events are printed with `--log-json`.
+static LOG_JSON: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables printing events.
+pub(crate) fn set_log_json(log_json: bool) {
+    LOG_JSON.store(log_json, Ordering::Relaxed);
+}
+
+/// Checks whether events are printed.
+pub(crate) fn is_log_json() -> bool {
+    LOG_JSON.load(Ordering::Relaxed)
+}
+
+/// A step of a command reported to wrappers and dashboards.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub(crate) enum Event {
+    /// A request is sent to the network.
+    FetchStart { url: String },
+    /// A response was read from the HTTP cache or the imported
+    /// mirror, named by `source`.
+    CacheHit { url: String, source: &'static str },
+    /// A file was written or replaced.
+    FileWritten { path: String },
+    /// A check found a problem.
+    ViolationFound {
+        /// The check that found the problem, as named by the
+        /// `fix-json` format of `check`.
+        check: &'static str,
+        file: String,
+        /// The line of the finding, starting at 1, if it has one.
+        line: Option<usize>,
+        message: String,
+    },
+}
+
+/// An event along with the time it happened.
+#[derive(Serialize)]
+struct Record<'a> {
+    time: String,
+    #[serde(flatten)]
+    event: &'a Event,
+}
+
+/// Prints an event to stderr as a line of JSON with `--log-json`.
+pub(crate) fn emit(event: Event) {
+    if !is_log_json() {
+        return;
+    }
+    let record = Record {
+        time: Local::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        event: &event,
+    };
+    if let Ok(line) = serde_json::to_string(&record) {
+        eprintln!("{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::Event;
+
+    #[test]
+    fn test_event_format() {
+        let event = Event::ViolationFound {
+            check: "unfilled-placeholder",
+            file: "LICENSE".to_string(),
+            line: Some(3),
+            message: "[year]".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&event).unwrap(),
+            r#"{"event":"violation-found","check":"unfilled-placeholder","file":"LICENSE","line":3,"message":"[year]"}"#
+        );
+    }
+}
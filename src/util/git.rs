@@ -1,4 +1,13 @@
-use git2::Config;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Datelike};
+use git2::{Config, Index, Oid, Repository};
+
+use super::{errors::LictoolResult, remote::remote_repo_name};
 
 #[derive(Debug, Default)]
 pub(crate) struct GitConfig {
@@ -19,3 +28,236 @@ fn retrieve_git_config() -> Result<GitConfig, git2::Error> {
         email: config.get_string("user.email").unwrap_or_default(),
     })
 }
+
+/// Returns the working tree of the git repository containing `dir`,
+/// or `None` outside of one.
+pub(crate) fn repository_workdir(dir: &Path) -> Option<PathBuf> {
+    Repository::discover(dir)
+        .ok()?
+        .workdir()
+        .map(Path::to_path_buf)
+}
+
+/// Returns the URL of the `origin` remote of the repository
+/// containing `dir`, if it has one.
+pub(crate) fn remote_url(dir: &Path) -> Option<String> {
+    Repository::discover(dir)
+        .ok()?
+        .find_remote("origin")
+        .ok()?
+        .url()
+        .map(str::to_string)
+}
+
+/// Returns the name of the repository containing `dir`: the last
+/// segment of the URL of its `origin` remote, or else the name of its
+/// working tree.
+pub(crate) fn repository_name(dir: &Path) -> Option<String> {
+    remote_url(dir)
+        .as_deref()
+        .and_then(remote_repo_name)
+        .or_else(|| {
+            repository_workdir(dir)?
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+        })
+}
+
+/// Returns the year of the earliest commit reachable from `HEAD` in
+/// the repository containing `dir`, or `None` without commits.
+pub(crate) fn first_commit_year(dir: &Path) -> Option<i32> {
+    let repo = Repository::discover(dir).ok()?;
+    let mut revwalk = repo.revwalk().ok()?;
+    revwalk.push_head().ok()?;
+    let seconds = revwalk
+        .flatten()
+        .filter_map(|oid| repo.find_commit(oid).ok())
+        .map(|commit| commit.time().seconds())
+        .min()?;
+    DateTime::from_timestamp(seconds, 0).map(|time| time.year())
+}
+
+/// Returns the years of the oldest and newest commits that last
+/// changed a line of `path`, per `git blame`, or `None` if the file
+/// is not committed.
+pub(crate) fn file_commit_years(path: &Path) -> Option<(i32, i32)> {
+    let dir = path
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let repo = Repository::discover(dir).ok()?;
+    let workdir = fs::canonicalize(repo.workdir()?).ok()?;
+    let path = fs::canonicalize(path).ok()?;
+    let blame = repo
+        .blame_file(path.strip_prefix(&workdir).ok()?, None)
+        .ok()?;
+    let years: Vec<i32> = blame
+        .iter()
+        .filter_map(|hunk| DateTime::from_timestamp(hunk.final_signature().when().seconds(), 0))
+        .map(|time| time.year())
+        .collect();
+    Some((*years.iter().min()?, *years.iter().max()?))
+}
+
+/// A person who authored commits, as resolved by the `.mailmap`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Contributor {
+    pub(crate) name: String,
+    pub(crate) email: String,
+    pub(crate) commits: usize,
+}
+
+/// Lists the authors of the commits reachable from `HEAD` in the
+/// repository containing `dir`, most commits first.
+///
+/// Authors are resolved through the `.mailmap` of the repository and
+/// merged by email, so that one person committing under several names
+/// or addresses counts once.
+///
+/// # Errors
+/// - If no repository is found, it has no commits, or the history
+///   cannot be read.
+pub(crate) fn contributors(dir: &Path) -> LictoolResult<Vec<Contributor>> {
+    let repo = Repository::discover(dir)?;
+    let mailmap = repo.mailmap()?;
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+    let mut contributors: HashMap<String, Contributor> = HashMap::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        let author = commit.author_with_mailmap(&mailmap)?;
+        let name = author.name().unwrap_or_default().trim().to_string();
+        let email = author.email().unwrap_or_default().trim().to_string();
+        let key = if email.is_empty() {
+            name.clone()
+        } else {
+            email.to_lowercase()
+        };
+        contributors
+            .entry(key)
+            .or_insert(Contributor {
+                name,
+                email,
+                commits: 0,
+            })
+            .commits += 1;
+    }
+    let mut contributors: Vec<Contributor> = contributors.into_values().collect();
+    contributors.sort_by(|a, b| b.commits.cmp(&a.commits).then_with(|| a.name.cmp(&b.name)));
+    Ok(contributors)
+}
+
+/// Stages the given files and commits only them on top of `HEAD`, as
+/// `git commit --only` does, so that other staged changes stay staged
+/// rather than being swept into the commit.
+///
+/// The repository is discovered from the current directory, and the
+/// commit is authored with the identity from the git configuration.
+///
+/// # Errors
+/// - If no repository is found, a path lies outside of its working
+///   tree, or the commit cannot be created.
+pub(crate) fn commit_files(
+    paths: &[PathBuf],
+    message: &str,
+) -> LictoolResult<Oid> {
+    let repo = Repository::discover(".")?;
+    let workdir = fs::canonicalize(
+        repo.workdir()
+            .ok_or_else(|| git2::Error::from_str("cannot commit in a bare repository"))?,
+    )?;
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let mut index = repo.index()?;
+    // The tree of `HEAD` with only the given files changed.
+    let mut only = Index::new()?;
+    if let Some(parent) = &parent {
+        only.read_tree(&parent.tree()?)?;
+    }
+    for path in paths {
+        let path = fs::canonicalize(path)?;
+        let relative = path.strip_prefix(&workdir).map_err(|_| {
+            git2::Error::from_str(&format!("{} is outside of the repository", path.display()))
+        })?;
+        index.add_path(relative)?;
+        let entry = index
+            .get_path(relative, 0)
+            .ok_or_else(|| git2::Error::from_str("staged file missing from the index"))?;
+        only.add(&entry)?;
+    }
+    index.write()?;
+    let tree = repo.find_tree(only.write_tree_to(&repo)?)?;
+    let signature = repo.signature()?;
+    let parents: Vec<_> = parent.iter().collect();
+    Ok(repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use git2::{Repository, Signature};
+    use pretty_assertions::assert_eq;
+
+    use super::{contributors, repository_name};
+
+    #[test]
+    fn test_contributors() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(
+            dir.path().join(".mailmap"),
+            "Jane Doe <jane@example.com> <jdoe@old.example.com>\n",
+        )
+        .unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let mut parent = None;
+        for (name, email) in [
+            ("Jane Doe", "jane@example.com"),
+            ("jdoe", "jdoe@old.example.com"),
+            ("John Roe", "john@example.com"),
+        ] {
+            let signature = Signature::now(name, email).unwrap();
+            let parents: Vec<_> = parent.iter().collect();
+            let oid = repo
+                .commit(
+                    Some("HEAD"),
+                    &signature,
+                    &signature,
+                    "Commit",
+                    &tree,
+                    &parents,
+                )
+                .unwrap();
+            parent = Some(repo.find_commit(oid).unwrap());
+        }
+        let contributors = contributors(dir.path()).unwrap();
+        assert_eq!(
+            contributors
+                .iter()
+                .map(|contributor| (contributor.name.as_str(), contributor.commits))
+                .collect::<Vec<_>>(),
+            vec![("Jane Doe", 2), ("John Roe", 1)]
+        );
+    }
+
+    #[test]
+    fn test_repository_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let workdir = dir.path().join("fresh-project");
+        Repository::init(&workdir).unwrap();
+        assert_eq!(repository_name(&workdir).as_deref(), Some("fresh-project"));
+        let repo = Repository::open(&workdir).unwrap();
+        repo.remote("origin", "https://example.com/team/renamed.git")
+            .unwrap();
+        assert_eq!(repository_name(&workdir).as_deref(), Some("renamed"));
+    }
+}
@@ -0,0 +1,58 @@
+use std::{fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of recently used licenses that are remembered.
+const MAX_RECENT: usize = 5;
+
+/// The licenses recently chosen in `init` and `add`, most recent
+/// first.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct History {
+    pub(crate) recent: Vec<String>,
+}
+
+impl History {
+    /// Loads the history, falling back to an empty one if it is
+    /// missing or unreadable.
+    pub fn load() -> Self {
+        fs::read_to_string(history_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Moves the given license ID to the front of the history.
+    pub fn record(
+        &mut self,
+        license_id: &str,
+    ) -> &mut Self {
+        self.recent.retain(|id| id != license_id);
+        self.recent.insert(0, license_id.to_string());
+        self.recent.truncate(MAX_RECENT);
+        self
+    }
+
+    /// Persists the history to disk.
+    ///
+    /// # Errors
+    /// - If the data directory cannot be created or written to.
+    pub fn save(&self) -> io::Result<()> {
+        let path = history_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+}
+
+/// Returns the path to the history file.
+///
+/// # Panics
+/// - If the data directory cannot be found.
+fn history_path() -> PathBuf {
+    dirs::data_dir()
+        .expect("Data directory not found.")
+        .join(env!("CARGO_PKG_NAME"))
+        .join("history.json")
+}
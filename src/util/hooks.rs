@@ -0,0 +1,63 @@
+use std::{path::PathBuf, process::Command};
+
+use super::{
+    dry_run::is_dry_run,
+    errors::{Error, LictoolResult},
+};
+
+/// Runs the configured post-write hooks.
+///
+/// Each hook is a shell command that receives the written paths as
+/// additional arguments, so `git add` stages exactly the files that
+/// were just created. Nothing runs during a dry run, since no files
+/// were written.
+///
+/// # Errors
+/// - If a hook cannot be started or exits unsuccessfully.
+pub(crate) fn run_post_write_hooks(
+    hooks: &[String],
+    paths: &[PathBuf],
+) -> LictoolResult<()> {
+    if is_dry_run() {
+        return Ok(());
+    }
+    for hook in hooks {
+        let status = shell_command(hook, paths).status()?;
+        if !status.success() {
+            return Err(Error::HookFailed {
+                command: hook.clone(),
+                status: status.to_string(),
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Builds a shell invocation of `hook` with `paths` appended as
+/// arguments.
+#[cfg(unix)]
+fn shell_command(
+    hook: &str,
+    paths: &[PathBuf],
+) -> Command {
+    let mut command = Command::new("sh");
+    command
+        .arg("-c")
+        .arg(format!("{} \"$@\"", hook))
+        .arg(env!("CARGO_PKG_NAME"))
+        .args(paths);
+    command
+}
+
+/// Builds a shell invocation of `hook` with `paths` appended as
+/// arguments.
+#[cfg(not(unix))]
+fn shell_command(
+    hook: &str,
+    paths: &[PathBuf],
+) -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C").arg(hook).args(paths);
+    command
+}
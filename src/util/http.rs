@@ -0,0 +1,525 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use async_trait::async_trait;
+use http::Extensions;
+use http_cache_reqwest::{CACacheManager, Cache, CacheMode, HttpCache, HttpCacheOptions};
+use reqwest::{Certificate, Client, Proxy, Request, Response, StatusCode};
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, Middleware, Next};
+use serde::{de::DeserializeOwned, Deserialize};
+use tokio::sync::{Mutex, Semaphore};
+
+use super::{
+    cache::http_cache_dir,
+    errors::{Error, LictoolResult},
+    events::{emit, Event},
+    mirror::read_mirrored,
+};
+use crate::i18n::t;
+
+/// The default number of requests started per second.
+const DEFAULT_REQUESTS_PER_SECOND: f64 = 10.0;
+/// The default number of requests in flight at once.
+const DEFAULT_MAX_CONNECTIONS: usize = 4;
+/// The default number of seconds to wait for a connection.
+const DEFAULT_CONNECT_TIMEOUT: f64 = 10.0;
+/// The default number of seconds to wait for data once connected.
+const DEFAULT_READ_TIMEOUT: f64 = 30.0;
+/// The default number of retries of a request failing transiently.
+const DEFAULT_RETRIES: u32 = 3;
+/// The delay before the first retry, doubled for each further one.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// The user agent sent with every request, which the GitHub API
+/// requires.
+const USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+/// How long a connectivity probe waits for a response.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The header set by the cache layer to `HIT` when a response was
+/// served from the cache.
+const CACHE_STATUS_HEADER: &str = "x-cache";
+
+static CLIENT: OnceLock<ClientWithMiddleware> = OnceLock::new();
+static BASE_CLIENT: OnceLock<Client> = OnceLock::new();
+static LIMITS: OnceLock<HttpConfig> = OnceLock::new();
+
+/// Settings of outgoing requests, read from the `[http]` section of
+/// the configuration.
+///
+/// Responses served from the HTTP cache do not count against the
+/// limits.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct HttpConfig {
+    /// The maximum number of requests started per second.
+    pub(crate) requests_per_second: Option<f64>,
+    /// The maximum number of requests in flight at once.
+    pub(crate) max_connections: Option<usize>,
+    /// Seconds to wait for a connection to be established.
+    pub(crate) connect_timeout: Option<f64>,
+    /// Seconds to wait for data once connected.
+    pub(crate) read_timeout: Option<f64>,
+    /// How many times a request failing transiently, such as with a
+    /// timeout or a 503 status, is retried.
+    pub(crate) retries: Option<u32>,
+    /// The proxy requests go through, overriding `HTTPS_PROXY` and
+    /// the other proxy variables, which are honored otherwise.
+    pub(crate) proxy: Option<String>,
+    /// A PEM file of additional CA certificates to trust, such as
+    /// that of a TLS-intercepting corporate proxy.
+    pub(crate) ca_bundle: Option<PathBuf>,
+}
+
+/// Sets the settings applied by the shared client.
+///
+/// Only takes effect if called before the first request.
+///
+/// # Errors
+/// - If the proxy URL is invalid or the CA bundle cannot be loaded.
+pub(crate) fn configure(config: &HttpConfig) -> LictoolResult<()> {
+    let client = build_client(config)?;
+    let _ = BASE_CLIENT.set(client);
+    let _ = LIMITS.set(config.clone());
+    Ok(())
+}
+
+/// Builds a client with the proxy, CA certificates and timeouts of
+/// `config`.
+///
+/// # Errors
+/// - If the proxy URL is invalid or the CA bundle cannot be loaded.
+fn build_client(config: &HttpConfig) -> LictoolResult<Client> {
+    let seconds = |value: Option<f64>, default: f64| {
+        Duration::from_secs_f64(value.filter(|seconds| *seconds > 0.0).unwrap_or(default))
+    };
+    let mut builder = Client::builder()
+        .user_agent(USER_AGENT)
+        .connect_timeout(seconds(config.connect_timeout, DEFAULT_CONNECT_TIMEOUT))
+        .read_timeout(seconds(config.read_timeout, DEFAULT_READ_TIMEOUT));
+    if let Some(proxy) = &config.proxy {
+        builder = builder.proxy(Proxy::all(proxy).with_context(|| t!("error-proxy", url = proxy))?);
+    }
+    if let Some(path) = &config.ca_bundle {
+        let context = || t!("error-ca-bundle", path = path.display().to_string());
+        let pem = fs::read(path).with_context(context)?;
+        for certificate in Certificate::from_pem_bundle(&pem).with_context(context)? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    Ok(builder.build()?)
+}
+
+/// Returns the client without cache or rate limits, configured like
+/// the shared one.
+fn base_client() -> Client {
+    BASE_CLIENT
+        .get_or_init(|| build_client(&HttpConfig::default()).unwrap_or_default())
+        .clone()
+}
+
+/// Returns the HTTP client shared by all requests, building it on
+/// first use.
+///
+/// Sharing one client lets requests reuse connections and TLS
+/// sessions, and responses go through the on-disk HTTP cache.
+pub(crate) fn client() -> &'static ClientWithMiddleware {
+    CLIENT.get_or_init(|| {
+        let limits = LIMITS.get().cloned().unwrap_or_default();
+        ClientBuilder::new(base_client())
+            .with(Cache(HttpCache {
+                mode: CacheMode::Default,
+                manager: CACacheManager {
+                    path: http_cache_dir(),
+                },
+                options: HttpCacheOptions::default(),
+            }))
+            .with(Retry {
+                retries: limits.retries.unwrap_or(DEFAULT_RETRIES),
+                base_delay: RETRY_BASE_DELAY,
+            })
+            .with(RateLimit::new(&limits))
+            .build()
+    })
+}
+
+/// Fetches `url` through the shared client and decodes the JSON
+/// response, reading it from the imported mirror instead when it
+/// holds the URL.
+///
+/// # Errors
+/// - If the request fails, naming the URL.
+/// - If the server responds with an error status.
+/// - If the body cannot be decoded, naming the URL, the status and
+///   whether the response came from the cache.
+pub(crate) async fn get_json<T: DeserializeOwned>(url: &str) -> LictoolResult<T> {
+    get_json_with(url, |body| serde_json::from_slice(body)).await
+}
+
+/// Like `get_json`, but decodes the body with `decode`, which may
+/// borrow from it rather than copy every string out of it.
+///
+/// # Errors
+/// - The errors of `get_json`.
+pub(crate) async fn get_json_with<T>(
+    url: &str,
+    decode: impl FnOnce(&[u8]) -> serde_json::Result<T>,
+) -> LictoolResult<T> {
+    if let Some(body) = read_mirrored(url)? {
+        return decode_mirrored_with(url, &body, decode);
+    }
+    let response = send(url).await?;
+    decode_json_with(url, response, decode).await
+}
+
+/// Like `get_json`, but revalidates a cached response with the
+/// server even while it is fresh, so that the cache ends up holding
+/// the current version.
+///
+/// # Errors
+/// - The errors of `get_json`.
+pub(crate) async fn revalidate_json<T: DeserializeOwned>(url: &str) -> LictoolResult<T> {
+    emit(Event::FetchStart {
+        url: url.to_string(),
+    });
+    let response = client()
+        .get(url)
+        .with_extension(CacheMode::NoCache)
+        .send()
+        .await
+        .with_context(|| t!("error-request", url = url))?;
+    decode_json(url, response).await
+}
+
+/// Like `get_json`, but only reads the imported mirror and the cache,
+/// never the network, even if the cached response is stale.
+///
+/// # Errors
+/// - The errors of `get_json`, with a 504 status if `url` is not
+///   cached.
+pub(crate) async fn get_cached_json<T: DeserializeOwned>(url: &str) -> LictoolResult<T> {
+    if let Some(body) = read_mirrored(url)? {
+        return decode_mirrored(url, &body);
+    }
+    let response = client()
+        .get(url)
+        .with_extension(CacheMode::OnlyIfCached)
+        .send()
+        .await
+        .with_context(|| t!("error-request", url = url))?;
+    decode_json(url, response).await
+}
+
+/// Like `get_json`, but returns `None` when the server responds with
+/// 404 Not Found.
+pub(crate) async fn get_optional_json<T: DeserializeOwned>(url: &str) -> LictoolResult<Option<T>> {
+    if let Some(body) = read_mirrored(url)? {
+        return decode_mirrored(url, &body).map(Some);
+    }
+    let response = send(url).await?;
+    if response.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    decode_json(url, response).await.map(Some)
+}
+
+/// Sends a HEAD request to `url` through the shared client.
+///
+/// # Returns
+///
+/// The status of the response.
+///
+/// # Errors
+/// - If the request fails, naming the URL.
+pub(crate) async fn head(url: &str) -> LictoolResult<StatusCode> {
+    let response = client()
+        .head(url)
+        .send()
+        .await
+        .with_context(|| t!("error-request", url = url))?;
+    Ok(response.status())
+}
+
+/// Sends a HEAD request to `url` directly, bypassing the cache and
+/// the rate limits, to check that the server can be reached.
+///
+/// # Returns
+///
+/// The status of the response and how long it took.
+///
+/// # Errors
+/// - If the request fails or times out, naming the URL.
+pub(crate) async fn probe(url: &str) -> LictoolResult<(StatusCode, Duration)> {
+    let start = Instant::now();
+    let response = base_client()
+        .head(url)
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+        .with_context(|| t!("error-request", url = url))?;
+    Ok((response.status(), start.elapsed()))
+}
+
+/// Downloads the body of `url` directly, bypassing the cache so that
+/// large files such as release binaries are not stored in it.
+///
+/// # Errors
+/// - If the request fails, naming the URL.
+/// - If the server responds with an error status.
+pub(crate) async fn download(url: &str) -> LictoolResult<Vec<u8>> {
+    let response = base_client()
+        .get(url)
+        .send()
+        .await
+        .with_context(|| t!("error-request", url = url))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(Error::HttpStatus {
+            url: url.to_string(),
+            status: status.to_string(),
+            origin: t!("http-origin-network"),
+        }
+        .into());
+    }
+    let body = response
+        .bytes()
+        .await
+        .with_context(|| t!("error-request", url = url))?;
+    Ok(body.to_vec())
+}
+
+/// Sends a GET request to `url` through the shared client.
+async fn send(url: &str) -> LictoolResult<Response> {
+    emit(Event::FetchStart {
+        url: url.to_string(),
+    });
+    client()
+        .get(url)
+        .send()
+        .await
+        .with_context(|| t!("error-request", url = url))
+}
+
+/// Checks the status of a response and decodes its JSON body.
+async fn decode_json<T: DeserializeOwned>(
+    url: &str,
+    response: Response,
+) -> LictoolResult<T> {
+    decode_json_with(url, response, |body| serde_json::from_slice(body)).await
+}
+
+/// Like `decode_json`, decoding the body with `decode`.
+async fn decode_json_with<T>(
+    url: &str,
+    response: Response,
+    decode: impl FnOnce(&[u8]) -> serde_json::Result<T>,
+) -> LictoolResult<T> {
+    let status = response.status();
+    let origin = if response
+        .headers()
+        .get(CACHE_STATUS_HEADER)
+        .is_some_and(|value| value == "HIT")
+    {
+        emit(Event::CacheHit {
+            url: url.to_string(),
+            source: "http-cache",
+        });
+        t!("http-origin-cache")
+    } else {
+        t!("http-origin-network")
+    };
+    if !status.is_success() {
+        return Err(Error::HttpStatus {
+            url: url.to_string(),
+            status: status.to_string(),
+            origin,
+        }
+        .into());
+    }
+    let body = response
+        .bytes()
+        .await
+        .with_context(|| t!("error-request", url = url))?;
+    decode(&body).with_context(|| {
+        t!(
+            "error-decode-response",
+            url = url,
+            status = status,
+            origin = origin
+        )
+    })
+}
+
+/// Decodes a JSON response read from the imported mirror.
+fn decode_mirrored<T: DeserializeOwned>(
+    url: &str,
+    body: &[u8],
+) -> LictoolResult<T> {
+    decode_mirrored_with(url, body, |body| serde_json::from_slice(body))
+}
+
+/// Like `decode_mirrored`, decoding the body with `decode`.
+fn decode_mirrored_with<T>(
+    url: &str,
+    body: &[u8],
+    decode: impl FnOnce(&[u8]) -> serde_json::Result<T>,
+) -> LictoolResult<T> {
+    emit(Event::CacheHit {
+        url: url.to_string(),
+        source: "mirror",
+    });
+    decode(body).with_context(|| {
+        t!(
+            "error-decode-response",
+            url = url,
+            status = StatusCode::OK.to_string(),
+            origin = t!("http-origin-mirror")
+        )
+    })
+}
+
+/// Middleware spacing out requests and capping how many are in
+/// flight, so bulk fetches stay polite towards spdx.org and mirrors.
+struct RateLimit {
+    /// The minimum delay between the start of two requests.
+    interval: Duration,
+    /// The earliest time the next request may start.
+    next_start: Mutex<Instant>,
+    /// Permits for the requests in flight.
+    connections: Semaphore,
+}
+
+impl RateLimit {
+    fn new(config: &HttpConfig) -> Self {
+        let per_second = config
+            .requests_per_second
+            .filter(|rate| *rate > 0.0)
+            .unwrap_or(DEFAULT_REQUESTS_PER_SECOND);
+        Self {
+            interval: Duration::from_secs_f64(1.0 / per_second),
+            next_start: Mutex::new(Instant::now()),
+            connections: Semaphore::new(
+                config
+                    .max_connections
+                    .unwrap_or(DEFAULT_MAX_CONNECTIONS)
+                    .max(1),
+            ),
+        }
+    }
+}
+
+#[async_trait]
+impl Middleware for RateLimit {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let _permit = self
+            .connections
+            .acquire()
+            .await
+            .expect("the semaphore is never closed");
+        let start = {
+            let mut next_start = self.next_start.lock().await;
+            let start = (*next_start).max(Instant::now());
+            *next_start = start + self.interval;
+            start
+        };
+        tokio::time::sleep_until(start.into()).await;
+        next.run(req, extensions).await
+    }
+}
+
+/// Middleware retrying requests that fail transiently, waiting twice
+/// as long before each further attempt.
+struct Retry {
+    /// How many times a request is retried at most.
+    retries: u32,
+    /// The delay before the first retry.
+    base_delay: Duration,
+}
+
+#[async_trait]
+impl Middleware for Retry {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let mut attempt = 0;
+        loop {
+            // Requests with streamed bodies cannot be sent again.
+            let Some(request) = req.try_clone() else {
+                return next.run(req, extensions).await;
+            };
+            let result = next.clone().run(request, extensions).await;
+            if attempt >= self.retries || !is_transient(&result) {
+                return result;
+            }
+            tokio::time::sleep(self.base_delay * 2u32.pow(attempt)).await;
+            attempt += 1;
+        }
+    }
+}
+
+/// Checks whether a request failed in a way that may not recur, such
+/// as a timeout, a refused connection, or a 429 or 5xx status.
+fn is_transient(result: &reqwest_middleware::Result<Response>) -> bool {
+    match result {
+        Ok(response) => {
+            response.status() == StatusCode::TOO_MANY_REQUESTS
+                || response.status().is_server_error()
+        }
+        Err(reqwest_middleware::Error::Reqwest(err)) => err.is_timeout() || err.is_connect(),
+        Err(reqwest_middleware::Error::Middleware(_)) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reqwest::{Client, StatusCode};
+    use reqwest_middleware::ClientBuilder;
+
+    use super::Retry;
+
+    #[tokio::test]
+    async fn test_retry_transient_failures() {
+        let mut server = mockito::Server::new_async().await;
+        let unavailable = server
+            .mock("GET", "/licenses.json")
+            .with_status(503)
+            .expect(3)
+            .create_async()
+            .await;
+        let missing = server
+            .mock("GET", "/missing.json")
+            .with_status(404)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = ClientBuilder::new(Client::new())
+            .with(Retry {
+                retries: 2,
+                base_delay: Duration::from_millis(1),
+            })
+            .build();
+        let get = |path: &str| client.get(format!("{}{}", server.url(), path)).send();
+        let response = get("/licenses.json").await.unwrap();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let response = get("/missing.json").await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        unavailable.assert_async().await;
+        missing.assert_async().await;
+    }
+}
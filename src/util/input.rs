@@ -0,0 +1,55 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::Path,
+};
+
+use anyhow::Context;
+
+use crate::{
+    i18n::t,
+    util::errors::{Error, LictoolResult},
+};
+
+/// The path standing for the standard input, as in `lictool check
+/// --drift --path -`.
+pub(crate) const STDIN_PATH: &str = "-";
+
+/// Reads text from a file, or from the standard input if `path` is
+/// `-`, so that license text can be pasted or piped in.
+///
+/// # Errors
+/// - `Error::FileNotFound` if the file does not exist.
+/// - If the file or the standard input cannot be read, or is not
+///   valid UTF-8.
+pub(crate) fn read_input(path: &str) -> LictoolResult<String> {
+    if path == STDIN_PATH {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .with_context(|| t!("error-read-file", path = path))?;
+        Ok(text)
+    } else {
+        read_file(Path::new(path))
+    }
+}
+
+/// Reads a file the user named, telling a missing file apart from
+/// other errors.
+///
+/// # Errors
+/// - `Error::FileNotFound` if the file does not exist.
+/// - If the file cannot be read or is not valid UTF-8.
+pub(crate) fn read_file(path: &Path) -> LictoolResult<String> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(text),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            Err(Error::FileNotFound {
+                path: path.display().to_string(),
+            })?
+        }
+        Err(err) => {
+            Err(err).with_context(|| t!("error-read-file", path = path.display().to_string()))?
+        }
+    }
+}
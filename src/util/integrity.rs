@@ -0,0 +1,59 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use sha2::{Digest, Sha256};
+
+/// The file listing the digests of a license dataset, in the format
+/// of `sha256sum`.
+pub(crate) const CHECKSUMS_FILE: &str = "SHA256SUMS";
+
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether license data failing its checksum is refused rather
+/// than warned about.
+pub(crate) fn set_strict(strict: bool) {
+    STRICT.store(strict, Ordering::Relaxed);
+}
+
+/// Checks whether license data failing its checksum is refused.
+pub(crate) fn is_strict() -> bool {
+    STRICT.load(Ordering::Relaxed)
+}
+
+/// Returns the SHA-256 digest of `bytes` as lowercase hex.
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Lists the digests of files as `<hex digest>  <path>` lines, with
+/// `/` separating the components of the paths.
+pub(crate) fn format_checksums<'a>(
+    files: impl IntoIterator<Item = (&'a PathBuf, &'a Vec<u8>)>
+) -> String {
+    files
+        .into_iter()
+        .map(|(path, contents)| format!("{}  {}\n", sha256_hex(contents), slash_path(path)))
+        .collect()
+}
+
+/// Reads the digests of a checksum file by path, skipping malformed
+/// lines.
+pub(crate) fn parse_checksums(text: &str) -> BTreeMap<PathBuf, String> {
+    text.lines()
+        .filter_map(|line| {
+            let (digest, path) = line.split_once("  ")?;
+            Some((PathBuf::from(path), digest.to_lowercase()))
+        })
+        .collect()
+}
+
+/// Joins the components of a relative path with `/`.
+fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,404 @@
+use std::{
+    env,
+    fs::{self, OpenOptions, Permissions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock, PoisonError},
+};
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+use super::events::{emit, Event};
+
+/// The maximum number of operations that can be undone.
+const MAX_OPERATIONS: usize = 20;
+
+/// The id of the operation of this process, set on its first write.
+static OPERATION_ID: OnceLock<String> = OnceLock::new();
+
+/// The journal of this process, loaded on its first write and saved
+/// by `flush` once the command is done, so that commands writing many
+/// files read and write it only once.
+static JOURNAL: Mutex<Option<Journal>> = Mutex::new(None);
+
+/// The operations that wrote files, oldest first.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct Journal {
+    pub(crate) operations: Vec<Operation>,
+}
+
+/// A command run that wrote files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Operation {
+    /// Identifies the process that ran the command.
+    pub(crate) id: String,
+    /// The command line, such as `lictool add MIT`.
+    pub(crate) command: String,
+    /// When the command ran, in local time.
+    pub(crate) time: String,
+    pub(crate) files: Vec<FileChange>,
+}
+
+/// A file written by an operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct FileChange {
+    /// The absolute path of the file.
+    pub(crate) path: PathBuf,
+    /// The hash of the previous contents, backed up under that name,
+    /// or `None` if the operation created the file.
+    pub(crate) previous: Option<String>,
+    /// The hash of the written contents, telling whether the file was
+    /// changed since, or `None` if the operation removed the file.
+    pub(crate) written: Option<String>,
+}
+
+impl Journal {
+    /// Loads the journal, falling back to an empty one if it is
+    /// missing or unreadable.
+    pub fn load() -> Self {
+        fs::read_to_string(journal_dir().join("journal.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persists the journal to disk, dropping the backups of
+    /// operations too old to be undone.
+    ///
+    /// # Errors
+    /// - If the data directory cannot be created or written to.
+    pub fn save(&mut self) -> io::Result<()> {
+        let excess = self.operations.len().saturating_sub(MAX_OPERATIONS);
+        let dropped: Vec<Operation> = self.operations.drain(..excess).collect();
+        let dir = journal_dir();
+        fs::create_dir_all(dir.join("backups"))?;
+        fs::write(dir.join("journal.json"), serde_json::to_string(self)?)?;
+        dropped
+            .iter()
+            .for_each(|operation| self.discard_backups(operation));
+        Ok(())
+    }
+
+    /// Removes the backups of an operation no longer in the journal,
+    /// unless another operation shares them.
+    pub fn discard_backups(
+        &self,
+        operation: &Operation,
+    ) {
+        for hash in operation
+            .files
+            .iter()
+            .filter_map(|file| file.previous.as_ref())
+        {
+            if !self.references(hash) {
+                let _ = fs::remove_file(backup_path(hash));
+            }
+        }
+    }
+
+    /// Checks whether any operation still needs the given backup.
+    fn references(
+        &self,
+        hash: &str,
+    ) -> bool {
+        self.operations
+            .iter()
+            .flat_map(|operation| &operation.files)
+            .any(|file| file.previous.as_deref() == Some(hash))
+    }
+
+    /// Records that this process is about to write `contents` to
+    /// `path`, or remove it if `None`, backing up the current
+    /// contents.
+    ///
+    /// Only the first write of a file per operation is recorded, so
+    /// undoing restores the contents from before the command ran.
+    ///
+    /// # Errors
+    /// - If the current contents cannot be backed up.
+    pub fn record(
+        &mut self,
+        path: &Path,
+        contents: Option<&[u8]>,
+    ) -> io::Result<()> {
+        let path = env::current_dir()?.join(path.strip_prefix(".").unwrap_or(path));
+        let written = contents.map(hash);
+        let id = OPERATION_ID.get_or_init(|| {
+            format!(
+                "{}-{}",
+                std::process::id(),
+                Local::now().timestamp_nanos_opt().unwrap_or_default()
+            )
+        });
+        if let Some(file) = self
+            .operations
+            .last_mut()
+            .filter(|operation| operation.id == *id)
+            .and_then(|operation| operation.files.iter_mut().find(|file| file.path == path))
+        {
+            file.written = written;
+            return Ok(());
+        }
+        let previous = match fs::read(&path) {
+            Ok(current) => {
+                let hash = hash(&current);
+                fs::create_dir_all(journal_dir().join("backups"))?;
+                fs::write(backup_path(&hash), current)?;
+                Some(hash)
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+            Err(error) => return Err(error),
+        };
+        if self.operations.last().map(|operation| &operation.id) != Some(id) {
+            self.operations.push(Operation {
+                id: id.clone(),
+                command: [env!("CARGO_PKG_NAME").to_string()]
+                    .into_iter()
+                    .chain(env::args().skip(1))
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                time: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                files: Vec::new(),
+            });
+        }
+        self.operations
+            .last_mut()
+            .expect("an operation was pushed")
+            .files
+            .push(FileChange {
+                path,
+                previous,
+                written,
+            });
+        Ok(())
+    }
+}
+
+impl FileChange {
+    /// Checks whether the file still has the contents the operation
+    /// wrote.
+    pub(crate) fn is_unchanged(&self) -> bool {
+        match &self.written {
+            Some(written) => fs::read(&self.path).is_ok_and(|current| hash(&current) == *written),
+            None => !self.path.exists(),
+        }
+    }
+
+    /// Restores the contents the file had before the operation,
+    /// removing it if the operation created it.
+    ///
+    /// # Errors
+    /// - If the backup cannot be read or the file cannot be written.
+    pub(crate) fn revert(&self) -> io::Result<()> {
+        match &self.previous {
+            Some(hash) => fs::write(&self.path, fs::read(backup_path(hash))?),
+            None => {
+                match fs::remove_file(&self.path) {
+                    Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Records that this process is about to write `contents` to `path`,
+/// or remove it if `None`, in its journal.
+///
+/// # Errors
+/// - If the current contents cannot be backed up.
+fn record(
+    path: &Path,
+    contents: Option<&[u8]>,
+) -> io::Result<()> {
+    JOURNAL
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get_or_insert_with(Journal::load)
+        .record(path, contents)
+}
+
+/// Saves the changes recorded by this process to the journal, once
+/// its command is done, however it ended.
+///
+/// # Errors
+/// - If the data directory cannot be created or written to.
+pub(crate) fn flush() -> io::Result<()> {
+    let journal = JOURNAL
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    match journal {
+        Some(mut journal) => journal.save(),
+        None => Ok(()),
+    }
+}
+
+/// Writes a file, recording the change in the journal so that `undo`
+/// can revert it.
+///
+/// Writes are refused if the backup fails, since the change could
+/// otherwise not be undone.
+///
+/// # Errors
+/// - If the file cannot be backed up or written.
+pub(crate) fn write<C: AsRef<[u8]>>(
+    path: &Path,
+    contents: C,
+) -> io::Result<()> {
+    write_with_permissions(path, contents, None)
+}
+
+/// Writes a file like `write`, giving it `permissions` from the
+/// start.
+///
+/// The contents go to a temporary file created with the permissions,
+/// which then replaces the file, so that they are never readable
+/// with looser ones, such as those of the umask.
+///
+/// # Errors
+/// - If the file cannot be backed up or written.
+pub(crate) fn write_with_permissions<C: AsRef<[u8]>>(
+    path: &Path,
+    contents: C,
+    permissions: Option<Permissions>,
+) -> io::Result<()> {
+    record(path, Some(contents.as_ref()))?;
+    match permissions {
+        Some(permissions) => replace_file(path, contents.as_ref(), permissions)?,
+        None => fs::write(path, contents)?,
+    }
+    emit(Event::FileWritten {
+        path: path.display().to_string(),
+    });
+    Ok(())
+}
+
+/// Replaces a file with `contents` through a temporary file next to
+/// it that has `permissions` from its creation.
+fn replace_file(
+    path: &Path,
+    contents: &[u8],
+    permissions: Permissions,
+) -> io::Result<()> {
+    // A symbolic link keeps pointing at the replaced file.
+    let path = &fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(".tmp");
+    let temporary = path.with_file_name(format!(".{}", name.to_string_lossy()));
+    let _ = fs::remove_file(&temporary);
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(permissions.mode());
+    }
+    let result = options.open(&temporary).and_then(|mut file| {
+        file.write_all(contents)?;
+        // The umask may have left out some of the permissions.
+        file.set_permissions(permissions)?;
+        drop(file);
+        fs::rename(&temporary, path)
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    result
+}
+
+/// Renames a file, recording the removal of `from` and the creation
+/// of `to` in the journal so that `undo` can revert it.
+///
+/// Like writes, renames are refused if the backup fails.
+///
+/// # Errors
+/// - If the file cannot be backed up or renamed.
+pub(crate) fn rename(
+    from: &Path,
+    to: &Path,
+) -> io::Result<()> {
+    let contents = fs::read(from)?;
+    record(from, None)?;
+    record(to, Some(&contents))?;
+    fs::rename(from, to)?;
+    emit(Event::FileWritten {
+        path: to.display().to_string(),
+    });
+    Ok(())
+}
+
+/// Removes a file, backing it up in the journal so that `undo` can
+/// restore it.
+///
+/// Removals are refused if the backup fails, since the contents would
+/// otherwise be lost.
+///
+/// # Errors
+/// - If the file cannot be backed up or removed.
+pub(crate) fn remove(path: &Path) -> io::Result<()> {
+    record(path, None)?;
+    fs::remove_file(path)
+}
+
+/// Hashes file contents with 64-bit FNV-1a, which is stable across
+/// releases unlike the hasher of the standard library.
+fn hash(contents: &[u8]) -> String {
+    let hash = contents
+        .iter()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+        });
+    format!("{:016x}", hash)
+}
+
+/// Returns the file backing up contents with the given hash.
+fn backup_path(hash: &str) -> PathBuf {
+    journal_dir().join("backups").join(hash)
+}
+
+/// Returns the directory of the journal and its backups.
+///
+/// # Panics
+/// - If the data directory cannot be found.
+fn journal_dir() -> PathBuf {
+    dirs::data_dir()
+        .expect("Data directory not found.")
+        .join(env!("CARGO_PKG_NAME"))
+        .join("journal")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{hash, replace_file};
+
+    #[test]
+    fn test_hash() {
+        assert_eq!(hash(b""), "cbf29ce484222325");
+        assert_eq!(hash(b"a"), "af63dc4c8601ec8c");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_replace_file() {
+        use std::{
+            fs::{self, Permissions},
+            os::unix::fs::PermissionsExt,
+        };
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("LICENSE");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, Permissions::from_mode(0o644)).unwrap();
+        replace_file(&path, b"new", Permissions::from_mode(0o600)).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
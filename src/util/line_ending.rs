@@ -0,0 +1,159 @@
+use std::{fs, path::Path};
+
+use clap::ValueEnum;
+
+/// The maximum number of neighbouring files inspected when detecting
+/// the line endings used by a project.
+const MAX_SAMPLED_FILES: usize = 20;
+
+/// The line ending convention used when writing files.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub(crate) enum LineEnding {
+    /// Unix line endings (`\n`).
+    Lf,
+    /// Windows line endings (`\r\n`).
+    Crlf,
+    /// The convention of the current platform.
+    Native,
+}
+
+impl LineEnding {
+    /// Detects the line endings to use for a file written at
+    /// `target`.
+    ///
+    /// An `eol` attribute in a `.gitattributes` file applying to the
+    /// target wins; otherwise the endings of existing files in the
+    /// target's directory are used, falling back to `Lf`.
+    pub fn detect(target: &Path) -> Self {
+        gitattributes_eol(target)
+            .or_else(|| neighbours_eol(target))
+            .unwrap_or(LineEnding::Lf)
+    }
+
+    /// Converts all line endings in `text` to this convention.
+    pub fn apply(
+        &self,
+        text: &str,
+    ) -> String {
+        let normalized = text.replace("\r\n", "\n");
+        if self.is_crlf() {
+            normalized.replace('\n', "\r\n")
+        } else {
+            normalized
+        }
+    }
+
+    /// Checks whether this convention resolves to `\r\n`.
+    fn is_crlf(&self) -> bool {
+        match self {
+            LineEnding::Lf => false,
+            LineEnding::Crlf => true,
+            LineEnding::Native => cfg!(windows),
+        }
+    }
+}
+
+/// Looks up the `eol` attribute applying to `target` in the
+/// `.gitattributes` files between its directory and the repository
+/// root.
+fn gitattributes_eol(target: &Path) -> Option<LineEnding> {
+    let file_name = target.file_name()?.to_string_lossy();
+    let dir = target.parent().filter(|dir| !dir.as_os_str().is_empty());
+    let dir = fs::canonicalize(dir.unwrap_or(Path::new("."))).ok()?;
+    for ancestor in dir.ancestors() {
+        if let Ok(content) = fs::read_to_string(ancestor.join(".gitattributes")) {
+            // Later lines override earlier ones, as in git.
+            let eol = content.lines().rev().find_map(|line| {
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next().filter(|pattern| !pattern.starts_with('#'))?;
+                if !glob_matches(pattern.trim_start_matches('/'), &file_name) {
+                    return None;
+                }
+                parts.find_map(|attr| {
+                    match attr {
+                        "eol=crlf" => Some(LineEnding::Crlf),
+                        "eol=lf" => Some(LineEnding::Lf),
+                        _ => None,
+                    }
+                })
+            });
+            if eol.is_some() {
+                return eol;
+            }
+        }
+        if ancestor.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// Infers the line endings from existing text files next to `target`.
+fn neighbours_eol(target: &Path) -> Option<LineEnding> {
+    let dir = target.parent().filter(|dir| !dir.as_os_str().is_empty());
+    let entries = fs::read_dir(dir.unwrap_or(Path::new("."))).ok()?;
+    let (mut crlf, mut lf) = (0, 0);
+    for entry in entries
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()))
+        .take(MAX_SAMPLED_FILES)
+    {
+        let Ok(content) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        if content.contains("\r\n") {
+            crlf += 1;
+        } else if content.contains('\n') {
+            lf += 1;
+        }
+    }
+    match (crlf, lf) {
+        (0, 0) => None,
+        (crlf, lf) if crlf > lf => Some(LineEnding::Crlf),
+        _ => Some(LineEnding::Lf),
+    }
+}
+
+/// Matches a name against a pattern, such as a gitattributes one,
+/// supporting `*` wildcards.
+pub(crate) fn glob_matches(
+    pattern: &str,
+    name: &str,
+) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => {
+            let Some(remaining) = name.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=remaining.len())
+                .filter(|&idx| remaining.is_char_boundary(idx))
+                .any(|idx| glob_matches(rest, &remaining[idx..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{glob_matches, LineEnding};
+
+    #[test]
+    fn test_apply_line_endings() {
+        let text = "MIT License\r\n\nCopyright\n";
+        assert_eq!(LineEnding::Lf.apply(text), "MIT License\n\nCopyright\n");
+        assert_eq!(
+            LineEnding::Crlf.apply(text),
+            "MIT License\r\n\r\nCopyright\r\n"
+        );
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("*", "LICENSE.md"));
+        assert!(glob_matches("*.md", "LICENSE.md"));
+        assert!(glob_matches("LICENSE*", "LICENSE-MIT"));
+        assert!(!glob_matches("*.txt", "LICENSE.md"));
+    }
+}
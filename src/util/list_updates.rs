@@ -0,0 +1,126 @@
+use std::{fs, io, path::PathBuf, time::Duration};
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tokio::time::timeout;
+
+use crate::{
+    spdx::{fetch_upstream_list_version, Licenses},
+    update::is_newer,
+};
+
+/// The minimum number of seconds between two checks for a newer list.
+const CHECK_INTERVAL_SECS: i64 = 24 * 60 * 60;
+
+/// How long the upstream and the cached list may take to arrive
+/// before the check is given up, so an unreachable spdx.org does not
+/// hold up commands.
+const CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// When the upstream license list was last checked for a newer
+/// release.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ListCheck {
+    /// The Unix time of the last check.
+    pub(crate) checked_at: Option<i64>,
+}
+
+/// A newer SPDX license list than the cached one.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ListUpdate {
+    pub(crate) cached: String,
+    pub(crate) upstream: String,
+}
+
+impl ListCheck {
+    /// Loads the time of the last check, falling back to none if it
+    /// is missing or unreadable.
+    pub fn load() -> Self {
+        fs::read_to_string(list_check_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Checks whether a day has passed since the last check.
+    pub fn is_due(
+        &self,
+        now: i64,
+    ) -> bool {
+        self.checked_at
+            .is_none_or(|checked_at| now - checked_at >= CHECK_INTERVAL_SECS)
+    }
+
+    /// Persists the time of the last check.
+    ///
+    /// # Errors
+    /// - If the data directory cannot be created or written to.
+    pub fn save(&self) -> io::Result<()> {
+        let path = list_check_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)
+    }
+}
+
+/// Compares the cached SPDX license list with the one of the
+/// configured source, at most once a day.
+///
+/// Every failure is silent, since the hint is only a courtesy.
+///
+/// # Returns
+///
+/// The newer list release, or `None` if there is none, the check is
+/// not due, or it failed.
+pub(crate) async fn check_list_update() -> Option<ListUpdate> {
+    let now = Utc::now().timestamp();
+    let mut check = ListCheck::load();
+    if !check.is_due(now) {
+        return None;
+    }
+    // Recorded up front, so that an offline machine is not retried on
+    // every command.
+    check.checked_at = Some(now);
+    let _ = check.save();
+    // Loading the cached list may revalidate it over the network, so
+    // it counts against the timeout too. GitHub has no releases to
+    // compare, so it is never waited on.
+    let (upstream, cached) = timeout(CHECK_TIMEOUT, async {
+        let upstream = fetch_upstream_list_version().await.ok()??;
+        let cached = Licenses::new().await.ok()?.version;
+        Some((upstream, cached))
+    })
+    .await
+    .ok()??;
+    is_newer(&upstream, &cached).then_some(ListUpdate {
+        cached,
+        upstream,
+    })
+}
+
+/// Returns the path to the file recording the last check.
+///
+/// # Panics
+/// - If the data directory cannot be found.
+fn list_check_path() -> PathBuf {
+    dirs::data_dir()
+        .expect("Data directory not found.")
+        .join(env!("CARGO_PKG_NAME"))
+        .join("list-check.json")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ListCheck;
+
+    #[test]
+    fn test_list_check_interval() {
+        assert!(ListCheck::default().is_due(0));
+        let check = ListCheck {
+            checked_at: Some(1_000_000),
+        };
+        assert!(!check.is_due(1_000_000 + 60));
+        assert!(check.is_due(1_000_000 + 24 * 60 * 60));
+    }
+}
@@ -0,0 +1,104 @@
+use clap::Args;
+
+/// Quotation marks treated as a plain double quote.
+const QUOTES: [char; 12] = ['\'', '‘', '’', '‚', '‛', '“', '”', '„', '‟', '«', '»', '`'];
+
+/// Dashes and hyphens treated as a plain hyphen.
+const DASHES: [char; 7] = ['‐', '‑', '‒', '–', '—', '―', '−'];
+
+/// Punctuation that whitespace before it does not change.
+const CLOSING_PUNCTUATION: [char; 6] = ['.', ',', ';', ':', ')', ']'];
+
+/// Differences ignored when comparing a license file against the
+/// canonical text, following the SPDX matching guidelines.
+#[derive(Args, Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct MatchNormalization {
+    /// Treat all quotation marks, straight or curly, as equivalent
+    #[arg(long)]
+    pub(crate) normalize_quotes: bool,
+    /// Treat hyphens, en dashes and em dashes as equivalent
+    #[arg(long)]
+    pub(crate) normalize_dashes: bool,
+    /// Ignore whitespace before punctuation and after opening
+    /// parentheses
+    #[arg(long)]
+    pub(crate) normalize_whitespace: bool,
+    /// Compare without regard to letter case
+    #[arg(long)]
+    pub(crate) ignore_case: bool,
+    /// Skip copyright lines, which differ between projects
+    #[arg(long)]
+    pub(crate) ignore_copyright: bool,
+    /// Enable all of the above
+    #[arg(long)]
+    pub(crate) spdx_matching: bool,
+}
+
+impl MatchNormalization {
+    /// Applies the enabled normalizations to a line whose runs of
+    /// whitespace are already collapsed.
+    pub fn apply(
+        &self,
+        line: &str,
+    ) -> String {
+        let all = self.spdx_matching;
+        let mut text: String = line
+            .chars()
+            .map(|ch| {
+                if (all || self.normalize_quotes) && QUOTES.contains(&ch) {
+                    '"'
+                } else if (all || self.normalize_dashes) && DASHES.contains(&ch) {
+                    '-'
+                } else {
+                    ch
+                }
+            })
+            .collect();
+        if all || self.normalize_whitespace {
+            for punctuation in CLOSING_PUNCTUATION {
+                text = text.replace(&format!(" {}", punctuation), &punctuation.to_string());
+            }
+            text = text.replace("( ", "(").replace("[ ", "[");
+        }
+        if all || self.ignore_case {
+            text = text.to_lowercase();
+        }
+        text
+    }
+
+    /// Checks whether a line is skipped as a copyright notice, such
+    /// as `Copyright (c) 2024 Jane Doe` or `© 2024 Jane Doe`.
+    pub fn skips(
+        &self,
+        line: &str,
+    ) -> bool {
+        (self.spdx_matching || self.ignore_copyright) && {
+            let line = line.trim_start().to_lowercase();
+            line.starts_with("copyright") || line.starts_with('©') || line.starts_with("(c)")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::MatchNormalization;
+
+    #[test]
+    fn test_match_normalization() {
+        let line = "“Software” — provided ( as is ) , WITHOUT warranty";
+        assert_eq!(MatchNormalization::default().apply(line), line);
+        let all = MatchNormalization {
+            spdx_matching: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            all.apply(line),
+            "\"software\" - provided (as is), without warranty"
+        );
+        assert!(all.skips("© 2024 Jane Doe"));
+        assert!(!all.skips("The above copyright notice"));
+        assert!(!MatchNormalization::default().skips("Copyright 2024"));
+    }
+}
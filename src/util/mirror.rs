@@ -0,0 +1,104 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Component, Path, PathBuf},
+    sync::OnceLock,
+};
+
+use color_print::cformat;
+
+use super::{
+    errors::{Error, LictoolResult},
+    integrity::{is_strict, parse_checksums, sha256_hex, CHECKSUMS_FILE},
+};
+use crate::i18n::t;
+
+static CHECKSUMS: OnceLock<BTreeMap<PathBuf, String>> = OnceLock::new();
+
+/// Returns the directory holding the license dataset imported with
+/// `db import`.
+///
+/// # Panics
+/// - If the data directory cannot be found.
+pub(crate) fn mirror_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .expect("Data directory not found.")
+        .join(env!("CARGO_PKG_NAME"))
+        .join("mirror")
+}
+
+/// Returns the path a response of `url` is stored at within a mirror,
+/// such as `spdx.org/licenses/MIT.json`.
+///
+/// # Returns
+///
+/// `None` for URLs that are not HTTPS or would escape the mirror.
+pub(crate) fn mirrored_path(url: &str) -> Option<PathBuf> {
+    let path = Path::new(url.strip_prefix("https://")?);
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_)))
+        .then(|| path.to_path_buf())
+}
+
+/// Returns the digests recorded when the mirror was imported, read
+/// once per run.
+fn checksums() -> &'static BTreeMap<PathBuf, String> {
+    CHECKSUMS.get_or_init(|| {
+        fs::read_to_string(mirror_dir().join(CHECKSUMS_FILE))
+            .map(|text| parse_checksums(&text))
+            .unwrap_or_default()
+    })
+}
+
+/// Reads the response of `url` from the imported mirror, if any.
+///
+/// A file not matching its recorded digest is skipped with a warning,
+/// so that it is fetched from the network instead.
+///
+/// # Errors
+/// - `Error::CorruptedMirror` for such a file in strict mode.
+pub(crate) fn read_mirrored(url: &str) -> LictoolResult<Option<Vec<u8>>> {
+    let Some(relative) = mirrored_path(url) else {
+        return Ok(None);
+    };
+    let path = mirror_dir().join(&relative);
+    let Ok(body) = fs::read(&path) else {
+        return Ok(None);
+    };
+    if checksums().get(&relative) == Some(&sha256_hex(&body)) {
+        return Ok(Some(body));
+    }
+    let file = path.display().to_string();
+    if is_strict() {
+        return Err(Error::CorruptedMirror {
+            file,
+        }
+        .into());
+    }
+    // Printed to stderr, keeping the output of the command itself
+    // intact.
+    eprintln!(
+        "{}",
+        cformat!("<yellow>{}</>", t!("mirror-checksum-mismatch", file = file))
+    );
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use pretty_assertions::assert_eq;
+
+    use super::mirrored_path;
+
+    #[test]
+    fn test_mirrored_path() {
+        assert_eq!(
+            mirrored_path("https://spdx.org/licenses/MIT.json"),
+            Some(PathBuf::from("spdx.org/licenses/MIT.json"))
+        );
+        assert_eq!(mirrored_path("http://spdx.org/licenses.json"), None);
+        assert_eq!(mirrored_path("https://spdx.org/../secret"), None);
+    }
+}
@@ -1,3 +1,28 @@
 pub(crate) mod cache;
+pub(crate) mod defaults;
+pub(crate) mod diff;
+pub(crate) mod dry_run;
+pub(crate) mod encoding;
 pub mod errors;
+pub(crate) mod events;
 pub(crate) mod git;
+pub(crate) mod history;
+pub(crate) mod hooks;
+pub(crate) mod http;
+pub(crate) mod input;
+pub(crate) mod integrity;
+pub(crate) mod journal;
+pub(crate) mod line_ending;
+pub(crate) mod list_updates;
+pub(crate) mod matching;
+pub(crate) mod mirror;
+pub(crate) mod normalize;
+pub(crate) mod output;
+pub(crate) mod pager;
+pub(crate) mod progress;
+pub(crate) mod remote;
+pub(crate) mod schema;
+pub(crate) mod theme;
+pub(crate) mod validate;
+pub(crate) mod walk;
+pub(crate) mod watch;
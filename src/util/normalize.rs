@@ -0,0 +1,175 @@
+use clap::Args;
+
+/// Clean-ups applied to rendered licenses before they are written.
+#[derive(Args, Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct Normalization {
+    /// End the written file with exactly one newline
+    #[arg(long)]
+    pub(crate) trailing_newline: bool,
+    /// Strip whitespace at the end of each line
+    #[arg(long)]
+    pub(crate) trim_trailing_whitespace: bool,
+    /// Collapse runs of blank lines into a single blank line
+    #[arg(long)]
+    pub(crate) collapse_blank_lines: bool,
+    /// Re-wrap the paragraphs of the text to this many columns
+    /// instead of keeping the line breaks of the SPDX text
+    #[arg(long, value_name = "COLUMNS", value_parser = clap::value_parser!(u16).range(1..))]
+    pub(crate) wrap: Option<u16>,
+}
+
+impl Normalization {
+    /// Applies the enabled clean-ups to `text`.
+    ///
+    /// Expects `\n` line endings, so it runs before the configured
+    /// line endings are applied.
+    pub fn apply(
+        &self,
+        text: &str,
+    ) -> String {
+        let mut lines: Vec<&str> = text.split('\n').collect();
+        if self.trim_trailing_whitespace {
+            lines.iter_mut().for_each(|line| *line = line.trim_end());
+        }
+        if self.collapse_blank_lines {
+            lines.dedup_by(|line, previous| line.trim().is_empty() && previous.trim().is_empty());
+        }
+        let mut text = lines.join("\n");
+        if let Some(width) = self.wrap {
+            text = wrap_text(&text, width.into());
+        }
+        if self.trailing_newline {
+            text.truncate(text.trim_end_matches(['\n', '\r']).len());
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// Re-wraps the paragraphs of a text so that no line is wider than
+/// `width`, except for words that are wider on their own.
+///
+/// A paragraph ends at a blank line, a change of indentation or a
+/// line starting a list item such as `1.`, `(a)` or `-`. Its first
+/// line keeps its indentation; the others take that of its second
+/// line for list items, so that hanging indents survive, and that of
+/// its first line otherwise.
+fn wrap_text(
+    text: &str,
+    width: usize,
+) -> String {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut wrapped: Vec<String> = Vec::new();
+    let mut idx = 0;
+    while idx < lines.len() {
+        let first = lines[idx];
+        idx += 1;
+        if first.trim().is_empty() {
+            wrapped.push(first.to_string());
+            continue;
+        }
+        let first_indent = indentation(first);
+        let mut rest_indent = (!starts_list_item(first.trim_start())).then_some(first_indent);
+        let mut words: Vec<&str> = first.split_whitespace().collect();
+        while let Some(&line) = lines.get(idx) {
+            if line.trim().is_empty()
+                || starts_list_item(line.trim_start())
+                || rest_indent.is_some_and(|indent| indent != indentation(line))
+            {
+                break;
+            }
+            rest_indent = Some(indentation(line));
+            words.extend(line.split_whitespace());
+            idx += 1;
+        }
+        let mut current = first_indent.to_string();
+        let mut has_words = false;
+        for word in words {
+            if has_words && current.chars().count() + 1 + word.chars().count() > width {
+                let indent = rest_indent.unwrap_or(first_indent).to_string();
+                wrapped.push(std::mem::replace(&mut current, indent));
+                has_words = false;
+            }
+            if has_words {
+                current.push(' ');
+            }
+            current.push_str(word);
+            has_words = true;
+        }
+        wrapped.push(current);
+    }
+    wrapped.join("\n")
+}
+
+/// Returns the whitespace a line starts with.
+fn indentation(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Checks whether a line, without its indentation, starts a list item
+/// with a bullet or a label such as `1.`, `2.1.`, `a)`, `(iv)` or
+/// `b.`.
+fn starts_list_item(line: &str) -> bool {
+    let Some((marker, _)) = line.split_once(char::is_whitespace) else {
+        return false;
+    };
+    if matches!(marker, "-" | "*" | "•") {
+        return true;
+    }
+    let label = marker
+        .strip_prefix('(')
+        .and_then(|marker| marker.strip_suffix(')'))
+        .or_else(|| marker.strip_suffix('.'))
+        .or_else(|| marker.strip_suffix(')'));
+    label.is_some_and(|label| {
+        label.starts_with(|c: char| c.is_ascii_digit())
+            && label.chars().all(|c| c.is_ascii_digit() || c == '.')
+            || label.len() == 1 && label.chars().all(|c| c.is_ascii_alphabetic())
+            || !label.is_empty() && label.chars().all(|c| "ivxIVX".contains(c))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{wrap_text, Normalization};
+
+    #[test]
+    fn test_normalize_text() {
+        let text = "MIT License  \n\n\n\nCopyright\t\n\n";
+        assert_eq!(Normalization::default().apply(text), text);
+        assert_eq!(
+            Normalization {
+                trailing_newline: true,
+                trim_trailing_whitespace: true,
+                collapse_blank_lines: true,
+                wrap: None,
+            }
+            .apply(text),
+            "MIT License\n\nCopyright\n"
+        );
+        assert_eq!(
+            Normalization {
+                trailing_newline: true,
+                ..Normalization::default()
+            }
+            .apply("MIT License"),
+            "MIT License\n"
+        );
+    }
+
+    #[test]
+    fn test_wrap_text() {
+        let text = "Redistribution and use in source and binary forms are\npermitted:\n\n1. \
+                    Redistributions of source code must retain the\n   copyright notice.\n2. Keep \
+                    it.\n\n    Indented text that runs\n    long.\n";
+        assert_eq!(
+            wrap_text(text, 30),
+            "Redistribution and use in\nsource and binary forms are\npermitted:\n\n1. \
+             Redistributions of source\n   code must retain the\n   copyright notice.\n2. Keep \
+             it.\n\n    Indented text that runs\n    long.\n"
+        );
+        assert_eq!(wrap_text("averyverylongword b", 5), "averyverylongword\nb");
+    }
+}
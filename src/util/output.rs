@@ -0,0 +1,51 @@
+use std::io::{self, Write};
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use crate::util::errors::LictoolResult;
+
+/// The formats `list`, `info` and `detect` print their results in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum OutputFormat {
+    /// Colored text for reading in a terminal.
+    #[default]
+    Text,
+    /// Pretty-printed JSON, for jq and other tooling.
+    Json,
+}
+
+/// Prints a confirmation such as `✔ Created LICENSE.md` like
+/// `cprintln!`, unless `--quiet` is set, so that scripts only get the
+/// results of commands.
+macro_rules! success {
+    ($($arg:tt)*) => {
+        if !$crate::util::progress::is_quiet() {
+            color_print::cprintln!($($arg)*);
+        }
+    };
+}
+pub(crate) use success;
+
+/// Prints a dimmed note, such as how many findings a baseline
+/// suppressed, unless `--quiet` is set.
+macro_rules! note {
+    ($message:expr) => {
+        if !$crate::util::progress::is_quiet() {
+            color_print::cprintln!("<dim>{}</>", $message);
+        }
+    };
+}
+pub(crate) use note;
+
+/// Prints a value to stdout as pretty-printed JSON.
+///
+/// # Errors
+/// - If the value cannot be serialized or stdout is closed.
+pub(crate) fn print_json<T: Serialize + ?Sized>(value: &T) -> LictoolResult<()> {
+    let mut out = io::stdout().lock();
+    serde_json::to_writer_pretty(&mut out, value)?;
+    writeln!(out)?;
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+/// Displays text through the user's pager.
+///
+/// The pager is taken from the `PAGER` environment variable and
+/// defaults to `less -R`. When stdout is not a terminal, or the pager
+/// cannot be started, the text is printed directly instead.
+///
+/// # Errors
+/// - If writing to stdout or to the pager fails.
+pub(crate) fn page(text: &str) -> io::Result<()> {
+    if io::stdout().is_terminal() {
+        let pager = env::var("PAGER").unwrap_or_else(|_| String::from("less -R"));
+        let mut parts = pager.split_whitespace();
+        if let Some(program) = parts.next() {
+            if let Ok(mut child) = Command::new(program)
+                .args(parts)
+                .stdin(Stdio::piped())
+                .spawn()
+            {
+                if let Some(mut stdin) = child.stdin.take() {
+                    // The pager may exit before reading everything.
+                    let _ = stdin.write_all(text.as_bytes());
+                }
+                child.wait()?;
+                return Ok(());
+            }
+        }
+    }
+    io::stdout().lock().write_all(text.as_bytes())
+}
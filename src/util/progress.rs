@@ -0,0 +1,99 @@
+use std::{
+    future::Future,
+    io::{self, IsTerminal},
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// How often the spinner advances.
+const TICK_INTERVAL: Duration = Duration::from_millis(80);
+
+/// Whether progress output is suppressed with `--quiet`.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Suppresses or re-enables all progress output.
+pub(crate) fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// Checks whether `--quiet` is set.
+pub(crate) fn is_quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// Checks whether progress output should be displayed.
+fn is_enabled() -> bool {
+    !QUIET.load(Ordering::Relaxed) && io::stderr().is_terminal()
+}
+
+/// Returns a progress bar counting up to `len` items with the given
+/// message and the estimated time left on stderr.
+///
+/// The bar is hidden with `--quiet` or when stderr is not a terminal,
+/// like the spinner of `with_spinner`.
+pub(crate) fn progress_bar(
+    message: String,
+    len: usize,
+) -> ProgressBar {
+    styled_bar(
+        message,
+        len as u64,
+        "{spinner:.cyan} {msg} {pos}/{len} ({eta})",
+    )
+}
+
+/// Returns a progress bar like `progress_bar` counting up to `len`
+/// bytes, for reading a file.
+pub(crate) fn byte_progress_bar(
+    message: String,
+    len: u64,
+) -> ProgressBar {
+    styled_bar(
+        message,
+        len,
+        "{spinner:.cyan} {msg} {bytes}/{total_bytes} ({eta})",
+    )
+}
+
+/// Returns a progress bar drawn with `template`, hidden unless
+/// progress output is enabled.
+fn styled_bar(
+    message: String,
+    len: u64,
+    template: &str,
+) -> ProgressBar {
+    if !is_enabled() {
+        return ProgressBar::hidden();
+    }
+    let bar = ProgressBar::new(len)
+        .with_style(ProgressStyle::with_template(template).expect("progress template is valid"))
+        .with_message(message);
+    bar.enable_steady_tick(TICK_INTERVAL);
+    bar
+}
+
+/// Awaits `future` while showing a spinner with the given message on
+/// stderr.
+///
+/// Nothing is displayed with `--quiet` or when stderr is not a
+/// terminal, so piped and CI output stays clean.
+pub(crate) async fn with_spinner<F: Future>(
+    message: String,
+    future: F,
+) -> F::Output {
+    if !is_enabled() {
+        return future.await;
+    }
+    let spinner = ProgressBar::new_spinner()
+        .with_style(
+            ProgressStyle::with_template("{spinner:.cyan} {msg}")
+                .expect("spinner template is valid"),
+        )
+        .with_message(message);
+    spinner.enable_steady_tick(TICK_INTERVAL);
+    let output = future.await;
+    spinner.finish_and_clear();
+    output
+}
@@ -0,0 +1,188 @@
+use serde::Deserialize;
+
+/// A code hosting service whose repository URLs name an owner and a
+/// repository.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Forge {
+    GitHub,
+    GitLab,
+    Bitbucket,
+}
+
+/// Self-hosted instances of the forges, read from the `[forges]`
+/// section of the configuration, such as `gitlab =
+/// ["git.example.com"]`.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct ForgeConfig {
+    pub(crate) github: Vec<String>,
+    pub(crate) gitlab: Vec<String>,
+    pub(crate) bitbucket: Vec<String>,
+}
+
+impl ForgeConfig {
+    /// Returns the forge serving `host`, either a public one or a
+    /// configured self-hosted instance.
+    pub fn forge(
+        &self,
+        host: &str,
+    ) -> Option<Forge> {
+        let host = host.to_lowercase();
+        let listed = |hosts: &[String]| {
+            hosts
+                .iter()
+                .any(|listed| listed.eq_ignore_ascii_case(&host))
+        };
+        if host == "github.com" || listed(&self.github) {
+            Some(Forge::GitHub)
+        } else if host == "gitlab.com" || listed(&self.gitlab) {
+            Some(Forge::GitLab)
+        } else if host == "bitbucket.org" || listed(&self.bitbucket) {
+            Some(Forge::Bitbucket)
+        } else {
+            None
+        }
+    }
+}
+
+/// A repository identified by the URL of a git remote.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RemoteRepo {
+    pub(crate) forge: Forge,
+    pub(crate) host: String,
+    /// The user, organization or top-level group owning the
+    /// repository.
+    pub(crate) owner: String,
+    pub(crate) name: String,
+}
+
+/// Parses the URL of a repository on a known forge, given in any of
+/// the forms git accepts, such as `https://gitlab.com/group/repo`,
+/// `git@bitbucket.org:team/repo.git` or
+/// `ssh://git@git.example.com:7999/project/repo.git`.
+///
+/// GitLab repositories may sit in nested groups, in which case the
+/// top-level group is the owner. The `scm/` prefix of self-hosted
+/// Bitbucket clone URLs is skipped.
+///
+/// # Returns
+///
+/// `None` if the URL is not of a known forge or names no repository.
+pub(crate) fn parse_remote(
+    url: &str,
+    forges: &ForgeConfig,
+) -> Option<RemoteRepo> {
+    let (host, mut segments) = split_remote(url)?;
+    let forge = forges.forge(host)?;
+    if forge == Forge::Bitbucket && segments.first() == Some(&"scm") {
+        segments.remove(0);
+    }
+    let (owner, name) = match forge {
+        Forge::GitLab if segments.len() >= 2 => (segments[0], segments[segments.len() - 1]),
+        _ if segments.len() >= 2 => (segments[0], segments[1]),
+        _ => return None,
+    };
+    Some(RemoteRepo {
+        forge,
+        host: host.to_lowercase(),
+        owner: owner.to_string(),
+        name: name.to_string(),
+    })
+}
+
+/// Returns the name of the repository a remote URL points to, on any
+/// host, such as `lictool` for `git@example.com:team/lictool.git`.
+/// Local paths such as `/srv/git/project.git` are read the same way.
+pub(crate) fn remote_repo_name(url: &str) -> Option<String> {
+    let (_, segments) = split_remote(url)?;
+    segments.last().map(|name| name.to_string())
+}
+
+/// Splits a remote URL into its host and the segments of its
+/// repository path, without a `.git` suffix. Local paths have an
+/// empty host.
+fn split_remote(url: &str) -> Option<(&str, Vec<&str>)> {
+    let url = url.trim();
+    let (authority, path) = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?,
+        // The scp-like syntax, `user@host:path`.
+        None => url.split_once(':').unwrap_or(("", url)),
+    };
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    let mut segments: Vec<&str> = path
+        .trim_end_matches('/')
+        .trim_end_matches(".git")
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    // Web URLs of files or merge requests go beyond the repository,
+    // which GitLab marks with a `-` segment.
+    if let Some(end) = segments.iter().position(|segment| *segment == "-") {
+        segments.truncate(end);
+    }
+    Some((host, segments))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{parse_remote, remote_repo_name, Forge, ForgeConfig};
+
+    #[test]
+    fn test_parse_remote() {
+        let forges = ForgeConfig {
+            gitlab: vec!["git.example.com".to_string()],
+            bitbucket: vec!["stash.example.com".to_string()],
+            ..Default::default()
+        };
+        let parse = |url| {
+            parse_remote(url, &forges).map(|remote| (remote.forge, remote.owner, remote.name))
+        };
+        let repo =
+            |forge, owner: &str, name: &str| Some((forge, owner.to_string(), name.to_string()));
+        assert_eq!(
+            parse("https://github.com/fracturesky/lictool.git"),
+            repo(Forge::GitHub, "fracturesky", "lictool")
+        );
+        assert_eq!(
+            parse("git@gitlab.com:group/sub/tool.git"),
+            repo(Forge::GitLab, "group", "tool")
+        );
+        assert_eq!(
+            parse("https://gitlab.com/group/tool/-/blob/main/LICENSE"),
+            repo(Forge::GitLab, "group", "tool")
+        );
+        assert_eq!(
+            parse("https://jane@bitbucket.org/team/app.git"),
+            repo(Forge::Bitbucket, "team", "app")
+        );
+        assert_eq!(
+            parse("ssh://git@git.example.com:2222/infra/deploy.git"),
+            repo(Forge::GitLab, "infra", "deploy")
+        );
+        assert_eq!(
+            parse("https://stash.example.com/scm/proj/app.git"),
+            repo(Forge::Bitbucket, "proj", "app")
+        );
+        assert_eq!(parse("https://example.org/team/app.git"), None);
+        assert_eq!(parse("https://github.com/fracturesky"), None);
+    }
+
+    #[test]
+    fn test_remote_repo_name() {
+        assert_eq!(
+            remote_repo_name("git@git.example.com:team/lictool.git").as_deref(),
+            Some("lictool")
+        );
+        assert_eq!(
+            remote_repo_name("https://example.com/team/tool/").as_deref(),
+            Some("tool")
+        );
+        assert_eq!(
+            remote_repo_name("/srv/git/project.git").as_deref(),
+            Some("project")
+        );
+    }
+}
@@ -0,0 +1,387 @@
+use std::cell::RefCell;
+
+use serde::{
+    de::{
+        value::{MapDeserializer, SeqDeserializer},
+        DeserializeOwned, Deserializer, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any,
+};
+use toml::{de::Error, Table, Value};
+
+/// The lowest similarity for a known key or command to be suggested
+/// in place of an unknown one.
+const MIN_SUGGESTION_SIMILARITY: f64 = 0.7;
+
+/// What deserializing through `Checked` found out about the schema.
+#[derive(Debug, Default)]
+struct Report {
+    /// Keys that are not fields of their struct.
+    unknown: Vec<UnknownKey>,
+    /// The dotted path and fields of each struct deserialized.
+    structs: Vec<(String, &'static [&'static str])>,
+}
+
+/// A key of a configuration file that no setting reads.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct UnknownKey {
+    /// The dotted path of the key, such as `policy.alow`.
+    pub(crate) path: String,
+    /// The most similar known key at the same level, if any is close.
+    pub(crate) suggestion: Option<&'static str>,
+}
+
+/// Deserializes a TOML table, also returning the keys that are not
+/// fields of the structs they belong to.
+///
+/// Serde silently skips such keys, so a typo such as `policy.alow`
+/// would otherwise leave the setting unset.
+///
+/// # Errors
+/// - If a value has the wrong type.
+pub(crate) fn deserialize_checked<T: DeserializeOwned>(
+    table: Table
+) -> Result<(T, Vec<UnknownKey>), Error> {
+    let report = RefCell::new(Report::default());
+    let value = T::deserialize(Checked {
+        value: Value::Table(table),
+        path: String::new(),
+        report: &report,
+    })?;
+    Ok((value, report.into_inner().unknown))
+}
+
+/// Builds a table of settings from environment variables such as
+/// `LICTOOL_POLICY_DENY` or `LICTOOL_DEFAULT_LICENSE`.
+///
+/// After `prefix`, underscores separate both the sections and the
+/// words of a key, which is resolved against the fields of `T`.
+/// Variables that match no setting, or name a whole section such as
+/// `LICTOOL_PROFILE` or `LICTOOL_HTTP`, are skipped, as are keyed
+/// sections such as `[profile.<name>]`. Values are read as a string,
+/// a TOML literal such as `true` or `8`, or a comma-separated list,
+/// whichever the setting accepts.
+pub(crate) fn env_overrides<T: DeserializeOwned>(
+    vars: impl IntoIterator<Item = (String, String)>,
+    prefix: &str,
+) -> Table {
+    let mut overrides = Table::new();
+    for (name, raw) in vars {
+        let Some(path) = name
+            .strip_prefix(prefix)
+            .and_then(|name| resolve_key::<T>(&name.to_lowercase()))
+        else {
+            continue;
+        };
+        // A string written into a section would fail every command.
+        if fits::<T>(&path, Value::Table(Table::new())) {
+            continue;
+        }
+        let value = typed_value::<T>(&path, &raw);
+        let (key, sections) = path.split_last().expect("a resolved key is never empty");
+        let mut table = &mut overrides;
+        for section in sections {
+            table = table
+                .entry(*section)
+                .or_insert_with(|| Value::Table(Table::new()))
+                .as_table_mut()
+                .expect("sections are tables");
+        }
+        table.insert(key.to_string(), value);
+    }
+    overrides
+}
+
+/// Finds the key path an environment variable name refers to, such
+/// as `["policy", "deny"]` for `policy_deny`.
+fn resolve_key<T: DeserializeOwned>(name: &str) -> Option<Vec<&'static str>> {
+    let mut path: Vec<&'static str> = Vec::new();
+    let mut rest = name;
+    loop {
+        let fields = struct_fields::<T>(&path)?;
+        let mut matches = fields.iter().filter_map(|field| {
+            let field_name = field.replace('-', "_");
+            if rest == field_name {
+                Some((*field, ""))
+            } else {
+                rest.strip_prefix(&field_name)
+                    .and_then(|rest| rest.strip_prefix('_'))
+                    .map(|rest| (*field, rest))
+            }
+        });
+        // Prefer the longest field, so `default_license` is not read
+        // as a `default` section.
+        let (field, remainder) = matches.by_ref().min_by_key(|(_, rest)| rest.len())?;
+        path.push(field);
+        if remainder.is_empty() {
+            return Some(path);
+        }
+        rest = remainder;
+    }
+}
+
+/// Returns the fields of the struct at `path` within `T`, or `None`
+/// if there is no struct there.
+fn struct_fields<T: DeserializeOwned>(path: &[&str]) -> Option<&'static [&'static str]> {
+    let mut table = Table::new();
+    for section in path.iter().rev() {
+        table = Table::from_iter([(section.to_string(), Value::Table(table))]);
+    }
+    let report = RefCell::new(Report::default());
+    // Deserializing stops at the first value of the wrong type, after
+    // the structs along the path have been visited.
+    let _ = T::deserialize(Checked {
+        value: Value::Table(table),
+        path: String::new(),
+        report: &report,
+    });
+    let path = path.join(".");
+    let structs = report.into_inner().structs;
+    structs
+        .into_iter()
+        .find(|(struct_path, _)| *struct_path == path)
+        .map(|(_, fields)| fields)
+}
+
+/// Converts the raw value of an environment variable to the first
+/// type the setting at `path` accepts.
+fn typed_value<T: DeserializeOwned>(
+    path: &[&str],
+    raw: &str,
+) -> Value {
+    let literal = toml::from_str::<Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"));
+    let list = Value::Array(
+        raw.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| Value::String(item.to_string()))
+            .collect(),
+    );
+    let candidates = [Some(Value::String(raw.to_string())), literal, Some(list)];
+    candidates
+        .into_iter()
+        .flatten()
+        .find(|value| fits::<T>(path, value.clone()))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// Checks whether the setting at `path` within `T` accepts `value`.
+fn fits<T: DeserializeOwned>(
+    path: &[&str],
+    value: Value,
+) -> bool {
+    let mut table = Table::new();
+    let mut inner = value;
+    for section in path.iter().rev() {
+        table = Table::from_iter([(section.to_string(), inner)]);
+        inner = Value::Table(table.clone());
+    }
+    deserialize_checked::<T>(table).is_ok()
+}
+
+/// A TOML value deserializer comparing the keys of tables against the
+/// fields of the structs they are deserialized into.
+struct Checked<'a> {
+    value: Value,
+    /// The dotted path of the value, empty for the root table.
+    path: String,
+    report: &'a RefCell<Report>,
+}
+
+impl<'a> Checked<'a> {
+    /// Returns the dotted path of a key of this value.
+    fn child_path(
+        &self,
+        key: &str,
+    ) -> String {
+        if self.path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", self.path, key)
+        }
+    }
+
+    fn child(
+        &self,
+        key: &str,
+        value: Value,
+    ) -> Self {
+        Checked {
+            value,
+            path: self.child_path(key),
+            report: self.report,
+        }
+    }
+}
+
+impl<'de, 'a> IntoDeserializer<'de, Error> for Checked<'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de, 'a> Deserializer<'de> for Checked<'a> {
+    type Error = Error;
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map identifier ignored_any
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            Value::String(value) => visitor.visit_string(value),
+            Value::Integer(value) => visitor.visit_i64(value),
+            Value::Float(value) => visitor.visit_f64(value),
+            Value::Boolean(value) => visitor.visit_bool(value),
+            Value::Datetime(value) => visitor.visit_string(value.to_string()),
+            Value::Array(ref values) => {
+                let items: Vec<Checked> = values
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, value)| self.child(&idx.to_string(), value.clone()))
+                    .collect();
+                let mut seq = SeqDeserializer::new(items.into_iter());
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Table(ref table) => {
+                let entries: Vec<(String, Checked)> = table
+                    .iter()
+                    .map(|(key, value)| (key.clone(), self.child(key, value.clone())))
+                    .collect();
+                let mut map = MapDeserializer::new(entries.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        // TOML has no null, so present values are always `Some`.
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.value {
+            // Unit variants are written as strings, such as `format =
+            // "json"`.
+            Value::String(value) => visitor.visit_enum(value.into_deserializer()),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if let Value::Table(table) = &self.value {
+            let mut report = self.report.borrow_mut();
+            report.structs.push((self.path.clone(), fields));
+            for key in table.keys().filter(|key| !fields.contains(&key.as_str())) {
+                report.unknown.push(UnknownKey {
+                    path: self.child_path(key),
+                    suggestion: closest(key, fields.iter().copied()),
+                });
+            }
+        }
+        self.deserialize_any(visitor)
+    }
+}
+
+/// Returns the candidate most similar to `key`, such as a field or a
+/// command, if it is close enough to be a likely typo.
+pub(crate) fn closest<'a>(
+    key: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    candidates
+        .into_iter()
+        .map(|candidate| (strsim::jaro_winkler(key, candidate), candidate))
+        .filter(|(similarity, _)| *similarity >= MIN_SUGGESTION_SIMILARITY)
+        .max_by(|(a, _), (b, _)| a.total_cmp(b))
+        .map(|(_, field)| field)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{deserialize_checked, env_overrides, UnknownKey};
+    use crate::config::Config;
+
+    #[test]
+    fn test_unknown_keys() {
+        let table = toml::from_str(
+            "favorites = [\"MIT\"]\nfavourite = \"MIT\"\n\n[policy]\nalow = \
+             [\"MIT\"]\n\n[profile.work]\nowner = \"Acme\"\nzzz = 1\n",
+        )
+        .unwrap();
+        let (config, unknown) = deserialize_checked::<Config>(table).unwrap();
+        assert_eq!(config.favorites, vec!["MIT"]);
+        assert_eq!(
+            unknown,
+            vec![
+                UnknownKey {
+                    path: "favourite".to_string(),
+                    suggestion: Some("favorites"),
+                },
+                UnknownKey {
+                    path: "policy.alow".to_string(),
+                    suggestion: Some("allow"),
+                },
+                UnknownKey {
+                    path: "profile.work.zzz".to_string(),
+                    suggestion: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let vars = [
+            ("LICTOOL_POLICY_DENY", "GPL-3.0-only"),
+            ("LICTOOL_DEFAULT_LICENSE", "MIT"),
+            ("LICTOOL_FAVORITES", "MIT, Apache-2.0"),
+            ("LICTOOL_HTTP_MAX_CONNECTIONS", "4"),
+            ("LICTOOL_RANK_BY_POPULARITY", "false"),
+            ("LICTOOL_LANG", "de"),
+            ("LICTOOL_PROFILE", "work"),
+            ("LICTOOL_HTTP", "fast"),
+            ("LICTOOL_POLICY", "strict"),
+            ("HOME", "/root"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+        let overrides = env_overrides::<Config>(vars, "LICTOOL_");
+        assert_eq!(
+            overrides,
+            toml::from_str(
+                "default-license = \"MIT\"\nfavorites = [\"MIT\", \
+                 \"Apache-2.0\"]\nrank-by-popularity = false\n[policy]\ndeny = \
+                 [\"GPL-3.0-only\"]\n[http]\nmax-connections = 4\n"
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use console::{style, Color, Style};
+use dialoguer::theme::ColorfulTheme;
+use serde::Deserialize;
+
+/// Appearance of the interactive prompts, read from the `[theme]`
+/// section of the configuration.
+///
+/// Unset values keep the look of dialoguer's `ColorfulTheme`.
+#[derive(Debug, Default, Clone, Deserialize, PartialEq)]
+#[serde(default, rename_all = "kebab-case")]
+pub(crate) struct ThemeConfig {
+    /// The symbol displayed before a prompt.
+    pub(crate) prompt_symbol: Option<String>,
+    /// The symbol displayed before an answered prompt.
+    pub(crate) success_symbol: Option<String>,
+    /// The symbol displayed before a validation error.
+    pub(crate) error_symbol: Option<String>,
+    /// The symbol marking the highlighted item of a list.
+    pub(crate) active_symbol: Option<String>,
+    /// The glyph of a checked checkbox.
+    pub(crate) checked_symbol: Option<String>,
+    /// The glyph of an unchecked checkbox.
+    pub(crate) unchecked_symbol: Option<String>,
+    /// The color of the prompt symbol.
+    pub(crate) prompt_color: Option<ThemeColor>,
+    /// The color of default values.
+    pub(crate) default_color: Option<ThemeColor>,
+    /// The color of answers and of the success symbol.
+    pub(crate) value_color: Option<ThemeColor>,
+    /// The color of the highlighted item of a list.
+    pub(crate) active_color: Option<ThemeColor>,
+    /// The color of validation errors.
+    pub(crate) error_color: Option<ThemeColor>,
+}
+
+/// A terminal color given either by name (`"cyan"`) or as an ANSI 256
+/// color index (`"208"`).
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(try_from = "String")]
+pub(crate) struct ThemeColor(Color);
+
+impl TryFrom<String> for ThemeColor {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let color = match value.to_lowercase().as_str() {
+            "black" => Color::Black,
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "yellow" => Color::Yellow,
+            "blue" => Color::Blue,
+            "magenta" => Color::Magenta,
+            "cyan" => Color::Cyan,
+            "white" => Color::White,
+            other => {
+                Color::Color256(other.parse().map_err(|_| {
+                    format!(
+                        "invalid color `{}`, expected a color name or a number from 0 to 255",
+                        value
+                    )
+                })?)
+            }
+        };
+        Ok(Self(color))
+    }
+}
+
+impl ThemeConfig {
+    /// Builds the prompt theme described by this configuration.
+    pub fn build(&self) -> ColorfulTheme {
+        let mut theme = ColorfulTheme::default();
+        let symbol = |symbol: &Option<String>, default: &str, color: Option<ThemeColor>| {
+            let text = symbol.clone().unwrap_or_else(|| default.to_string());
+            match color {
+                Some(ThemeColor(color)) => style(text).for_stderr().fg(color),
+                None => style(text).for_stderr(),
+            }
+        };
+        let prompt_color = self.prompt_color.or(Some(ThemeColor(Color::Yellow)));
+        let value_color = self.value_color.or(Some(ThemeColor(Color::Green)));
+        let error_color = self.error_color.or(Some(ThemeColor(Color::Red)));
+
+        theme.prompt_prefix = symbol(&self.prompt_symbol, "?", prompt_color);
+        theme.success_prefix = symbol(&self.success_symbol, "✔", value_color);
+        theme.error_prefix = symbol(&self.error_symbol, "✘", error_color);
+        theme.active_item_prefix = symbol(&self.active_symbol, "❯", value_color);
+        theme.picked_item_prefix = symbol(&self.active_symbol, "❯", value_color);
+        theme.checked_item_prefix = symbol(&self.checked_symbol, "✔", value_color);
+        theme.unchecked_item_prefix = symbol(
+            &self.unchecked_symbol,
+            "⬚",
+            Some(ThemeColor(Color::Magenta)),
+        );
+        if let Some(ThemeColor(color)) = self.value_color {
+            theme.values_style = Style::new().for_stderr().fg(color);
+        }
+        if let Some(ThemeColor(color)) = self.default_color {
+            theme.defaults_style = Style::new().for_stderr().fg(color);
+        }
+        if let Some(ThemeColor(color)) = self.active_color {
+            theme.active_item_style = Style::new().for_stderr().fg(color);
+        }
+        if let Some(ThemeColor(color)) = self.error_color {
+            theme.error_style = Style::new().for_stderr().fg(color);
+        }
+        theme
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use console::Color;
+    use pretty_assertions::assert_eq;
+
+    use super::{ThemeColor, ThemeConfig};
+
+    #[test]
+    fn test_parse_theme_config() {
+        let config: ThemeConfig =
+            toml::from_str("prompt-symbol = \">\"\nprompt-color = \"208\"\nerror-color = \"Blue\"")
+                .unwrap();
+        assert_eq!(config.prompt_symbol.as_deref(), Some(">"));
+        assert_eq!(config.prompt_color, Some(ThemeColor(Color::Color256(208))));
+        assert_eq!(config.error_color, Some(ThemeColor(Color::Blue)));
+        assert!(toml::from_str::<ThemeConfig>("value-color = \"teal\"").is_err());
+    }
+}
@@ -0,0 +1,105 @@
+use std::path::Path;
+
+use chrono::{Datelike, Local};
+
+use super::defaults::years_since_first_commit;
+use crate::i18n::t;
+
+/// The year argument standing for the years since the first commit
+/// of the repository.
+pub(crate) const YEAR_FROM_GIT: &str = "from-git";
+
+/// The earliest year accepted as a copyright year.
+const MIN_YEAR: i32 = 1900;
+
+/// Validates a copyright year such as `2024`, or a range such as
+/// `2019-2024`.
+///
+/// Years must lie between 1900 and next year, and ranges must not end
+/// before they start.
+///
+/// # Errors
+/// - A message describing why the value was rejected.
+pub(crate) fn validate_year(input: &str) -> Result<(), String> {
+    let max_year = Local::now().year() + 1;
+    let parse = |year: &str| {
+        year.trim()
+            .parse::<i32>()
+            .ok()
+            .filter(|year| (MIN_YEAR..=max_year).contains(year))
+            .ok_or_else(|| {
+                t!(
+                    "invalid-year",
+                    year = year.trim(),
+                    min = MIN_YEAR,
+                    max = max_year
+                )
+            })
+    };
+    match input.split_once('-') {
+        Some((start, end)) => {
+            if parse(start)? > parse(end)? {
+                Err(t!("invalid-year-range", range = input))
+            } else {
+                Ok(())
+            }
+        }
+        None => parse(input).map(|_| ()),
+    }
+}
+
+/// Parses a copyright year or year range for a command-line argument.
+///
+/// `from-git` becomes the range from the year of the first commit of
+/// the current repository to the current year.
+pub(crate) fn parse_year(input: &str) -> Result<String, String> {
+    if input.trim() == YEAR_FROM_GIT {
+        return years_since_first_commit(Path::new(".")).ok_or_else(|| t!("year-no-git-history"));
+    }
+    validate_year(input).map(|_| input.trim().to_string())
+}
+
+/// Validates the basic syntax of an email address.
+///
+/// # Errors
+/// - A message describing why the value was rejected.
+pub(crate) fn validate_email(input: &str) -> Result<(), String> {
+    let valid = match input.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !input.chars().any(char::is_whitespace)
+        }
+        None => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(t!("invalid-email", email = input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_email, validate_year};
+
+    #[test]
+    fn test_validate_year() {
+        assert!(validate_year("2024").is_ok());
+        assert!(validate_year("2019-2024").is_ok());
+        assert!(validate_year("24").is_err());
+        assert!(validate_year("2024-2019").is_err());
+        assert!(validate_year("9999").is_err());
+    }
+
+    #[test]
+    fn test_validate_email() {
+        assert!(validate_email("jane@example.com").is_ok());
+        assert!(validate_email("jane@localhost").is_err());
+        assert!(validate_email("jane doe@example.com").is_err());
+        assert!(validate_email("@example.com").is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use git2::Repository;
+
+/// Directories skipped when walking a tree outside of git, since they
+/// hold build output or dependencies.
+const SKIPPED_DIRS: [&str; 3] = ["target", "node_modules", "vendor"];
+
+/// Lists the files of the project under `root`.
+///
+/// Inside a git repository, these are the files tracked in the index,
+/// so ignored files are left out. Elsewhere the tree is walked,
+/// skipping hidden entries and build directories.
+pub(crate) fn project_files(root: &Path) -> Vec<PathBuf> {
+    tracked_files(root).unwrap_or_else(|| {
+        let mut files = Vec::new();
+        walk(root, &mut files);
+        files.sort();
+        files
+    })
+}
+
+/// Checks whether a path relative to the project root lies where the
+/// walk outside of git descends, rather than in a hidden entry or a
+/// build directory.
+pub(crate) fn is_walked(relative: &Path) -> bool {
+    let names: Vec<_> = relative
+        .components()
+        .filter_map(|component| {
+            match component {
+                Component::Normal(name) => Some(name.to_string_lossy()),
+                _ => None,
+            }
+        })
+        .collect();
+    let dirs = &names[..names.len().saturating_sub(1)];
+    !names.iter().any(|name| name.starts_with('.'))
+        && !dirs
+            .iter()
+            .any(|name| SKIPPED_DIRS.contains(&name.as_ref()))
+}
+
+/// Lists the files tracked by git under `root`, or `None` outside of
+/// a repository.
+fn tracked_files(root: &Path) -> Option<Vec<PathBuf>> {
+    let repo = Repository::discover(root).ok()?;
+    let workdir = fs::canonicalize(repo.workdir()?).ok()?;
+    let root_abs = fs::canonicalize(root).ok()?;
+    let index = repo.index().ok()?;
+    let files = index
+        .iter()
+        .filter_map(|entry| {
+            let path = workdir.join(String::from_utf8(entry.path).ok()?);
+            let relative = path.strip_prefix(&root_abs).ok()?;
+            Some(root.join(relative))
+        })
+        .filter(|path| path.is_file())
+        .collect();
+    Some(files)
+}
+
+/// Recursively collects the files below `dir`.
+fn walk(
+    dir: &Path,
+    files: &mut Vec<PathBuf>,
+) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() && !SKIPPED_DIRS.contains(&name.as_ref()) => {
+                walk(&path, files);
+            }
+            Ok(file_type) if file_type.is_file() => files.push(path),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use pretty_assertions::assert_eq;
+
+    use super::{is_walked, project_files};
+
+    #[test]
+    fn test_project_files_outside_git() {
+        let dir = tempfile::tempdir().unwrap();
+        for path in ["LICENSE", "src/main.rs", ".hidden/a", "target/debug/b"] {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let files: Vec<String> = project_files(dir.path())
+            .iter()
+            .map(|path| {
+                path.strip_prefix(dir.path())
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect();
+        assert_eq!(files, ["LICENSE", "src/main.rs"]);
+        assert!(is_walked(Path::new("./src/main.rs")));
+        assert!(!is_walked(Path::new("target/debug/b")));
+        assert!(!is_walked(Path::new(".git/index")));
+    }
+}
@@ -0,0 +1,62 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+
+use notify::{RecursiveMode, Watcher};
+
+use super::{errors::LictoolResult, walk::is_walked};
+
+/// How long to wait for further changes before reporting a batch, so
+/// that an editor saving several files triggers a single run.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// Watches the files below `root`, calling `on_change` with each
+/// batch of changed files until the watcher fails or the process is
+/// interrupted.
+///
+/// Paths are given relative to `root` and joined to it, as
+/// `project_files` lists them. Changes in hidden entries and build
+/// directories are ignored.
+///
+/// # Errors
+/// - If the directory cannot be watched.
+pub(crate) fn watch_files(
+    root: &Path,
+    mut on_change: impl FnMut(&BTreeSet<PathBuf>),
+) -> LictoolResult<()> {
+    let root_abs = fs::canonicalize(root)?;
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    watcher.watch(&root_abs, RecursiveMode::Recursive)?;
+    while let Ok(event) = receiver.recv() {
+        let mut changed = BTreeSet::new();
+        let mut collect = |event: notify::Result<notify::Event>| {
+            // Reading the files to check them raises access events,
+            // which would otherwise trigger another run.
+            let paths = event
+                .ok()
+                .filter(|event| !event.kind.is_access())
+                .map(|event| event.paths)
+                .unwrap_or_default();
+            for path in paths {
+                if let Ok(relative) = path.strip_prefix(&root_abs) {
+                    if is_walked(relative) {
+                        changed.insert(root.join(relative));
+                    }
+                }
+            }
+        };
+        collect(event);
+        while let Ok(event) = receiver.recv_timeout(DEBOUNCE) {
+            collect(event);
+        }
+        if !changed.is_empty() {
+            on_change(&changed);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,186 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// A year or a range of years, such as `2019` or `2019 - 2024`.
+static YEARS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(\d{4})(?:(\s*[-–]\s*)(\d{4}))?\b").unwrap());
+
+/// The copyright holders of license texts, whose notices are part of
+/// the license rather than of the project.
+const LICENSE_STEWARDS: [&str; 1] = ["Free Software Foundation"];
+
+/// Template placeholders left in the copyright lines of unfilled
+/// license texts, in lowercase.
+const PLACEHOLDERS: [&str; 5] = ["yyyy", "<year>", "[year]", "{year}", "<copyright holders>"];
+
+/// How many lines of titles, such as `Apache License` and `Version
+/// 2.0, January 2004`, may come before the copyright notices.
+const MAX_TITLE_LINES: usize = 4;
+
+/// Checks whether a line is a copyright notice, such as `Copyright
+/// (c) 2024 Jane Doe` or `© 2024 Jane Doe`.
+pub(crate) fn is_copyright_line(line: &str) -> bool {
+    let line = line.trim_start().to_lowercase();
+    line.starts_with("copyright") || line.starts_with('©') || line.starts_with("(c)")
+}
+
+/// Returns the copyright notices at the top of a license text, such
+/// as `Copyright (c) 2024 Jane Doe` above the MIT terms, trimmed.
+///
+/// The block ends at the first line of the terms, so copyright lines
+/// further down, like the one in the appendix of the Apache license,
+/// are not picked up. The notices of license stewards, like the FSF's
+/// in the GPL, and lines still holding placeholders such as `[yyyy]`
+/// are left out.
+pub(crate) fn notice_lines(text: &str) -> Vec<&str> {
+    let mut notices = Vec::new();
+    let (mut in_block, mut titles) = (false, 0);
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        if !is_copyright_line(line) {
+            titles += 1;
+            if in_block || titles > MAX_TITLE_LINES {
+                break;
+            }
+            continue;
+        }
+        in_block = true;
+        let lowercase = line.to_lowercase();
+        let stewarded = LICENSE_STEWARDS
+            .iter()
+            .any(|steward| line.contains(steward));
+        let placeholder = PLACEHOLDERS
+            .iter()
+            .any(|placeholder| lowercase.contains(placeholder));
+        if !stewarded && !placeholder {
+            notices.push(line);
+        }
+    }
+    notices
+}
+
+/// Extends the last year of a copyright line to `year`, turning a
+/// single year into a range.
+///
+/// # Returns
+///
+/// `None` if the line names no year before `year`.
+fn bump_line(
+    line: &str,
+    year: i32,
+) -> Option<String> {
+    let found = YEARS.captures_iter(line).last()?;
+    let start: i32 = found[1].parse().ok()?;
+    let (end, separator) = match (found.get(3), found.get(2)) {
+        (Some(end), Some(separator)) => (end.as_str().parse().ok()?, separator.as_str()),
+        _ => (start, "-"),
+    };
+    if end >= year || start > year {
+        return None;
+    }
+    let range = found.get(0)?;
+    Some(format!(
+        "{}{}{}{}{}",
+        &line[..range.start()],
+        start,
+        separator,
+        year,
+        &line[range.end()..]
+    ))
+}
+
+/// Extends the years of the copyright lines of a license text to
+/// `year`, such as `2021` to `2021-2025` or `2019-2023` to
+/// `2019-2025`.
+///
+/// The last year or range of each line is extended, so `2019, 2021`
+/// becomes `2019, 2021-2025`. The notices of license stewards such as
+/// the FSF in the GPL are left alone.
+///
+/// # Returns
+///
+/// The updated text and the number of lines changed.
+pub(crate) fn bump_years(
+    text: &str,
+    year: i32,
+) -> (String, usize) {
+    let mut bumped = 0;
+    let lines: Vec<String> = text
+        .split_inclusive('\n')
+        .map(|line| {
+            let stewarded = LICENSE_STEWARDS
+                .iter()
+                .any(|steward| line.contains(steward));
+            match (is_copyright_line(line) && !stewarded)
+                .then(|| bump_line(line, year))
+                .flatten()
+            {
+                Some(line) => {
+                    bumped += 1;
+                    line
+                }
+                None => line.to_string(),
+            }
+        })
+        .collect();
+    (lines.concat(), bumped)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{bump_years, notice_lines};
+
+    #[test]
+    fn test_bump_years() {
+        let text = "MIT License\n\nCopyright (c) 2021 Jane Doe\nCopyright (c) 2019 – 2023 Acme \
+                    Inc.\n© 2018, 2020 John Roe\nCopyright 2025 Current Corp\n\nPermission \
+                    granted since 2001.\n";
+        let (bumped, count) = bump_years(text, 2025);
+        assert_eq!(
+            bumped,
+            "MIT License\n\nCopyright (c) 2021-2025 Jane Doe\nCopyright (c) 2019 – 2025 Acme \
+             Inc.\n© 2018, 2020-2025 John Roe\nCopyright 2025 Current Corp\n\nPermission granted \
+             since 2001.\n"
+        );
+        assert_eq!(count, 3);
+        let gpl = " Copyright (C) 2007 Free Software Foundation, Inc. <https://fsf.org/>\n";
+        assert_eq!(bump_years(gpl, 2025), (gpl.to_string(), 0));
+    }
+
+    #[test]
+    fn test_notice_lines() {
+        assert_eq!(
+            notice_lines(
+                "MIT License\n\nCopyright (c) 2021 Jane Doe\n© 2024 Acme Inc.\n\nPermission is \
+                 hereby granted.\nCopyright notices must be kept.\n"
+            ),
+            ["Copyright (c) 2021 Jane Doe", "© 2024 Acme Inc."]
+        );
+        // The FSF notice of the GPL and its how-to template.
+        assert_eq!(
+            notice_lines(
+                "GNU GENERAL PUBLIC LICENSE\nVersion 3, 29 June 2007\n\n Copyright (C) 2007 Free \
+                 Software Foundation, Inc. <https://fsf.org/>\n Everyone is permitted.\n\n    \
+                 Copyright (C) <year>  <name of author>\n"
+            ),
+            Vec::<&str>::new()
+        );
+        // The template in the appendix of the Apache license.
+        assert_eq!(
+            notice_lines(
+                "Apache License\nVersion 2.0, January 2004\nhttp://www.apache.org/licenses/\n\n\
+                 TERMS AND CONDITIONS\n\n1. Definitions.\n\nCopyright 2024 Jane Doe\n"
+            ),
+            Vec::<&str>::new()
+        );
+        assert_eq!(
+            notice_lines("Copyright [yyyy] [name of copyright owner]\n"),
+            Vec::<&str>::new()
+        );
+    }
+}